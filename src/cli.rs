@@ -1,9 +1,60 @@
 use clap::{Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(name = "jenkins")]
 #[command(about = "A CLI tool for interacting with Jenkins", long_about = None)]
+#[command(after_help = "Exit codes:
+  0    Success
+  1    Other error
+  2    Invalid arguments
+  3    Config error (missing, unreadable or unknown host)
+  4    Authentication or permission failure
+  5    Network error (controller unreachable or timed out)
+  6    Job, build, folder, view or agent not found
+  7    Build failed (a followed build did not succeed, or a quality gate failed)
+  130  Cancelled by the user")]
 pub struct Cli {
+    #[arg(
+        long,
+        global = true,
+        value_name = "PATH",
+        help = "Path to an alternate config file (or set JENKINS_CLI_CONFIG)"
+    )]
+    pub config: Option<PathBuf>,
+
+    #[arg(
+        long = "as",
+        global = true,
+        value_name = "IDENTITY",
+        help = "Use one of the host's named identities instead of its default user"
+    )]
+    pub identity: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Fail instead of prompting for input, using the defaults of build parameters (or set JENKINS_CLI_NONINTERACTIVE=1)"
+    )]
+    pub non_interactive: bool,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "PATH",
+        help = "Write debug logs (request timings, errors) to a file (or set log_file in the config)"
+    )]
+    pub log_file: Option<PathBuf>,
+
+    #[arg(long, global = true, help = "Print long output (logs, test results) directly instead of through $PAGER")]
+    pub no_pager: bool,
+
+    #[arg(long, global = true, help = "Always ask the controller instead of reusing recent responses (TTLs are set under cache in the config)")]
+    pub no_cache: bool,
+
+    #[arg(long, global = true, help = "Show build times as absolute UTC times instead of in the local time zone")]
+    pub utc: bool,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -23,6 +74,50 @@ pub enum Commands {
 
         #[arg(short = 'f', long, help = "Follow the build logs in real-time after triggering")]
         follow: bool,
+
+        #[arg(
+            long,
+            value_name = "DURATION",
+            value_parser = crate::helpers::params::parse_duration,
+            help = "How long --follow waits for a queued build to start, e.g. 15m (default: 30s or the host's queue_timeout)"
+        )]
+        queue_timeout: Option<std::time::Duration>,
+
+        #[arg(
+            long,
+            requires = "follow",
+            conflicts_with = "all_hosts",
+            help = "With --follow, print progress as newline-delimited JSON events on stdout"
+        )]
+        events: bool,
+
+        #[arg(
+            long,
+            value_name = "WEBHOOK_URL",
+            requires = "follow",
+            conflicts_with = "all_hosts",
+            help = "With --follow, POST a JSON summary to a Slack or generic webhook when the build finishes (default: notify_webhook in the config)"
+        )]
+        notify: Option<String>,
+
+        #[arg(
+            long,
+            requires = "follow",
+            conflicts_with_all = ["events", "all_hosts"],
+            help = "With --follow, also follow the builds this one triggers, and theirs, until the chain finishes"
+        )]
+        follow_downstream: bool,
+
+        #[arg(
+            long,
+            value_name = "NAMES",
+            value_delimiter = ',',
+            help = "Jenkins host(s) to build on, e.g. prod,dr or tag:staging - several hosts are triggered at once"
+        )]
+        jenkins: Vec<String>,
+
+        #[arg(long, conflicts_with_all = ["jenkins", "follow"], help = "Trigger the job on every configured host at once")]
+        all_hosts: bool,
     },
 
     #[command(about = "Check the status of a Jenkins job or build")]
@@ -32,6 +127,21 @@ pub enum Commands {
 
         #[arg(short, long, help = "Specific build number to check")]
         build: Option<i32>,
+
+        #[arg(long, value_name = "AXES", help = "Configuration of a matrix job, e.g. OS=linux,JDK=17")]
+        axis: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "HOSTS",
+            value_delimiter = ',',
+            conflicts_with_all = ["build", "axis"],
+            help = "Compare the job's latest build across hosts, e.g. prod,staging or tag:payments"
+        )]
+        compare: Vec<String>,
+
+        #[arg(long, conflicts_with_all = ["axis", "compare"], help = "Use the checked-out git branch's job in the multibranch project (the job given, or the directory profile's)")]
+        branch: bool,
     },
 
     #[command(about = "View console logs for a build")]
@@ -44,6 +154,107 @@ pub enum Commands {
 
         #[arg(short = 'f', long, help = "Follow the build logs in real-time")]
         follow: bool,
+
+        #[arg(long, value_name = "AXES", help = "Configuration of a matrix job, e.g. OS=linux,JDK=17")]
+        axis: Option<String>,
+
+        #[arg(long, conflicts_with = "follow", help = "Fold each pipeline stage into a one-line summary of its steps")]
+        fold: bool,
+
+        #[arg(
+            long,
+            value_name = "STAGE",
+            requires = "fold",
+            help = "With --fold, show this stage's output in full (repeatable)"
+        )]
+        expand: Vec<String>,
+
+        #[arg(long, conflicts_with = "axis", help = "Use the checked-out git branch's job in the multibranch project (the job given, or the directory profile's)")]
+        branch: bool,
+    },
+
+    #[command(about = "Show code coverage for a build")]
+    Coverage {
+        #[arg(help = "Name of the Jenkins job (optional - will prompt to select if not provided)")]
+        job_name: Option<String>,
+
+        #[arg(short, long, help = "Specific build number (defaults to last build)")]
+        build: Option<i32>,
+    },
+
+    #[command(about = "Show test results for a build")]
+    Tests {
+        #[arg(help = "Name of the Jenkins job (optional - will prompt to select if not provided)")]
+        job_name: Option<String>,
+
+        #[arg(short, long, help = "Specific build number (defaults to last build)")]
+        build: Option<i32>,
+
+        #[arg(long, help = "Show only failing tests with error details and stack traces")]
+        failed: bool,
+
+        #[arg(long, value_name = "PATH", help = "Write the test results to a JUnit XML file instead of printing them")]
+        junit_out: Option<PathBuf>,
+    },
+
+    #[command(about = "Show build duration trends and success rate for a job")]
+    Stats {
+        #[arg(help = "Name of the Jenkins job (optional - will prompt to select if not provided)")]
+        job_name: Option<String>,
+
+        #[arg(short, long, default_value_t = 20, help = "Number of recent builds to analyze")]
+        last: usize,
+    },
+
+    #[command(about = "Summarize static analysis (warnings-ng) results for a build")]
+    Warnings {
+        #[arg(help = "Name of the Jenkins job (optional - will prompt to select if not provided)")]
+        job_name: Option<String>,
+
+        #[arg(short, long, help = "Specific build number (defaults to last build)")]
+        build: Option<i32>,
+
+        #[arg(long, help = "Exit with a non-zero status if the build introduced new issues")]
+        fail_on_new: bool,
+    },
+
+    #[command(about = "List the jobs of a folder (or the root) with their last build")]
+    Jobs {
+        #[arg(help = "Folder to list (defaults to the root)")]
+        folder: Option<String>,
+
+        #[arg(long, value_enum, default_value = "name", help = "Order of the listed jobs")]
+        sort: JobSort,
+
+        #[arg(long, value_enum, help = "Only list failing, building or disabled jobs")]
+        filter: Option<JobFilter>,
+    },
+
+    #[command(about = "List every failing or unstable job below a folder or view, with when its failures started")]
+    Failing {
+        #[arg(help = "Folder to scan (defaults to the whole instance)")]
+        folder: Option<String>,
+
+        #[arg(long, conflicts_with = "folder", help = "Scan the jobs of a view instead of a folder")]
+        view: Option<String>,
+
+        #[arg(long, default_value = "5", help = "Folder levels to descend into")]
+        depth: usize,
+    },
+
+    #[command(about = "List jobs sorted by health score")]
+    Health {
+        #[arg(help = "Folder to inspect (defaults to the root)")]
+        folder: Option<String>,
+    },
+
+    #[command(about = "Show the environment variables injected into a build")]
+    Env {
+        #[arg(help = "Name of the Jenkins job (optional - will prompt to select if not provided)")]
+        job_name: Option<String>,
+
+        #[arg(short, long, help = "Specific build number (defaults to last build)")]
+        build: Option<i32>,
     },
 
     #[command(about = "Open a Jenkins job or build in the browser")]
@@ -53,8 +264,312 @@ pub enum Commands {
 
         #[arg(short, long, help = "Specific build number to open")]
         build: Option<i32>,
+
+        #[arg(long, help = "Print the URL instead of opening a browser")]
+        print: bool,
+
+        #[arg(long, help = "Copy the URL to the clipboard instead of opening a browser")]
+        copy: bool,
+
+        #[arg(long, help = "Use the checked-out git branch's job in the multibranch project (the job given, or the directory profile's)")]
+        branch: bool,
+    },
+
+    #[command(about = "Install or remove a git hook that builds a job on every push")]
+    Hook {
+        #[command(subcommand)]
+        action: HookAction,
+    },
+
+    #[command(about = "Watch this repository and trigger a build whenever it's pushed or files are saved")]
+    Autobuild {
+        #[arg(long, help = "Job or alias to build (optional - will prompt to select if not provided)")]
+        job: Option<String>,
+
+        #[arg(long, value_enum, default_value = "push", help = "What triggers a build")]
+        on: AutobuildTrigger,
+
+        #[arg(
+            short,
+            long,
+            default_value_t = 2,
+            value_parser = clap::value_parser!(u64).range(1..),
+            help = "Seconds between checks for changes"
+        )]
+        interval: u64,
+
+        #[arg(
+            long,
+            default_value_t = 3,
+            value_parser = clap::value_parser!(u64).range(1..),
+            help = "Seconds without further changes before a build is triggered"
+        )]
+        debounce: u64,
+    },
+
+    #[command(about = "Replay a pipeline build with a local Jenkinsfile")]
+    Replay {
+        #[arg(help = "Name of the Jenkins job (optional - will prompt to select if not provided)")]
+        job_name: Option<String>,
+
+        #[arg(short, long, help = "Build to replay (defaults to last build)")]
+        build: Option<i32>,
+
+        #[arg(long, value_name = "PATH", help = "Pipeline script to run instead of the build's Jenkinsfile")]
+        jenkinsfile: PathBuf,
+    },
+
+    #[command(about = "Restart a declarative pipeline build from one of its stages")]
+    RestartFromStage {
+        #[arg(help = "Name of the Jenkins job (optional - will prompt to select if not provided)")]
+        job_name: Option<String>,
+
+        #[arg(short, long, help = "Build to restart (defaults to last build)")]
+        build: Option<i32>,
+
+        #[arg(short, long, help = "Top-level stage to restart from (optional - will prompt to select if not provided)")]
+        stage: Option<String>,
+    },
+
+    #[command(about = "Show a pipeline build's stages on a timeline, with parallel stages side by side")]
+    Timeline {
+        #[arg(help = "Name of the Jenkins job (optional - will prompt to select if not provided)")]
+        job_name: Option<String>,
+
+        #[arg(short, long, help = "Specific build number (defaults to last build)")]
+        build: Option<i32>,
+    },
+
+    #[command(about = "List or download the artifacts archived by a build")]
+    Artifacts {
+        #[command(subcommand)]
+        action: ArtifactsAction,
+    },
+
+    #[command(about = "Export a build's details, parameters, causes, changes, tests and artifacts as one JSON document")]
+    ExportBuild {
+        #[arg(help = "Name of the Jenkins job (optional - will prompt to select if not provided)")]
+        job_name: Option<String>,
+
+        #[arg(short, long, help = "Specific build number (defaults to last build)")]
+        build: Option<i32>,
+
+        #[arg(short, long, value_name = "FILE", help = "File to write the JSON to (defaults to printing it)")]
+        output: Option<PathBuf>,
+    },
+
+    #[command(about = "Inspect and compare job configurations")]
+    Job {
+        #[command(subcommand)]
+        action: JobAction,
+    },
+
+    #[command(about = "Download config.xml of every job under a folder, keeping the folder hierarchy")]
+    Backup {
+        #[arg(long, help = "Folder to back up (defaults to the whole instance)")]
+        folder: Option<String>,
+
+        #[arg(long, value_name = "DIR", help = "Directory to write the configurations to")]
+        dest: PathBuf,
     },
 
+    #[command(about = "Upload job configurations saved by 'jenkins backup', showing what would change first")]
+    Restore {
+        #[arg(help = "Backup directory to restore from")]
+        dir: PathBuf,
+
+        #[arg(long, help = "Create jobs and folders that don't exist yet")]
+        create_missing: bool,
+
+        #[arg(long, help = "Only show what would change")]
+        dry_run: bool,
+
+        #[arg(short, long, help = "Apply the changes without asking for confirmation")]
+        yes: bool,
+    },
+
+    #[command(about = "Run a Job DSL seed job and list the jobs it created, updated or removed")]
+    Seed {
+        #[arg(help = "Name of the seed job (optional - will prompt to select if not provided)")]
+        job_name: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "DURATION",
+            value_parser = crate::helpers::params::parse_duration,
+            help = "How long to wait for the queued build to start, e.g. 15m (default: 30s or the host's queue_timeout)"
+        )]
+        queue_timeout: Option<std::time::Duration>,
+    },
+
+    #[command(about = "Work with the controller's Configuration as Code (JCasC)")]
+    Casc {
+        #[command(subcommand)]
+        action: CascAction,
+    },
+
+    #[command(about = "Run a Groovy script in the script console of the controller or of selected agents")]
+    Script {
+        #[arg(help = "Groovy script file to run")]
+        file: PathBuf,
+
+        #[arg(
+            short,
+            long = "node",
+            value_name = "NAME",
+            help = "Agent to run the script on, printing each agent's output (repeatable; defaults to the controller)"
+        )]
+        nodes: Vec<String>,
+    },
+
+    #[command(about = "Manage agents (nodes)")]
+    Node {
+        #[command(subcommand)]
+        action: NodeAction,
+    },
+
+    #[command(about = "Inspect installed plugins")]
+    Plugins {
+        #[command(subcommand)]
+        action: PluginsAction,
+    },
+
+    #[command(about = "Check the config, connection, credentials and permissions, with tips to fix problems")]
+    Doctor {
+        #[arg(help = "Jenkins host to check (defaults to the current host)")]
+        jenkins: Option<String>,
+
+        #[arg(long, value_name = "JOB", help = "Job to check the Build permission on")]
+        job: Option<String>,
+    },
+
+    #[command(about = "Time requests to the controller and report latency and whether it is quieting down")]
+    Ping {
+        #[arg(help = "Jenkins host to ping (defaults to the current host)")]
+        jenkins: Option<String>,
+
+        #[arg(short, long, default_value_t = crate::commands::ping::DEFAULT_PING_COUNT, help = "Number of rounds")]
+        count: usize,
+    },
+
+    #[command(about = "Show queue length and executor usage averaged over the last 1, 5 and 15 minutes")]
+    Load {
+        #[arg(help = "Jenkins host to read (defaults to the current host)")]
+        jenkins: Option<String>,
+    },
+
+    #[command(about = "Show the queue, busy and idle executors, and executors per label in one snapshot")]
+    Capacity {
+        #[arg(help = "Jenkins host to read (defaults to the current host)")]
+        jenkins: Option<String>,
+
+        #[arg(short, long, value_enum, default_value = "table", help = "Output format")]
+        output: OutputFormat,
+    },
+
+    #[command(about = "List recently used jobs and run status, build or logs on one of them")]
+    Recent {
+        #[arg(help = "Jenkins host whose jobs to list (defaults to the current host)")]
+        jenkins: Option<String>,
+    },
+
+    #[command(about = "Show builds, cancellations and config changes made through the CLI")]
+    History {
+        #[arg(long, help = "Only show actions on this Jenkins host")]
+        jenkins: Option<String>,
+
+        #[arg(long, help = "Only show actions on jobs whose path contains this text")]
+        job: Option<String>,
+
+        #[arg(short, long, default_value_t = 20, help = "Number of entries to show")]
+        last: usize,
+    },
+
+    #[command(about = "Show the status of every alias and pinned job in one table")]
+    Dash {
+        #[arg(short, long, conflicts_with_all = ["pin", "unpin"], help = "Keep refreshing the table until Ctrl+C")]
+        watch: bool,
+
+        #[arg(
+            short,
+            long,
+            default_value_t = 10,
+            requires = "watch",
+            value_parser = clap::value_parser!(u64).range(1..),
+            help = "Refresh interval in seconds"
+        )]
+        interval: u64,
+
+        #[arg(long, value_name = "JOB", conflicts_with = "unpin", help = "Add a job (path or job URL) to the dashboard")]
+        pin: Option<String>,
+
+        #[arg(long, value_name = "JOB", help = "Remove a pinned job from the dashboard")]
+        unpin: Option<String>,
+    },
+
+    #[command(about = "Watch the status of several jobs live")]
+    Monitor {
+        #[arg(help = "Jobs or aliases to watch")]
+        jobs: Vec<String>,
+
+        #[arg(long, help = "Watch all jobs in a Jenkins view")]
+        view: Option<String>,
+
+        #[arg(
+            short,
+            long,
+            default_value = "10",
+            value_parser = crate::helpers::params::parse_interval,
+            help = "Refresh interval, e.g. 30 (seconds) or 5m"
+        )]
+        interval: std::time::Duration,
+
+        #[arg(
+            long,
+            help = "Run without the live table and report only jobs that change state; watches all aliases and pinned jobs if none are given"
+        )]
+        daemon: bool,
+
+        #[arg(long, value_name = "FILE", requires = "daemon", help = "Append each digest to a file instead of printing it")]
+        digest_file: Option<PathBuf>,
+
+        #[arg(long, value_name = "URL", requires = "daemon", help = "POST each digest as JSON to a Slack or generic webhook")]
+        webhook: Option<String>,
+    },
+
+    #[command(about = "List every build currently running on the instance, plus the queue")]
+    Running,
+
+    #[command(about = "List recent builds started by the configured user")]
+    Mine {
+        #[arg(help = "Name of the Jenkins job (optional - scans all jobs if not provided)")]
+        job_name: Option<String>,
+
+        #[arg(short, long, default_value_t = 20, help = "Number of recent builds to scan per job")]
+        last: usize,
+    },
+
+    #[command(about = "Send a raw authenticated request to the Jenkins API")]
+    Api {
+        #[arg(help = "HTTP method (GET, POST, ...)")]
+        method: String,
+
+        #[arg(help = "Path on the Jenkins host, e.g. /queue/api/json")]
+        path: String,
+
+        #[arg(short, long, help = "Request body (use @file to read it from a file)")]
+        data: Option<String>,
+    },
+
+    #[command(about = "Show the status, logs or page of the build last triggered from this CLI")]
+    Last {
+        #[command(subcommand)]
+        action: Option<LastAction>,
+    },
+
+    #[command(about = "Start an interactive shell that keeps the host and job selected between commands")]
+    Shell,
+
     #[command(about = "Generate shell completion scripts")]
     Completion {
         #[arg(value_enum, help = "Shell type to generate completion for")]
@@ -66,9 +581,16 @@ pub enum Commands {
         #[command(subcommand)]
         action: AliasAction,
     },
+
+    #[command(name = "__complete", hide = true, about = "Print host or alias names for the completion scripts")]
+    CompleteValues {
+        #[arg(value_enum)]
+        values: CompletionValues,
+    },
 }
 
 #[derive(ValueEnum, Clone, Copy, Debug)]
+#[allow(clippy::enum_variant_names)]
 pub enum Shell {
     Bash,
     Zsh,
@@ -76,16 +598,305 @@ pub enum Shell {
     PowerShell,
 }
 
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum JobSort {
+    Name,
+    /// Worst status first
+    Status,
+    /// Most recently built first
+    LastBuild,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum JobFilter {
+    /// Failed or unstable
+    Failing,
+    Building,
+    Disabled,
+}
+
+/// When the installed git hook triggers the build
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum HookTrigger {
+    /// Before git sends the commits, reporting trigger failures in the push output; jobs that check out
+    /// the branch build what the remote had before the push
+    PrePush,
+    /// In the background once the push has finished, if it updated the branch on the remote
+    PostPush,
+}
+
+/// What makes `jenkins autobuild` trigger a build
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum AutobuildTrigger {
+    /// New commits are pushed from this clone (the branch's upstream moves)
+    Push,
+    /// Files in the working tree change
+    Save,
+}
+
+/// How a command prints its result
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum OutputFormat {
+    Table,
+    /// One JSON document, for scripts
+    Json,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum CompletionValues {
+    Hosts,
+    Aliases,
+}
+
 #[derive(Subcommand)]
 pub enum ConfigAction {
     #[command(about = "Add a new Jenkins host")]
-    Add,
+    Add {
+        #[arg(long, help = "Log in with your password once to create an API token (the password is not saved)")]
+        generate_token: bool,
+    },
 
     #[command(about = "List all configured Jenkins hosts")]
     List,
 
     #[command(about = "Remove a Jenkins host")]
     Remove,
+
+    #[command(about = "Check the config file for problems")]
+    Validate,
+
+    #[command(about = "Check that the stored credentials still work")]
+    Verify {
+        #[arg(help = "Jenkins host to verify (defaults to all hosts)")]
+        name: Option<String>,
+    },
+
+    #[command(about = "Replace a host's API token with a new one and revoke the old token")]
+    RotateToken {
+        #[arg(help = "Jenkins host whose token to rotate (prompts if several are configured)")]
+        name: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ArtifactsAction {
+    #[command(about = "List the artifacts of a build")]
+    List {
+        #[arg(help = "Name of the Jenkins job (optional - will prompt to select if not provided)")]
+        job_name: Option<String>,
+
+        #[arg(short, long, help = "Specific build number (defaults to last build)")]
+        build: Option<i32>,
+    },
+
+    #[command(about = "Download the artifacts of a build, verifying their size and recorded checksum")]
+    Download {
+        #[arg(help = "Name of the Jenkins job (optional - will prompt to select if not provided)")]
+        job_name: Option<String>,
+
+        #[arg(short, long, help = "Specific build number (defaults to last build)")]
+        build: Option<i32>,
+
+        #[arg(short, long, value_name = "DIR", help = "Directory to download into (defaults to the current directory)")]
+        output: Option<PathBuf>,
+
+        #[arg(
+            short,
+            long = "pattern",
+            value_name = "GLOB",
+            help = "Only download artifacts whose path matches, e.g. \"dist/*.tar.gz\" (repeatable)"
+        )]
+        patterns: Vec<String>,
+
+        #[arg(
+            long,
+            value_name = "N",
+            default_value_t = crate::commands::artifacts::DEFAULT_PARALLEL_DOWNLOADS,
+            help = "How many artifacts to download at the same time"
+        )]
+        parallel: usize,
+
+        #[arg(long, help = "Keep partial downloads when interrupted and continue them with HTTP Range requests")]
+        resume: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum JobAction {
+    #[command(about = "Show a unified diff of two jobs' config.xml")]
+    Diff {
+        #[arg(help = "Job to compare")]
+        job_a: String,
+
+        #[arg(help = "Job to compare with (defaults to the first job, with --other-jenkins)")]
+        job_b: Option<String>,
+
+        #[arg(long, value_name = "NAME", help = "Jenkins host of the first job")]
+        jenkins: Option<String>,
+
+        #[arg(long, value_name = "NAME", help = "Jenkins host of the second job (defaults to the first job's host)")]
+        other_jenkins: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum NodeAction {
+    #[command(about = "Create a permanent inbound (JNLP) agent and print how to connect it")]
+    Create {
+        #[arg(help = "Name of the agent")]
+        name: String,
+
+        #[arg(long, default_value_t = 1, help = "Number of executors")]
+        executors: u32,
+
+        #[arg(short, long = "label", value_name = "LABEL", help = "Label to assign to the agent (repeatable)")]
+        labels: Vec<String>,
+
+        #[arg(
+            long,
+            value_name = "DIR",
+            default_value = crate::commands::node::DEFAULT_REMOTE_FS,
+            help = "Root directory on the agent"
+        )]
+        remote_fs: String,
+    },
+
+    #[command(about = "List agents with their disk, temp and swap space, response time and clock skew, flagging problems")]
+    List {
+        #[arg(
+            long,
+            value_name = "SIZE",
+            default_value = crate::commands::node::DEFAULT_MIN_SPACE,
+            value_parser = crate::helpers::params::parse_size,
+            help = "Flag nodes with less free disk space, e.g. 10GB"
+        )]
+        min_disk: u64,
+
+        #[arg(
+            long,
+            value_name = "SIZE",
+            default_value = crate::commands::node::DEFAULT_MIN_SPACE,
+            value_parser = crate::helpers::params::parse_size,
+            help = "Flag nodes with less free temp space"
+        )]
+        min_temp: u64,
+
+        #[arg(
+            long,
+            value_name = "SIZE",
+            value_parser = crate::helpers::params::parse_size,
+            help = "Flag nodes with less free swap (not checked by default)"
+        )]
+        min_swap: Option<u64>,
+
+        #[arg(
+            long,
+            value_name = "DURATION",
+            default_value = crate::commands::node::DEFAULT_MAX_DELAY,
+            value_parser = crate::helpers::params::parse_duration,
+            help = "Flag nodes responding slower than this"
+        )]
+        max_response: std::time::Duration,
+
+        #[arg(
+            long,
+            value_name = "DURATION",
+            default_value = crate::commands::node::DEFAULT_MAX_DELAY,
+            value_parser = crate::helpers::params::parse_duration,
+            help = "Flag nodes whose clock is further off than this"
+        )]
+        max_clock_skew: std::time::Duration,
+    },
+
+    #[command(about = "Show an agent's launch and connection log")]
+    Logs {
+        #[arg(help = "Name of the agent")]
+        name: String,
+    },
+
+    #[command(about = "Delete an agent")]
+    Delete {
+        #[arg(help = "Name of the agent")]
+        name: String,
+
+        #[arg(short, long, help = "Delete without asking for confirmation")]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum HookAction {
+    #[command(about = "Write a pre-push hook into this repository that runs 'jenkins build' for each pushed branch")]
+    Install {
+        #[arg(long, help = "Job or alias to build (defaults to the directory profile's job)")]
+        job: Option<String>,
+
+        #[arg(long, help = "Jenkins host to build on (defaults to the alias's or the directory profile's host)")]
+        jenkins: Option<String>,
+
+        #[arg(
+            short = 'p',
+            long = "param",
+            value_name = "KEY=TEMPLATE",
+            value_parser = crate::helpers::params::parse_key_value,
+            help = "Build parameter, with {branch}, {commit} or {remote} filled in for each push, e.g. BRANCH={branch} (repeatable)"
+        )]
+        params: Vec<(String, String)>,
+
+        #[arg(long, value_enum, default_value = "post-push", help = "When to trigger the build")]
+        when: HookTrigger,
+
+        #[arg(long, help = "Replace a pre-push hook that jenkins-cli didn't write")]
+        force: bool,
+    },
+
+    #[command(about = "Remove the hook written by 'jenkins hook install'")]
+    Uninstall,
+}
+
+#[derive(Subcommand)]
+pub enum PluginsAction {
+    #[command(about = "List plugins with newer versions available, flagging security advisories")]
+    Outdated,
+
+    #[command(about = "Show a plugin's dependency tree, or what depends on it with --reverse")]
+    Tree {
+        #[arg(help = "Short name of the plugin, e.g. git")]
+        name: String,
+
+        #[arg(short, long, help = "Show the plugins that depend on it instead")]
+        reverse: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum CascAction {
+    #[command(about = "Export the controller's JCasC YAML")]
+    Export {
+        #[arg(
+            short,
+            long,
+            value_name = "FILE",
+            default_value = crate::commands::casc::DEFAULT_EXPORT_FILE,
+            help = "File to write the YAML to (use - to print it)"
+        )]
+        output: PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum LastAction {
+    #[command(about = "Show build details (default)")]
+    Status,
+
+    #[command(about = "Show the console output")]
+    Logs {
+        #[arg(short = 'f', long, help = "Follow the build logs in real-time")]
+        follow: bool,
+    },
+
+    #[command(about = "Open the build in the browser")]
+    Open,
 }
 
 #[derive(Subcommand)]
@@ -97,14 +908,35 @@ pub enum AliasAction {
 
         #[arg(help = "Actual job name (optional - will prompt to select if not provided)")]
         job_name: Option<String>,
+
+        #[arg(
+            short = 'p',
+            long = "param",
+            value_name = "KEY=VALUE",
+            value_parser = crate::helpers::params::parse_key_value,
+            help = "Build parameter to apply whenever the alias is built; file parameters take a local path (repeatable)"
+        )]
+        params: Vec<(String, String)>,
     },
 
     #[command(about = "List all job aliases")]
     List,
 
+    #[command(about = "Show the current status of every aliased job")]
+    Status,
+
     #[command(about = "Remove a job alias")]
     Remove {
         #[arg(help = "Alias to remove (optional - will prompt to select if not provided)")]
         alias: Option<String>,
     },
+
+    #[command(about = "Rename a job alias")]
+    Rename {
+        #[arg(help = "Current alias name")]
+        old: String,
+
+        #[arg(help = "New alias name")]
+        new: String,
+    },
 }
@@ -1,18 +1,35 @@
 use anyhow::{Context, Result};
-use reqwest::blocking::Client;
+use reqwest::blocking::{Client, RequestBuilder, Response};
 use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Instant;
 
 use crate::config::JenkinsHost;
-use crate::helpers::url::{build_api_url, build_job_url, normalize_host_url};
+use crate::helpers::cache::{self, CacheKind};
+use crate::helpers::cron;
+use crate::helpers::errors::{kind_of, ErrorKind};
+use crate::helpers::url::{
+    branch_job_candidates, build_api_url, build_job_url, encode_path_segment, normalize_host_url, to_display_job_path,
+    to_jenkins_job_path,
+};
 
+#[derive(Clone)]
 pub struct JenkinsClient {
     client: Client,
     host: JenkinsHost,
+    name: Option<String>,
+    /// Serve job and build status from the response cache (see `with_cached_reads`)
+    cached_reads: bool,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct JobInfo {
+    #[serde(rename = "_class")]
+    pub class: Option<String>,
     pub name: Option<String>,
     pub url: Option<String>,
     pub color: Option<String>,
@@ -21,6 +38,47 @@ pub struct JobInfo {
     pub last_build: Option<BuildInfo>,
     pub jobs: Option<Vec<SubJobInfo>>,
     pub property: Option<Vec<JobProperty>>,
+    #[serde(rename = "healthReport")]
+    pub health_report: Option<Vec<HealthReport>>,
+    /// Axis combinations of a matrix (multi-configuration) project, e.g. `OS=linux,JDK=17`
+    #[serde(rename = "activeConfigurations")]
+    pub active_configurations: Option<Vec<SubJobInfo>>,
+}
+
+impl JobInfo {
+    /// True for matrix (multi-configuration) projects
+    pub fn is_matrix(&self) -> bool {
+        self.class.as_deref().is_some_and(|class| class.contains("MatrixProject"))
+    }
+
+    /// True for multibranch projects, which hold one job per branch
+    pub fn is_multibranch(&self) -> bool {
+        self.class.as_deref().is_some_and(|class| class.contains("MultiBranch"))
+    }
+
+    /// True for folders, multibranch projects and organization folders, which hold jobs but can't be built
+    pub fn is_folder(&self) -> bool {
+        self.jobs.is_some()
+            || self
+                .class
+                .as_deref()
+                .is_some_and(|class| class.contains("Folder") || class.contains("MultiBranch"))
+    }
+
+    fn has_children(&self) -> bool {
+        self.jobs.as_ref().is_some_and(|jobs| !jobs.is_empty())
+    }
+
+    /// Why a folder can't be used as a job, and what to do instead
+    pub fn folder_explanation(&self, job_name: &str) -> String {
+        if self.has_children() {
+            format!("'{}' is a folder, not a job. Pick one of the jobs inside it.", job_name)
+        } else if self.is_multibranch() {
+            format!("'{}' is a multibranch project with no branches yet. Scan it in Jenkins first.", job_name)
+        } else {
+            format!("'{}' is an empty folder. There is no job to use.", job_name)
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
@@ -28,6 +86,111 @@ pub struct SubJobInfo {
     pub name: String,
     pub url: String,
     pub color: Option<String>,
+    #[serde(rename = "healthReport")]
+    pub health_report: Option<Vec<HealthReport>>,
+    /// Only requested by the job selector
+    #[serde(rename = "lastBuild")]
+    pub last_build: Option<BuildInfo>,
+}
+
+/// A job or folder directly inside a folder, as listed for walking the job tree
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct FolderItem {
+    pub name: String,
+    #[serde(rename = "_class")]
+    pub class: Option<String>,
+}
+
+/// A job somewhere below a folder, found by searching its subtree
+#[derive(Debug, Clone, PartialEq)]
+pub struct DescendantJob {
+    /// Path relative to the searched folder, in Jenkins form (`team/job/api`)
+    pub path: String,
+    pub color: Option<String>,
+    /// Only filled in by `get_descendant_jobs_with_builds`
+    pub builds: BuildMarks,
+}
+
+/// Builds a job keeps permalinks to, used to tell how long it has been failing
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct BuildMarks {
+    #[serde(rename = "firstBuild")]
+    pub first: Option<BuildMark>,
+    #[serde(rename = "lastFailedBuild")]
+    pub last_failed: Option<BuildMark>,
+    #[serde(rename = "lastUnstableBuild")]
+    pub last_unstable: Option<BuildMark>,
+    /// Successful here includes unstable, as in Jenkins
+    #[serde(rename = "lastSuccessfulBuild")]
+    pub last_successful: Option<BuildMark>,
+    #[serde(rename = "lastStableBuild")]
+    pub last_stable: Option<BuildMark>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct BuildMark {
+    pub number: i32,
+    pub timestamp: Option<i64>,
+}
+
+/// Fields requested for every job by `get_descendant_jobs_with_builds`
+const BUILD_MARK_FIELDS: &str = "firstBuild[number],lastFailedBuild[number,timestamp],lastUnstableBuild[number,timestamp],lastSuccessfulBuild[number],lastStableBuild[number]";
+
+/// One level of a nested `jobs[...]` tree query
+#[derive(Debug, Deserialize)]
+struct JobTreeNode {
+    name: String,
+    color: Option<String>,
+    #[serde(rename = "_class")]
+    class: Option<String>,
+    jobs: Option<Vec<JobTreeNode>>,
+    #[serde(flatten)]
+    builds: BuildMarks,
+}
+
+/// Flatten a nested job tree into the jobs it contains, skipping the folders themselves
+fn flatten_job_tree(nodes: Vec<JobTreeNode>, prefix: Option<&str>, jobs: &mut Vec<DescendantJob>) {
+    for node in nodes {
+        let path = match prefix {
+            Some(prefix) => format!("{}/job/{}", prefix, node.name),
+            None => node.name.clone(),
+        };
+        // Folders at the depth limit come without their jobs, so also go by class
+        let is_folder = node.jobs.is_some() || FolderItem { name: node.name, class: node.class }.is_folder();
+        match node.jobs {
+            Some(children) => flatten_job_tree(children, Some(&path), jobs),
+            None if !is_folder => jobs.push(DescendantJob { path, color: node.color, builds: node.builds }),
+            None => {}
+        }
+    }
+}
+
+impl FolderItem {
+    /// True for folders, multibranch projects and organization folders
+    pub fn is_folder(&self) -> bool {
+        self.class
+            .as_deref()
+            .is_some_and(|class| class.contains("Folder") || class.contains("MultiBranch"))
+    }
+
+    /// Multibranch projects and organization folders create their jobs from SCM, so their
+    /// children's configuration isn't maintained by hand
+    pub fn has_generated_jobs(&self) -> bool {
+        self.class
+            .as_deref()
+            .is_some_and(|class| class.contains("MultiBranch") || class.contains("OrganizationFolder"))
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct HealthReport {
+    pub score: i32,
+    pub description: String,
+}
+
+/// The worst health report (Jenkins reports the overall score as the minimum)
+pub fn worst_health(reports: Option<&Vec<HealthReport>>) -> Option<&HealthReport> {
+    reports?.iter().min_by_key(|report| report.score)
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
@@ -37,6 +200,52 @@ pub struct BuildInfo {
     pub result: Option<String>,
     pub building: Option<bool>,
     pub timestamp: Option<i64>,
+    pub duration: Option<i64>,
+    #[serde(rename = "estimatedDuration")]
+    pub estimated_duration: Option<i64>,
+    #[serde(default)]
+    pub actions: Vec<BuildAction>,
+}
+
+impl BuildInfo {
+    /// True if the build was started by the given Jenkins user (via a UserIdCause)
+    pub fn started_by(&self, user_id: &str) -> bool {
+        self.actions
+            .iter()
+            .filter_map(|action| action.causes.as_ref())
+            .flatten()
+            .any(|cause| cause.user_id.as_deref().is_some_and(|id| id.eq_ignore_ascii_case(user_id)))
+    }
+
+    /// True if the build was triggered by build `number` of the job with the given full name
+    pub fn started_by_upstream(&self, upstream_project: &str, number: i32) -> bool {
+        self.actions
+            .iter()
+            .filter_map(|action| action.causes.as_ref())
+            .flatten()
+            .any(|cause| cause.upstream_project.as_deref() == Some(upstream_project) && cause.upstream_build == Some(number))
+    }
+
+    /// Who or what started the build: the user's name, or the cause (e.g. "timer") for automatic builds
+    pub fn triggered_by(&self) -> Option<String> {
+        let cause = self.actions.iter().filter_map(|action| action.causes.as_ref()).flatten().next()?;
+        if let Some(user) = cause.user_name.as_ref().or(cause.user_id.as_ref()) {
+            return Some(user.clone());
+        }
+        let description = cause.short_description.as_deref()?;
+        Some(description.strip_prefix("Started by ").unwrap_or(description).to_string())
+    }
+
+    /// Estimated completion percentage of a running build, based on Jenkins' duration estimate
+    pub fn progress_percent(&self, now_ms: i64) -> Option<u8> {
+        if !self.building.unwrap_or(false) {
+            return None;
+        }
+        let started = self.timestamp?;
+        let estimate = self.estimated_duration.filter(|e| *e > 0)?;
+        let elapsed = (now_ms - started).max(0);
+        Some((elapsed * 100 / estimate).min(99) as u8)
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -49,293 +258,2854 @@ pub struct BuildDetails {
     pub duration: i64,
     #[serde(rename = "fullDisplayName")]
     pub full_display_name: String,
+    #[serde(default)]
+    pub actions: Vec<BuildAction>,
+    /// Commits picked up by a pipeline, one set per checkout
+    #[serde(rename = "changeSets", default)]
+    pub change_sets: Vec<ChangeSet>,
+    /// Commits picked up by a freestyle job
+    #[serde(rename = "changeSet", default)]
+    pub change_set: Option<ChangeSet>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
-pub struct JobProperty {
-    #[serde(rename = "parameterDefinitions")]
-    pub parameter_definitions: Option<Vec<ParameterDefinition>>,
+pub struct ChangeSet {
+    /// SCM the changes came from, e.g. "git"
+    pub kind: Option<String>,
+    #[serde(default)]
+    pub items: Vec<ChangeSetItem>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
-pub struct ParameterDefinition {
-    #[serde(rename = "_class")]
-    pub class: String,
-    pub name: String,
-    #[serde(rename = "type")]
-    pub param_type: String,
-    pub description: Option<String>,
-    #[serde(rename = "defaultParameterValue")]
-    pub default_value: Option<DefaultParameterValue>,
-    pub choices: Option<Vec<String>>,
+pub struct ChangeSetItem {
+    #[serde(rename = "commitId")]
+    pub commit_id: Option<String>,
+    pub msg: Option<String>,
+    pub author: Option<ChangeAuthor>,
+    pub timestamp: Option<i64>,
+    #[serde(rename = "affectedPaths", default)]
+    pub affected_paths: Vec<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
-pub struct DefaultParameterValue {
-    pub value: Option<serde_json::Value>,
+pub struct ChangeAuthor {
+    #[serde(rename = "fullName")]
+    pub full_name: String,
 }
 
-#[derive(Debug, Clone)]
-pub struct ParameterValue {
-    pub name: String,
-    pub value: String,
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct BuildAction {
+    #[serde(rename = "_class")]
+    pub class: Option<String>,
+    pub parameters: Option<Vec<BuildParameter>>,
+    pub causes: Option<Vec<BuildCause>>,
+    /// Git plugin BuildData: the revision the build checked out
+    #[serde(rename = "lastBuiltRevision")]
+    pub last_built_revision: Option<GitRevision>,
+    #[serde(rename = "remoteUrls")]
+    pub remote_urls: Option<Vec<String>>,
 }
 
-impl JenkinsClient {
-    pub fn new(host: JenkinsHost) -> Result<Self> {
-        let client = Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .build()
-            .context("Failed to create HTTP client")?;
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct GitRevision {
+    #[serde(rename = "SHA1")]
+    pub sha1: String,
+}
 
-        Ok(Self { client, host })
-    }
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct BuildCause {
+    #[serde(rename = "shortDescription")]
+    pub short_description: Option<String>,
+    #[serde(rename = "userId")]
+    pub user_id: Option<String>,
+    #[serde(rename = "userName")]
+    pub user_name: Option<String>,
+    /// Full name of the job whose build triggered this one
+    #[serde(rename = "upstreamProject", default, skip_serializing_if = "Option::is_none")]
+    pub upstream_project: Option<String>,
+    #[serde(rename = "upstreamBuild", default, skip_serializing_if = "Option::is_none")]
+    pub upstream_build: Option<i32>,
+}
 
-    pub fn get_root_jobs(&self) -> Result<Vec<SubJobInfo>> {
-        let url = format!(
-            "{}?tree=jobs[name,url,color]",
-            build_api_url(&self.host.host)
-        );
+/// A build triggered by another build
+#[derive(Debug, Clone, PartialEq)]
+pub struct DownstreamBuild {
+    /// Jenkins job path (`team/job/deploy`)
+    pub job_name: String,
+    pub number: i32,
+}
 
-        let response = self
-            .client
-            .get(&url)
-            .basic_auth(&self.host.user, Some(&self.host.token))
-            .send()
-            .context("Failed to send request")?;
+/// The builds a build triggered so far
+#[derive(Debug, Default, PartialEq)]
+pub struct Downstream {
+    pub builds: Vec<DownstreamBuild>,
+    /// More may still show up: a triggered build is still queued, or a downstream project hasn't started one yet
+    pub pending: bool,
+}
 
-        #[derive(Deserialize)]
-        struct RootResponse {
-            jobs: Vec<SubJobInfo>,
-        }
+/// Recent builds of a downstream project checked for the upstream cause
+const DOWNSTREAM_BUILDS_CHECKED: usize = 10;
 
-        let root: RootResponse = response
-            .error_for_status()
-            .context("Request failed")?
-            .json()
-            .context("Failed to parse response")?;
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct BuildParameter {
+    pub name: String,
+    pub value: Option<serde_json::Value>,
+}
 
-        Ok(root.jobs)
+impl BuildDetails {
+    /// Parameters the build was triggered with (from the ParametersAction)
+    pub fn parameters(&self) -> Vec<&BuildParameter> {
+        self.actions
+            .iter()
+            .filter_map(|action| action.parameters.as_ref())
+            .flatten()
+            .collect()
     }
 
-    pub fn get_job(&self, job_name: &str) -> Result<JobInfo> {
-        let url = format!(
-            "{}/api/json",
-            build_job_url(&self.host.host, job_name)
-        );
-
-        let response = self
-            .client
-            .get(&url)
-            .basic_auth(&self.host.user, Some(&self.host.token))
-            .send()
-            .context("Failed to send request")?;
+    /// Every commit the build picked up, from all of its checkouts
+    pub fn changes(&self) -> Vec<&ChangeSetItem> {
+        self.change_sets
+            .iter()
+            .chain(self.change_set.as_ref())
+            .flat_map(|set| set.items.iter())
+            .collect()
+    }
 
-        if response.status() == StatusCode::NOT_FOUND {
-            anyhow::bail!("Job '{}' not found", job_name);
-        }
+    /// Causes recorded for the build, e.g. the user or upstream build that started it
+    pub fn causes(&self) -> Vec<&BuildCause> {
+        self.actions
+            .iter()
+            .filter_map(|action| action.causes.as_ref())
+            .flatten()
+            .collect()
+    }
 
-        response
-            .error_for_status()
-            .context("Request failed")?
-            .json::<JobInfo>()
-            .context("Failed to parse response")
+    /// Commit SHA and remote URL of the first git checkout recorded by the build
+    pub fn git_revision(&self) -> Option<(&str, &str)> {
+        self.actions.iter().find_map(|action| {
+            let sha = action.last_built_revision.as_ref()?.sha1.as_str();
+            let remote = action.remote_urls.as_ref()?.first()?.as_str();
+            Some((sha, remote))
+        })
     }
+}
 
-    pub fn get_build(&self, job_name: &str, build_number: i32) -> Result<BuildDetails> {
-        let url = format!(
-            "{}/api/json",
-            crate::helpers::url::build_build_url(&self.host.host, job_name, build_number)
-        );
+impl BuildParameter {
+    /// Format the parameter value for display
+    pub fn display_value(&self) -> String {
+        match &self.value {
+            Some(serde_json::Value::String(s)) => s.clone(),
+            Some(serde_json::Value::Null) | None => String::new(),
+            Some(v) => v.to_string(),
+        }
+    }
+}
 
-        let response = self
-            .client
-            .get(&url)
-            .basic_auth(&self.host.user, Some(&self.host.token))
-            .send()
-            .context("Failed to send request")?;
+/// State of a queue item while waiting for a build to start
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueueItemStatus {
+    pub build_number: Option<i32>,
+    /// Jenkins' explanation of why the item is still waiting
+    pub why: Option<String>,
+    pub cancelled: bool,
+}
 
-        response
-            .error_for_status()
-            .context("Request failed")?
-            .json::<BuildDetails>()
-            .context("Failed to parse response")
-    }
+/// A build occupying an executor somewhere on the instance
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunningBuild {
+    pub name: String,
+    pub url: Option<String>,
+    pub timestamp: Option<i64>,
+    pub estimated_duration: Option<i64>,
+    /// Display name of the node the executor belongs to
+    pub node: String,
+    /// Flyweight executors run pipeline top-level code on the controller, without a real slot
+    pub flyweight: bool,
+}
 
-    pub fn get_console_log(&self, job_name: &str, build_number: i32) -> Result<String> {
-        let url = format!(
-            "{}/consoleText",
-            crate::helpers::url::build_build_url(&self.host.host, job_name, build_number)
-        );
+/// An agent or the built-in node, with the health data its node monitors last recorded
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct NodeInfo {
+    #[serde(rename = "_class")]
+    pub class: Option<String>,
+    #[serde(rename = "displayName")]
+    pub display_name: String,
+    #[serde(default)]
+    pub offline: bool,
+    /// Taken offline by someone, as opposed to disconnected
+    #[serde(rename = "temporarilyOffline", default)]
+    pub temporarily_offline: bool,
+    #[serde(rename = "numExecutors", default)]
+    pub num_executors: u32,
+    #[serde(rename = "monitorData", default)]
+    pub monitor_data: MonitorData,
+    /// Labels jobs can ask for, including the node's own name
+    #[serde(rename = "assignedLabels", default)]
+    pub assigned_labels: Vec<NodeLabel>,
+    #[serde(default)]
+    pub executors: Vec<NodeExecutor>,
+}
 
-        let response = self
-            .client
-            .get(&url)
-            .basic_auth(&self.host.user, Some(&self.host.token))
-            .send()
-            .context("Failed to send request")?;
+impl NodeInfo {
+    /// The built-in node, which runs builds on the controller itself
+    pub fn is_built_in(&self) -> bool {
+        self.class.as_deref().is_some_and(|class| class.ends_with("MasterComputer"))
+    }
 
-        response
-            .error_for_status()
-            .context("Request failed")?
-            .text()
-            .context("Failed to read response")
+    /// Labels given to the node, without the one every node gets from its own name
+    pub fn labels(&self) -> Vec<&str> {
+        let own: &[&str] = if self.is_built_in() { &["built-in", "master"] } else { &[self.display_name.as_str()] };
+        self.assigned_labels
+            .iter()
+            .map(|label| label.name.as_str())
+            .filter(|name| !own.contains(name))
+            .collect()
     }
+}
 
-    pub fn get_job_parameters(&self, job_name: &str) -> Result<Vec<ParameterDefinition>> {
-        let url = format!(
-            "{}/api/json?tree=property[parameterDefinitions[*]]",
-            build_job_url(&self.host.host, job_name)
-        );
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct NodeLabel {
+    pub name: String,
+}
 
-        let response = self
-            .client
-            .get(&url)
-            .basic_auth(&self.host.user, Some(&self.host.token))
-            .send()
-            .context("Failed to send request")?;
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct NodeExecutor {
+    #[serde(default)]
+    pub idle: bool,
+}
 
-        let job_info: JobInfo = response
-            .error_for_status()
-            .context("Request failed")?
-            .json()
-            .context("Failed to parse response")?;
+/// Node monitor readings; a monitor that hasn't run yet (or is disabled) reports nothing
+#[derive(Debug, Deserialize, Clone, PartialEq, Default)]
+pub struct MonitorData {
+    #[serde(rename = "hudson.node_monitors.DiskSpaceMonitor")]
+    pub disk_space: Option<SpaceMonitor>,
+    #[serde(rename = "hudson.node_monitors.TemporarySpaceMonitor")]
+    pub temp_space: Option<SpaceMonitor>,
+    #[serde(rename = "hudson.node_monitors.SwapSpaceMonitor")]
+    pub swap_space: Option<SwapMonitor>,
+    #[serde(rename = "hudson.node_monitors.ResponseTimeMonitor")]
+    pub response_time: Option<ResponseTimeMonitor>,
+    #[serde(rename = "hudson.node_monitors.ClockMonitor")]
+    pub clock: Option<ClockMonitor>,
+}
 
-        // Extract parameter definitions from properties
-        if let Some(properties) = job_info.property {
-            for prop in properties {
-                if let Some(params) = prop.parameter_definitions {
-                    return Ok(params);
-                }
-            }
-        }
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct SpaceMonitor {
+    /// Free space in bytes
+    pub size: i64,
+}
 
-        // No parameters found - return empty vec
-        Ok(vec![])
-    }
+/// Sizes are in bytes, -1 where the agent couldn't tell
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct SwapMonitor {
+    #[serde(rename = "availableSwapSpace")]
+    pub available_swap_space: i64,
+    #[serde(rename = "totalSwapSpace")]
+    pub total_swap_space: i64,
+}
 
-    pub fn trigger_build(&self, job_name: &str, parameters: Option<Vec<ParameterValue>>) -> Result<Option<String>> {
-        let (url, form_data) = if let Some(params) = parameters {
-            // Use buildWithParameters endpoint
-            let url = format!(
-                "{}/buildWithParameters",
-                build_job_url(&self.host.host, job_name)
-            );
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ResponseTimeMonitor {
+    /// Average round trip of the last few pings, in milliseconds
+    pub average: i64,
+}
 
-            // Build form data: param1=value1&param2=value2
-            let mut form_pairs: Vec<(String, String)> = Vec::new();
-            for param in params {
-                form_pairs.push((param.name, param.value));
-            }
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ClockMonitor {
+    /// How far the node's clock is ahead of the controller's, in milliseconds (negative if behind)
+    pub diff: i64,
+}
 
-            (url, Some(form_pairs))
-        } else {
-            // Use regular build endpoint
-            let url = format!(
-                "{}/build",
-                build_job_url(&self.host.host, job_name)
-            );
-            (url, None)
-        };
+/// Load statistics of the whole instance, as `/overallLoad` reports them
+#[derive(Debug, Deserialize, Clone, PartialEq, Default)]
+pub struct OverallLoad {
+    #[serde(rename = "queueLength", default)]
+    pub queue_length: LoadStatistic,
+    #[serde(rename = "busyExecutors", default)]
+    pub busy_executors: LoadStatistic,
+    #[serde(rename = "totalExecutors", default)]
+    pub total_executors: LoadStatistic,
+}
 
-        let mut request = self.client.post(&url)
-            .basic_auth(&self.host.user, Some(&self.host.token));
+/// One statistic sampled over time; only the per-minute series is read
+#[derive(Debug, Deserialize, Clone, PartialEq, Default)]
+pub struct LoadStatistic {
+    #[serde(default)]
+    pub min: LoadSeries,
+}
 
-        // Add form data if parameters exist
-        if let Some(form) = form_data {
-            request = request.form(&form);
+#[derive(Debug, Deserialize, Clone, PartialEq, Default)]
+pub struct LoadSeries {
+    pub latest: Option<f64>,
+    /// Newest sample first
+    #[serde(default)]
+    pub history: Vec<f64>,
+}
+
+impl LoadSeries {
+    /// Average of the newest `samples` samples, or of all of them on a controller that hasn't collected that many yet
+    pub fn average(&self, samples: usize) -> Option<f64> {
+        let window = &self.history[..samples.min(self.history.len())];
+        if window.is_empty() {
+            return self.latest;
         }
+        Some(window.iter().sum::<f64>() / window.len() as f64)
+    }
+}
 
-        let response = request
-            .send()
-            .context("Failed to send request")?;
+/// An item waiting in the build queue
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct QueuedItem {
+    pub id: i64,
+    pub why: Option<String>,
+    #[serde(rename = "inQueueSince")]
+    pub in_queue_since: Option<i64>,
+    #[serde(default)]
+    pub stuck: bool,
+    /// Waiting only for a free executor, not blocked or in its quiet period
+    #[serde(default)]
+    pub buildable: bool,
+    pub task: QueuedTask,
+}
 
-        let response = response
-            .error_for_status()
-            .context("Failed to trigger build")?;
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct QueuedTask {
+    pub name: Option<String>,
+    pub url: Option<String>,
+}
 
-        // Get queue item location from Location header
-        let queue_location = response
-            .headers()
-            .get("Location")
-            .and_then(|v| v.to_str().ok())
-            .map(|s| s.to_string());
+#[derive(Deserialize)]
+struct ComputerSet {
+    #[serde(default)]
+    computer: Vec<Computer>,
+}
 
-        Ok(queue_location)
-    }
+#[derive(Deserialize)]
+struct Computer {
+    #[serde(rename = "displayName")]
+    display_name: String,
+    #[serde(default)]
+    executors: Vec<Executor>,
+    #[serde(default, rename = "oneOffExecutors")]
+    one_off_executors: Vec<Executor>,
+}
 
-    /// Get build number from queue item
-    pub fn get_build_number_from_queue(&self, queue_url: &str) -> Result<Option<i32>> {
-        let api_url = format!("{}api/json", normalize_host_url(queue_url));
+#[derive(Deserialize)]
+struct Executor {
+    #[serde(rename = "currentExecutable")]
+    current_executable: Option<Executable>,
+}
 
-        let response = self
-            .client
-            .get(&api_url)
-            .basic_auth(&self.host.user, Some(&self.host.token))
-            .send()
-            .context("Failed to query queue item")?;
+#[derive(Deserialize)]
+struct Executable {
+    url: Option<String>,
+    #[serde(rename = "fullDisplayName")]
+    full_display_name: Option<String>,
+    timestamp: Option<i64>,
+    #[serde(rename = "estimatedDuration")]
+    estimated_duration: Option<i64>,
+}
 
-        #[derive(Deserialize)]
-        struct QueueItem {
-            executable: Option<QueueExecutable>,
+impl ComputerSet {
+    fn running_builds(self) -> Vec<RunningBuild> {
+        let mut builds = Vec::new();
+        for computer in self.computer {
+            let executors = computer
+                .executors
+                .into_iter()
+                .map(|e| (e, false))
+                .chain(computer.one_off_executors.into_iter().map(|e| (e, true)));
+
+            for (executor, flyweight) in executors {
+                let Some(executable) = executor.current_executable else {
+                    continue;
+                };
+                builds.push(RunningBuild {
+                    name: executable
+                        .full_display_name
+                        .or_else(|| executable.url.clone())
+                        .unwrap_or_else(|| "(unknown)".to_string()),
+                    url: executable.url,
+                    timestamp: executable.timestamp,
+                    estimated_duration: executable.estimated_duration,
+                    node: computer.display_name.clone(),
+                    flyweight,
+                });
+            }
         }
+        builds
+    }
+}
 
+/// A pipeline stage from the Pipeline Stage View (wfapi) API
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct PipelineStage {
+    pub id: String,
+    pub name: String,
+    /// SUCCESS, FAILED, IN_PROGRESS, NOT_EXECUTED, ABORTED, UNSTABLE or PAUSED_PENDING_INPUT
+    pub status: String,
+    /// Start time in epoch milliseconds, 0 for stages that never ran
+    #[serde(rename = "startTimeMillis", default)]
+    pub start_time: Option<i64>,
+    /// Time taken in milliseconds, so far for a running stage
+    #[serde(rename = "durationMillis", default)]
+    pub duration: Option<i64>,
+}
+
+/// API token created through the token-generation endpoint
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct GeneratedToken {
+    #[serde(rename = "tokenName")]
+    pub name: String,
+    #[serde(rename = "tokenUuid")]
+    pub uuid: String,
+    #[serde(rename = "tokenValue")]
+    pub value: String,
+}
+
+/// Body of the token-generation endpoint's answer
+#[derive(Deserialize)]
+struct GeneratedTokenResponse {
+    data: GeneratedToken,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct InjectedEnvVars {
+    #[serde(rename = "envMap", default)]
+    pub env_map: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct JacocoReport {
+    #[serde(rename = "lineCoverage")]
+    pub line_coverage: Option<JacocoCounter>,
+    #[serde(rename = "branchCoverage")]
+    pub branch_coverage: Option<JacocoCounter>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct JacocoCounter {
+    pub covered: i64,
+    pub missed: i64,
+    pub total: i64,
+    #[serde(rename = "percentageFloat")]
+    pub percentage: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct CoberturaReport {
+    pub results: Option<CoberturaResults>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct CoberturaResults {
+    #[serde(default)]
+    pub elements: Vec<CoberturaElement>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct CoberturaElement {
+    pub name: String,
+    pub ratio: f64,
+}
+
+/// Line/branch coverage percentages normalized across coverage plugins
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoverageSummary {
+    pub source: String,
+    pub line: Option<f64>,
+    pub branch: Option<f64>,
+}
+
+impl From<JacocoReport> for CoverageSummary {
+    fn from(report: JacocoReport) -> Self {
+        fn percentage(counter: Option<JacocoCounter>) -> Option<f64> {
+            let counter = counter?;
+            counter.percentage.or_else(|| {
+                (counter.total > 0).then(|| counter.covered as f64 * 100.0 / counter.total as f64)
+            })
+        }
+
+        CoverageSummary {
+            source: "JaCoCo".to_string(),
+            line: percentage(report.line_coverage),
+            branch: percentage(report.branch_coverage),
+        }
+    }
+}
+
+impl From<CoberturaReport> for CoverageSummary {
+    fn from(report: CoberturaReport) -> Self {
+        let elements = report.results.map(|r| r.elements).unwrap_or_default();
+        let ratio = |name: &str| elements.iter().find(|e| e.name == name).map(|e| e.ratio);
+
+        CoverageSummary {
+            source: "Cobertura".to_string(),
+            line: ratio("Lines"),
+            branch: ratio("Conditionals"),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct WarningsTool {
+    pub id: String,
+    pub name: String,
+    pub size: Option<i64>,
+}
+
+/// Body of warnings-ng's per-build overview: one entry per analysis tool
+#[derive(Deserialize)]
+struct WarningsToolsResponse {
+    #[serde(default)]
+    tools: Vec<WarningsTool>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct WarningsSummary {
+    #[serde(rename = "totalSize", default)]
+    pub total_size: i64,
+    #[serde(rename = "newSize", default)]
+    pub new_size: i64,
+    #[serde(rename = "fixedSize", default)]
+    pub fixed_size: i64,
+}
+
+impl WarningsSummary {
+    /// Issues that existed before this build and are still present
+    pub fn outstanding(&self) -> i64 {
+        self.total_size - self.new_size
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct TestReport {
+    #[serde(rename = "failCount", default)]
+    pub fail_count: i64,
+    #[serde(rename = "passCount", default)]
+    pub pass_count: i64,
+    #[serde(rename = "skipCount", default)]
+    pub skip_count: i64,
+    #[serde(default)]
+    pub suites: Vec<TestSuite>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct TestSuite {
+    pub name: String,
+    #[serde(default)]
+    pub cases: Vec<TestCase>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct TestCase {
+    #[serde(rename = "className")]
+    pub class_name: String,
+    pub name: String,
+    pub status: String,
+    #[serde(default)]
+    pub duration: f64,
+    #[serde(rename = "errorDetails")]
+    pub error_details: Option<String>,
+    #[serde(rename = "errorStackTrace")]
+    pub error_stack_trace: Option<String>,
+}
+
+impl TestCase {
+    /// FAILED and REGRESSION both mean the test failed in this build
+    pub fn is_failed(&self) -> bool {
+        matches!(self.status.as_str(), "FAILED" | "REGRESSION")
+    }
+}
+
+impl TestReport {
+    pub fn failed_cases(&self) -> Vec<&TestCase> {
+        self.suites
+            .iter()
+            .flat_map(|suite| suite.cases.iter())
+            .filter(|case| case.is_failed())
+            .collect()
+    }
+}
+
+/// A file archived by a build
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Artifact {
+    #[serde(rename = "fileName")]
+    pub file_name: String,
+    #[serde(rename = "relativePath")]
+    pub relative_path: String,
+}
+
+/// What If-Range can resume a download against: a strong ETag, or else the Last-Modified date
+/// (weak ETags aren't allowed there)
+fn resume_validator(etag: Option<&str>, last_modified: Option<&str>) -> Option<String> {
+    etag.filter(|etag| !etag.starts_with("W/")).or(last_modified).map(str::to_string)
+}
+
+/// Body of an artifact download
+pub struct ArtifactStream {
+    /// Position in the artifact the stream starts at (non-zero when resuming)
+    pub offset: u64,
+    /// Full size of the artifact announced by Jenkins, if any
+    pub size: Option<u64>,
+    /// Strong ETag or Last-Modified of the artifact, to resume this download with later
+    pub validator: Option<String>,
+    response: Response,
+}
+
+impl std::io::Read for ArtifactStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.response.read(buf)
+    }
+}
+
+/// MD5 Jenkins recorded for a file when fingerprinting was enabled
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Fingerprint {
+    #[serde(rename = "fileName")]
+    pub file_name: String,
+    pub hash: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct BuildArtifacts {
+    #[serde(default)]
+    pub artifacts: Vec<Artifact>,
+    #[serde(default)]
+    pub fingerprint: Vec<Fingerprint>,
+}
+
+impl BuildArtifacts {
+    /// Recorded MD5 of an artifact, matched by path first and then by an unambiguous file name
+    pub fn recorded_md5(&self, artifact: &Artifact) -> Option<&str> {
+        if let Some(exact) = self.fingerprint.iter().find(|f| f.file_name == artifact.relative_path) {
+            return Some(&exact.hash);
+        }
+        let mut by_name = self.fingerprint.iter().filter(|f| f.file_name == artifact.file_name);
+        match (by_name.next(), by_name.next()) {
+            (Some(only), None) => Some(&only.hash),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct JobProperty {
+    #[serde(rename = "parameterDefinitions")]
+    pub parameter_definitions: Option<Vec<ParameterDefinition>>,
+    pub triggers: Option<Vec<JobTrigger>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct JobTrigger {
+    #[serde(rename = "_class")]
+    pub class: Option<String>,
+    pub spec: Option<String>,
+    #[serde(rename = "upstreamProjects")]
+    pub upstream_projects: Option<String>,
+}
+
+impl JobTrigger {
+    /// Human-readable label for the trigger type
+    pub fn kind(&self) -> String {
+        match self.class.as_deref() {
+            Some("hudson.triggers.TimerTrigger") => "Schedule".to_string(),
+            Some("hudson.triggers.SCMTrigger") => "SCM polling".to_string(),
+            Some("jenkins.triggers.ReverseBuildTrigger") => "Upstream".to_string(),
+            Some("com.cloudbees.jenkins.GitHubPushTrigger") => "GitHub push".to_string(),
+            Some(class) => class.rsplit('.').next().unwrap_or(class).to_string(),
+            None => "Unknown".to_string(),
+        }
+    }
+
+    /// Trigger details (cron spec or upstream jobs)
+    pub fn detail(&self) -> String {
+        if let Some(upstream) = self.upstream_projects.as_deref().filter(|u| !u.is_empty()) {
+            return upstream.to_string();
+        }
+        // Cron specs may span multiple lines and contain comments
+        self.spec
+            .as_deref()
+            .unwrap_or("")
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+
+    /// When a schedule or SCM polling trigger of the job fires next, in epoch millis
+    /// The spec is read in the local time zone, as if the controller ran in it
+    pub fn next_run(&self, job_name: &str, now_ms: i64) -> Option<i64> {
+        if !matches!(self.class.as_deref(), Some("hudson.triggers.TimerTrigger" | "hudson.triggers.SCMTrigger")) {
+            return None;
+        }
+        let now = chrono::DateTime::from_timestamp_millis(now_ms)?.with_timezone(&chrono::Local);
+        cron::next_run(self.spec.as_deref()?, &to_display_job_path(job_name), &now).map(|at| at.timestamp_millis())
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ParameterDefinition {
+    #[serde(rename = "_class")]
+    pub class: String,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub param_type: String,
+    pub description: Option<String>,
+    #[serde(rename = "defaultParameterValue")]
+    pub default_value: Option<DefaultParameterValue>,
+    pub choices: Option<Vec<String>>,
+    /// Job whose builds a RunParameterDefinition selects from
+    #[serde(rename = "projectName")]
+    pub project_name: Option<String>,
+    /// Which runs a RunParameterDefinition accepts (ALL, COMPLETED, SUCCESSFUL, STABLE)
+    pub filter: Option<String>,
+    /// Credential class accepted by a CredentialsParameterDefinition
+    #[serde(rename = "credentialType")]
+    pub credential_type: Option<String>,
+}
+
+impl ParameterDefinition {
+    /// True if a build with the given result can be chosen for this run parameter
+    pub fn accepts_run_result(&self, result: Option<&str>) -> bool {
+        match self.filter.as_deref() {
+            Some("COMPLETED") => result.is_some(),
+            Some("SUCCESSFUL") => matches!(result, Some("SUCCESS") | Some("UNSTABLE")),
+            Some("STABLE") => result == Some("SUCCESS"),
+            _ => true,
+        }
+    }
+
+    /// True if a credential can be chosen for this credentials parameter
+    /// Credentials of unknown kinds, and parameters accepting any kind, don't narrow the choice
+    pub fn accepts_credential(&self, credential: &CredentialInfo) -> bool {
+        let kinds = self.credential_type.as_deref().and_then(credential_type_names);
+        match (kinds, credential.type_name.as_deref()) {
+            (Some(kinds), Some(type_name)) => kinds.contains(&type_name),
+            _ => true,
+        }
+    }
+}
+
+/// The `typeName`s the credentials API shows for a credential class or interface
+fn credential_type_names(credential_type: &str) -> Option<&'static [&'static str]> {
+    let class = credential_type.rsplit('.').next().unwrap_or(credential_type);
+    let names: &'static [&'static str] = match class {
+        "UsernamePasswordCredentialsImpl" | "StandardUsernamePasswordCredentials" | "UsernamePasswordCredentials" => {
+            &["Username with password"]
+        }
+        "StandardUsernameCredentials" => &["Username with password", "SSH Username with private key"],
+        "BasicSSHUserPrivateKey" | "SSHUserPrivateKey" => &["SSH Username with private key"],
+        "StringCredentialsImpl" | "StringCredentials" => &["Secret text"],
+        "FileCredentialsImpl" | "FileCredentials" => &["Secret file"],
+        "CertificateCredentialsImpl" | "StandardCertificateCredentials" | "CertificateCredentials" => &["Certificate"],
+        _ => return None,
+    };
+    Some(names)
+}
+
+/// A credential entry from the credentials plugin API
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct CredentialInfo {
+    pub id: String,
+    #[serde(rename = "displayName")]
+    pub display_name: Option<String>,
+    #[serde(rename = "typeName")]
+    pub type_name: Option<String>,
+}
+
+/// Body of a credentials store domain
+#[derive(Deserialize)]
+struct CredentialsResponse {
+    #[serde(default)]
+    credentials: Vec<CredentialInfo>,
+}
+
+/// What the controller reveals without credentials
+#[derive(Debug, Clone)]
+pub struct ServerProbe {
+    pub status: StatusCode,
+    /// The `X-Jenkins` header
+    pub version: Option<String>,
+    /// The server clock from the `Date` header, in epoch millis
+    pub date: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct WhoAmI {
+    pub name: String,
+    #[serde(default)]
+    pub anonymous: bool,
+    #[serde(default)]
+    pub authorities: Vec<String>,
+}
+
+/// An installed plugin from the plugin manager API
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct InstalledPlugin {
+    #[serde(rename = "shortName")]
+    pub short_name: String,
+    #[serde(rename = "longName", default)]
+    pub long_name: String,
+    pub version: String,
+    #[serde(rename = "hasUpdate", default)]
+    pub has_update: bool,
+    #[serde(default)]
+    pub active: bool,
+    #[serde(default)]
+    pub dependencies: Vec<PluginDependency>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct PluginDependency {
+    #[serde(rename = "shortName")]
+    pub short_name: String,
+    pub version: String,
+    #[serde(default)]
+    pub optional: bool,
+}
+
+/// A newer plugin version offered by an update site
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct PluginUpdate {
+    pub name: String,
+    pub version: String,
+    /// Security advisories affecting the installed version
+    #[serde(default)]
+    pub warnings: Vec<SecurityWarning>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct SecurityWarning {
+    pub id: String,
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct DefaultParameterValue {
+    pub value: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ParameterValue {
+    pub name: String,
+    pub value: String,
+    /// Local file uploaded for a FileParameterDefinition
+    pub file: Option<PathBuf>,
+}
+
+/// Tokens (or passwords) of the clients created in this process
+static TOKENS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Mask every known token in a response body
+/// Servers and proxies sometimes echo credentials back in their error pages
+fn scrub_tokens(body: &str) -> String {
+    let tokens = TOKENS.lock().unwrap_or_else(|e| e.into_inner());
+    tokens.iter().fold(body.to_string(), |body, token| body.replace(token.as_str(), "****"))
+}
+
+/// Sends a request, recording its timing and outcome in the debug log (`--log-file`)
+trait SendTraced {
+    fn send_traced(self) -> reqwest::Result<Response>;
+}
+
+impl SendTraced for RequestBuilder {
+    fn send_traced(self) -> reqwest::Result<Response> {
+        let (client, request) = self.build_split();
+        let request = request?;
+        let method = request.method().clone();
+        let url = request.url().clone();
+
+        let started = Instant::now();
+        let result = client.execute(request);
+        let elapsed_ms = started.elapsed().as_millis() as u64;
+
+        match &result {
+            Ok(response) => tracing::debug!(%method, %url, status = response.status().as_u16(), elapsed_ms, "request finished"),
+            Err(e) => tracing::warn!(%method, %url, elapsed_ms, error = %e, "request failed"),
+        }
+        result
+    }
+}
+
+/// Parses a JSON response, recording bodies that don't parse in the debug log (`--log-file`)
+trait JsonTraced {
+    fn json_traced<T: DeserializeOwned>(self) -> Result<T>;
+}
+
+impl JsonTraced for Response {
+    fn json_traced<T: DeserializeOwned>(self) -> Result<T> {
+        let url = self.url().to_string();
+        let body = self.text().context("Failed to read response")?;
+        parse_traced(&url, &body)
+    }
+}
+
+/// Parse a JSON body, logging the URL, the error and an excerpt of the body when it doesn't parse
+fn parse_traced<T: DeserializeOwned>(url: &str, body: &str) -> Result<T> {
+    serde_json::from_str(body).map_err(|e| {
+        let excerpt = body_excerpt(body, false).unwrap_or_default();
+        tracing::warn!(%url, error = %e, body = %excerpt, "failed to parse response");
+        e.into()
+    })
+}
+
+/// Longest response body excerpt included in error messages
+const ERROR_BODY_EXCERPT_LEN: usize = 300;
+
+/// Turns non-2xx responses into errors that carry the status, URL and a body excerpt
+trait CheckStatus: Sized {
+    fn check_status(self, context: &str) -> Result<Self>;
+}
+
+impl CheckStatus for Response {
+    fn check_status(self, context: &str) -> Result<Self> {
+        let status = self.status();
+        if !status.is_client_error() && !status.is_server_error() {
+            return Ok(self);
+        }
+
+        let url = self.url().to_string();
+        let is_html = self
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.contains("html"));
+        let body = scrub_tokens(&self.text().unwrap_or_default());
+
+        tracing::debug!(%url, status = status.as_u16(), body = %body, "error response");
+
+        let message = match body_excerpt(&body, is_html) {
+            Some(excerpt) => format!("{}: HTTP {} from {}\n  {}", context, status, url, excerpt),
+            None => format!("{}: HTTP {} from {}", context, status, url),
+        };
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Err(ErrorKind::Auth.error(message)),
+            StatusCode::NOT_FOUND => Err(ErrorKind::NotFound.error(message)),
+            _ => Err(anyhow::anyhow!(message)),
+        }
+    }
+}
+
+/// The first `<argument>` of an agent JNLP file is its secret
+fn agent_secret_from_jnlp(jnlp: &str) -> Option<String> {
+    let start = jnlp.find("<argument>")? + "<argument>".len();
+    let end = start + jnlp[start..].find("</argument>")?;
+    let secret = jnlp[start..end].trim();
+    (!secret.is_empty()).then(|| secret.to_string())
+}
+
+/// A short, single-line summary of an error response body
+/// HTML pages (Jenkins' error pages) are reduced to their visible text
+fn body_excerpt(body: &str, is_html: bool) -> Option<String> {
+    let text = if is_html { html_to_text(body) } else { body.to_string() };
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if text.is_empty() {
+        return None;
+    }
+
+    if text.chars().count() > ERROR_BODY_EXCERPT_LEN {
+        let truncated: String = text.chars().take(ERROR_BODY_EXCERPT_LEN).collect();
+        return Some(format!("{}…", truncated.trim_end()));
+    }
+    Some(text)
+}
+
+/// Strip tags, scripts and styles from an HTML page, keeping the visible text
+fn html_to_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        text.push(' ');
+        rest = &rest[start..];
+
+        let tag = rest.get(1..8).unwrap_or_default().to_ascii_lowercase();
+        let skip_until = if tag.starts_with("script") {
+            Some("</script>")
+        } else if tag.starts_with("style") {
+            Some("</style>")
+        } else {
+            None
+        };
+
+        let end = match skip_until {
+            Some(close) => rest.to_ascii_lowercase().find(close).map(|i| i + close.len()),
+            None => rest.find('>').map(|i| i + 1),
+        };
+        rest = &rest[end.unwrap_or(rest.len())..];
+    }
+    text.push_str(rest);
+
+    decode_entities(&text)
+}
+
+/// Replace the HTML entities Jenkins' pages use with their characters
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// The scripts a build loaded besides its Jenkinsfile, by form field, from the textareas of its Replay page
+/// Jenkins names the fields after the script (`Script1`, or a library class with `_` for `.`)
+fn replay_loaded_scripts(html: &str) -> Vec<(String, String)> {
+    let mut scripts = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find("<textarea") {
+        rest = &rest[start..];
+        let Some(tag_end) = rest.find('>') else { break };
+        let tag = &rest[..tag_end];
+        let content_start = tag_end + 1;
+        let Some(content_len) = rest[content_start..].find("</textarea>") else { break };
+        let content = &rest[content_start..content_start + content_len];
+        rest = &rest[content_start + content_len..];
+
+        let Some(name) = tag
+            .split_once("name=\"")
+            .and_then(|(_, value)| value.split_once('"'))
+            .map(|(name, _)| name.trim_start_matches("_."))
+        else {
+            continue;
+        };
+        if name != "mainScript" {
+            // The page puts a newline after the opening tag, which the browser drops too
+            let content = content.strip_prefix('\n').unwrap_or(content);
+            scripts.push((name.to_string(), decode_entities(content)));
+        }
+    }
+    scripts
+}
+
+impl JenkinsClient {
+    pub fn new(host: JenkinsHost) -> Result<Self> {
+        let client = Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .build()
+            .context("Failed to create HTTP client")?;
+
+        if !host.token.is_empty() {
+            let mut tokens = TOKENS.lock().unwrap_or_else(|e| e.into_inner());
+            if !tokens.contains(&host.token) {
+                tokens.push(host.token.clone());
+            }
+        }
+
+        Ok(Self { client, host, name: None, cached_reads: false })
+    }
+
+    /// Allow job and build status to come from the response cache
+    /// Only for commands that just display it; anything acting on the status must see it live
+    pub fn with_cached_reads(mut self) -> Self {
+        self.cached_reads = true;
+        self
+    }
+
+    /// Remember which configured host this client talks to
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Name of the configured host, if the client was created from config
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Refuse a change on a host configured with `read_only: true`, before any request is sent
+    pub fn ensure_writable(&self, action: &str) -> Result<()> {
+        if self.host.read_only {
+            anyhow::bail!(
+                "Jenkins '{}' is read-only: {} is not allowed. Remove 'read_only' from its config to make changes.",
+                self.name().unwrap_or(&self.host.host),
+                action
+            );
+        }
+        Ok(())
+    }
+
+    /// Cache kind for job and build status, if this client may serve it from the cache
+    fn status_cache(&self) -> Option<CacheKind> {
+        self.cached_reads.then_some(CacheKind::Status)
+    }
+
+    /// GET a read-only JSON endpoint, reusing a fresh cached response when `cache` is given
+    fn get_json_cached<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        cache: Option<CacheKind>,
+        not_found: impl FnOnce() -> String,
+    ) -> Result<T> {
+        let scope = format!("{}@{}", self.host.user, normalize_host_url(&self.host.host));
+        if let Some(kind) = cache
+            && let Some(body) = cache::load(&scope, url, kind)
+            && let Ok(value) = parse_traced(url, &body)
+        {
+            return Ok(value);
+        }
+
+        let response = self
+            .client
+            .get(url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(ErrorKind::NotFound.error(not_found()));
+        }
+
+        let body = response
+            .check_status("Request failed")?
+            .text()
+            .context("Failed to read response")?;
+        let value = parse_traced(url, &body).context("Failed to parse response")?;
+
+        if let Some(kind) = cache {
+            cache::store(&scope, url, &body, kind);
+        }
+        Ok(value)
+    }
+
+    /// Jobs and folders directly inside a folder (or at the root)
+    pub fn get_folder_items(&self, folder: Option<&str>) -> Result<Vec<FolderItem>> {
+        let tree = "tree=jobs[name,_class]";
+        let url = match folder {
+            Some(folder) => format!("{}/api/json?{}", build_job_url(&self.host.host, folder), tree),
+            None => format!("{}?{}", build_api_url(&self.host.host), tree),
+        };
+
+        let response = self
+            .client
+            .get(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(ErrorKind::NotFound.error(format!("Folder '{}' not found", folder.unwrap_or_default())));
+        }
+
+        #[derive(Deserialize)]
+        struct ItemsResponse {
+            jobs: Option<Vec<FolderItem>>,
+        }
+
+        let items: ItemsResponse = response
+            .check_status("Request failed")?
+            .json_traced()
+            .context("Failed to parse response")?;
+
+        Ok(items.jobs.unwrap_or_default())
+    }
+
+    /// Jobs anywhere below a folder (or the root), down to `depth` folder levels, in one request
+    pub fn get_descendant_jobs(&self, folder: Option<&str>, depth: usize) -> Result<Vec<DescendantJob>> {
+        let url = match folder {
+            Some(folder) => format!("{}/api/json", build_job_url(&self.host.host, folder)),
+            None => build_api_url(&self.host.host),
+        };
+        let not_found = || format!("Folder '{}' not found", folder.unwrap_or_default());
+        self.get_job_tree(&url, "name,color,_class", depth, CacheKind::Jobs, not_found)
+    }
+
+    /// Like `get_descendant_jobs`, with the builds each job keeps permalinks to
+    /// Starts from a view instead of a folder when `view` is given
+    pub fn get_descendant_jobs_with_builds(
+        &self,
+        folder: Option<&str>,
+        view: Option<&str>,
+        depth: usize,
+    ) -> Result<Vec<DescendantJob>> {
+        let (url, not_found) = match (view, folder) {
+            (Some(view), _) => (
+                format!("{}/view/{}/api/json", normalize_host_url(&self.host.host), encode_path_segment(view)),
+                format!("View '{}' not found", view),
+            ),
+            (None, Some(folder)) => (
+                format!("{}/api/json", build_job_url(&self.host.host, folder)),
+                format!("Folder '{}' not found", folder),
+            ),
+            (None, None) => (build_api_url(&self.host.host), String::new()),
+        };
+        let fields = format!("name,color,_class,{}", BUILD_MARK_FIELDS);
+        self.get_job_tree(&url, &fields, depth, CacheKind::Status, || not_found)
+    }
+
+    /// Fetch `depth` levels of nested jobs below `url` in one request and flatten them
+    fn get_job_tree(
+        &self,
+        url: &str,
+        fields: &str,
+        depth: usize,
+        cache: CacheKind,
+        not_found: impl FnOnce() -> String,
+    ) -> Result<Vec<DescendantJob>> {
+        let tree = (1..depth).fold(format!("jobs[{}]", fields), |inner, _| format!("jobs[{},{}]", fields, inner));
+        let url = format!("{}?tree={}", url, tree);
+
+        #[derive(Deserialize)]
+        struct TreeResponse {
+            #[serde(default)]
+            jobs: Vec<JobTreeNode>,
+        }
+
+        let tree: TreeResponse = self.get_json_cached(&url, Some(cache), not_found)?;
+
+        let mut jobs = Vec::new();
+        flatten_job_tree(tree.jobs, None, &mut jobs);
+        Ok(jobs)
+    }
+
+    /// Get one page of the jobs in a folder (or at the root) using tree range pagination
+    /// Returns an empty list for jobs that aren't folders
+    /// Selection prompts can reuse pages for the jobs TTL; listings that show status pass `CacheKind::Status`
+    pub fn get_jobs_page(
+        &self,
+        folder: Option<&str>,
+        start: usize,
+        count: usize,
+        cache: CacheKind,
+    ) -> Result<Vec<SubJobInfo>> {
+        let tree = format!(
+            "tree=jobs[name,url,color,lastBuild[number,url,building,timestamp,duration]]{{{},{}}}",
+            start,
+            start + count
+        );
+        let url = match folder {
+            Some(folder) => format!("{}/api/json?{}", build_job_url(&self.host.host, folder), tree),
+            None => format!("{}?{}", build_api_url(&self.host.host), tree),
+        };
+
+        #[derive(Deserialize)]
+        struct JobsResponse {
+            #[serde(default)]
+            jobs: Vec<SubJobInfo>,
+        }
+
+        let not_found = || format!("Job '{}' not found", folder.unwrap_or_default());
+        let page: JobsResponse = self.get_json_cached(&url, Some(cache), not_found)?;
+
+        Ok(page.jobs)
+    }
+
+    /// Get the jobs in a folder (or at the root) along with their health reports
+    pub fn get_jobs_health(&self, folder: Option<&str>) -> Result<Vec<SubJobInfo>> {
+        let tree = "tree=jobs[name,url,color,healthReport[score,description]]";
+        let url = match folder {
+            Some(folder) => format!("{}/api/json?{}", build_job_url(&self.host.host, folder), tree),
+            None => format!("{}?{}", build_api_url(&self.host.host), tree),
+        };
+
+        let response = self
+            .client
+            .get(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(ErrorKind::NotFound.error(format!("Folder '{}' not found", folder.unwrap_or_default())));
+        }
+
+        #[derive(Deserialize)]
+        struct JobsResponse {
+            jobs: Option<Vec<SubJobInfo>>,
+        }
+
+        let jobs: JobsResponse = response
+            .check_status("Request failed")?
+            .json_traced()
+            .context("Failed to parse response")?;
+
+        Ok(jobs.jobs.unwrap_or_default())
+    }
+
+    /// Get a job's status and last build details in a single lightweight request
+    pub fn get_job_summary(&self, job_name: &str) -> Result<JobInfo> {
+        let url = format!(
+            "{}/api/json?tree=name,url,color,lastBuild[number,url,result,building,timestamp,duration,estimatedDuration,actions[causes[userId,userName,shortDescription]]]",
+            build_job_url(&self.host.host, job_name)
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(ErrorKind::NotFound.error(format!("Job '{}' not found", job_name)));
+        }
+
+        response
+            .check_status("Request failed")?
+            .json_traced::<JobInfo>()
+            .context("Failed to parse response")
+    }
+
+    /// Find the branch job a path means when its trailing names are one branch name with slashes,
+    /// e.g. `my-service/feature/foo` for the branch `feature/foo` of the `my-service` multibranch project
+    pub fn find_branch_job(&self, job_name: &str) -> Result<Option<String>> {
+        for candidate in branch_job_candidates(job_name) {
+            let url = format!("{}/api/json?tree=name", build_job_url(&self.host.host, &candidate));
+            match self.get_json_cached::<serde_json::Value>(&url, None, String::new) {
+                Ok(_) => return Ok(Some(candidate)),
+                Err(e) if kind_of(&e) == Some(ErrorKind::NotFound) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(None)
+    }
+
+    /// Get the jobs listed in a view
+    pub fn get_view_jobs(&self, view_name: &str) -> Result<Vec<SubJobInfo>> {
+        let url = format!(
+            "{}/view/{}/api/json?tree=jobs[name,url,color]",
+            normalize_host_url(&self.host.host),
+            encode_path_segment(view_name)
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(ErrorKind::NotFound.error(format!("View '{}' not found", view_name)));
+        }
+
+        #[derive(Deserialize)]
+        struct ViewResponse {
+            #[serde(default)]
+            jobs: Vec<SubJobInfo>,
+        }
+
+        let view: ViewResponse = response
+            .check_status("Request failed")?
+            .json_traced()
+            .context("Failed to parse response")?;
+
+        Ok(view.jobs)
+    }
+
+    pub fn get_job(&self, job_name: &str) -> Result<JobInfo> {
+        let url = format!(
+            "{}/api/json",
+            build_job_url(&self.host.host, job_name)
+        );
+
+        self.get_json_cached(&url, self.status_cache(), || format!("Job '{}' not found", job_name))
+    }
+
+    /// Get the most recent builds of a job (newest first)
+    pub fn get_build_history(&self, job_name: &str, limit: usize) -> Result<Vec<BuildInfo>> {
+        let url = format!(
+            "{}/api/json?tree=builds[number,url,result,building,timestamp,duration]{{0,{}}}",
+            build_job_url(&self.host.host, job_name),
+            limit
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(ErrorKind::NotFound.error(format!("Job '{}' not found", job_name)));
+        }
+
+        #[derive(Deserialize)]
+        struct HistoryResponse {
+            #[serde(default)]
+            builds: Vec<BuildInfo>,
+        }
+
+        let history: HistoryResponse = response
+            .check_status("Request failed")?
+            .json_traced()
+            .context("Failed to parse response")?;
+
+        Ok(history.builds)
+    }
+
+    /// Get recent builds (with causes) of one job, or of every job up to three folder levels deep
+    /// Returns (job path, build) pairs
+    pub fn get_recent_builds_with_causes(&self, job_name: Option<&str>, limit: usize) -> Result<Vec<(String, BuildInfo)>> {
+        let builds_tree = format!(
+            "builds[number,url,result,building,timestamp,duration,actions[causes[userId,userName,shortDescription]]]{{0,{}}}",
+            limit
+        );
+
+        let url = match job_name {
+            Some(job) => format!("{}/api/json?tree={}", build_job_url(&self.host.host, job), builds_tree),
+            None => format!(
+                "{}?tree=jobs[name,{b},jobs[name,{b},jobs[name,{b}]]]",
+                build_api_url(&self.host.host),
+                b = builds_tree
+            ),
+        };
+
+        let response = self
+            .client
+            .get(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(ErrorKind::NotFound.error(format!("Job '{}' not found", job_name.unwrap_or_default())));
+        }
+
+        #[derive(Deserialize)]
+        struct JobBuilds {
+            #[serde(default)]
+            name: String,
+            #[serde(default)]
+            builds: Vec<BuildInfo>,
+            #[serde(default)]
+            jobs: Vec<JobBuilds>,
+        }
+
+        fn flatten(job: JobBuilds, path: &str, out: &mut Vec<(String, BuildInfo)>) {
+            for build in job.builds {
+                out.push((path.to_string(), build));
+            }
+            for child in job.jobs {
+                let child_path = if path.is_empty() {
+                    child.name.clone()
+                } else {
+                    format!("{}/job/{}", path, child.name)
+                };
+                flatten(child, &child_path, out);
+            }
+        }
+
+        let root: JobBuilds = response
+            .check_status("Request failed")?
+            .json_traced()
+            .context("Failed to parse response")?;
+
+        let mut builds = Vec::new();
+        flatten(root, job_name.unwrap_or_default(), &mut builds);
+        Ok(builds)
+    }
+
+    /// User name the client authenticates as
+    pub fn user(&self) -> &str {
+        &self.host.user
+    }
+
+    pub fn get_build(&self, job_name: &str, build_number: i32) -> Result<BuildDetails> {
+        let url = format!(
+            "{}/api/json",
+            crate::helpers::url::build_build_url(&self.host.host, job_name, build_number)
+        );
+
+        self.get_json_cached(&url, self.status_cache(), || format!("Build #{} of '{}' not found", build_number, job_name))
+    }
+
+    /// Builds started by a build, from the pipeline `build` steps it ran and from the job's
+    /// downstream projects (matched by their upstream cause)
+    /// Downstream projects are only waited for after a successful build, since Jenkins triggers
+    /// them on success unless they're set up otherwise
+    pub fn get_downstream_builds(&self, job_name: &str, build_number: i32, succeeded: bool) -> Result<Downstream> {
+        #[derive(Deserialize)]
+        struct StepBuild {
+            #[serde(rename = "jobFullName")]
+            job_full_name: String,
+            #[serde(rename = "buildNumber")]
+            build_number: Option<i32>,
+        }
+        #[derive(Deserialize)]
+        struct StepAction {
+            #[serde(rename = "downstreamBuilds", default)]
+            downstream_builds: Vec<StepBuild>,
+        }
+        #[derive(Deserialize)]
+        struct UpstreamBuild {
+            #[serde(default)]
+            actions: Vec<Option<StepAction>>,
+        }
+        #[derive(Deserialize)]
+        struct Project {
+            #[serde(rename = "fullName")]
+            full_name: String,
+        }
+        #[derive(Deserialize)]
+        struct UpstreamJob {
+            #[serde(rename = "fullName")]
+            full_name: String,
+            #[serde(rename = "downstreamProjects", default)]
+            downstream_projects: Vec<Project>,
+        }
+        #[derive(Deserialize)]
+        struct ProjectBuilds {
+            #[serde(default)]
+            builds: Vec<BuildInfo>,
+        }
+
+        let mut downstream = Downstream::default();
+
+        let url = format!(
+            "{}/api/json?tree=actions[downstreamBuilds[jobFullName,buildNumber]]",
+            crate::helpers::url::build_build_url(&self.host.host, job_name, build_number)
+        );
+        let build: UpstreamBuild =
+            self.get_json_cached(&url, None, || format!("Build #{} of '{}' not found", build_number, job_name))?;
+        for step in build.actions.into_iter().flatten().flat_map(|action| action.downstream_builds) {
+            match step.build_number {
+                Some(number) => downstream
+                    .builds
+                    .push(DownstreamBuild { job_name: to_jenkins_job_path(&step.job_full_name), number }),
+                None => downstream.pending = true,
+            }
+        }
+
+        let url = format!(
+            "{}/api/json?tree=fullName,downstreamProjects[fullName]",
+            build_job_url(&self.host.host, job_name)
+        );
+        let job: UpstreamJob = self.get_json_cached(&url, None, || format!("Job '{}' not found", job_name))?;
+        for project in job.downstream_projects {
+            let project_path = to_jenkins_job_path(&project.full_name);
+            let url = format!(
+                "{}/api/json?tree=builds[number,actions[causes[upstreamProject,upstreamBuild]]]{{0,{}}}",
+                build_job_url(&self.host.host, &project_path),
+                DOWNSTREAM_BUILDS_CHECKED
+            );
+            let builds: ProjectBuilds =
+                self.get_json_cached(&url, None, || format!("Job '{}' not found", project.full_name))?;
+            let triggered = builds.builds.iter().find(|b| b.started_by_upstream(&job.full_name, build_number));
+            match triggered {
+                Some(b) if !downstream.builds.iter().any(|d| d.job_name == project_path && d.number == b.number) => {
+                    downstream.builds.push(DownstreamBuild { job_name: project_path, number: b.number })
+                }
+                Some(_) => {}
+                None if succeeded => downstream.pending = true,
+                None => {}
+            }
+        }
+
+        Ok(downstream)
+    }
+
+    pub fn get_console_log(&self, job_name: &str, build_number: i32) -> Result<String> {
+        let url = format!(
+            "{}/consoleText",
+            crate::helpers::url::build_build_url(&self.host.host, job_name, build_number)
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?;
+
+        response
+            .check_status("Request failed")?
+            .text()
+            .context("Failed to read response")
+    }
+
+    /// Raw config.xml of a job or folder
+    pub fn get_job_config(&self, job_name: &str) -> Result<String> {
+        self.find_job_config(job_name)?
+            .ok_or_else(|| ErrorKind::NotFound.error(format!("Job '{}' not found", job_name)))
+    }
+
+    /// Raw config.xml of a job or folder, or None if it doesn't exist
+    pub fn find_job_config(&self, job_name: &str) -> Result<Option<String>> {
+        let url = format!("{}/config.xml", build_job_url(&self.host.host, job_name));
+
+        let response = self
+            .client
+            .get(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        response
+            .check_status("Failed to read job configuration (it needs the Job/ExtendedRead permission)")?
+            .text()
+            .map(Some)
+            .context("Failed to read response")
+    }
+
+    /// Replace the config.xml of an existing job or folder
+    pub fn update_job_config(&self, job_name: &str, config_xml: &str) -> Result<()> {
+        self.ensure_writable("updating a job configuration")?;
+
+        let url = format!("{}/config.xml", build_job_url(&self.host.host, job_name));
+
+        self.client
+            .post(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .header(reqwest::header::CONTENT_TYPE, "application/xml")
+            .body(config_xml.to_string())
+            .send_traced()
+            .context("Failed to send request")?
+            .check_status("Failed to update job configuration")?;
+
+        Ok(())
+    }
+
+    /// Create a job or folder named `name` inside `parent` (the root when None) from a config.xml
+    pub fn create_job(&self, parent: Option<&str>, name: &str, config_xml: &str) -> Result<()> {
+        self.ensure_writable("creating a job")?;
+
+        let base = match parent {
+            Some(parent) => build_job_url(&self.host.host, parent),
+            None => normalize_host_url(&self.host.host).to_string(),
+        };
+
+        self.client
+            .post(format!("{}/createItem", base))
+            .query(&[("name", name)])
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .header(reqwest::header::CONTENT_TYPE, "application/xml")
+            .body(config_xml.to_string())
+            .send_traced()
+            .context("Failed to send request")?
+            .check_status("Failed to create job")?;
+
+        Ok(())
+    }
+
+    /// Export the controller's configuration as JCasC YAML (requires the configuration-as-code plugin)
+    pub fn export_casc(&self) -> Result<String> {
+        let url = format!("{}/configuration-as-code/export", normalize_host_url(&self.host.host));
+
+        let response = self
+            .client
+            .post(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(ErrorKind::NotFound.error("Configuration as Code export not found (is the configuration-as-code plugin installed?)"));
+        }
+
+        response
+            .check_status("Failed to export configuration (it needs the Overall/Administer or Overall/SystemRead permission)")?
+            .text()
+            .context("Failed to read response")
+    }
+
+    pub fn get_job_parameters(&self, job_name: &str) -> Result<Vec<ParameterDefinition>> {
+        let url = format!(
+            "{}/api/json?tree=property[parameterDefinitions[*]]",
+            build_job_url(&self.host.host, job_name)
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?;
+
+        let job_info: JobInfo = response
+            .check_status("Request failed")?
+            .json_traced()
+            .context("Failed to parse response")?;
+
+        // Extract parameter definitions from properties
+        if let Some(properties) = job_info.property {
+            for prop in properties {
+                if let Some(params) = prop.parameter_definitions {
+                    return Ok(params);
+                }
+            }
+        }
+
+        // No parameters found - return empty vec
+        Ok(vec![])
+    }
+
+    /// List the credentials a job can use: those of its folders, nearest first, then the global ones
+    /// A credential ID defined at several levels is listed once, as the nearest one (the one Jenkins uses)
+    /// Requires the credentials plugin
+    pub fn get_credentials(&self, job_name: &str) -> Result<Vec<CredentialInfo>> {
+        let job_path = to_jenkins_job_path(job_name);
+        let names: Vec<&str> = job_path.split("/job/").collect();
+        let folder_stores = (1..names.len())
+            .rev()
+            .map(|depth| format!("{}/credentials/store/folder", build_job_url(&self.host.host, &names[..depth].join("/job/"))));
+
+        let mut credentials: Vec<CredentialInfo> = Vec::new();
+        for store in folder_stores {
+            // Folders without credentials of their own have no store, and viewing one takes its own permission
+            match self.get_credential_store(&store) {
+                Ok(found) => credentials.extend(found.unwrap_or_default()),
+                Err(e) if kind_of(&e) == Some(ErrorKind::Auth) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+
+        let system_store = format!("{}/credentials/store/system", normalize_host_url(&self.host.host));
+        let Some(global) = self.get_credential_store(&system_store)? else {
+            return Err(ErrorKind::NotFound.error("Credentials store not found (is the credentials plugin installed?)"));
+        };
+        credentials.extend(global);
+
+        let mut seen = HashSet::new();
+        credentials.retain(|c| seen.insert(c.id.clone()));
+        Ok(credentials)
+    }
+
+    /// Credentials of the global domain of a store, or None when the store doesn't exist
+    fn get_credential_store(&self, store_url: &str) -> Result<Option<Vec<CredentialInfo>>> {
+        let url = format!("{}/domain/_/api/json?tree=credentials[id,displayName,typeName]", store_url);
+
+        let response = self
+            .client
+            .get(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        let store: CredentialsResponse = response
+            .check_status("Request failed")?
+            .json_traced()
+            .context("Failed to parse response")?;
+
+        Ok(Some(store.credentials))
+    }
+
+    /// Get the triggers configured for a job (cron, SCM polling, upstream)
+    pub fn get_job_triggers(&self, job_name: &str) -> Result<Vec<JobTrigger>> {
+        let url = format!(
+            "{}/api/json?tree=property[triggers[spec,upstreamProjects]],upstreamProjects[name]",
+            build_job_url(&self.host.host, job_name)
+        );
+
+        #[derive(Deserialize)]
+        struct TriggersResponse {
+            property: Option<Vec<JobProperty>>,
+            #[serde(rename = "upstreamProjects")]
+            upstream_projects: Option<Vec<UpstreamProject>>,
+        }
+
+        #[derive(Deserialize)]
+        struct UpstreamProject {
+            name: String,
+        }
+
+        let response: TriggersResponse =
+            self.get_json_cached(&url, self.status_cache(), || format!("Job '{}' not found", job_name))?;
+
+        let mut triggers: Vec<JobTrigger> = response
+            .property
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|prop| prop.triggers)
+            .flatten()
+            .collect();
+
+        // Freestyle jobs expose upstream dependencies at the job level
+        let has_upstream_trigger = triggers.iter().any(|t| t.upstream_projects.is_some());
+        if !has_upstream_trigger
+            && let Some(upstream) = response.upstream_projects.filter(|u| !u.is_empty())
+        {
+            triggers.push(JobTrigger {
+                class: Some("jenkins.triggers.ReverseBuildTrigger".to_string()),
+                spec: None,
+                upstream_projects: Some(
+                    upstream.into_iter().map(|p| p.name).collect::<Vec<_>>().join(", ")
+                ),
+            });
+        }
+
+        Ok(triggers)
+    }
+
+    pub fn trigger_build(&self, job_name: &str, parameters: Option<Vec<ParameterValue>>) -> Result<Option<String>> {
+        self.ensure_writable("triggering a build")?;
+
+        let (url, form_data) = if let Some(params) = parameters {
+            // Use buildWithParameters endpoint
+            let url = format!(
+                "{}/buildWithParameters",
+                build_job_url(&self.host.host, job_name)
+            );
+
+            (url, Some(params))
+        } else {
+            // Use regular build endpoint
+            let url = format!(
+                "{}/build",
+                build_job_url(&self.host.host, job_name)
+            );
+            (url, None)
+        };
+
+        let mut request = self.client.post(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token));
+
+        // Add form data if parameters exist
+        if let Some(params) = form_data {
+            if params.iter().any(|p| p.file.is_some()) {
+                // File parameters can only be sent as multipart/form-data
+                let mut form = reqwest::blocking::multipart::Form::new();
+                for param in params {
+                    form = match param.file {
+                        Some(path) => form
+                            .file(param.name, &path)
+                            .with_context(|| format!("Failed to read file '{}'", path.display()))?,
+                        None => form.text(param.name, param.value),
+                    };
+                }
+                request = request.multipart(form);
+            } else {
+                // Build form data: param1=value1&param2=value2
+                let form_pairs: Vec<(String, String)> = params
+                    .into_iter()
+                    .map(|p| (p.name, p.value))
+                    .collect();
+                request = request.form(&form_pairs);
+            }
+        }
+
+        let response = request
+            .send_traced()
+            .context("Failed to send request")?;
+
+        let response = response
+            .check_status("Failed to trigger build")?;
+
+        // Get queue item location from Location header
+        let queue_location = response
+            .headers()
+            .get("Location")
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string());
+
+        Ok(queue_location)
+    }
+
+    /// Create a new API token for the authenticated user
+    /// The client must be authenticated with the user's password - Jenkins then requires a crumb
+    /// tied to the login session, so the session cookie is carried over to the token request
+    pub fn generate_api_token(&self, token_name: &str) -> Result<GeneratedToken> {
+        self.ensure_writable("rotating an API token")?;
+        let host = normalize_host_url(&self.host.host);
+
+        let crumb_response = self
+            .client
+            .get(format!("{}/crumbIssuer/api/json", host))
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?;
+
+        if crumb_response.status() == StatusCode::UNAUTHORIZED {
+            return Err(ErrorKind::Auth.error("Authentication failed. Please check your username and password."));
+        }
+
+        let cookies: Vec<String> = crumb_response
+            .headers()
+            .get_all(reqwest::header::SET_COOKIE)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .filter_map(|v| v.split(';').next())
+            .map(|v| v.to_string())
+            .collect();
+
+        #[derive(Deserialize)]
+        struct CrumbResponse {
+            crumb: String,
+            #[serde(rename = "crumbRequestField")]
+            crumb_request_field: String,
+        }
+
+        // Instances without CSRF protection have no crumb issuer
+        let crumb: Option<CrumbResponse> = if crumb_response.status() == StatusCode::NOT_FOUND {
+            None
+        } else {
+            Some(
+                crumb_response
+                    .check_status("Failed to get crumb")?
+                    .json_traced()
+                    .context("Failed to parse crumb response")?,
+            )
+        };
+
+        let mut request = self
+            .client
+            .post(format!(
+                "{}/me/descriptorByName/jenkins.security.ApiTokenProperty/generateNewToken",
+                host
+            ))
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .form(&[("newTokenName", token_name)]);
+
+        if let Some(crumb) = &crumb {
+            request = request.header(crumb.crumb_request_field.as_str(), crumb.crumb.as_str());
+        }
+        if !cookies.is_empty() {
+            request = request.header(reqwest::header::COOKIE, cookies.join("; "));
+        }
+
+        let response = request.send_traced().context("Failed to send request")?;
+
+        if response.status() == StatusCode::UNAUTHORIZED {
+            return Err(ErrorKind::Auth.error("Authentication failed. Please check your username and password."));
+        }
+
+        let token: GeneratedTokenResponse = response
+            .check_status("Failed to generate API token")?
+            .json_traced()
+            .context("Failed to parse response")?;
+
+        Ok(token.data)
+    }
+
+    /// Revoke one of the authenticated user's API tokens
+    /// Requests authenticated with an API token need no crumb
+    pub fn revoke_api_token(&self, token_uuid: &str) -> Result<()> {
+        self.ensure_writable("rotating an API token")?;
+        let url = format!(
+            "{}/me/descriptorByName/jenkins.security.ApiTokenProperty/revoke",
+            normalize_host_url(&self.host.host)
+        );
+
+        self.client
+            .post(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .form(&[("tokenUuid", token_uuid)])
+            .send_traced()
+            .context("Failed to send request")?
+            .check_status("Failed to revoke API token")?;
+
+        Ok(())
+    }
+
+    /// Send an authenticated request to an arbitrary path on the host
+    /// Returns the status code and the response body
+    pub fn raw_request(&self, method: &str, path: &str, body: Option<String>) -> Result<(StatusCode, String)> {
+        let method = reqwest::Method::from_bytes(method.to_uppercase().as_bytes())
+            .map_err(|_| anyhow::anyhow!("Invalid HTTP method '{}'", method))?;
+        if method != reqwest::Method::GET && method != reqwest::Method::HEAD {
+            self.ensure_writable(&format!("sending a {} request", method))?;
+        }
+
+        let url = if path.starts_with("http://") || path.starts_with("https://") {
+            let on_host = path
+                .strip_prefix(normalize_host_url(&self.host.host))
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'));
+            if !on_host {
+                anyhow::bail!("URL '{}' is not on the configured host {}", path, self.host.host);
+            }
+            path.to_string()
+        } else {
+            format!("{}/{}", normalize_host_url(&self.host.host), path.trim_start_matches('/'))
+        };
+
+        let mut request = self
+            .client
+            .request(method, &url)
+            .basic_auth(&self.host.user, Some(&self.host.token));
+
+        if let Some(body) = body {
+            let content_type = if serde_json::from_str::<serde_json::Value>(&body).is_ok() {
+                "application/json"
+            } else {
+                "application/x-www-form-urlencoded"
+            };
+            request = request.header(reqwest::header::CONTENT_TYPE, content_type).body(body);
+        }
+
+        let response = request.send_traced().context("Failed to send request")?;
+        let status = response.status();
+        let text = response.text().context("Failed to read response")?;
+
+        Ok((status, text))
+    }
+
+    /// Get build number from queue item
+    pub fn get_build_number_from_queue(&self, queue_url: &str) -> Result<Option<i32>> {
+        Ok(self.get_queue_item(queue_url)?.build_number)
+    }
+
+    /// Get the state of a queue item: its build number once started, or why it is still waiting
+    pub fn get_queue_item(&self, queue_url: &str) -> Result<QueueItemStatus> {
+        let api_url = format!("{}/api/json", normalize_host_url(queue_url));
+
+        let response = self
+            .client
+            .get(&api_url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to query queue item")?;
+
+        #[derive(Deserialize)]
+        struct QueueItem {
+            executable: Option<QueueExecutable>,
+            why: Option<String>,
+            #[serde(default)]
+            cancelled: bool,
+        }
+
+        #[derive(Deserialize)]
+        struct QueueExecutable {
+            number: i32,
+        }
+
+        let queue_item: QueueItem = response
+            .check_status("Failed to get queue item")?
+            .json_traced()
+            .context("Failed to parse queue response")?;
+
+        Ok(QueueItemStatus {
+            build_number: queue_item.executable.map(|e| e.number),
+            why: queue_item.why,
+            cancelled: queue_item.cancelled,
+        })
+    }
+
+    /// Configured wait for queued builds on this host (e.g. `15m`), if any
+    pub fn queue_timeout(&self) -> Option<&str> {
+        self.host.queue_timeout.as_deref()
+    }
+
+    /// Cancel a queued item so the build never starts
+    pub fn cancel_queue_item(&self, queue_id: i64) -> Result<()> {
+        self.ensure_writable("cancelling a queued build")?;
+
+        let url = format!("{}/queue/cancelItem?id={}", normalize_host_url(&self.host.host), queue_id);
+
+        let response = self
+            .client
+            .post(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?;
+
+        // Jenkins redirects back to the queue page after cancelling
+        if response.status().is_redirection() {
+            return Ok(());
+        }
+
+        response
+            .check_status("Failed to cancel queue item")?;
+
+        Ok(())
+    }
+
+    /// Re-run a pipeline build with a different main script (Replay)
+    pub fn replay_build(&self, job_name: &str, build_number: i32, script: &str) -> Result<()> {
+        self.ensure_writable("replaying a build")?;
+
+        let replay_url = format!(
+            "{}/replay",
+            crate::helpers::url::build_build_url(&self.host.host, job_name, build_number)
+        );
+
+        let not_replayable = || {
+            anyhow::anyhow!(
+                "Build #{} of '{}' can't be replayed (not a pipeline build, or Replay permission missing)",
+                build_number,
+                job_name
+            )
+        };
+
+        // Scripts the build loaded (`load` steps, libraries) are sent back unchanged, as the Replay page does
+        let page = self
+            .client
+            .get(format!("{}/", replay_url))
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?;
+        if page.status() == StatusCode::NOT_FOUND {
+            return Err(not_replayable());
+        }
+        let loaded_scripts = replay_loaded_scripts(&page.check_status("Failed to open the Replay page")?.text()?);
+
+        // Stapler reads the submitted form from the `json` field; the scripts are sent alongside as well
+        let mut form_json = serde_json::Map::new();
+        form_json.insert("mainScript".to_string(), script.into());
+        for (field, loaded) in &loaded_scripts {
+            form_json.insert(field.clone(), loaded.as_str().into());
+        }
+        let form_json = serde_json::Value::Object(form_json).to_string();
+        let mut form = vec![("mainScript", script), ("json", form_json.as_str())];
+        form.extend(loaded_scripts.iter().map(|(field, loaded)| (field.as_str(), loaded.as_str())));
+
+        let response = self
+            .client
+            .post(format!("{}/run", replay_url))
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .form(&form)
+            .send_traced()
+            .context("Failed to send request")?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(not_replayable());
+        }
+
+        response.check_status("Failed to replay build")?;
+
+        Ok(())
+    }
+
+    /// Run a Groovy script in the script console of the controller, or of an agent when `node` is given
+    pub fn run_script(&self, node: Option<&str>, script: &str) -> Result<String> {
+        self.ensure_writable("running a script")?;
+
+        let url = match node {
+            Some(node) => format!("{}/scriptText", crate::helpers::url::build_computer_url(&self.host.host, node)),
+            None => format!("{}/scriptText", normalize_host_url(&self.host.host)),
+        };
+
+        let response = self
+            .client
+            .post(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .form(&[("script", script)])
+            .send_traced()
+            .context("Failed to send request")?;
+
+        if response.status() == StatusCode::NOT_FOUND
+            && let Some(node) = node
+        {
+            return Err(ErrorKind::NotFound.error(format!("Agent '{}' not found", node)));
+        }
+
+        response
+            .check_status("Failed to run script (it needs the Overall/Administer permission)")?
+            .text()
+            .context("Failed to read response")
+    }
+
+    /// Re-run a declarative pipeline build starting at the given top-level stage
+    pub fn restart_from_stage(&self, job_name: &str, build_number: i32, stage: &str) -> Result<()> {
+        self.ensure_writable("restarting a build")?;
+
+        let url = format!(
+            "{}/restart/restart",
+            crate::helpers::url::build_build_url(&self.host.host, job_name, build_number)
+        );
+
+        let form_json = serde_json::json!({ "stageName": stage }).to_string();
+        let form = [("stageName", stage), ("json", form_json.as_str())];
+
+        let response = self
+            .client
+            .post(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .form(&form)
+            .send_traced()
+            .context("Failed to send request")?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            anyhow::bail!(
+                "Build #{} of '{}' can't be restarted from a stage (not a declarative pipeline, or it is still running)",
+                build_number,
+                job_name
+            );
+        }
+
+        response.check_status("Failed to restart build")?;
+
+        Ok(())
+    }
+
+    /// Stream console log progressively (from start_bytes offset)
+    pub fn get_console_log_progressive(&self, job_name: &str, build_number: i32, start: usize) -> Result<(String, usize, bool)> {
+        let url = format!(
+            "{}/logText/progressiveText?start={}",
+            crate::helpers::url::build_build_url(&self.host.host, job_name, build_number),
+            start
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?;
+
+        // Check X-More-Data header to see if build is still running
+        let more_data = response
+            .headers()
+            .get("X-More-Data")
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v == "true")
+            .unwrap_or(false);
+
+        // Get X-Text-Size header for next offset
+        let text_size = response
+            .headers()
+            .get("X-Text-Size")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<usize>().ok())
+            .unwrap_or(start);
+
+        let text = response
+            .check_status("Request failed")?
+            .text()
+            .context("Failed to read response")?;
+
+        Ok((text, text_size, more_data))
+    }
+
+    /// List the builds currently running on any executor of the instance
+    pub fn get_running_builds(&self) -> Result<Vec<RunningBuild>> {
+        let executable = "currentExecutable[url,fullDisplayName,timestamp,estimatedDuration]";
+        let url = format!(
+            "{}/computer/api/json?tree=computer[displayName,executors[{}],oneOffExecutors[{}]]",
+            normalize_host_url(&self.host.host),
+            executable,
+            executable
+        );
+
+        let computers: ComputerSet = self
+            .client
+            .get(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?
+            .check_status("Request failed")?
+            .json_traced()
+            .context("Failed to parse response")?;
+
+        Ok(computers.running_builds())
+    }
+
+    /// List the installed plugins with their dependencies
+    pub fn get_plugins(&self) -> Result<Vec<InstalledPlugin>> {
+        let url = format!(
+            "{}/pluginManager/api/json?tree=plugins[shortName,longName,version,hasUpdate,active,dependencies[shortName,version,optional]]",
+            normalize_host_url(&self.host.host)
+        );
+
+        #[derive(Deserialize)]
+        struct PluginManager {
+            #[serde(default)]
+            plugins: Vec<InstalledPlugin>,
+        }
+
+        let manager: PluginManager = self
+            .client
+            .get(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?
+            .check_status("Failed to list plugins (it needs the Overall/SystemRead or Administer permission)")?
+            .json_traced()
+            .context("Failed to parse response")?;
+
+        Ok(manager.plugins)
+    }
+
+    /// Newer versions of installed plugins, from the update center data cached on the controller
+    pub fn get_plugin_updates(&self) -> Result<Vec<PluginUpdate>> {
+        let url = format!(
+            "{}/updateCenter/api/json?tree=sites[updates[name,version,warnings[id,message,url]]]",
+            normalize_host_url(&self.host.host)
+        );
+
+        #[derive(Deserialize)]
+        struct UpdateCenter {
+            #[serde(default)]
+            sites: Vec<UpdateSite>,
+        }
+
+        #[derive(Deserialize)]
+        struct UpdateSite {
+            #[serde(default)]
+            updates: Vec<PluginUpdate>,
+        }
+
+        let center: UpdateCenter = self
+            .client
+            .get(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?
+            .check_status("Failed to read the update center data")?
+            .json_traced()
+            .context("Failed to parse response")?;
+
+        Ok(center.sites.into_iter().flat_map(|site| site.updates).collect())
+    }
+
+    /// Create a permanent agent that connects inbound (JNLP) to the controller
+    pub fn create_inbound_agent(&self, name: &str, executors: u32, labels: &str, remote_fs: &str) -> Result<()> {
+        self.ensure_writable("creating an agent")?;
+
+        let url = format!("{}/computer/doCreateItem", normalize_host_url(&self.host.host));
+
+        // The same form the "New node" page submits
+        let launcher = "hudson.slaves.JNLPLauncher";
+        let retention = "hudson.slaves.RetentionStrategy$Always";
+        let form_json = serde_json::json!({
+            "name": name,
+            "nodeDescription": "",
+            "numExecutors": executors.to_string(),
+            "remoteFS": remote_fs,
+            "labelString": labels,
+            "mode": "NORMAL",
+            "launcher": {
+                "stapler-class": launcher,
+                "$class": launcher,
+                "workDirSettings": { "disabled": false, "internalDir": "remoting", "failIfWorkDirIsMissing": false },
+            },
+            "retentionStrategy": { "stapler-class": retention, "$class": retention },
+            "nodeProperties": { "stapler-class-bag": "true" },
+            "type": "hudson.slaves.DumbSlave",
+        })
+        .to_string();
+
+        self.client
+            .post(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .form(&[("name", name), ("type", "hudson.slaves.DumbSlave"), ("json", form_json.as_str())])
+            .send_traced()
+            .context("Failed to send request")?
+            .check_status("Failed to create agent (it needs the Agent/Create permission)")?;
+
+        Ok(())
+    }
+
+    /// The secret an inbound agent connects with, read from its JNLP file
+    pub fn get_agent_secret(&self, name: &str) -> Result<String> {
+        let url = format!("{}/jenkins-agent.jnlp", crate::helpers::url::build_computer_url(&self.host.host, name));
+
+        let response = self
+            .client
+            .get(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(ErrorKind::NotFound.error(format!("Agent '{}' not found or not an inbound agent", name)));
+        }
+
+        let jnlp = response
+            .check_status("Failed to read the agent secret (it needs the Agent/Connect permission)")?
+            .text()
+            .context("Failed to read response")?;
+
+        agent_secret_from_jnlp(&jnlp).context("No secret found in the agent's JNLP file")
+    }
+
+    /// The launch/connection log of an agent (the "Log" page of the node)
+    pub fn get_node_log(&self, name: &str) -> Result<String> {
+        let url = format!(
+            "{}/logText/progressiveText?start=0",
+            crate::helpers::url::build_computer_url(&self.host.host, name)
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(ErrorKind::NotFound.error(format!("Agent '{}' not found", name)));
+        }
+
+        response
+            .check_status("Failed to read the agent log (it needs the Agent/Configure or Overall/SystemRead permission)")?
+            .text()
+            .context("Failed to read response")
+    }
+
+    /// List the agents and the built-in node with their node monitor data, labels and executors
+    pub fn get_nodes(&self) -> Result<Vec<NodeInfo>> {
         #[derive(Deserialize)]
-        struct QueueExecutable {
-            number: i32,
+        struct NodeList {
+            #[serde(default)]
+            computer: Vec<NodeInfo>,
         }
 
-        let queue_item: QueueItem = response
-            .error_for_status()
-            .context("Failed to get queue item")?
-            .json()
-            .context("Failed to parse queue response")?;
+        let url = format!(
+            "{}/computer/api/json?tree=computer[_class,displayName,offline,temporarilyOffline,numExecutors,monitorData[*],assignedLabels[name],executors[idle]]",
+            normalize_host_url(&self.host.host)
+        );
+        let nodes: NodeList = self
+            .client
+            .get(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?
+            .check_status("Request failed")?
+            .json_traced()
+            .context("Failed to parse response")?;
 
-        Ok(queue_item.executable.map(|e| e.number))
+        Ok(nodes.computer)
     }
 
-    /// Stream console log progressively (from start_bytes offset)
-    pub fn get_console_log_progressive(&self, job_name: &str, build_number: i32, start: usize) -> Result<(String, usize, bool)> {
+    /// Delete an agent
+    pub fn delete_node(&self, name: &str) -> Result<()> {
+        self.ensure_writable("deleting an agent")?;
+
+        let url = format!("{}/doDelete", crate::helpers::url::build_computer_url(&self.host.host, name));
+
+        let response = self
+            .client
+            .post(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(ErrorKind::NotFound.error(format!("Agent '{}' not found", name)));
+        }
+
+        response.check_status("Failed to delete agent (it needs the Agent/Delete permission)")?;
+
+        Ok(())
+    }
+
+    /// List the items waiting in the build queue
+    pub fn get_queue_items(&self) -> Result<Vec<QueuedItem>> {
         let url = format!(
-            "{}/logText/progressiveText?start={}",
+            "{}/queue/api/json?tree=items[id,why,inQueueSince,stuck,buildable,task[name,url]]",
+            normalize_host_url(&self.host.host)
+        );
+
+        #[derive(Deserialize)]
+        struct QueueResponse {
+            #[serde(default)]
+            items: Vec<QueuedItem>,
+        }
+
+        let queue: QueueResponse = self
+            .client
+            .get(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?
+            .check_status("Request failed")?
+            .json_traced()
+            .context("Failed to parse response")?;
+
+        Ok(queue.items)
+    }
+
+    /// Get the stages of a pipeline build from the wfapi endpoint
+    /// Returns None if the build is not a pipeline (or the Stage View plugin is missing)
+    pub fn get_pipeline_stages(&self, job_name: &str, build_number: i32) -> Result<Option<Vec<PipelineStage>>> {
+        let url = format!(
+            "{}/wfapi/describe",
+            crate::helpers::url::build_build_url(&self.host.host, job_name, build_number)
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        #[derive(Deserialize)]
+        struct DescribeResponse {
+            #[serde(default)]
+            stages: Vec<PipelineStage>,
+        }
+
+        let describe: DescribeResponse = response
+            .check_status("Request failed")?
+            .json_traced()
+            .context("Failed to parse response")?;
+
+        Ok(Some(describe.stages))
+    }
+
+    /// Get the environment variables injected into a build (EnvInject plugin)
+    /// Archived artifacts of a build, with any fingerprints Jenkins recorded for them
+    pub fn get_artifacts(&self, job_name: &str, build_number: i32) -> Result<BuildArtifacts> {
+        let url = format!(
+            "{}/api/json?tree=artifacts[fileName,relativePath],fingerprint[fileName,hash]",
+            crate::helpers::url::build_build_url(&self.host.host, job_name, build_number)
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(ErrorKind::NotFound.error(format!("Build #{} not found for job '{}'", build_number, job_name)));
+        }
+
+        response
+            .check_status("Request failed")?
+            .json_traced()
+            .context("Failed to parse response")
+    }
+
+    /// Start downloading an artifact; read the returned stream to get its content
+    /// With `resume_from`, asks for the rest of the file only (HTTP Range), guarded by the validator the
+    /// earlier download got (If-Range) so a changed artifact is sent whole; check `ArtifactStream::offset`
+    /// for where the stream actually starts
+    pub fn open_artifact(
+        &self,
+        job_name: &str,
+        build_number: i32,
+        relative_path: &str,
+        resume_from: Option<(u64, &str)>,
+    ) -> Result<ArtifactStream> {
+        let path: Vec<String> = relative_path.split('/').map(encode_path_segment).collect();
+        let url = format!(
+            "{}/artifact/{}",
             crate::helpers::url::build_build_url(&self.host.host, job_name, build_number),
-            start
+            path.join("/")
+        );
+
+        let mut request = self
+            .client
+            .get(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            // Large artifacts take longer than the default request timeout
+            .timeout(std::time::Duration::from_secs(30 * 60));
+        let resume_from = resume_from.filter(|(offset, _)| *offset > 0);
+        if let Some((offset, validator)) = resume_from {
+            request = request
+                .header(reqwest::header::RANGE, format!("bytes={}-", offset))
+                .header(reqwest::header::IF_RANGE, validator);
+        }
+
+        let response = request.send_traced().context("Failed to send request")?;
+
+        // The partial file is already as long as (or longer than) the artifact - start over
+        if resume_from.is_some() && response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+            return self.open_artifact(job_name, build_number, relative_path, None);
+        }
+
+        let response = response.check_status("Failed to download artifact")?;
+        // Anything but 206 is the whole file, e.g. because the artifact changed since the partial download
+        let offset = match resume_from {
+            Some((offset, _)) if response.status() == StatusCode::PARTIAL_CONTENT => offset,
+            _ => 0,
+        };
+        let header = |name| response.headers().get(name).and_then(|value| value.to_str().ok());
+        let validator = resume_validator(header(reqwest::header::ETAG), header(reqwest::header::LAST_MODIFIED));
+
+        Ok(ArtifactStream { offset, size: response.content_length().map(|len| offset + len), validator, response })
+    }
+
+    pub fn get_injected_env_vars(&self, job_name: &str, build_number: i32) -> Result<BTreeMap<String, String>> {
+        let url = format!(
+            "{}/injectedEnvVars/api/json",
+            crate::helpers::url::build_build_url(&self.host.host, job_name, build_number)
         );
 
         let response = self
             .client
             .get(&url)
             .basic_auth(&self.host.user, Some(&self.host.token))
-            .send()
+            .send_traced()
             .context("Failed to send request")?;
 
-        // Check X-More-Data header to see if build is still running
-        let more_data = response
-            .headers()
-            .get("X-More-Data")
-            .and_then(|v| v.to_str().ok())
-            .map(|v| v == "true")
-            .unwrap_or(false);
+        if response.status() == StatusCode::NOT_FOUND {
+            anyhow::bail!(
+                "No injected environment variables found for {}#{}. Is the EnvInject plugin installed?",
+                job_name,
+                build_number
+            );
+        }
 
-        // Get X-Text-Size header for next offset
-        let text_size = response
-            .headers()
-            .get("X-Text-Size")
-            .and_then(|v| v.to_str().ok())
-            .and_then(|v| v.parse::<usize>().ok())
-            .unwrap_or(start);
+        let env_vars: InjectedEnvVars = response
+            .check_status("Request failed")?
+            .json_traced()
+            .context("Failed to parse response")?;
+
+        Ok(env_vars.env_map)
+    }
+
+    /// Get the coverage summary for a build from the JaCoCo or Cobertura plugin
+    /// Returns None if neither plugin recorded coverage for the build
+    pub fn get_coverage(&self, job_name: &str, build_number: i32) -> Result<Option<CoverageSummary>> {
+        let build_url = crate::helpers::url::build_build_url(&self.host.host, job_name, build_number);
+
+        let response = self
+            .client
+            .get(format!("{}/jacoco/api/json", build_url))
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?;
+
+        if response.status() != StatusCode::NOT_FOUND {
+            let report: JacocoReport = response
+                .check_status("Request failed")?
+                .json_traced()
+                .context("Failed to parse JaCoCo response")?;
+            return Ok(Some(report.into()));
+        }
+
+        let response = self
+            .client
+            .get(format!("{}/cobertura/api/json?depth=2", build_url))
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?;
+
+        if response.status() != StatusCode::NOT_FOUND {
+            let report: CoberturaReport = response
+                .check_status("Request failed")?
+                .json_traced()
+                .context("Failed to parse Cobertura response")?;
+            return Ok(Some(report.into()));
+        }
+
+        Ok(None)
+    }
+
+    /// List the static analysis tools recorded by the warnings-ng plugin for a build
+    pub fn get_warnings_tools(&self, job_name: &str, build_number: i32) -> Result<Vec<WarningsTool>> {
+        let url = format!(
+            "{}/warnings-ng/api/json",
+            crate::helpers::url::build_build_url(&self.host.host, job_name, build_number)
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            anyhow::bail!(
+                "No static analysis results found for {}#{}. Is the Warnings Next Generation plugin installed?",
+                job_name,
+                build_number
+            );
+        }
+
+        let tools: WarningsToolsResponse = response
+            .check_status("Request failed")?
+            .json_traced()
+            .context("Failed to parse response")?;
+
+        Ok(tools.tools)
+    }
+
+    /// Get new/outstanding/fixed issue counts for one warnings-ng tool
+    pub fn get_warnings_summary(&self, job_name: &str, build_number: i32, tool_id: &str) -> Result<WarningsSummary> {
+        let url = format!(
+            "{}/{}/api/json",
+            crate::helpers::url::build_build_url(&self.host.host, job_name, build_number),
+            tool_id
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?;
+
+        response
+            .check_status("Request failed")?
+            .json_traced::<WarningsSummary>()
+            .context("Failed to parse response")
+    }
+
+    /// Get the test report (JUnit results) for a build
+    pub fn get_test_report(&self, job_name: &str, build_number: i32) -> Result<TestReport> {
+        let url = format!(
+            "{}/testReport/api/json?tree=failCount,passCount,skipCount,suites[name,cases[className,name,status,duration,errorDetails,errorStackTrace]]",
+            crate::helpers::url::build_build_url(&self.host.host, job_name, build_number)
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(ErrorKind::NotFound.error(format!("No test report found for {}#{}", job_name, build_number)));
+        }
+
+        response
+            .check_status("Request failed")?
+            .json_traced::<TestReport>()
+            .context("Failed to parse response")
+    }
+
+    pub fn get_job_url(&self, job_name: &str) -> String {
+        build_job_url(&self.host.host, job_name)
+    }
+
+    pub fn get_node_url(&self, name: &str) -> String {
+        crate::helpers::url::build_computer_url(&self.host.host, name)
+    }
+
+    pub fn host_url(&self) -> &str {
+        normalize_host_url(&self.host.host)
+    }
+
+    /// Unauthenticated request to the controller: whether it answers at all, its version and clock
+    pub fn probe_server(&self) -> Result<ServerProbe> {
+        let url = format!("{}/login", normalize_host_url(&self.host.host));
+
+        let response = self.client.get(&url).send_traced().context("Failed to connect to Jenkins server")?;
+
+        let header = |name: &str| response.headers().get(name).and_then(|v| v.to_str().ok()).map(|v| v.to_string());
+        Ok(ServerProbe {
+            status: response.status(),
+            version: header("X-Jenkins"),
+            date: header("Date").as_deref().and_then(crate::helpers::formatting::parse_http_date),
+        })
+    }
+
+    /// Whether the controller is quieting down (not starting new builds, e.g. before a restart)
+    pub fn get_quieting_down(&self) -> Result<bool> {
+        let url = format!("{}/api/json?tree=quietingDown", normalize_host_url(&self.host.host));
+
+        #[derive(Deserialize)]
+        struct Root {
+            #[serde(rename = "quietingDown", default)]
+            quieting_down: bool,
+        }
+
+        let root: Root = self
+            .client
+            .get(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?
+            .check_status("Request failed")?
+            .json_traced()
+            .context("Failed to parse response")?;
+
+        Ok(root.quieting_down)
+    }
+
+    /// Queue length and executor usage of the whole instance, sampled every minute
+    pub fn get_overall_load(&self) -> Result<OverallLoad> {
+        let series = "min[latest,history]";
+        let url = format!(
+            "{}/overallLoad/api/json?tree=queueLength[{}],busyExecutors[{}],totalExecutors[{}]",
+            normalize_host_url(&self.host.host),
+            series,
+            series,
+            series
+        );
+
+        self.client
+            .get(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?
+            .check_status("Request failed")?
+            .json_traced()
+            .context("Failed to parse response")
+    }
+
+    /// Fetch a static file Jenkins serves without authentication or API work, as a network baseline
+    pub fn fetch_static_file(&self) -> Result<()> {
+        let url = format!("{}/robots.txt", normalize_host_url(&self.host.host));
+
+        self.client
+            .get(&url)
+            .send_traced()
+            .context("Failed to send request")?
+            .check_status("Request failed")?
+            .bytes()
+            .context("Failed to read response")?;
+
+        Ok(())
+    }
+
+    /// The user Jenkins sees for the configured credentials
+    pub fn who_am_i(&self) -> Result<WhoAmI> {
+        let url = format!("{}/whoAmI/api/json", normalize_host_url(&self.host.host));
+
+        let response = self
+            .client
+            .get(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?;
+
+        if response.status() == StatusCode::UNAUTHORIZED {
+            return Err(ErrorKind::Auth.error("Authentication failed. Please check your username and API token."));
+        }
+
+        response
+            .check_status("Request failed")?
+            .json_traced()
+            .context("Failed to parse response")
+    }
+
+    /// Whether the controller issues CSRF crumbs (false when the crumb issuer is disabled)
+    pub fn crumb_issuer_enabled(&self) -> Result<bool> {
+        let url = format!("{}/crumbIssuer/api/json", normalize_host_url(&self.host.host));
 
-        let text = response
-            .error_for_status()
-            .context("Request failed")?
-            .text()
-            .context("Failed to read response")?;
+        let response = self
+            .client
+            .get(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?;
 
-        Ok((text, text_size, more_data))
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(false);
+        }
+
+        response.check_status("Failed to query the crumb issuer")?;
+        Ok(true)
     }
 
-    pub fn get_job_url(&self, job_name: &str) -> String {
-        build_job_url(&self.host.host, job_name)
+    /// Whether a GET on a host path is allowed for the configured user (false on 401/403)
+    /// Pages like `scriptText` check their permission before showing anything, so they can be probed
+    /// this way without running anything
+    pub fn can_access(&self, path: &str) -> Result<bool> {
+        let url = format!("{}/{}", normalize_host_url(&self.host.host), path.trim_start_matches('/'));
+
+        let response = self
+            .client
+            .get(&url)
+            .basic_auth(&self.host.user, Some(&self.host.token))
+            .send_traced()
+            .context("Failed to send request")?;
+
+        match response.status() {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Ok(false),
+            StatusCode::METHOD_NOT_ALLOWED => Ok(true),
+            _ => response.check_status("Request failed").map(|_| true),
+        }
     }
 
     /// Verify connection to Jenkins by making a simple API call
@@ -346,7 +3116,7 @@ impl JenkinsClient {
             .client
             .get(&url)
             .basic_auth(&self.host.user, Some(&self.host.token))
-            .send()
+            .send_traced()
             .context("Failed to connect to Jenkins server")?;
 
         let status = response.status();
@@ -354,10 +3124,10 @@ impl JenkinsClient {
         match status {
             StatusCode::OK => Ok(()),
             StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
-                anyhow::bail!("Authentication failed. Please check your username and API token.")
+                Err(ErrorKind::Auth.error("Authentication failed. Please check your username and API token."))
             }
             StatusCode::NOT_FOUND => {
-                anyhow::bail!("Jenkins server not found. Please check the URL.")
+                Err(ErrorKind::NotFound.error("Jenkins server not found. Please check the URL."))
             }
             _ => {
                 anyhow::bail!("Failed to connect to Jenkins: HTTP {}", status)
@@ -375,160 +3145,711 @@ mod tests {
             host: "https://jenkins.example.com".to_string(),
             user: "testuser".to_string(),
             token: "testtoken".to_string(),
+            ..Default::default()
         }
     }
 
     #[test]
-    fn test_client_creation() {
-        let host = create_test_host();
-        let client = JenkinsClient::new(host.clone()).unwrap();
-        assert_eq!(client.host.host, host.host);
-        assert_eq!(client.host.user, host.user);
-        assert_eq!(client.host.token, host.token);
+    fn test_scrub_tokens_masks_tokens_of_created_clients() {
+        JenkinsClient::new(create_test_host()).unwrap();
+        assert_eq!(scrub_tokens("Bad credentials testuser:testtoken"), "Bad credentials testuser:****");
+    }
+
+    #[test]
+    fn test_client_creation() {
+        let host = create_test_host();
+        let client = JenkinsClient::new(host.clone()).unwrap();
+        assert_eq!(client.host.host, host.host);
+        assert_eq!(client.host.user, host.user);
+        assert_eq!(client.host.token, host.token);
+    }
+
+    #[test]
+    fn test_read_only_host_refuses_writes() {
+        let mut host = create_test_host();
+        host.read_only = true;
+        let client = JenkinsClient::new(host).unwrap().with_name("observer");
+
+        // Refused before any request, so the unreachable example host never matters
+        let error = client.trigger_build("api", None).unwrap_err().to_string();
+        assert!(error.contains("Jenkins 'observer' is read-only: triggering a build is not allowed"));
+        assert!(client.raw_request("DELETE", "job/api/", None).unwrap_err().to_string().contains("read-only"));
+        assert!(client.generate_api_token("jenkins-cli").unwrap_err().to_string().contains("rotating an API token"));
+        assert!(client.revoke_api_token("uuid").unwrap_err().to_string().contains("rotating an API token"));
+        assert!(JenkinsClient::new(create_test_host()).unwrap().ensure_writable("triggering a build").is_ok());
+    }
+
+    #[test]
+    fn test_get_job_url() {
+        let host = create_test_host();
+        let client = JenkinsClient::new(host).unwrap();
+
+        let url = client.get_job_url("my-job");
+        assert_eq!(url, "https://jenkins.example.com/job/my-job");
+    }
+
+    #[test]
+    fn test_resume_validator() {
+        let date = "Wed, 21 Oct 2026 07:28:00 GMT";
+        assert_eq!(resume_validator(Some("\"abc\""), Some(date)).as_deref(), Some("\"abc\""));
+        assert_eq!(resume_validator(Some("W/\"abc\""), Some(date)).as_deref(), Some(date));
+        assert_eq!(resume_validator(Some("W/\"abc\""), None), None);
+    }
+
+    #[test]
+    fn test_get_job_url_with_trailing_slash() {
+        let mut host = create_test_host();
+        host.host = "https://jenkins.example.com/".to_string();
+        let client = JenkinsClient::new(host).unwrap();
+
+        let url = client.get_job_url("my-job");
+        assert_eq!(url, "https://jenkins.example.com/job/my-job");
+    }
+
+    #[test]
+    fn test_get_job_url_build_number() {
+        let host = create_test_host();
+        let client = JenkinsClient::new(host).unwrap();
+
+        let base_url = client.get_job_url("my-job");
+        let build_url = format!("{}/{}", base_url, 123);
+        assert_eq!(build_url, "https://jenkins.example.com/job/my-job/123");
+    }
+
+    #[test]
+    fn test_job_info_deserialization() {
+        let json = r#"{
+            "name": "test-job",
+            "url": "https://jenkins.example.com/job/test-job/",
+            "color": "blue",
+            "lastBuild": {
+                "number": 42,
+                "url": "https://jenkins.example.com/job/test-job/42/",
+                "result": "SUCCESS",
+                "building": false,
+                "timestamp": 1234567890000
+            }
+        }"#;
+
+        let job_info: JobInfo = serde_json::from_str(json).unwrap();
+        assert_eq!(job_info.name, Some("test-job".to_string()));
+        assert_eq!(job_info.color, Some("blue".to_string()));
+        assert!(job_info.last_build.is_some());
+
+        let last_build = job_info.last_build.unwrap();
+        assert_eq!(last_build.number, 42);
+        assert_eq!(last_build.result, Some("SUCCESS".to_string()));
+        assert_eq!(last_build.building, Some(false));
+    }
+
+    #[test]
+    fn test_job_info_with_subjobs_deserialization() {
+        let json = r#"{
+            "name": "folder",
+            "url": "https://jenkins.example.com/job/folder/",
+            "color": "notbuilt",
+            "jobs": [
+                {
+                    "name": "sub-job-1",
+                    "url": "https://jenkins.example.com/job/folder/job/sub-job-1/",
+                    "color": "blue"
+                },
+                {
+                    "name": "sub-job-2",
+                    "url": "https://jenkins.example.com/job/folder/job/sub-job-2/",
+                    "color": "red"
+                }
+            ]
+        }"#;
+
+        let job_info: JobInfo = serde_json::from_str(json).unwrap();
+        assert_eq!(job_info.name, Some("folder".to_string()));
+        assert!(job_info.jobs.is_some());
+
+        let jobs = job_info.jobs.unwrap();
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[0].name, "sub-job-1");
+        assert_eq!(jobs[0].color, Some("blue".to_string()));
+        assert_eq!(jobs[1].name, "sub-job-2");
+        assert_eq!(jobs[1].color, Some("red".to_string()));
+    }
+
+    #[test]
+    fn test_job_info_with_health_report_deserialization() {
+        let json = r#"{
+            "name": "test-job",
+            "url": "https://jenkins.example.com/job/test-job/",
+            "color": "yellow",
+            "healthReport": [
+                {
+                    "score": 97,
+                    "description": "Test Result: 3 tests failing out of a total of 120 tests."
+                },
+                {
+                    "score": 60,
+                    "description": "Build stability: 2 out of the last 5 builds failed."
+                }
+            ]
+        }"#;
+
+        let job_info: JobInfo = serde_json::from_str(json).unwrap();
+        let reports = job_info.health_report.as_ref().unwrap();
+        assert_eq!(reports.len(), 2);
+
+        let worst = worst_health(job_info.health_report.as_ref()).unwrap();
+        assert_eq!(worst.score, 60);
+        assert_eq!(worst.description, "Build stability: 2 out of the last 5 builds failed.");
+    }
+
+    #[test]
+    fn test_worst_health_empty() {
+        assert_eq!(worst_health(None), None);
+        assert_eq!(worst_health(Some(&vec![])), None);
+    }
+
+    #[test]
+    fn test_job_info_without_color() {
+        let json = r#"{
+            "name": "test-job",
+            "url": "https://jenkins.example.com/job/test-job/"
+        }"#;
+
+        let job_info: JobInfo = serde_json::from_str(json).unwrap();
+        assert_eq!(job_info.name, Some("test-job".to_string()));
+        assert_eq!(job_info.color, None);
+        assert_eq!(job_info.last_build, None);
+        assert_eq!(job_info.jobs, None);
+        assert_eq!(job_info.health_report, None);
+    }
+
+    #[test]
+    fn test_job_info_deserialization_minimal_last_build() {
+        let json = r#"{
+            "name": "test-job",
+            "url": "https://jenkins.example.com/job/test-job/",
+            "color": "blue",
+            "lastBuild": {
+                "number": 42,
+                "url": "https://jenkins.example.com/job/test-job/42/"
+            }
+        }"#;
+
+        let job_info: JobInfo = serde_json::from_str(json).unwrap();
+        assert_eq!(job_info.name, Some("test-job".to_string()));
+        assert!(job_info.last_build.is_some());
+
+        let last_build = job_info.last_build.unwrap();
+        assert_eq!(last_build.number, 42);
+        assert_eq!(last_build.url, "https://jenkins.example.com/job/test-job/42/");
+        assert_eq!(last_build.result, None);
+        assert_eq!(last_build.building, None);
+        assert_eq!(last_build.timestamp, None);
+        assert_eq!(last_build.duration, None);
+        assert_eq!(last_build.estimated_duration, None);
+    }
+
+    #[test]
+    fn test_build_info_progress_percent() {
+        let mut build = BuildInfo {
+            number: 1,
+            url: "https://jenkins.example.com/job/app/1/".to_string(),
+            result: None,
+            building: Some(true),
+            timestamp: Some(1_000_000),
+            duration: Some(0),
+            estimated_duration: Some(100_000),
+            actions: vec![],
+        };
+
+        assert_eq!(build.progress_percent(1_050_000), Some(50));
+        // Overrunning builds stay below 100% until they actually finish
+        assert_eq!(build.progress_percent(1_500_000), Some(99));
+
+        build.estimated_duration = Some(-1);
+        assert_eq!(build.progress_percent(1_050_000), None);
+
+        build.estimated_duration = Some(100_000);
+        build.building = Some(false);
+        assert_eq!(build.progress_percent(1_050_000), None);
+    }
+
+    #[test]
+    fn test_build_details_deserialization() {
+        let json = r#"{
+            "number": 42,
+            "url": "https://jenkins.example.com/job/test-job/42/",
+            "result": "SUCCESS",
+            "building": false,
+            "timestamp": 1234567890000,
+            "duration": 5000,
+            "fullDisplayName": "test-job #42"
+        }"#;
+
+        let build_details: BuildDetails = serde_json::from_str(json).unwrap();
+        assert_eq!(build_details.number, 42);
+        assert_eq!(build_details.result, Some("SUCCESS".to_string()));
+        assert_eq!(build_details.duration, 5000);
+        assert_eq!(build_details.full_display_name, "test-job #42");
+        assert!(build_details.parameters().is_empty());
+    }
+
+    #[test]
+    fn test_build_details_with_parameters_deserialization() {
+        let json = r#"{
+            "number": 7,
+            "url": "https://jenkins.example.com/job/deploy/7/",
+            "result": "SUCCESS",
+            "building": false,
+            "timestamp": 1234567890000,
+            "duration": 5000,
+            "fullDisplayName": "deploy #7",
+            "actions": [
+                {
+                    "_class": "hudson.model.ParametersAction",
+                    "parameters": [
+                        {
+                            "_class": "hudson.model.StringParameterValue",
+                            "name": "BRANCH",
+                            "value": "main"
+                        },
+                        {
+                            "_class": "hudson.model.BooleanParameterValue",
+                            "name": "DEPLOY",
+                            "value": true
+                        }
+                    ]
+                },
+                {},
+                {
+                    "_class": "hudson.model.CauseAction"
+                }
+            ]
+        }"#;
+
+        let build_details: BuildDetails = serde_json::from_str(json).unwrap();
+        let params = build_details.parameters();
+        assert_eq!(params.len(), 2);
+        assert_eq!(params[0].name, "BRANCH");
+        assert_eq!(params[0].display_value(), "main");
+        assert_eq!(params[1].name, "DEPLOY");
+        assert_eq!(params[1].display_value(), "true");
+    }
+
+    #[test]
+    fn test_build_artifacts_recorded_md5() {
+        let json = r#"{
+            "_class": "hudson.model.FreeStyleBuild",
+            "artifacts": [
+                { "fileName": "app.tar.gz", "relativePath": "dist/app.tar.gz" },
+                { "fileName": "notes.txt", "relativePath": "docs/notes.txt" },
+                { "fileName": "build.log", "relativePath": "a/build.log" }
+            ],
+            "fingerprint": [
+                { "fileName": "dist/app.tar.gz", "hash": "9e107d9d372bb6826bd81d3542a419d6" },
+                { "fileName": "notes.txt", "hash": "e4d909c290d0fb1ca068ffaddf22cbd0" },
+                { "fileName": "build.log", "hash": "aaaa" },
+                { "fileName": "build.log", "hash": "bbbb" }
+            ]
+        }"#;
+
+        let artifacts: BuildArtifacts = serde_json::from_str(json).unwrap();
+        assert_eq!(artifacts.recorded_md5(&artifacts.artifacts[0]), Some("9e107d9d372bb6826bd81d3542a419d6"));
+        assert_eq!(artifacts.recorded_md5(&artifacts.artifacts[1]), Some("e4d909c290d0fb1ca068ffaddf22cbd0"));
+        // Two fingerprints share the name, so neither can be trusted
+        assert_eq!(artifacts.recorded_md5(&artifacts.artifacts[2]), None);
+    }
+
+    #[test]
+    fn test_build_details_git_revision() {
+        let json = r#"{
+            "number": 12,
+            "url": "https://jenkins.example.com/job/web/12/",
+            "result": "SUCCESS",
+            "building": false,
+            "timestamp": 1234567890000,
+            "duration": 5000,
+            "fullDisplayName": "web #12",
+            "actions": [
+                { "_class": "hudson.model.CauseAction" },
+                {
+                    "_class": "hudson.plugins.git.util.BuildData",
+                    "lastBuiltRevision": { "SHA1": "3f2a9c1d", "branch": [{ "name": "origin/main" }] },
+                    "remoteUrls": ["git@github.com:acme/web.git"]
+                }
+            ]
+        }"#;
+
+        let build_details: BuildDetails = serde_json::from_str(json).unwrap();
+        assert_eq!(build_details.git_revision(), Some(("3f2a9c1d", "git@github.com:acme/web.git")));
+
+        let without_git: BuildDetails = serde_json::from_str(&json.replace("lastBuiltRevision", "other")).unwrap();
+        assert_eq!(without_git.git_revision(), None);
+    }
+
+    #[test]
+    fn test_build_details_changes() {
+        let json = r#"{
+            "number": 7,
+            "url": "https://jenkins.example.com/job/web/7/",
+            "result": "SUCCESS",
+            "building": false,
+            "timestamp": 1234567890000,
+            "duration": 5000,
+            "fullDisplayName": "web #7",
+            "changeSets": [
+                { "kind": "git", "items": [
+                    { "commitId": "3f2a9c1d", "msg": "Fix login", "author": { "fullName": "Ana" }, "timestamp": 1234567000000, "affectedPaths": ["src/login.rs"] }
+                ] },
+                { "kind": "git", "items": [{ "commitId": "9b8e7d6c", "msg": "Bump deps" }] }
+            ]
+        }"#;
+
+        let build_details: BuildDetails = serde_json::from_str(json).unwrap();
+        let commits: Vec<&str> = build_details.changes().iter().filter_map(|c| c.commit_id.as_deref()).collect();
+        assert_eq!(commits, vec!["3f2a9c1d", "9b8e7d6c"]);
+        assert_eq!(build_details.changes()[0].author.as_ref().unwrap().full_name, "Ana");
+        assert!(build_details.changes()[1].affected_paths.is_empty());
+
+        // Freestyle jobs report a single set
+        let freestyle = r#"{
+            "number": 3,
+            "url": "https://jenkins.example.com/job/legacy/3/",
+            "result": "SUCCESS",
+            "building": false,
+            "timestamp": 1234567890000,
+            "duration": 5000,
+            "fullDisplayName": "legacy #3",
+            "changeSet": { "kind": "git", "items": [{ "commitId": "0c1d2e3f", "msg": "Tweak" }] }
+        }"#;
+        let build_details: BuildDetails = serde_json::from_str(freestyle).unwrap();
+        assert_eq!(build_details.changes().len(), 1);
     }
 
     #[test]
-    fn test_get_job_url() {
-        let host = create_test_host();
-        let client = JenkinsClient::new(host).unwrap();
+    fn test_generated_token_deserialization() {
+        let json = r#"{
+            "status": "ok",
+            "data": {
+                "tokenName": "jenkins-cli",
+                "tokenUuid": "7f1c0a7e-2b5d-4c52-9d4a-1b2c3d4e5f60",
+                "tokenValue": "11aa22bb33cc44dd55ee66ff77889900aa"
+            }
+        }"#;
 
-        let url = client.get_job_url("my-job");
-        assert_eq!(url, "https://jenkins.example.com/job/my-job");
+        let token: GeneratedTokenResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(token.data.name, "jenkins-cli");
+        assert_eq!(token.data.uuid, "7f1c0a7e-2b5d-4c52-9d4a-1b2c3d4e5f60");
+        assert_eq!(token.data.value, "11aa22bb33cc44dd55ee66ff77889900aa");
     }
 
     #[test]
-    fn test_get_job_url_with_trailing_slash() {
-        let mut host = create_test_host();
-        host.host = "https://jenkins.example.com/".to_string();
-        let client = JenkinsClient::new(host).unwrap();
+    fn test_run_and_credentials_parameter_deserialization() {
+        let json = r#"[
+            {
+                "_class": "hudson.model.RunParameterDefinition",
+                "name": "UPSTREAM",
+                "type": "RunParameterDefinition",
+                "projectName": "team/api-build",
+                "filter": "SUCCESSFUL"
+            },
+            {
+                "_class": "com.cloudbees.plugins.credentials.CredentialsParameterDefinition",
+                "name": "DEPLOY_KEY",
+                "type": "CredentialsParameterDefinition",
+                "credentialType": "com.cloudbees.plugins.credentials.impl.UsernamePasswordCredentialsImpl",
+                "defaultParameterValue": {"value": "deploy-creds"}
+            }
+        ]"#;
 
-        let url = client.get_job_url("my-job");
-        assert_eq!(url, "https://jenkins.example.com/job/my-job");
+        let params: Vec<ParameterDefinition> = serde_json::from_str(json).unwrap();
+        assert_eq!(params[0].project_name.as_deref(), Some("team/api-build"));
+        assert!(params[0].accepts_run_result(Some("UNSTABLE")));
+        assert!(!params[0].accepts_run_result(Some("FAILURE")));
+        assert!(!params[0].accepts_run_result(None));
+        assert_eq!(
+            params[1].credential_type.as_deref(),
+            Some("com.cloudbees.plugins.credentials.impl.UsernamePasswordCredentialsImpl")
+        );
+
+        let credential = |type_name: Option<&str>| CredentialInfo {
+            id: "deploy-creds".to_string(),
+            display_name: None,
+            type_name: type_name.map(str::to_string),
+        };
+        assert!(params[1].accepts_credential(&credential(Some("Username with password"))));
+        assert!(!params[1].accepts_credential(&credential(Some("Secret text"))));
+        assert!(params[1].accepts_credential(&credential(None)));
+
+        let any = ParameterDefinition {
+            credential_type: Some("com.cloudbees.plugins.credentials.common.StandardCredentials".to_string()),
+            ..params[1].clone()
+        };
+        assert!(any.accepts_credential(&credential(Some("Secret text"))));
     }
 
     #[test]
-    fn test_get_job_url_build_number() {
-        let host = create_test_host();
-        let client = JenkinsClient::new(host).unwrap();
+    fn test_overall_load_deserialization() {
+        let json = r#"{
+            "_class": "hudson.model.OverallLoadStatistics",
+            "busyExecutors": { "min": { "history": [6.0, 4.0, 2.0], "latest": 6.0 } },
+            "queueLength": { "min": { "history": [], "latest": 1.5 } },
+            "totalExecutors": { "min": { "latest": 8.0 } }
+        }"#;
 
-        let base_url = client.get_job_url("my-job");
-        let build_url = format!("{}/{}", base_url, 123);
-        assert_eq!(build_url, "https://jenkins.example.com/job/my-job/123");
+        let load: OverallLoad = serde_json::from_str(json).unwrap();
+        assert_eq!(load.busy_executors.min.average(1), Some(6.0));
+        // Fewer samples than asked for average what there is
+        assert_eq!(load.busy_executors.min.average(5), Some(4.0));
+        assert_eq!(load.queue_length.min.average(15), Some(1.5));
+        assert_eq!(load.total_executors.min.average(5), Some(8.0));
+        assert_eq!(LoadSeries::default().average(1), None);
     }
 
     #[test]
-    fn test_job_info_deserialization() {
+    fn test_node_info_deserialization() {
         let json = r#"{
-            "name": "test-job",
-            "url": "https://jenkins.example.com/job/test-job/",
-            "color": "blue",
-            "lastBuild": {
-                "number": 42,
-                "url": "https://jenkins.example.com/job/test-job/42/",
-                "result": "SUCCESS",
-                "building": false,
-                "timestamp": 1234567890000
+            "displayName": "agent-1",
+            "offline": false,
+            "temporarilyOffline": false,
+            "numExecutors": 2,
+            "assignedLabels": [{ "name": "agent-1" }],
+            "monitorData": {
+                "hudson.node_monitors.DiskSpaceMonitor": { "_class": "hudson.node_monitors.DiskSpaceMonitorDescriptor$DiskSpace", "path": "/var/lib/jenkins", "size": 5368709120 },
+                "hudson.node_monitors.TemporarySpaceMonitor": null,
+                "hudson.node_monitors.SwapSpaceMonitor": { "availablePhysicalMemory": 1024, "availableSwapSpace": -1, "totalPhysicalMemory": 4096, "totalSwapSpace": -1 },
+                "hudson.node_monitors.ResponseTimeMonitor": { "average": 42, "timestamp": 1000 },
+                "hudson.node_monitors.ClockMonitor": { "diff": -1500 },
+                "hudson.node_monitors.ArchitectureMonitor": "Linux (amd64)"
             }
         }"#;
 
-        let job_info: JobInfo = serde_json::from_str(json).unwrap();
-        assert_eq!(job_info.name, Some("test-job".to_string()));
-        assert_eq!(job_info.color, Some("blue".to_string()));
-        assert!(job_info.last_build.is_some());
+        let node: NodeInfo = serde_json::from_str(json).unwrap();
+        assert_eq!(node.num_executors, 2);
+        assert!(node.labels().is_empty());
+        assert_eq!(node.monitor_data.disk_space, Some(SpaceMonitor { size: 5_368_709_120 }));
+        assert_eq!(node.monitor_data.temp_space, None);
+        assert_eq!(node.monitor_data.swap_space.unwrap().available_swap_space, -1);
+        assert_eq!(node.monitor_data.response_time.unwrap().average, 42);
+        assert_eq!(node.monitor_data.clock.unwrap().diff, -1500);
 
-        let last_build = job_info.last_build.unwrap();
-        assert_eq!(last_build.number, 42);
-        assert_eq!(last_build.result, Some("SUCCESS".to_string()));
-        assert_eq!(last_build.building, Some(false));
+
+        let built_in: NodeInfo = serde_json::from_str(
+            r#"{"_class": "hudson.model.Hudson$MasterComputer", "displayName": "Built-In Node", "assignedLabels": [{"name": "built-in"}, {"name": "docker"}]}"#,
+        )
+        .unwrap();
+        assert!(built_in.is_built_in());
+        assert_eq!(built_in.labels(), vec!["docker"]);
+
+        // Offline agents come without monitor data
+        let offline: NodeInfo = serde_json::from_str(r#"{"displayName": "agent-2", "offline": true}"#).unwrap();
+        assert_eq!(offline.monitor_data, MonitorData::default());
     }
 
     #[test]
-    fn test_job_info_with_subjobs_deserialization() {
+    fn test_running_builds_from_computers() {
         let json = r#"{
-            "name": "folder",
-            "url": "https://jenkins.example.com/job/folder/",
-            "color": "notbuilt",
-            "jobs": [
+            "computer": [
                 {
-                    "name": "sub-job-1",
-                    "url": "https://jenkins.example.com/job/folder/job/sub-job-1/",
-                    "color": "blue"
+                    "displayName": "Built-In Node",
+                    "executors": [{"currentExecutable": null}],
+                    "oneOffExecutors": [{
+                        "currentExecutable": {
+                            "url": "http://jenkins/job/api/12/",
+                            "fullDisplayName": "api #12",
+                            "timestamp": 1000,
+                            "estimatedDuration": 60000
+                        }
+                    }]
                 },
                 {
-                    "name": "sub-job-2",
-                    "url": "https://jenkins.example.com/job/folder/job/sub-job-2/",
-                    "color": "red"
+                    "displayName": "agent-1",
+                    "executors": [{
+                        "currentExecutable": {
+                            "url": "http://jenkins/job/api/12/",
+                            "fullDisplayName": "api #12 (part of api #12)"
+                        }
+                    }],
+                    "oneOffExecutors": []
                 }
             ]
         }"#;
 
-        let job_info: JobInfo = serde_json::from_str(json).unwrap();
-        assert_eq!(job_info.name, Some("folder".to_string()));
-        assert!(job_info.jobs.is_some());
-
-        let jobs = job_info.jobs.unwrap();
-        assert_eq!(jobs.len(), 2);
-        assert_eq!(jobs[0].name, "sub-job-1");
-        assert_eq!(jobs[0].color, Some("blue".to_string()));
-        assert_eq!(jobs[1].name, "sub-job-2");
-        assert_eq!(jobs[1].color, Some("red".to_string()));
+        let computers: ComputerSet = serde_json::from_str(json).unwrap();
+        let builds = computers.running_builds();
+        assert_eq!(builds.len(), 2);
+        assert_eq!(builds[0].node, "Built-In Node");
+        assert!(builds[0].flyweight);
+        assert_eq!(builds[0].timestamp, Some(1000));
+        assert_eq!(builds[1].node, "agent-1");
+        assert!(!builds[1].flyweight);
     }
 
     #[test]
-    fn test_job_info_without_color() {
+    fn test_matrix_job_deserialization() {
         let json = r#"{
-            "name": "test-job",
-            "url": "https://jenkins.example.com/job/test-job/"
+            "_class": "hudson.matrix.MatrixProject",
+            "name": "compat",
+            "color": "blue",
+            "activeConfigurations": [
+                {"name": "JDK=17,OS=linux", "url": "http://jenkins/job/compat/JDK=17,OS=linux/", "color": "blue"},
+                {"name": "JDK=17,OS=windows", "url": "http://jenkins/job/compat/JDK=17,OS=windows/", "color": "red"}
+            ]
         }"#;
 
-        let job_info: JobInfo = serde_json::from_str(json).unwrap();
-        assert_eq!(job_info.name, Some("test-job".to_string()));
-        assert_eq!(job_info.color, None);
-        assert_eq!(job_info.last_build, None);
-        assert_eq!(job_info.jobs, None);
+        let job: JobInfo = serde_json::from_str(json).unwrap();
+        assert!(job.is_matrix());
+        assert!(!job.is_folder());
+        let configurations = job.active_configurations.unwrap();
+        assert_eq!(configurations.len(), 2);
+        assert_eq!(configurations[1].name, "JDK=17,OS=windows");
     }
 
     #[test]
-    fn test_job_info_deserialization_minimal_last_build() {
-        let json = r#"{
-            "name": "test-job",
-            "url": "https://jenkins.example.com/job/test-job/",
-            "color": "blue",
-            "lastBuild": {
-                "number": 42,
-                "url": "https://jenkins.example.com/job/test-job/42/"
-            }
-        }"#;
+    fn test_job_info_folder_detection() {
+        let multibranch: JobInfo = serde_json::from_str(
+            r#"{"_class": "org.jenkinsci.plugins.workflow.multibranch.WorkflowMultiBranchProject", "name": "api", "jobs": []}"#,
+        )
+        .unwrap();
+        assert!(multibranch.is_folder());
+        assert!(multibranch.folder_explanation("api").contains("no branches yet"));
 
-        let job_info: JobInfo = serde_json::from_str(json).unwrap();
-        assert_eq!(job_info.name, Some("test-job".to_string()));
-        assert!(job_info.last_build.is_some());
+        let folder: JobInfo = serde_json::from_str(
+            r#"{"_class": "com.cloudbees.hudson.plugins.folder.Folder", "name": "team",
+                "jobs": [{"name": "api", "url": "http://jenkins/job/team/job/api/"}]}"#,
+        )
+        .unwrap();
+        assert!(folder.is_folder());
+        assert!(folder.folder_explanation("team").contains("Pick one of the jobs"));
 
-        let last_build = job_info.last_build.unwrap();
-        assert_eq!(last_build.number, 42);
-        assert_eq!(last_build.url, "https://jenkins.example.com/job/test-job/42/");
-        assert_eq!(last_build.result, None);
-        assert_eq!(last_build.building, None);
-        assert_eq!(last_build.timestamp, None);
+        let job: JobInfo = serde_json::from_str(
+            r#"{"_class": "hudson.model.FreeStyleProject", "name": "api", "buildable": true}"#,
+        )
+        .unwrap();
+        assert!(!job.is_folder());
     }
 
     #[test]
-    fn test_build_details_deserialization() {
+    fn test_flatten_job_tree() {
+        let tree: Vec<JobTreeNode> = serde_json::from_str(
+            r#"[
+                {"_class": "hudson.model.FreeStyleProject", "name": "smoke", "color": "blue"},
+                {"_class": "com.cloudbees.hudson.plugins.folder.Folder", "name": "team", "jobs": [
+                    {"_class": "org.jenkinsci.plugins.workflow.job.WorkflowJob", "name": "api-deploy", "color": "red",
+                     "lastFailedBuild": {"number": 14, "timestamp": 1700000000000}, "lastSuccessfulBuild": {"number": 11}},
+                    {"_class": "com.cloudbees.hudson.plugins.folder.Folder", "name": "archive"}
+                ]}
+            ]"#,
+        )
+        .unwrap();
+
+        let mut jobs = Vec::new();
+        flatten_job_tree(tree, None, &mut jobs);
+
+        assert_eq!(
+            jobs,
+            vec![
+                DescendantJob { path: "smoke".to_string(), color: Some("blue".to_string()), builds: BuildMarks::default() },
+                DescendantJob {
+                    path: "team/job/api-deploy".to_string(),
+                    color: Some("red".to_string()),
+                    builds: BuildMarks {
+                        last_failed: Some(BuildMark { number: 14, timestamp: Some(1_700_000_000_000) }),
+                        last_successful: Some(BuildMark { number: 11, timestamp: None }),
+                        ..Default::default()
+                    },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_folder_item_kinds() {
+        let item = |class: &str| FolderItem { name: "x".to_string(), class: Some(class.to_string()) };
+
+        assert!(item("com.cloudbees.hudson.plugins.folder.Folder").is_folder());
+        assert!(!item("com.cloudbees.hudson.plugins.folder.Folder").has_generated_jobs());
+        assert!(item("org.jenkinsci.plugins.workflow.multibranch.WorkflowMultiBranchProject").has_generated_jobs());
+        assert!(item("jenkins.branch.OrganizationFolder").has_generated_jobs());
+        assert!(!item("hudson.model.FreeStyleProject").is_folder());
+        assert!(!FolderItem { name: "x".to_string(), class: None }.is_folder());
+    }
+
+    #[test]
+    fn test_body_excerpt_from_html_error_page() {
+        let page = r#"<html><head><title>Error 403 No valid crumb was included in the request</title>
+            <style>body { color: red; }</style><script>var x = "<b>";</script></head>
+            <body><h2>HTTP ERROR 403</h2><p>No valid crumb &amp; no session</p></body></html>"#;
+
+        assert_eq!(
+            body_excerpt(page, true).unwrap(),
+            "Error 403 No valid crumb was included in the request HTTP ERROR 403 No valid crumb & no session"
+        );
+    }
+
+    #[test]
+    fn test_body_excerpt_truncates_and_skips_empty() {
+        assert_eq!(body_excerpt("  \n ", false), None);
+        assert_eq!(body_excerpt("Nothing is submitted\n", false).unwrap(), "Nothing is submitted");
+
+        let long = "x".repeat(ERROR_BODY_EXCERPT_LEN + 50);
+        let excerpt = body_excerpt(&long, false).unwrap();
+        assert_eq!(excerpt.chars().count(), ERROR_BODY_EXCERPT_LEN + 1);
+        assert!(excerpt.ends_with('…'));
+    }
+
+    #[test]
+    fn test_build_info_started_by() {
         let json = r#"{
-            "number": 42,
-            "url": "https://jenkins.example.com/job/test-job/42/",
+            "number": 7,
+            "url": "https://jenkins.example.com/job/app/7/",
             "result": "SUCCESS",
-            "building": false,
-            "timestamp": 1234567890000,
-            "duration": 5000,
-            "fullDisplayName": "test-job #42"
+            "actions": [
+                {},
+                {
+                    "_class": "hudson.model.CauseAction",
+                    "causes": [
+                        {
+                            "_class": "hudson.model.Cause$UserIdCause",
+                            "shortDescription": "Started by user Jane Doe",
+                            "userId": "jdoe",
+                            "userName": "Jane Doe"
+                        }
+                    ]
+                }
+            ]
         }"#;
 
-        let build_details: BuildDetails = serde_json::from_str(json).unwrap();
-        assert_eq!(build_details.number, 42);
-        assert_eq!(build_details.result, Some("SUCCESS".to_string()));
-        assert_eq!(build_details.duration, 5000);
-        assert_eq!(build_details.full_display_name, "test-job #42");
+        let build: BuildInfo = serde_json::from_str(json).unwrap();
+        assert!(build.started_by("jdoe"));
+        assert!(build.started_by("JDoe"));
+        assert!(!build.started_by("someone-else"));
+
+        let timer: BuildInfo = serde_json::from_str(r#"{
+            "number": 8,
+            "url": "https://jenkins.example.com/job/app/8/",
+            "actions": [{"causes": [{"shortDescription": "Started by timer"}]}]
+        }"#).unwrap();
+        assert!(!timer.started_by("jdoe"));
+
+        assert_eq!(build.triggered_by().as_deref(), Some("Jane Doe"));
+        assert_eq!(timer.triggered_by().as_deref(), Some("timer"));
+    }
+
+    #[test]
+    fn test_build_info_started_by_upstream() {
+        let build: BuildInfo = serde_json::from_str(r#"{
+            "number": 31,
+            "url": "https://jenkins.example.com/job/team/job/deploy/31/",
+            "actions": [{"causes": [{
+                "_class": "hudson.model.Cause$UpstreamCause",
+                "shortDescription": "Started by upstream project \"team/api\" build number 12",
+                "upstreamProject": "team/api",
+                "upstreamBuild": 12
+            }]}]
+        }"#).unwrap();
+        assert!(build.started_by_upstream("team/api", 12));
+        assert!(!build.started_by_upstream("team/api", 11));
+        assert!(!build.started_by_upstream("team/web", 12));
     }
 
     #[test]
@@ -687,12 +4008,63 @@ mod tests {
         assert_eq!(params[1].name, "DEPLOY");
     }
 
+    #[test]
+    fn test_job_property_with_triggers_deserialization() {
+        let json = r##"{
+            "_class": "org.jenkinsci.plugins.workflow.job.properties.PipelineTriggersJobProperty",
+            "triggers": [
+                {
+                    "_class": "hudson.triggers.TimerTrigger",
+                    "spec": "# nightly\nH 2 * * *"
+                },
+                {
+                    "_class": "hudson.triggers.SCMTrigger",
+                    "spec": "H/5 * * * *"
+                },
+                {
+                    "_class": "jenkins.triggers.ReverseBuildTrigger",
+                    "spec": "",
+                    "upstreamProjects": "core-lib, api"
+                }
+            ]
+        }"##;
+
+        let prop: JobProperty = serde_json::from_str(json).unwrap();
+        let triggers = prop.triggers.unwrap();
+        assert_eq!(triggers.len(), 3);
+
+        assert_eq!(triggers[0].kind(), "Schedule");
+        assert_eq!(triggers[0].detail(), "H 2 * * *");
+        assert_eq!(triggers[1].kind(), "SCM polling");
+        assert_eq!(triggers[1].detail(), "H/5 * * * *");
+        assert_eq!(triggers[2].kind(), "Upstream");
+        assert_eq!(triggers[2].detail(), "core-lib, api");
+    }
+
+    #[test]
+    fn test_job_trigger_unknown_class() {
+        let trigger = JobTrigger {
+            class: Some("org.example.CustomTrigger".to_string()),
+            spec: None,
+            upstream_projects: None,
+        };
+        assert_eq!(trigger.kind(), "CustomTrigger");
+        assert_eq!(trigger.detail(), "");
+        assert_eq!(JobTrigger { spec: Some("0 2 * * *".to_string()), ..trigger.clone() }.next_run("api", 0), None);
+
+        let timer = JobTrigger { class: Some("hudson.triggers.TimerTrigger".to_string()), ..trigger };
+        assert_eq!(timer.next_run("api", 0), None);
+        let timer = JobTrigger { spec: Some("H/5 * * * *".to_string()), ..timer };
+        assert!(timer.next_run("api", 0).is_some_and(|next| next > 0 && next <= 5 * 60 * 1000));
+    }
+
     #[test]
     fn test_job_property_without_parameters() {
         let json = r#"{}"#;
 
         let prop: JobProperty = serde_json::from_str(json).unwrap();
         assert_eq!(prop.parameter_definitions, None);
+        assert_eq!(prop.triggers, None);
     }
 
     #[test]
@@ -740,14 +4112,218 @@ mod tests {
         assert_eq!(job_info.property, None);
     }
 
+    #[test]
+    fn test_injected_env_vars_deserialization() {
+        let json = r#"{
+            "_class": "org.jenkinsci.plugins.envinject.EnvInjectVarList",
+            "envMap": {
+                "JOB_NAME": "deploy",
+                "BUILD_NUMBER": "7",
+                "BRANCH": "main"
+            }
+        }"#;
+
+        let env_vars: InjectedEnvVars = serde_json::from_str(json).unwrap();
+        assert_eq!(env_vars.env_map.len(), 3);
+        assert_eq!(env_vars.env_map.get("BRANCH"), Some(&"main".to_string()));
+
+        // BTreeMap keeps variables sorted for display
+        let keys: Vec<&String> = env_vars.env_map.keys().collect();
+        assert_eq!(keys, vec!["BRANCH", "BUILD_NUMBER", "JOB_NAME"]);
+    }
+
+    #[test]
+    fn test_injected_env_vars_empty() {
+        let env_vars: InjectedEnvVars = serde_json::from_str("{}").unwrap();
+        assert!(env_vars.env_map.is_empty());
+    }
+
+    #[test]
+    fn test_jacoco_report_to_coverage_summary() {
+        let json = r#"{
+            "_class": "hudson.plugins.jacoco.report.CoverageReport",
+            "branchCoverage": {"covered": 40, "missed": 10, "percentage": 80, "percentageFloat": 80.0, "total": 50},
+            "lineCoverage": {"covered": 900, "missed": 100, "percentage": 90, "percentageFloat": 90.0, "total": 1000},
+            "methodCoverage": {"covered": 10, "missed": 0, "percentage": 100, "percentageFloat": 100.0, "total": 10}
+        }"#;
+
+        let report: JacocoReport = serde_json::from_str(json).unwrap();
+        let summary = CoverageSummary::from(report);
+        assert_eq!(summary.source, "JaCoCo");
+        assert_eq!(summary.line, Some(90.0));
+        assert_eq!(summary.branch, Some(80.0));
+    }
+
+    #[test]
+    fn test_jacoco_report_without_percentage() {
+        let json = r#"{
+            "lineCoverage": {"covered": 1, "missed": 3, "total": 4},
+            "branchCoverage": {"covered": 0, "missed": 0, "total": 0}
+        }"#;
+
+        let report: JacocoReport = serde_json::from_str(json).unwrap();
+        let summary = CoverageSummary::from(report);
+        assert_eq!(summary.line, Some(25.0));
+        assert_eq!(summary.branch, None);
+    }
+
+    #[test]
+    fn test_cobertura_report_to_coverage_summary() {
+        let json = r#"{
+            "_class": "hudson.plugins.cobertura.targets.CoverageResult",
+            "results": {
+                "elements": [
+                    {"name": "Packages", "ratio": 100.0},
+                    {"name": "Lines", "ratio": 82.5},
+                    {"name": "Conditionals", "ratio": 61.25}
+                ]
+            }
+        }"#;
+
+        let report: CoberturaReport = serde_json::from_str(json).unwrap();
+        let summary = CoverageSummary::from(report);
+        assert_eq!(summary.source, "Cobertura");
+        assert_eq!(summary.line, Some(82.5));
+        assert_eq!(summary.branch, Some(61.25));
+    }
+
+    #[test]
+    fn test_warnings_tools_deserialization() {
+        let json = r#"{
+            "_class": "io.jenkins.plugins.analysis.core.restapi.AggregationApi",
+            "tools": [
+                {"id": "java", "latestUrl": "https://jenkins.example.com/job/app/7/java", "name": "Java Compiler", "size": 12},
+                {"id": "checkstyle", "latestUrl": "https://jenkins.example.com/job/app/7/checkstyle", "name": "CheckStyle", "size": 3}
+            ]
+        }"#;
+
+        let tools: WarningsToolsResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(tools.tools.len(), 2);
+        assert_eq!(tools.tools[0].id, "java");
+        assert_eq!(tools.tools[0].name, "Java Compiler");
+        assert_eq!(tools.tools[1].size, Some(3));
+    }
+
+    #[test]
+    fn test_warnings_summary_deserialization() {
+        let json = r#"{
+            "_class": "io.jenkins.plugins.analysis.core.restapi.ReportApi",
+            "totalSize": 12,
+            "newSize": 2,
+            "fixedSize": 5,
+            "errorsSize": 0
+        }"#;
+
+        let summary: WarningsSummary = serde_json::from_str(json).unwrap();
+        assert_eq!(summary.total_size, 12);
+        assert_eq!(summary.new_size, 2);
+        assert_eq!(summary.fixed_size, 5);
+        assert_eq!(summary.outstanding(), 10);
+    }
+
+    #[test]
+    fn test_test_report_deserialization() {
+        let json = r#"{
+            "_class": "hudson.tasks.junit.TestResult",
+            "failCount": 2,
+            "passCount": 10,
+            "skipCount": 1,
+            "suites": [
+                {
+                    "name": "com.example.ApiTest",
+                    "cases": [
+                        {
+                            "className": "com.example.ApiTest",
+                            "name": "returnsOk",
+                            "status": "PASSED",
+                            "duration": 0.12
+                        },
+                        {
+                            "className": "com.example.ApiTest",
+                            "name": "handlesTimeout",
+                            "status": "REGRESSION",
+                            "duration": 3.5,
+                            "errorDetails": "expected 200 but was 504",
+                            "errorStackTrace": "java.lang.AssertionError: expected 200 but was 504\n\tat com.example.ApiTest.handlesTimeout(ApiTest.java:42)"
+                        }
+                    ]
+                },
+                {
+                    "name": "com.example.DbTest",
+                    "cases": [
+                        {
+                            "className": "com.example.DbTest",
+                            "name": "migrates",
+                            "status": "FAILED",
+                            "duration": 1.0,
+                            "errorDetails": null,
+                            "errorStackTrace": null
+                        },
+                        {
+                            "className": "com.example.DbTest",
+                            "name": "skipped",
+                            "status": "SKIPPED",
+                            "duration": 0.0
+                        }
+                    ]
+                }
+            ]
+        }"#;
+
+        let report: TestReport = serde_json::from_str(json).unwrap();
+        assert_eq!(report.fail_count, 2);
+        assert_eq!(report.pass_count, 10);
+        assert_eq!(report.skip_count, 1);
+        assert_eq!(report.suites.len(), 2);
+
+        let failed = report.failed_cases();
+        assert_eq!(failed.len(), 2);
+        assert_eq!(failed[0].name, "handlesTimeout");
+        assert_eq!(failed[0].error_details, Some("expected 200 but was 504".to_string()));
+        assert!(failed[0].error_stack_trace.as_ref().unwrap().contains("ApiTest.java:42"));
+        assert_eq!(failed[1].name, "migrates");
+        assert_eq!(failed[1].error_details, None);
+    }
+
     #[test]
     fn test_parameter_value_creation() {
         let param_value = ParameterValue {
             name: "BRANCH".to_string(),
             value: "develop".to_string(),
+            file: None,
         };
 
         assert_eq!(param_value.name, "BRANCH");
         assert_eq!(param_value.value, "develop");
     }
+
+    #[test]
+    fn test_agent_secret_from_jnlp() {
+        let jnlp = r#"<jnlp codebase="https://jenkins.example.com/computer/linux-1/"><application-desc>
+            <argument>8f2a4c</argument><argument>linux-1</argument>
+        </application-desc></jnlp>"#;
+        assert_eq!(agent_secret_from_jnlp(jnlp), Some("8f2a4c".to_string()));
+        assert_eq!(agent_secret_from_jnlp("<jnlp></jnlp>"), None);
+    }
+
+    #[test]
+    fn test_replay_loaded_scripts() {
+        let html = r#"<form method="post" action="run">
+            <textarea name="_.mainScript" class="jenkinsfile">
+node { load 'ci/deploy.groovy' }</textarea>
+            <textarea name="Script1" class="jenkinsfile">
+sh &quot;make deploy &amp;&amp; echo &lt;done&gt;&quot;
+</textarea>
+            <textarea name="org_example_Helpers">def greet() { 'hi' }</textarea>
+        </form>"#;
+
+        assert_eq!(
+            replay_loaded_scripts(html),
+            vec![
+                ("Script1".to_string(), "sh \"make deploy && echo <done>\"\n".to_string()),
+                ("org_example_Helpers".to_string(), "def greet() { 'hi' }".to_string()),
+            ]
+        );
+        assert!(replay_loaded_scripts("<html><body>No form</body></html>").is_empty());
+    }
 }
@@ -1,17 +1,22 @@
 use anyhow::Result;
 use crate::config::Config;
 use crate::client::JenkinsClient;
+use crate::commands::dash;
+use crate::helpers::prompt;
+use crate::helpers::url::to_human_job_path;
 use crate::interactive;
 use crate::output;
 use inquire::{Text, Select, Confirm};
+use std::collections::HashMap;
 
-pub fn execute_add(alias: Option<String>, job_name: Option<String>) -> Result<()> {
+pub fn execute_add(alias: Option<String>, job_name: Option<String>, params: Vec<(String, String)>) -> Result<()> {
     let mut config = Config::load()?;
 
     // Get alias name - either from argument or prompt
     let alias = match alias {
         Some(a) => a,
         None => {
+            prompt::ensure_interactive("an alias name", "Pass it as an argument: 'jenkins alias add <alias> <job>'.")?;
             Text::new("Enter alias name:")
                 .with_validator(|input: &str| {
                     if input.trim().is_empty() {
@@ -28,6 +33,10 @@ pub fn execute_add(alias: Option<String>, job_name: Option<String>) -> Result<()
 
     // Check if the alias already exists
     if config.job_aliases.contains_key(&alias) {
+        prompt::ensure_interactive(
+            &format!("overwriting alias '{}'", alias),
+            &format!("Remove it first with 'jenkins alias remove {}'.", alias),
+        )?;
         let overwrite = Confirm::new(&format!("Job alias '{}' already exists. Do you want to overwrite it?", alias))
             .with_default(false)
             .prompt()?;
@@ -52,13 +61,14 @@ pub fn execute_add(alias: Option<String>, job_name: Option<String>) -> Result<()
         }
     };
 
-    config.add_job_alias(alias.clone(), final_job_name.clone(), selected_jenkins.clone());
+    let parameters: HashMap<String, String> = params.into_iter().collect();
+    config.add_job_alias_with_parameters(alias.clone(), final_job_name.clone(), selected_jenkins.clone(), parameters);
     config.save()?;
 
     if let Some(j) = selected_jenkins {
-        output::success(&format!("Job alias '{}' → '{}' (Jenkins: {}) added successfully!", alias, final_job_name, j));
+        output::success(&format!("Job alias '{}' → '{}' (Jenkins: {}) added successfully!", alias, to_human_job_path(&final_job_name), j));
     } else {
-        output::success(&format!("Job alias '{}' → '{}' added successfully!", alias, final_job_name));
+        output::success(&format!("Job alias '{}' → '{}' added successfully!", alias, to_human_job_path(&final_job_name)));
     }
 
     Ok(())
@@ -79,11 +89,19 @@ pub fn execute_list() -> Result<()> {
     aliases.sort_by_key(|(alias, _)| *alias);
 
     for (alias, job_alias) in aliases {
-        let display = if let Some(ref jenkins) = job_alias.jenkins {
+        let mut display = if let Some(ref jenkins) = job_alias.jenkins {
             format!("{} (Jenkins: {})", job_alias.job_name, jenkins)
         } else {
             job_alias.job_name.clone()
         };
+        if !job_alias.parameters.is_empty() {
+            let mut params: Vec<String> = job_alias.parameters
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect();
+            params.sort();
+            display = format!("{} [{}]", display, params.join(", "));
+        }
         output::list_item(format!("{}:", alias).as_str(), &display);
     }
 
@@ -101,6 +119,7 @@ pub fn execute_remove(alias: Option<String>) -> Result<()> {
     let alias = match alias {
         Some(a) => a,
         None => {
+            prompt::ensure_interactive("an alias to remove", "Pass it as an argument: 'jenkins alias remove <alias>'.")?;
             let aliases: Vec<String> = config.job_aliases.keys().cloned().collect();
             Select::new("Select a job alias to remove:", aliases)
                 .with_help_message("Use ↑↓ to navigate, type to search, Enter to select")
@@ -113,11 +132,16 @@ pub fn execute_remove(alias: Option<String>) -> Result<()> {
         .ok_or_else(|| anyhow::anyhow!("Job alias '{}' not found", alias))?;
 
     let display = if let Some(ref jenkins) = job_alias.jenkins {
-        format!("{} (Jenkins: {})", job_alias.job_name, jenkins)
+        format!("{} (Jenkins: {})", to_human_job_path(&job_alias.job_name), jenkins)
     } else {
-        job_alias.job_name.clone()
+        to_human_job_path(&job_alias.job_name)
     };
 
+    prompt::ensure_interactive(
+        &format!("confirmation to remove alias '{}'", alias),
+        "Remove the alias from the config file directly.",
+    )?;
+
     let confirm = Confirm::new(&format!("Remove job alias '{}' → '{}'?", alias, display))
         .with_default(false)
         .prompt()?;
@@ -134,3 +158,61 @@ pub fn execute_remove(alias: Option<String>) -> Result<()> {
 
     Ok(())
 }
+
+pub fn execute_rename(old: String, new: String) -> Result<()> {
+    let mut config = Config::load()?;
+
+    if !config.job_aliases.contains_key(&old) {
+        anyhow::bail!("Job alias '{}' not found", old);
+    }
+
+    if old == new {
+        output::info("Alias names are identical. Nothing to rename.");
+        return Ok(());
+    }
+
+    // Check if the new alias already exists
+    if config.job_aliases.contains_key(&new) {
+        prompt::ensure_interactive(
+            &format!("overwriting alias '{}'", new),
+            &format!("Remove it first with 'jenkins alias remove {}'.", new),
+        )?;
+        let overwrite = Confirm::new(&format!("Job alias '{}' already exists. Do you want to overwrite it?", new))
+            .with_default(false)
+            .prompt()?;
+
+        if !overwrite {
+            return Ok(());
+        }
+        config.remove_job_alias(&new)?;
+    }
+
+    config.rename_job_alias(&old, &new)?;
+    config.save()?;
+
+    output::success(&format!("Job alias '{}' renamed to '{}'", old, new));
+
+    Ok(())
+}
+
+/// Show the current status of every alias at once, each fetched from the host it is bound to
+pub fn execute_status() -> Result<()> {
+    let config = Config::load()?;
+
+    if config.job_aliases.is_empty() {
+        output::info("No job aliases configured.");
+        return Ok(());
+    }
+
+    let mut aliases: Vec<String> = config.job_aliases.keys().cloned().collect();
+    aliases.sort();
+    let targets = dash::resolve_targets(&config, &aliases)?;
+    let clients = dash::clients_for(&targets);
+
+    let sp = output::spinner(&format!("Fetching the status of {} alias(es)...", targets.len()));
+    let jobs = dash::fetch_summaries(&targets, &clients);
+    sp.finish_and_clear();
+
+    dash::print_status("Job aliases", "ALIAS", &targets, &jobs);
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use anyhow::{Context, Result};
+use crate::helpers::history::{self, Action};
+use crate::helpers::init::create_client;
+use crate::output;
+use std::collections::BTreeMap;
+use std::fs;
+
+pub fn execute(method: String, path: String, data: Option<String>) -> Result<()> {
+    let client = create_client(None)?;
+
+    // `--data @file` reads the request body from a file
+    let body = match data {
+        Some(data) => match data.strip_prefix('@') {
+            Some(file) => Some(fs::read_to_string(file).with_context(|| format!("Failed to read '{}'", file))?),
+            None => Some(data),
+        },
+        None => None,
+    };
+
+    let method = method.to_uppercase();
+    let sp = output::spinner(&format!("{} {}...", method, path));
+    let response = client.raw_request(&method, &path, body);
+    sp.finish_and_clear();
+
+    // Only requests that can change something belong in the history
+    let outcome = response.and_then(|(status, text)| {
+        if !text.is_empty() {
+            println!("{}", pretty_body(&text));
+        }
+        if !status.is_success() {
+            anyhow::bail!("Request failed with status {}", status);
+        }
+        Ok(())
+    });
+    if method != "GET" && method != "HEAD" {
+        history::record(&client, &format!("{} {}", method, path), Action::ApiRequest, BTreeMap::new(), &outcome);
+    }
+    outcome
+}
+
+/// Pretty-print JSON responses, leaving anything else untouched
+fn pretty_body(text: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(text)
+        .ok()
+        .and_then(|value| serde_json::to_string_pretty(&value).ok())
+        .unwrap_or_else(|| text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pretty_body() {
+        assert_eq!(pretty_body(r#"{"items":[]}"#), "{\n  \"items\": []\n}");
+        assert_eq!(pretty_body("<html>Not JSON</html>"), "<html>Not JSON</html>");
+    }
+}
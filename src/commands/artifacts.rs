@@ -0,0 +1,426 @@
+use anyhow::{Context, Result};
+use console::style;
+use crate::client::{Artifact, BuildArtifacts, JenkinsClient};
+use crate::helpers::formatting::format_duration;
+use crate::helpers::init::{create_client_for_job, resolve_build_number};
+use crate::helpers::url::to_human_job_path;
+use crate::interactive;
+use crate::output;
+use glob::{MatchOptions, Pattern};
+use indicatif::{HumanBytes, MultiProgress, ProgressBar};
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Artifacts downloaded at the same time unless `--parallel` says otherwise
+pub const DEFAULT_PARALLEL_DOWNLOADS: usize = 4;
+
+pub fn execute_list(job_name: Option<String>, build_number: Option<i32>) -> Result<()> {
+    let client = create_client_for_job(job_name.as_deref(), None)?;
+
+    // Resolve the final job name (handle sub-jobs if present)
+    let final_job_name = interactive::resolve_job_name(&client, job_name.as_deref())?;
+    let build_num = resolve_build_number(&client, &final_job_name, build_number)?;
+
+    let sp = output::spinner(&format!("Fetching artifacts for {}#{}...", to_human_job_path(&final_job_name), build_num));
+    let build_artifacts = client.get_artifacts(&final_job_name, build_num)?;
+    sp.finish_and_clear();
+
+    output::header(&format!("Artifacts: {}#{}", to_human_job_path(&final_job_name), build_num));
+
+    if build_artifacts.artifacts.is_empty() {
+        output::info("No artifacts archived for this build");
+        return Ok(());
+    }
+
+    for artifact in &build_artifacts.artifacts {
+        output::bullet(&artifact.relative_path);
+    }
+
+    Ok(())
+}
+
+pub fn execute_download(
+    job_name: Option<String>,
+    build_number: Option<i32>,
+    output_dir: Option<PathBuf>,
+    patterns: Vec<String>,
+    parallel: usize,
+    resume: bool,
+) -> Result<()> {
+    let patterns = compile_patterns(&patterns)?;
+    let client = create_client_for_job(job_name.as_deref(), None)?;
+
+    // Resolve the final job name (handle sub-jobs if present)
+    let final_job_name = interactive::resolve_job_name(&client, job_name.as_deref())?;
+    let build_num = resolve_build_number(&client, &final_job_name, build_number)?;
+
+    let sp = output::spinner(&format!("Fetching artifacts for {}#{}...", to_human_job_path(&final_job_name), build_num));
+    let build_artifacts = client.get_artifacts(&final_job_name, build_num)?;
+    sp.finish_and_clear();
+
+    if build_artifacts.artifacts.is_empty() {
+        output::info(&format!("No artifacts archived for {}#{}", to_human_job_path(&final_job_name), build_num));
+        return Ok(());
+    }
+
+    let selected = select_artifacts(&build_artifacts.artifacts, &patterns);
+    if selected.is_empty() {
+        let patterns: Vec<&str> = patterns.iter().map(|p| p.as_str()).collect();
+        anyhow::bail!(
+            "No artifacts of {}#{} match {} (use 'jenkins artifacts list' to see them)",
+            final_job_name,
+            build_num,
+            patterns.join(", ")
+        );
+    }
+
+    let output_dir = output_dir.unwrap_or_else(|| PathBuf::from("."));
+    let downloads = selected
+        .into_iter()
+        .map(|artifact| Ok((artifact, output_dir.join(local_path(&artifact.relative_path)?))))
+        .collect::<Result<Vec<_>>>()?;
+
+    let started = Instant::now();
+    let multi = MultiProgress::new();
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::new());
+
+    // A fixed number of workers pull the next artifact until none are left
+    thread::scope(|scope| {
+        for _ in 0..parallel.clamp(1, downloads.len()) {
+            scope.spawn(|| {
+                while let Some((artifact, dest)) = downloads.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let pb = output::transfer_bar(&multi, &artifact.relative_path);
+                    let result =
+                        download(&client, &final_job_name, build_num, &build_artifacts, artifact, dest, resume, &pb);
+                    match &result {
+                        Ok(done) if done.resumed_from > 0 => output::finish_transfer_success(
+                            pb,
+                            &format!(
+                                "{} -> {} (resumed at {}, {})",
+                                artifact.relative_path,
+                                dest.display(),
+                                HumanBytes(done.resumed_from),
+                                done.check
+                            ),
+                        ),
+                        Ok(done) => output::finish_transfer_success(
+                            pb,
+                            &format!("{} -> {} ({})", artifact.relative_path, dest.display(), done.check),
+                        ),
+                        Err(e) => output::finish_transfer_error(pb, &format!("{}: {:#}", artifact.relative_path, e)),
+                    }
+                    results.lock().unwrap().push((*artifact, result));
+                }
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap();
+    results.sort_by(|(a, _), (b, _)| a.relative_path.cmp(&b.relative_path));
+    print_download_summary(&results, started.elapsed());
+
+    let failed = results.iter().filter(|(_, result)| result.is_err()).count();
+    if failed > 0 {
+        if !resume {
+            output::tip("Use --resume to keep partial downloads and continue them on the next run");
+        }
+        anyhow::bail!("{} of {} artifact(s) failed to download", failed, results.len());
+    }
+
+    Ok(())
+}
+
+fn print_download_summary(results: &[(&Artifact, Result<Downloaded>)], elapsed: Duration) {
+    output::header("Download summary");
+    let mut total = 0;
+    for (artifact, result) in results {
+        match result {
+            Ok(done) => {
+                total += done.bytes;
+                output::list_item(
+                    &format!("{}:", artifact.relative_path),
+                    &format!("{} in {}", HumanBytes(done.bytes), format_duration(done.elapsed.as_millis() as i64)),
+                );
+            }
+            Err(_) => output::list_item(&format!("{}:", artifact.relative_path), &style("failed").red().to_string()),
+        }
+    }
+    output::list_item(
+        "Total:",
+        &format!("{} in {}", HumanBytes(total), format_duration(elapsed.as_millis() as i64)),
+    );
+}
+
+fn compile_patterns(patterns: &[String]) -> Result<Vec<Pattern>> {
+    patterns
+        .iter()
+        .map(|p| Pattern::new(p).map_err(|e| anyhow::anyhow!("Invalid pattern '{}': {}", p, e)))
+        .collect()
+}
+
+/// Artifacts whose relative path matches any of the patterns (all of them when there are none)
+fn select_artifacts<'a>(artifacts: &'a [Artifact], patterns: &[Pattern]) -> Vec<&'a Artifact> {
+    // `*` stays within one directory, `**` crosses them
+    let options = MatchOptions { require_literal_separator: true, ..MatchOptions::new() };
+    artifacts
+        .iter()
+        .filter(|a| patterns.is_empty() || patterns.iter().any(|p| p.matches_with(&a.relative_path, options)))
+        .collect()
+}
+
+/// A verified download
+struct Downloaded {
+    bytes: u64,
+    elapsed: Duration,
+    /// How the content was checked
+    check: &'static str,
+    /// Bytes kept from an earlier partial download
+    resumed_from: u64,
+}
+
+/// Download one artifact next to its destination and only move it into place once it checks out
+/// With `resume`, an interrupted download keeps its partial file and continues from it next time
+#[allow(clippy::too_many_arguments)]
+fn download(
+    client: &JenkinsClient,
+    job_name: &str,
+    build_number: i32,
+    build_artifacts: &BuildArtifacts,
+    artifact: &Artifact,
+    dest: &Path,
+    resume: bool,
+    progress: &ProgressBar,
+) -> Result<Downloaded> {
+    let started = Instant::now();
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent).with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+
+    let partial = PartialDownload::next_to(dest, &artifact.file_name);
+    let resume_from = if resume { partial.resume_point() } else { None };
+
+    let transferred = transfer(client, job_name, build_number, artifact, &partial, resume_from, resume, progress)
+        .and_then(|(written, size, digest, resumed_from)| {
+            check_size(artifact, written, size)?;
+            Ok((written, digest, resumed_from))
+        });
+    let (written, digest, resumed_from) = match transferred {
+        Ok(transferred) => transferred,
+        Err(e) if resume => {
+            return Err(e.context(format!("partial download kept in {}", partial.file.display())));
+        }
+        Err(e) => {
+            // Never leave a truncated file behind for a script to pick up
+            partial.discard();
+            return Err(e);
+        }
+    };
+
+    // A corrupted file can't be resumed into a good one, so it's always discarded
+    let check = match check_md5(artifact, build_artifacts.recorded_md5(artifact), &digest) {
+        Ok(check) => check,
+        Err(e) => {
+            partial.discard();
+            return Err(e);
+        }
+    };
+
+    fs::rename(&partial.file, dest).with_context(|| format!("Failed to move artifact to {}", dest.display()))?;
+    let _ = fs::remove_file(&partial.validator);
+    Ok(Downloaded { bytes: written, elapsed: started.elapsed(), check, resumed_from })
+}
+
+/// A download in progress: the bytes received so far, and the validator (ETag or Last-Modified) of the
+/// artifact they came from, so they are only ever continued with the same file
+struct PartialDownload {
+    file: PathBuf,
+    validator: PathBuf,
+}
+
+impl PartialDownload {
+    fn next_to(dest: &Path, file_name: &str) -> Self {
+        PartialDownload {
+            file: dest.with_file_name(format!("{}.part", file_name)),
+            validator: dest.with_file_name(format!("{}.part.validator", file_name)),
+        }
+    }
+
+    /// Where to continue and the validator to guard it with; None when there is nothing that can safely be resumed
+    fn resume_point(&self) -> Option<(u64, String)> {
+        let validator = fs::read_to_string(&self.validator).ok()?;
+        let length = fs::metadata(&self.file).ok()?.len();
+        Some((length, validator.trim().to_string())).filter(|(length, validator)| *length > 0 && !validator.is_empty())
+    }
+
+    fn discard(&self) {
+        let _ = fs::remove_file(&self.file);
+        let _ = fs::remove_file(&self.validator);
+    }
+}
+
+/// Stream the artifact into the partial file, continuing from `resume_from` when the server allows it
+/// Returns the file's final length, the announced size, its MD5 and where the transfer started
+#[allow(clippy::too_many_arguments)]
+fn transfer(
+    client: &JenkinsClient,
+    job_name: &str,
+    build_number: i32,
+    artifact: &Artifact,
+    partial: &PartialDownload,
+    resume_from: Option<(u64, String)>,
+    resumable: bool,
+    progress: &ProgressBar,
+) -> Result<(u64, Option<u64>, String, u64)> {
+    let resume_from = resume_from.as_ref().map(|(offset, validator)| (*offset, validator.as_str()));
+    let mut stream = client.open_artifact(job_name, build_number, &artifact.relative_path, resume_from)?;
+    let mut digest = md5::Context::new();
+
+    let file = if stream.offset > 0 {
+        // The checksum covers the whole file, including what was downloaded before
+        let mut kept = File::open(&partial.file).with_context(|| format!("Failed to read {}", partial.file.display()))?;
+        io::copy(&mut kept, &mut digest).context("Failed to hash the partial download")?;
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&partial.file)
+            .with_context(|| format!("Failed to open {}", partial.file.display()))?
+    } else {
+        // A fresh copy of the artifact replaces whatever partial file was there
+        let _ = fs::remove_file(&partial.validator);
+        let file = File::create(&partial.file).with_context(|| format!("Failed to create {}", partial.file.display()))?;
+        if resumable && let Some(validator) = &stream.validator {
+            fs::write(&partial.validator, validator)
+                .with_context(|| format!("Failed to write {}", partial.validator.display()))?;
+        }
+        file
+    };
+
+    progress.set_length(stream.size.unwrap_or(0));
+    progress.set_position(stream.offset);
+
+    let mut writer = Md5Writer { inner: file, digest, progress };
+    let written = io::copy(&mut stream, &mut writer).context("Failed to read artifact")?;
+    writer.inner.flush().context("Failed to write artifact")?;
+
+    Ok((stream.offset + written, stream.size, format!("{:x}", writer.digest.finalize()), stream.offset))
+}
+
+/// Compare a finished download against the size Jenkins announced
+fn check_size(artifact: &Artifact, written: u64, expected: Option<u64>) -> Result<()> {
+    if let Some(expected) = expected
+        && expected != written
+    {
+        anyhow::bail!(
+            "Download of '{}' is truncated: received {} of {} bytes",
+            artifact.relative_path,
+            written,
+            expected
+        );
+    }
+    Ok(())
+}
+
+/// Compare a finished download against the MD5 Jenkins recorded
+fn check_md5(artifact: &Artifact, recorded_md5: Option<&str>, actual_md5: &str) -> Result<&'static str> {
+    match recorded_md5 {
+        Some(recorded) if !recorded.eq_ignore_ascii_case(actual_md5) => anyhow::bail!(
+            "Checksum mismatch for '{}': Jenkins recorded MD5 {}, downloaded file has {}",
+            artifact.relative_path,
+            recorded,
+            actual_md5
+        ),
+        Some(_) => Ok("MD5 verified"),
+        None => Ok("no fingerprint recorded"),
+    }
+}
+
+/// Map an artifact's relative path below the output directory, refusing paths that escape it
+fn local_path(relative_path: &str) -> Result<PathBuf> {
+    let path = Path::new(relative_path);
+    if relative_path.is_empty() || !path.components().all(|c| matches!(c, Component::Normal(_))) {
+        anyhow::bail!("Refusing to write artifact with unsafe path '{}'", relative_path);
+    }
+    Ok(path.to_path_buf())
+}
+
+/// Hashes everything written through it and advances the progress bar
+struct Md5Writer<'a> {
+    inner: File,
+    digest: md5::Context,
+    progress: &'a ProgressBar,
+}
+
+impl Write for Md5Writer<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.digest.consume(&buf[..written]);
+        self.progress.inc(written as u64);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn artifact() -> Artifact {
+        Artifact { file_name: "app.tar.gz".to_string(), relative_path: "dist/app.tar.gz".to_string() }
+    }
+
+    #[test]
+    fn test_verify_download() {
+        let md5 = "9e107d9d372bb6826bd81d3542a419d6";
+        assert!(check_size(&artifact(), 10, Some(10)).is_ok());
+        assert!(check_size(&artifact(), 10, None).is_ok());
+        assert_eq!(check_md5(&artifact(), Some(md5), md5).unwrap(), "MD5 verified");
+        assert_eq!(check_md5(&artifact(), None, md5).unwrap(), "no fingerprint recorded");
+
+        let truncated = check_size(&artifact(), 4, Some(10)).unwrap_err().to_string();
+        assert!(truncated.contains("truncated: received 4 of 10 bytes"));
+
+        let mismatch = check_md5(&artifact(), Some(md5), "0000").unwrap_err().to_string();
+        assert!(mismatch.contains("Checksum mismatch for 'dist/app.tar.gz'"));
+    }
+
+    #[test]
+    fn test_select_artifacts() {
+        let artifacts: Vec<Artifact> = ["dist/app.tar.gz", "dist/debug/app.tar.gz", "dist/app.zip", "README.md"]
+            .iter()
+            .map(|path| Artifact {
+                file_name: path.rsplit('/').next().unwrap().to_string(),
+                relative_path: path.to_string(),
+            })
+            .collect();
+        let paths = |patterns: &[&str]| -> Vec<String> {
+            let patterns: Vec<String> = patterns.iter().map(|p| p.to_string()).collect();
+            select_artifacts(&artifacts, &compile_patterns(&patterns).unwrap())
+                .iter()
+                .map(|a| a.relative_path.clone())
+                .collect()
+        };
+
+        assert_eq!(paths(&["dist/*.tar.gz"]), vec!["dist/app.tar.gz"]);
+        assert_eq!(paths(&["dist/**/*.tar.gz"]), vec!["dist/app.tar.gz", "dist/debug/app.tar.gz"]);
+        assert_eq!(paths(&["*.md", "dist/*.zip"]), vec!["dist/app.zip", "README.md"]);
+        assert_eq!(paths(&[]).len(), 4);
+        assert!(paths(&["*.exe"]).is_empty());
+        assert!(compile_patterns(&["dist/[".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_local_path() {
+        assert_eq!(local_path("dist/app.tar.gz").unwrap(), PathBuf::from("dist/app.tar.gz"));
+        assert!(local_path("../outside.txt").is_err());
+        assert!(local_path("/etc/passwd").is_err());
+        assert!(local_path("").is_err());
+    }
+}
@@ -0,0 +1,218 @@
+use anyhow::{Context, Result};
+use crate::cli::AutobuildTrigger;
+use crate::commands::build::{self, TriggerOutcome};
+use crate::helpers::formatting::{format_clock_time, now_millis};
+use crate::helpers::git;
+use crate::helpers::history;
+use crate::helpers::init::create_client_for_job;
+use crate::helpers::interrupt;
+use crate::helpers::url::to_human_job_path;
+use crate::interactive;
+use crate::output;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Directories left out when watching outside a git repository, where no .gitignore says what's generated
+const SKIPPED_DIRS: &[&str] = &["target", "node_modules"];
+
+/// What the watched directory looked like at one check
+#[derive(Debug, PartialEq)]
+enum Snapshot {
+    /// Commit the branch's upstream points at
+    Upstream(Option<String>),
+    /// Modification time and size of every watched file
+    Files(BTreeMap<PathBuf, (SystemTime, u64)>),
+}
+
+/// Trigger the job whenever the current branch is pushed or files are saved, until Ctrl+C
+pub fn execute(job: Option<String>, on: AutobuildTrigger, interval: u64, debounce: u64) -> Result<()> {
+    let dir = std::env::current_dir().context("Failed to read the current directory")?;
+    let client = create_client_for_job(job.as_deref(), None)?;
+    let final_job_name = interactive::resolve_job_name(&client, job.as_deref())?;
+
+    let mut last = snapshot(&dir, on);
+    if last == Snapshot::Upstream(None) {
+        anyhow::bail!("The current branch has no upstream to watch. Push it once with 'git push -u', or use --on save.");
+    }
+
+    // Parameters are collected once and reused for every build
+    let sp = output::spinner("Checking job parameters...");
+    let definitions = client.get_job_parameters(&final_job_name)?;
+    sp.finish_and_clear();
+    let secrets = history::secret_parameters(&definitions);
+    let parameters = build::collect_parameters(&client, &final_job_name, definitions, job.as_deref())?;
+
+    output::header(&format!("Autobuilding '{}'", to_human_job_path(&final_job_name)));
+    output::dim(match on {
+        AutobuildTrigger::Push => "Building whenever this branch is pushed - press Ctrl+C to stop",
+        AutobuildTrigger::Save => "Building whenever files are saved - press Ctrl+C to stop",
+    });
+
+    let _ctrl_c = interrupt::catch_ctrl_c();
+    let mut triggered = 0;
+    'watch: loop {
+        if interrupt::sleep(Duration::from_secs(interval)) {
+            break;
+        }
+        let mut settled = snapshot(&dir, on);
+        if settled == last {
+            continue;
+        }
+
+        // Let a burst of saves settle into one build
+        loop {
+            if interrupt::sleep(Duration::from_secs(debounce)) {
+                break 'watch;
+            }
+            let next = snapshot(&dir, on);
+            if next == settled {
+                break;
+            }
+            settled = next;
+        }
+
+        let change = describe_change(&last, &settled);
+        last = settled;
+        let Some(change) = change else {
+            continue;
+        };
+
+        let line = format!("{}  {}", format_clock_time(now_millis()), change);
+        match build::trigger_and_wait(&client, &final_job_name, parameters.clone(), &secrets, None) {
+            TriggerOutcome::Started(number) => {
+                triggered += 1;
+                output::success(&format!("{} → #{} started => {}/{}", line, number, client.get_job_url(&final_job_name), number));
+            }
+            TriggerOutcome::Queued(why) => {
+                triggered += 1;
+                let why = why.map(|why| format!(" ({})", why)).unwrap_or_default();
+                output::info(&format!("{} → queued{}", line, why));
+            }
+            TriggerOutcome::Cancelled => output::warning(&format!("{} → queue item cancelled", line)),
+            TriggerOutcome::Failed(e) => output::error(&format!("{} → not triggered: {:#}", line, e)),
+        }
+    }
+
+    output::newline();
+    output::info(&format!("Stopped after triggering {} build(s)", triggered));
+    Ok(())
+}
+
+/// Look at the watched state; anything unreadable counts as missing, so it shows up as a change later
+fn snapshot(dir: &Path, on: AutobuildTrigger) -> Snapshot {
+    match on {
+        AutobuildTrigger::Push => Snapshot::Upstream(git::upstream_commit(dir)),
+        AutobuildTrigger::Save => {
+            let files = git::listed_files(dir).unwrap_or_else(|| walk_files(dir));
+            let stats = files
+                .into_iter()
+                .filter_map(|path| {
+                    let metadata = fs::metadata(dir.join(&path)).ok()?;
+                    Some((path, (metadata.modified().ok()?, metadata.len())))
+                })
+                .collect();
+            Snapshot::Files(stats)
+        }
+    }
+}
+
+/// Files below `dir` (relative to it), leaving out hidden entries and build output
+fn walk_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![PathBuf::new()];
+    while let Some(relative) = pending.pop() {
+        let Ok(entries) = fs::read_dir(dir.join(&relative)) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if name.starts_with('.') || SKIPPED_DIRS.contains(&name.as_ref()) {
+                continue;
+            }
+            let path = relative.join(name.as_ref());
+            match entry.file_type() {
+                Ok(kind) if kind.is_dir() => pending.push(path),
+                Ok(_) => files.push(path),
+                Err(_) => {}
+            }
+        }
+    }
+    files
+}
+
+/// What changed between two checks, for the build log, or None if it shouldn't trigger a build
+fn describe_change(before: &Snapshot, after: &Snapshot) -> Option<String> {
+    match (before, after) {
+        (Snapshot::Upstream(before), Snapshot::Upstream(Some(commit))) if before.as_ref() != Some(commit) => {
+            Some(format!("pushed {}", &commit[..commit.len().min(8)]))
+        }
+        (Snapshot::Files(before), Snapshot::Files(after)) => {
+            let mut changes: Vec<String> = after
+                .iter()
+                .filter_map(|(path, stat)| match before.get(path) {
+                    None => Some(format!("{} added", path.display())),
+                    Some(old) if old != stat => Some(format!("{} changed", path.display())),
+                    Some(_) => None,
+                })
+                .collect();
+            changes.extend(
+                before
+                    .keys()
+                    .filter(|path| !after.contains_key(*path))
+                    .map(|path| format!("{} deleted", path.display())),
+            );
+            match changes.len() {
+                0 => None,
+                1 => changes.pop(),
+                n => Some(format!("{} and {} more", changes[0], n - 1)),
+            }
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_change() {
+        let commit = |sha: &str| Snapshot::Upstream(Some(sha.to_string()));
+        assert_eq!(describe_change(&commit("0123456789ab"), &commit("fedcba987654")).as_deref(), Some("pushed fedcba98"));
+        assert_eq!(describe_change(&commit("0123456789ab"), &commit("0123456789ab")), None);
+        // An upstream that went away (e.g. the remote branch was deleted) isn't worth a build
+        assert_eq!(describe_change(&commit("0123456789ab"), &Snapshot::Upstream(None)), None);
+
+        let at = |secs: u64| SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+        let files = |entries: &[(&str, u64)]| {
+            Snapshot::Files(entries.iter().map(|(path, secs)| (PathBuf::from(path), (at(*secs), 10))).collect())
+        };
+        let before = files(&[("src/main.rs", 1), ("README.md", 1)]);
+        assert_eq!(
+            describe_change(&before, &files(&[("src/main.rs", 2), ("README.md", 1)])).as_deref(),
+            Some("src/main.rs changed")
+        );
+        assert_eq!(
+            describe_change(&before, &files(&[("src/main.rs", 2), ("src/lib.rs", 2)])).as_deref(),
+            Some("src/lib.rs added and 2 more")
+        );
+        assert_eq!(describe_change(&before, &files(&[("src/main.rs", 1), ("README.md", 1)])), None);
+    }
+
+    #[test]
+    fn test_walk_files_skips_hidden_and_build_output() {
+        let dir = tempfile::tempdir().unwrap();
+        for path in ["src/main.rs", ".env", "target/debug/app", ".cache/x", "Cargo.toml"] {
+            let path = dir.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+
+        let mut files = walk_files(dir.path());
+        files.sort();
+        assert_eq!(files, vec![PathBuf::from("Cargo.toml"), PathBuf::from("src/main.rs")]);
+    }
+}
@@ -0,0 +1,108 @@
+use anyhow::{Context, Result};
+use crate::client::JenkinsClient;
+use crate::config::Config;
+use crate::helpers::init::create_client_for_job;
+use crate::helpers::url::to_display_job_path;
+use crate::output;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+pub fn execute(folder: Option<String>, dest: PathBuf) -> Result<()> {
+    let client = create_client_for_job(folder.as_deref(), None)?;
+
+    // Resolve alias if the folder was given as one
+    let config = Config::load()?;
+    let folder = folder.map(|name| config.resolve_job_name(&name).map(|(folder, _, _)| folder)).transpose()?;
+
+    let sp = output::spinner("Backing up job configurations...");
+    let mut backup = Backup { client: &client, dest: &dest, saved: 0, failed: Vec::new() };
+
+    if let Some(folder) = folder.as_deref() {
+        // The folder's own config (views, properties, credentials domains) is part of the hierarchy too
+        backup.save(folder);
+    }
+    backup.walk(folder.as_deref(), &|saved| sp.set_message(format!("Backing up job configurations... ({} saved)", saved)))?;
+    sp.finish_and_clear();
+
+    for (job, error) in &backup.failed {
+        output::warning(&format!("{}: {:#}", to_display_job_path(job), error));
+    }
+    output::success(&format!("Saved {} job configuration(s) to {}", backup.saved, dest.display()));
+
+    if !backup.failed.is_empty() {
+        anyhow::bail!("{} job configuration(s) could not be backed up", backup.failed.len());
+    }
+
+    Ok(())
+}
+
+struct Backup<'a> {
+    client: &'a JenkinsClient,
+    dest: &'a Path,
+    saved: usize,
+    failed: Vec<(String, anyhow::Error)>,
+}
+
+impl Backup<'_> {
+    /// Save every job below `folder` (the root when None), descending into folders
+    fn walk(&mut self, folder: Option<&str>, progress: &dyn Fn(usize)) -> Result<()> {
+        for item in self.client.get_folder_items(folder)? {
+            let job = match folder {
+                Some(folder) => format!("{}/job/{}", folder, item.name),
+                None => item.name.clone(),
+            };
+            self.save(&job);
+            progress(self.saved);
+
+            // Branch jobs are regenerated from SCM, so only the multibranch project itself is kept
+            if item.is_folder() && !item.has_generated_jobs() {
+                self.walk(Some(&job), progress)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Save one job's config.xml, recording failures instead of aborting the whole backup
+    fn save(&mut self, job: &str) {
+        let result = backup_path(self.dest, job).and_then(|path| {
+            let xml = self.client.get_job_config(job)?;
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).with_context(|| format!("Failed to create {}", parent.display()))?;
+            }
+            fs::write(&path, xml).with_context(|| format!("Failed to write {}", path.display()))
+        });
+
+        match result {
+            Ok(()) => self.saved += 1,
+            Err(e) => self.failed.push((job.to_string(), e)),
+        }
+    }
+}
+
+/// `dest/<folder>/<job>/config.xml` for a job path like `folder/job/job`
+fn backup_path(dest: &Path, job: &str) -> Result<PathBuf> {
+    let mut path = dest.to_path_buf();
+    for name in to_display_job_path(job).split('/') {
+        if !matches!(Path::new(name).components().next(), Some(Component::Normal(_))) || name.contains('\\') {
+            anyhow::bail!("Refusing to write job '{}' outside the backup directory", name);
+        }
+        path.push(name);
+    }
+    path.push("config.xml");
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_path() {
+        assert_eq!(
+            backup_path(Path::new("backup"), "team/job/api/job/main").unwrap(),
+            PathBuf::from("backup/team/api/main/config.xml")
+        );
+        assert_eq!(backup_path(Path::new("backup"), "deploy").unwrap(), PathBuf::from("backup/deploy/config.xml"));
+        assert!(backup_path(Path::new("backup"), "team/job/..").is_err());
+    }
+}
@@ -1,30 +1,196 @@
 use anyhow::Result;
-use crate::helpers::init::create_client_for_job;
+use crate::client::{DownstreamBuild, JenkinsClient, ParameterDefinition, ParameterValue, QueueItemStatus};
+use crate::config::Config;
+use crate::helpers::formatting::{format_duration, now_millis};
+use crate::helpers::init::{create_client, create_client_for_job};
+use crate::helpers::commit_status::{self, CommitStatus};
+use crate::helpers::errors::ErrorKind;
+use crate::helpers::events::{self, BuildEvent, Outcome};
+use crate::helpers::history::{self, Action};
+use crate::helpers::notify::{self, BuildSummary};
+use crate::helpers::stages::{StageTracker, STAGE_POLL_EVERY};
+use crate::helpers::{interrupt, prompt};
+use crate::helpers::params::{env_parameters, parse_duration, validate_parameter_values, ENV_PARAM_PREFIX};
+use crate::helpers::url::{queue_item_id, to_display_job_path, to_human_job_path};
+use crate::commands::logs;
 use crate::interactive;
 use crate::output;
+use crate::state::{LastBuild, State};
+use inquire::{Confirm, InquireError};
+use std::collections::{BTreeMap, HashSet, VecDeque};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-pub fn execute(job_name: Option<String>, follow: bool) -> Result<()> {
-    let client = create_client_for_job(job_name.as_deref(), None)?;
+/// Default wait for a queued build to start when following
+const DEFAULT_QUEUE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long to wait for triggered builds that are still queued or not yet started
+const DOWNSTREAM_WAIT: Duration = Duration::from_secs(20);
+const DOWNSTREAM_POLL: Duration = Duration::from_secs(2);
+
+/// A followed build that ran to completion
+pub struct FinishedBuild {
+    pub client: JenkinsClient,
+    pub job_name: String,
+    pub number: i32,
+    /// None when the result couldn't be fetched
+    pub result: Option<String>,
+}
+
+pub fn execute(
+    job_name: Option<String>,
+    jenkins: Option<String>,
+    follow: bool,
+    queue_timeout: Option<Duration>,
+    events: bool,
+    notify: Option<String>,
+    follow_downstream: bool,
+) -> Result<()> {
+    let Some(finished) = run(job_name, jenkins, follow, queue_timeout, events, notify)? else {
+        return Ok(());
+    };
+
+    // With --events stdout is reserved, so downstream builds are neither offered nor followed
+    let mut failed = Vec::new();
+    if !events {
+        failed = follow_downstream_builds(&finished, follow_downstream)?;
+    }
+
+    if let Some(result) = finished.result.as_deref().filter(|r| *r != "SUCCESS") {
+        return Err(ErrorKind::BuildFailed.error(format!("Build #{} finished with {}", finished.number, result)));
+    }
+    if !failed.is_empty() {
+        return Err(ErrorKind::BuildFailed.error(format!("Downstream build(s) did not succeed: {}", failed.join(", "))));
+    }
+    Ok(())
+}
+
+/// Follow the builds a finished build triggered, then the ones those triggered, until the chain ends
+/// Without `always`, each one is offered first; returns the followed builds that didn't succeed
+fn follow_downstream_builds(finished: &FinishedBuild, always: bool) -> Result<Vec<String>> {
+    let client = &finished.client;
+    let succeeded = finished.result.as_deref() == Some("SUCCESS");
+    let mut upstream = VecDeque::from([(finished.job_name.clone(), finished.number, succeeded)]);
+    let mut seen = HashSet::new();
+    let mut failed = Vec::new();
+
+    while let Some((job_name, number, succeeded)) = upstream.pop_front() {
+        let sp = output::spinner("Looking for downstream builds...");
+        let builds = wait_for_downstream(client, &job_name, number, succeeded);
+        sp.finish_and_clear();
+        let builds = match builds {
+            Ok(builds) => builds,
+            Err(e) => {
+                output::warning(&format!("Could not look up downstream builds: {:#}", e));
+                continue;
+            }
+        };
+
+        for build in builds {
+            if !seen.insert((build.job_name.clone(), build.number)) {
+                continue;
+            }
+            let label = format!("{}#{}", to_human_job_path(&build.job_name), build.number);
+            if !always {
+                if prompt::is_non_interactive() {
+                    output::info(&format!("Triggered downstream build {}", label));
+                    output::tip("Use --follow-downstream to follow downstream builds as well");
+                    continue;
+                }
+                let follow = match Confirm::new(&format!("Follow downstream build {}?", label)).with_default(true).prompt() {
+                    Ok(follow) => follow,
+                    Err(InquireError::OperationCanceled | InquireError::OperationInterrupted) => {
+                        return Err(ErrorKind::Cancelled.error("Stopped following downstream builds"));
+                    }
+                    Err(_) => false,
+                };
+                if !follow {
+                    continue;
+                }
+            }
+
+            logs::show_logs(client, &build.job_name, build.number, true)?;
+            let result = client.get_build(&build.job_name, build.number).ok().and_then(|b| b.result);
+            let succeeded = result.as_deref() == Some("SUCCESS");
+            if let Some(result) = result.filter(|r| r != "SUCCESS") {
+                output::warning(&format!("{} finished with {}", label, result));
+                failed.push(label);
+            }
+            upstream.push_back((build.job_name, build.number, succeeded));
+        }
+    }
+    Ok(failed)
+}
+
+/// Downstream builds of a build, waiting a little for triggered ones that haven't started yet
+fn wait_for_downstream(
+    client: &JenkinsClient,
+    job_name: &str,
+    number: i32,
+    succeeded: bool,
+) -> Result<Vec<DownstreamBuild>> {
+    let _ctrl_c = interrupt::catch_ctrl_c();
+    let started = Instant::now();
+    loop {
+        let downstream = client.get_downstream_builds(job_name, number, succeeded)?;
+        if !downstream.pending || started.elapsed() >= DOWNSTREAM_WAIT {
+            return Ok(downstream.builds);
+        }
+        if interrupt::sleep(DOWNSTREAM_POLL) {
+            return Ok(downstream.builds);
+        }
+    }
+}
+
+/// Trigger a build and, when following, return it once it has finished
+pub fn run(
+    job_name: Option<String>,
+    jenkins: Option<String>,
+    follow: bool,
+    queue_timeout: Option<Duration>,
+    events: bool,
+    notify: Option<String>,
+) -> Result<Option<FinishedBuild>> {
+    if events {
+        // stdout carries only the JSON event stream
+        output::messages_to_stderr();
+    }
+
+    let client = create_client_for_job(job_name.as_deref(), jenkins)?;
+    // Fail before prompting for the job and its parameters
+    client.ensure_writable("triggering a build")?;
 
     // Resolve the final job name (handle sub-jobs if present)
-    let final_job_name = interactive::resolve_job_name(&client, job_name.as_deref())?;
+    let mut final_job_name = interactive::resolve_job_name(&client, job_name.as_deref())?;
 
     // Check if job is buildable
-    let sp = output::spinner("Checking job status...");
-    let job_info = client.get_job(&final_job_name)?;
-    sp.finish_and_clear();
+    let job_info = loop {
+        let sp = output::spinner("Checking job status...");
+        let job_info = client.get_job(&final_job_name)?;
+        sp.finish_and_clear();
 
-    // Verify job is buildable
-    if let Some(buildable) = job_info.buildable {
-        if !buildable {
-            let reason = match job_info.color.as_deref() {
-                Some("disabled") => "The job is disabled",
-                _ => "The job is not buildable",
-            };
-            anyhow::bail!("{reason}. Please check the job configuration in Jenkins.");
+        if !job_info.is_folder() {
+            break job_info;
         }
+
+        // Folders can't be built - descend into their jobs instead of failing on the trigger request
+        let explanation = job_info.folder_explanation(&to_human_job_path(&final_job_name));
+        if job_info.jobs.as_ref().is_none_or(|jobs| jobs.is_empty()) {
+            anyhow::bail!(explanation);
+        }
+        output::warning(&explanation);
+        final_job_name = interactive::select_sub_job(&client, &final_job_name)?;
+    };
+
+    // Verify job is buildable
+    if let Some(buildable) = job_info.buildable
+        && !buildable
+    {
+        let reason = match job_info.color.as_deref() {
+            Some("disabled") => "The job is disabled",
+            _ => "The job is not buildable",
+        };
+        anyhow::bail!("{reason}. Please check the job configuration in Jenkins.");
     }
 
     // Fetch and collect parameters
@@ -32,60 +198,101 @@ pub fn execute(job_name: Option<String>, follow: bool) -> Result<()> {
     let parameter_definitions = client.get_job_parameters(&final_job_name)?;
     sp.finish_and_clear();
 
-    let parameters = if !parameter_definitions.is_empty() {
-        let param_values = interactive::collect_parameters(parameter_definitions)?;
-        Some(param_values)
-    } else {
-        None
-    };
+    let secrets = history::secret_parameters(&parameter_definitions);
+    let parameters = collect_parameters(&client, &final_job_name, parameter_definitions, job_name.as_deref())?;
+    let recorded = history::recorded_parameters(parameters.as_deref(), &secrets);
 
-    let sp = output::spinner(&format!("Triggering build for job '{}'...", final_job_name));
-    let queue_location = client.trigger_build(&final_job_name, parameters)?;
+    let sp = output::spinner(&format!("Triggering build for job '{}'...", to_human_job_path(&final_job_name)));
+    let triggered = client.trigger_build(&final_job_name, parameters);
+    history::record(&client, &final_job_name, Action::Build, recorded, &triggered);
+    let queue_location = triggered?;
 
     let job_url = client.get_job_url(&final_job_name);
     output::finish_spinner_success(sp, &format!("Build triggered successfully! => {}", job_url));
 
+    // Remember the build for 'jenkins last'
+    let mut last_build = client.name().map(|name| LastBuild {
+        jenkins: name.to_string(),
+        job: final_job_name.clone(),
+        number: None,
+        queue_url: queue_location.clone(),
+        triggered_at: now_millis(),
+    });
+    if let Some(last) = &last_build {
+        remember_last_build(last);
+    }
+
     if !follow {
-        return Ok(());
+        return Ok(None);
     }
 
     // Follow the build logs
     if let Some(queue_url) = queue_location {
-        let sp = output::spinner("Waiting for build to start...");
+        let timeout = resolve_queue_timeout(queue_timeout, client.queue_timeout())?;
+        if events {
+            events::emit(&BuildEvent::Queued { job: final_job_name.clone(), queue_url: queue_url.clone() });
+        }
+        // Every way following ends after `queued` is reported with a `finished` event
+        let stopped = |build: Option<i32>, outcome: Outcome| {
+            if events {
+                events::emit(&BuildEvent::Finished { job: final_job_name.clone(), build, result: None, outcome });
+            }
+        };
+
+        // Ctrl+C while queued offers to cancel instead of leaving an orphaned queue item
+        let _ctrl_c = interrupt::catch_ctrl_c();
+        let sp = output::spinner("Waiting for build to start... (Ctrl+C to cancel)");
 
         // Poll queue until build starts (with timeout)
-        let mut attempts = 0;
-        let max_attempts = 30; // 30 seconds max wait
+        let started = Instant::now();
         let build_number = loop {
-            thread::sleep(Duration::from_secs(1));
-            attempts += 1;
-            sp.set_message(format!("Waiting for build to start... ({}/30s)", attempts));
+            if interrupt::sleep(Duration::from_secs(1)) {
+                sp.finish_and_clear();
+                let cancelled = handle_queue_interrupt(&client, &queue_url, &final_job_name);
+                stopped(None, if matches!(cancelled, Ok(true)) { Outcome::QueueCancelled } else { Outcome::Interrupted });
+                return cancelled.map(|_| None);
+            }
+            let elapsed = started.elapsed();
+            let timed_out = elapsed >= timeout;
 
-            match client.get_build_number_from_queue(&queue_url) {
-                Ok(Some(num)) => {
+            match client.get_queue_item(&queue_url) {
+                Ok(QueueItemStatus { build_number: Some(num), .. }) => {
                     output::finish_spinner_success(sp, &format!("Build #{} started", num));
                     break Some(num);
                 }
-                Ok(None) => {
-                    if attempts >= max_attempts {
+                Ok(QueueItemStatus { cancelled: true, .. }) => {
+                    output::finish_spinner_warning(sp, "The queued build was cancelled");
+                    stopped(None, Outcome::QueueCancelled);
+                    return Ok(None);
+                }
+                Ok(QueueItemStatus { why, .. }) => {
+                    if timed_out {
                         output::finish_spinner_warning(sp, "Timeout waiting for build to start");
+                        if let Some(why) = why {
+                            output::dim(&format!("Still queued: {}", why));
+                        }
                         break None;
                     }
+                    // Show Jenkins' reason for the wait (e.g. "Waiting for next available executor")
+                    sp.set_message(format!(
+                        "Waiting for build to start... ({}/{}) {}",
+                        format_duration(elapsed.as_millis() as i64),
+                        format_duration(timeout.as_millis() as i64),
+                        why.unwrap_or_default()
+                    ));
                     continue;
                 }
-                Err(_) => {
+                Err(e) => {
+                    tracing::debug!(queue_url = %queue_url, error = %format!("{:#}", e), "queue poll failed, retrying");
                     // Queue item might be gone - try to get last build number
-                    match client.get_job(&final_job_name) {
-                        Ok(job) => {
-                            if let Some(last_build) = job.last_build {
-                                output::finish_spinner_success(sp, &format!("Build #{} already started", last_build.number));
-                                break Some(last_build.number);
-                            }
-                        }
-                        Err(_) => {}
+                    if let Ok(job) = client.get_job(&final_job_name)
+                        && let Some(last_build) = job.last_build
+                    {
+                        output::finish_spinner_success(sp, &format!("Build #{} already started", last_build.number));
+                        break Some(last_build.number);
                     }
 
-                    if attempts >= max_attempts {
+                    if timed_out {
                         output::finish_spinner_warning(sp, "Could not determine build number");
                         break None;
                     }
@@ -94,11 +301,21 @@ pub fn execute(job_name: Option<String>, follow: bool) -> Result<()> {
             }
         };
 
+        if let (Some(num), true) = (build_number, events) {
+            events::emit(&BuildEvent::Started { job: final_job_name.clone(), build: num });
+        }
+
+        if let (Some(num), Some(last)) = (build_number, last_build.as_mut()) {
+            last.number = Some(num);
+            remember_last_build(last);
+        }
+
         let build_number = match build_number {
             Some(num) => num,
             None => {
-                output::tip(&format!("Use 'jenkins logs {}' to view logs later", final_job_name));
-                return Ok(());
+                stopped(None, Outcome::QueueTimeout);
+                output::tip(&format!("Use 'jenkins logs {}' to view logs later", to_display_job_path(&final_job_name)));
+                return Ok(None);
             }
         };
 
@@ -107,12 +324,32 @@ pub fn execute(job_name: Option<String>, follow: bool) -> Result<()> {
 
         // Stream logs
         let sp = output::spinner("Streaming build logs...");
+        let mut outcome = Outcome::Completed;
+        let mut result = None;
         let mut offset = 0;
+        let mut stages = StageTracker::default();
+        let mut polls = 0;
         loop {
             match client.get_console_log_progressive(&final_job_name, build_number, offset) {
                 Ok((text, new_offset, more_data)) => {
+                    // Stage changes go ahead of the new text, which is most likely output of the new stage
+                    if polls % STAGE_POLL_EVERY == 0 || !more_data {
+                        if events {
+                            emit_stage_changes(&client, &mut stages, &final_job_name, build_number);
+                        } else {
+                            for stage in stages.poll_started(&client, &final_job_name, build_number) {
+                                sp.suspend(|| output::stage_marker(&stage.name));
+                            }
+                        }
+                    }
+                    polls += 1;
+
                     if !text.is_empty() {
-                        sp.suspend(|| print!("{}", text));
+                        if events {
+                            events::emit(&BuildEvent::LogChunk { job: final_job_name.clone(), build: build_number, text });
+                        } else {
+                            sp.suspend(|| print!("{}", text));
+                        }
                     }
                     offset = new_offset;
 
@@ -120,22 +357,359 @@ pub fn execute(job_name: Option<String>, follow: bool) -> Result<()> {
                         sp.finish_and_clear();
                         output::newline();
                         output::success("Build finished");
+                        result = after_finish(&client, &final_job_name, build_number, events, notify.as_deref());
                         break;
                     }
 
-                    thread::sleep(Duration::from_millis(500));
+                    if interrupt::sleep(Duration::from_millis(500)) {
+                        sp.finish_and_clear();
+                        output::newline();
+                        output::info(&format!("Stopped following - build #{} keeps running", build_number));
+                        outcome = Outcome::Interrupted;
+                        break;
+                    }
                 }
                 Err(e) => {
                     output::finish_spinner_warning(sp, "Failed to fetch logs");
                     output::warning(&format!("Failed to fetch logs: {}", e));
+                    outcome = Outcome::LogFailed;
                     break;
                 }
             }
         }
+
+        if outcome == Outcome::Completed {
+            return Ok(Some(FinishedBuild { client, job_name: final_job_name, number: build_number, result }));
+        }
+        stopped(Some(build_number), outcome);
     } else {
         output::warning("Could not get queue location to follow build");
-        output::tip(&format!("Use 'jenkins status {}' to check build status", final_job_name));
+        output::tip(&format!("Use 'jenkins status {}' to check build status", to_display_job_path(&final_job_name)));
     }
 
+    Ok(None)
+}
+
+/// Parameter values for a build: preset ones are used as-is, the rest are prompted for
+pub fn collect_parameters(
+    client: &JenkinsClient,
+    final_job_name: &str,
+    definitions: Vec<ParameterDefinition>,
+    job_name: Option<&str>,
+) -> Result<Option<Vec<ParameterValue>>> {
+    // JENKINS_PARAM_* variables come first, so parameters baked into the alias win
+    // Both are applied without prompting
+    let (mut preset, unmatched) = env_parameters(std::env::vars(), &definitions);
+    if !preset.is_empty() {
+        output::dim(&format!("Using {} parameter(s) from {}* variables", preset.len(), ENV_PARAM_PREFIX));
+    }
+    if !unmatched.is_empty() {
+        output::warning(&format!("Ignoring variables that match no parameter of the job: {}", unmatched.join(", ")));
+    }
+    if let Some(name) = job_name {
+        preset.extend(Config::load()?.alias_parameters(name));
+    }
+    validate_parameter_values(&definitions, &preset)?;
+
+    if definitions.is_empty() {
+        return Ok(None);
+    }
+    interactive::collect_parameters(client, final_job_name, definitions, &preset).map(Some)
+}
+
+/// How a triggered build got on, up to leaving the queue
+pub enum TriggerOutcome {
+    Started(i32),
+    Queued(Option<String>),
+    Cancelled,
+    Failed(anyhow::Error),
+}
+
+/// Trigger the same job on several hosts at once and report where each build got to
+/// Parameters are collected once, from the first reachable host's job, and sent to every host
+pub fn execute_fan_out(
+    job_name: Option<String>,
+    hosts: Vec<String>,
+    all_hosts: bool,
+    follow: bool,
+    queue_timeout: Option<Duration>,
+) -> Result<()> {
+    // --events, --notify and --follow-downstream all need --follow, so none of them is dropped here
+    if follow {
+        anyhow::bail!("--follow (and --events, --notify or --follow-downstream with it) works with a single host only.");
+    }
+    let Some(job_name) = job_name else {
+        anyhow::bail!("Pass the job to build on several hosts; it can't be selected interactively.");
+    };
+
+    let config = Config::load()?;
+    let hosts = if all_hosts {
+        let mut hosts: Vec<String> = config.jenkins.keys().cloned().collect();
+        hosts.sort();
+        hosts
+    } else {
+        config.expand_hosts(&hosts)?
+    };
+    if hosts.is_empty() {
+        return Err(ErrorKind::Config.error("No Jenkins configured. Use 'jenkins config add' to add one."));
+    }
+
+    // Aliases may name a host of their own, but the job path is what's shared between mirrors
+    let (final_job_name, _, _) = config.resolve_job_name(&job_name)?;
+    let clients = hosts
+        .iter()
+        .map(|host| create_client(Some(host.clone())))
+        .collect::<Result<Vec<_>>>()?;
+
+    // The first host that answers defines the parameters; unreachable ones fail below, per host
+    let sp = output::spinner("Checking job parameters...");
+    let mut definitions = Err(anyhow::anyhow!("No host to check the job parameters on"));
+    for client in &clients {
+        definitions = client.get_job_parameters(&final_job_name).map(|defs| (client, defs));
+        if definitions.is_ok() {
+            break;
+        }
+    }
+    sp.finish_and_clear();
+    let (first, parameter_definitions) = definitions?;
+    let secrets = history::secret_parameters(&parameter_definitions);
+    let parameters = collect_parameters(first, &final_job_name, parameter_definitions, Some(&job_name))?;
+
+    let sp = output::spinner(&format!(
+        "Triggering '{}' on {} host(s)...",
+        to_human_job_path(&final_job_name),
+        hosts.len()
+    ));
+    let outcomes: Vec<TriggerOutcome> = thread::scope(|scope| {
+        let handles: Vec<_> = clients
+            .iter()
+            .map(|client| {
+                let (job, parameters, secrets) = (&final_job_name, parameters.clone(), &secrets);
+                scope.spawn(move || trigger_and_wait(client, job, parameters, secrets, queue_timeout))
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle.join().unwrap_or_else(|_| TriggerOutcome::Failed(anyhow::anyhow!("The request thread panicked")))
+            })
+            .collect()
+    });
+    sp.finish_and_clear();
+
+    output::header(&format!("Builds of '{}'", to_human_job_path(&final_job_name)));
+    let mut failed = 0;
+    for ((host, client), outcome) in hosts.iter().zip(&clients).zip(outcomes) {
+        let label = format!("{}:", host);
+        match outcome {
+            TriggerOutcome::Started(number) => output::list_item(
+                &label,
+                &format!("#{} started => {}/{}", number, client.get_job_url(&final_job_name), number),
+            ),
+            TriggerOutcome::Queued(why) => output::list_item(
+                &label,
+                &format!("still queued{}", why.map(|why| format!(" ({})", why)).unwrap_or_default()),
+            ),
+            TriggerOutcome::Cancelled => output::list_item(&label, "queue item cancelled"),
+            TriggerOutcome::Failed(e) => {
+                failed += 1;
+                output::list_item(&label, &format!("failed: {:#}", e));
+            }
+        }
+    }
+
+    if failed > 0 {
+        anyhow::bail!("The build could not be triggered on {} of {} host(s)", failed, hosts.len());
+    }
     Ok(())
 }
+
+/// Trigger a build on one host and wait (up to the queue timeout) for it to leave the queue
+pub fn trigger_and_wait(
+    client: &JenkinsClient,
+    job_name: &str,
+    parameters: Option<Vec<ParameterValue>>,
+    secrets: &[String],
+    queue_timeout: Option<Duration>,
+) -> TriggerOutcome {
+    let timeout = match resolve_queue_timeout(queue_timeout, client.queue_timeout()) {
+        Ok(timeout) => timeout,
+        Err(e) => return TriggerOutcome::Failed(e),
+    };
+    let recorded = history::recorded_parameters(parameters.as_deref(), secrets);
+    let triggered = client.trigger_build(job_name, parameters);
+    history::record(client, job_name, Action::Build, recorded, &triggered);
+    let queue_url = match triggered {
+        Ok(Some(queue_url)) => queue_url,
+        Ok(None) => return TriggerOutcome::Queued(None),
+        Err(e) => return TriggerOutcome::Failed(e),
+    };
+
+    let started = Instant::now();
+    loop {
+        let why = match client.get_queue_item(&queue_url) {
+            Ok(QueueItemStatus { build_number: Some(number), .. }) => return TriggerOutcome::Started(number),
+            Ok(QueueItemStatus { cancelled: true, .. }) => return TriggerOutcome::Cancelled,
+            Ok(QueueItemStatus { why, .. }) => why,
+            // The item can vanish between polls; keep trying until the timeout
+            Err(e) => {
+                tracing::debug!(queue_url = %queue_url, error = %format!("{:#}", e), "queue poll failed, retrying");
+                None
+            }
+        };
+        if started.elapsed() >= timeout {
+            return TriggerOutcome::Queued(why);
+        }
+        thread::sleep(Duration::from_secs(1));
+    }
+}
+
+fn emit_stage_changes(client: &JenkinsClient, stages: &mut StageTracker, job_name: &str, build_number: i32) {
+    for stage in stages.poll(client, job_name, build_number) {
+        events::emit(&BuildEvent::StageChanged {
+            job: job_name.to_string(),
+            build: build_number,
+            stage: stage.name,
+            status: stage.status,
+        });
+    }
+}
+
+/// Report the result of a followed build to whatever is configured to hear about it
+/// Returns the result, when it could be fetched
+fn after_finish(
+    client: &JenkinsClient,
+    job_name: &str,
+    build_number: i32,
+    events: bool,
+    notify: Option<&str>,
+) -> Option<String> {
+    let build = client.get_build(job_name, build_number).ok();
+    let result = build.as_ref().and_then(|b| b.result.clone());
+
+    if events {
+        events::emit(&BuildEvent::Finished {
+            job: job_name.to_string(),
+            build: Some(build_number),
+            result: result.clone(),
+            outcome: Outcome::Completed,
+        });
+    }
+
+    let config = Config::load().unwrap_or_default();
+
+    if let Some(webhook) = notify.or(config.notify_webhook.as_deref()) {
+        let summary = match &build {
+            Some(build) => BuildSummary::new(
+                &to_display_job_path(job_name),
+                build_number,
+                build.result.clone(),
+                build.duration,
+                &build.url,
+            ),
+            None => BuildSummary::new(&to_display_job_path(job_name), build_number, None, 0, &client.get_job_url(job_name)),
+        };
+        if let Err(e) = notify::send(webhook, &summary) {
+            output::warning(&format!("Could not send the notification: {:#}", e));
+        }
+    }
+
+    let Some(settings) = config.commit_status else {
+        return result;
+    };
+    let Some(build) = build else {
+        output::warning("Could not fetch the build result to report a commit status");
+        return result;
+    };
+    let Some((sha, remote_url)) = build.git_revision() else {
+        output::dim("No git revision recorded for the build - skipping the commit status");
+        return result;
+    };
+
+    let status = CommitStatus {
+        sha,
+        remote_url,
+        result: build.result.as_deref(),
+        build_url: &build.url,
+        description: format!("{} {}", build.full_display_name, build.result.as_deref().unwrap_or("finished")),
+    };
+    match commit_status::report(&settings, &status) {
+        Ok(()) => output::dim(&format!("Commit status posted for {}", &sha[..sha.len().min(12)])),
+        // The build already finished - a reporting problem shouldn't fail the command
+        Err(e) => output::warning(&format!("Could not post the commit status: {:#}", e)),
+    }
+    result
+}
+
+/// The --queue-timeout flag wins over the host's queue_timeout setting
+fn resolve_queue_timeout(flag: Option<Duration>, host_setting: Option<&str>) -> Result<Duration> {
+    if let Some(timeout) = flag {
+        return Ok(timeout);
+    }
+    match host_setting {
+        Some(setting) => parse_duration(setting).map_err(|e| anyhow::anyhow!("Invalid queue_timeout in config: {}", e)),
+        None => Ok(DEFAULT_QUEUE_TIMEOUT),
+    }
+}
+
+/// Ctrl+C while the build is still queued: offer to cancel the queue item
+/// Returns whether the queued build was cancelled
+fn handle_queue_interrupt(client: &JenkinsClient, queue_url: &str, job_name: &str) -> Result<bool> {
+    // A build that has already left the queue can't be cancelled this way
+    if let Ok(Some(num)) = client.get_build_number_from_queue(queue_url) {
+        output::info(&format!("Build #{} has already started and keeps running", num));
+        output::tip(&format!("Use 'jenkins logs {} -b {} -f' to keep following it", to_display_job_path(job_name), num));
+        return Ok(false);
+    }
+
+    let Some(queue_id) = queue_item_id(queue_url) else {
+        output::warning("Could not determine the queue item to cancel");
+        return Ok(false);
+    };
+
+    prompt::ensure_interactive(
+        "cancelling the queued build",
+        &format!("The build stays in the queue (item {}).", queue_id),
+    )?;
+
+    let cancel = Confirm::new("The build is still queued. Cancel it?")
+        .with_default(true)
+        .prompt()
+        .unwrap_or(false);
+
+    if !cancel {
+        output::info("The build stays in the queue");
+        return Ok(false);
+    }
+
+    let sp = output::spinner("Cancelling queued build...");
+    let cancelled = client.cancel_queue_item(queue_id);
+    history::record(client, job_name, Action::CancelQueued, BTreeMap::new(), &cancelled);
+    cancelled?;
+    output::finish_spinner_success(sp, "Queued build cancelled");
+
+    Ok(true)
+}
+
+/// The build was triggered either way - only warn if it can't be recorded
+fn remember_last_build(last_build: &LastBuild) {
+    if let Err(e) = State::record_last_build(last_build.clone()) {
+        output::warning(&format!("Could not record the build for 'jenkins last': {}", e));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_queue_timeout() {
+        assert_eq!(resolve_queue_timeout(None, None).unwrap(), DEFAULT_QUEUE_TIMEOUT);
+        assert_eq!(resolve_queue_timeout(None, Some("15m")).unwrap(), Duration::from_secs(900));
+        assert_eq!(
+            resolve_queue_timeout(Some(Duration::from_secs(60)), Some("15m")).unwrap(),
+            Duration::from_secs(60)
+        );
+        assert!(resolve_queue_timeout(None, Some("soon")).is_err());
+    }
+}
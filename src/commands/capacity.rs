@@ -0,0 +1,177 @@
+use anyhow::{Context, Result};
+use crate::cli::OutputFormat;
+use crate::client::{NodeInfo, QueuedItem};
+use crate::helpers::formatting::now_millis;
+use crate::helpers::init::create_client;
+use crate::output::{self, Table};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Queue and executor usage of an instance at one moment, as `jenkins capacity --output json` prints it
+#[derive(Debug, Serialize, PartialEq)]
+struct Capacity {
+    jenkins: String,
+    /// When the snapshot was taken, in epoch milliseconds
+    timestamp: i64,
+    queue: QueueCounts,
+    executors: ExecutorCounts,
+    /// Sorted by label name
+    labels: Vec<LabelCapacity>,
+}
+
+#[derive(Debug, Serialize, PartialEq, Default)]
+struct QueueCounts {
+    total: usize,
+    /// Waiting only for a free executor
+    buildable: usize,
+    stuck: usize,
+}
+
+/// Executors of online nodes; offline nodes only count towards `offline_nodes`
+#[derive(Debug, Serialize, PartialEq, Default)]
+struct ExecutorCounts {
+    busy: usize,
+    idle: usize,
+    online_nodes: usize,
+    offline_nodes: usize,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct LabelCapacity {
+    name: String,
+    #[serde(flatten)]
+    executors: ExecutorCounts,
+}
+
+impl ExecutorCounts {
+    fn add(&mut self, node: &NodeInfo) {
+        if node.offline {
+            self.offline_nodes += 1;
+            return;
+        }
+        self.online_nodes += 1;
+        let idle = node.executors.iter().filter(|executor| executor.idle).count();
+        self.idle += idle;
+        self.busy += node.executors.len() - idle;
+    }
+}
+
+pub fn execute(jenkins: Option<String>, format: OutputFormat) -> Result<()> {
+    let client = create_client(jenkins)?;
+
+    let sp = output::spinner("Fetching queue and executors...");
+    let nodes = client.get_nodes()?;
+    let queue = client.get_queue_items()?;
+    sp.finish_and_clear();
+
+    let capacity = capacity(client.host_url(), &nodes, &queue, now_millis());
+    if format == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&capacity).context("Failed to serialize the snapshot")?);
+        return Ok(());
+    }
+
+    output::header(&format!("Capacity of {}", capacity.jenkins));
+    let queue = &capacity.queue;
+    output::list_item(
+        "Queue:",
+        &format!("{} waiting ({} ready to run, {} stuck)", queue.total, queue.buildable, queue.stuck),
+    );
+    let executors = &capacity.executors;
+    output::list_item(
+        "Executors:",
+        &format!(
+            "{} busy, {} idle on {} online node(s), {} offline",
+            executors.busy, executors.idle, executors.online_nodes, executors.offline_nodes
+        ),
+    );
+
+    if !capacity.labels.is_empty() {
+        output::newline();
+        labels_table(&capacity.labels).print();
+    }
+    Ok(())
+}
+
+fn capacity(jenkins: &str, nodes: &[NodeInfo], queue: &[QueuedItem], now: i64) -> Capacity {
+    let mut executors = ExecutorCounts::default();
+    let mut labels: BTreeMap<&str, ExecutorCounts> = BTreeMap::new();
+    for node in nodes {
+        executors.add(node);
+        for label in node.labels() {
+            labels.entry(label).or_default().add(node);
+        }
+    }
+
+    Capacity {
+        jenkins: jenkins.to_string(),
+        timestamp: now,
+        queue: QueueCounts {
+            total: queue.len(),
+            buildable: queue.iter().filter(|item| item.buildable).count(),
+            stuck: queue.iter().filter(|item| item.stuck).count(),
+        },
+        executors,
+        labels: labels
+            .into_iter()
+            .map(|(name, executors)| LabelCapacity { name: name.to_string(), executors })
+            .collect(),
+    }
+}
+
+/// One row per label: how many of its nodes are up and how many of their executors are free
+fn labels_table(labels: &[LabelCapacity]) -> Table {
+    let mut table = Table::new(&["LABEL", "NODES", "BUSY", "IDLE"]).align_right(1).align_right(2).align_right(3);
+    for label in labels {
+        let counts = &label.executors;
+        let nodes = match counts.offline_nodes {
+            0 => counts.online_nodes.to_string(),
+            offline => format!("{} (+{} offline)", counts.online_nodes, offline),
+        };
+        table.add_row(vec![label.name.clone(), nodes, counts.busy.to_string(), counts.idle.to_string()]);
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capacity() {
+        let nodes: Vec<NodeInfo> = serde_json::from_value(serde_json::json!([
+            {
+                "_class": "hudson.model.Hudson$MasterComputer",
+                "displayName": "Built-In Node",
+                "assignedLabels": [{ "name": "built-in" }],
+                "executors": [{ "idle": true }]
+            },
+            {
+                "displayName": "linux-1",
+                "assignedLabels": [{ "name": "linux" }, { "name": "docker" }, { "name": "linux-1" }],
+                "executors": [{ "idle": false }, { "idle": false }, { "idle": true }]
+            },
+            {
+                "displayName": "linux-2",
+                "offline": true,
+                "assignedLabels": [{ "name": "linux" }, { "name": "linux-2" }],
+                "executors": [{ "idle": true }]
+            }
+        ]))
+        .unwrap();
+        let queue: Vec<QueuedItem> = serde_json::from_value(serde_json::json!([
+            { "id": 1, "buildable": true, "task": {} },
+            { "id": 2, "buildable": true, "stuck": true, "task": {} },
+            { "id": 3, "task": {} }
+        ]))
+        .unwrap();
+
+        let capacity = capacity("https://jenkins.example.com", &nodes, &queue, 1_000);
+        assert_eq!(capacity.queue, QueueCounts { total: 3, buildable: 2, stuck: 1 });
+        assert_eq!(capacity.executors, ExecutorCounts { busy: 2, idle: 2, online_nodes: 2, offline_nodes: 1 });
+
+        let json = serde_json::to_value(&capacity).unwrap();
+        assert_eq!(json["labels"].as_array().unwrap().len(), 2);
+        assert_eq!(json["labels"][0], serde_json::json!({ "name": "docker", "busy": 2, "idle": 1, "online_nodes": 1, "offline_nodes": 0 }));
+        assert_eq!(json["labels"][1], serde_json::json!({ "name": "linux", "busy": 2, "idle": 1, "online_nodes": 1, "offline_nodes": 1 }));
+    }
+}
@@ -0,0 +1,28 @@
+use anyhow::{Context, Result};
+use crate::helpers::init::create_client;
+use crate::output;
+use std::fs;
+use std::path::PathBuf;
+
+/// File written when no --output is given, the name the plugin itself reads by default
+pub const DEFAULT_EXPORT_FILE: &str = "jenkins.yaml";
+
+pub fn execute_export(file: PathBuf) -> Result<()> {
+    let client = create_client(None)?;
+
+    let sp = output::spinner("Exporting configuration as code...");
+    let yaml = client.export_casc()?;
+    sp.finish_and_clear();
+
+    // `-o -` prints the YAML instead, for piping into other tools
+    if file.as_os_str() == "-" {
+        print!("{}", yaml);
+        return Ok(());
+    }
+
+    fs::write(&file, &yaml).with_context(|| format!("Failed to write {}", file.display()))?;
+    output::success(&format!("Saved the JCasC configuration to {} ({} lines)", file.display(), yaml.lines().count()));
+    output::tip("Secrets are exported masked; review the file before applying it to another controller");
+
+    Ok(())
+}
@@ -1,12 +1,35 @@
 use anyhow::Result;
-use clap::CommandFactory;
+use clap::{Command, CommandFactory};
 use clap_complete::{generate, Shell as CompletionShell};
-use crate::cli::{Cli, Shell};
-use std::io;
+use crate::cli::{Cli, CompletionValues, Shell};
+use crate::config::Config;
+use std::io::{self, Write};
+
+/// Hidden subcommand the scripts call to list hosts and aliases from the config
+const HELPER: &str = "__complete";
+
+/// Options that take a configured host name
+const HOST_OPTIONS: &[&str] = &["jenkins", "other-jenkins"];
+/// Positional arguments that take a configured host name
+const HOST_ARGS: &[&str] = &["jenkins"];
+/// Positional arguments that take a job name, so aliases are offered
+const JOB_ARGS: &[&str] = &["job_name", "jobs"];
+
+/// Where the generated scripts complete config values instead of (or on top of) clap's static completion
+#[derive(Debug, Default, PartialEq)]
+struct DynamicArgs {
+    /// Long options (without `--`) that take a host
+    host_options: Vec<String>,
+    /// Top-level subcommands whose first argument is a host
+    host_commands: Vec<String>,
+    /// Top-level subcommands whose first argument is a job
+    job_commands: Vec<String>,
+}
 
 pub fn execute(shell: Shell) -> Result<()> {
     let mut cmd = Cli::command();
     let bin_name = cmd.get_name().to_string();
+    let dynamic = dynamic_args(&cmd);
 
     let shell_type = match shell {
         Shell::Bash => CompletionShell::Bash,
@@ -15,7 +38,201 @@ pub fn execute(shell: Shell) -> Result<()> {
         Shell::PowerShell => CompletionShell::PowerShell,
     };
 
-    generate(shell_type, &mut cmd, &bin_name, &mut io::stdout());
+    let mut script = Vec::new();
+    generate(shell_type, &mut cmd, &bin_name, &mut script);
+    let mut script = String::from_utf8(script)?;
+
+    // PowerShell only gets the static completion
+    match shell {
+        Shell::Bash => script.push_str(&bash_extension(&bin_name, &dynamic)),
+        Shell::Zsh => script = zsh_with_extension(&script, &bin_name, &dynamic),
+        Shell::Fish => script.push_str(&fish_extension(&bin_name, &dynamic)),
+        Shell::PowerShell => {}
+    }
 
+    io::stdout().write_all(script.as_bytes())?;
     Ok(())
 }
+
+/// Print host or alias names, one per line, for the completion scripts
+/// Completion must never print errors into the prompt, so a broken config just yields nothing
+pub fn execute_values(values: CompletionValues) -> Result<()> {
+    let Ok(config) = Config::load() else {
+        return Ok(());
+    };
+
+    let mut names: Vec<&String> = match values {
+        CompletionValues::Hosts => config.jenkins.keys().collect(),
+        CompletionValues::Aliases => config.job_aliases.keys().collect(),
+    };
+    names.sort();
+    for name in names {
+        println!("{}", name);
+    }
+    Ok(())
+}
+
+fn dynamic_args(cmd: &Command) -> DynamicArgs {
+    let mut dynamic = DynamicArgs::default();
+    collect_host_options(cmd, &mut dynamic.host_options);
+    dynamic.host_options.sort();
+    dynamic.host_options.dedup();
+
+    for sub in cmd.get_subcommands().filter(|sub| !sub.is_hide_set()) {
+        let Some(first) = sub.get_positionals().next() else {
+            continue;
+        };
+        let id = first.get_id().as_str();
+        if HOST_ARGS.contains(&id) {
+            dynamic.host_commands.push(sub.get_name().to_string());
+        } else if JOB_ARGS.contains(&id) {
+            dynamic.job_commands.push(sub.get_name().to_string());
+        }
+    }
+    dynamic
+}
+
+fn collect_host_options(cmd: &Command, options: &mut Vec<String>) {
+    for arg in cmd.get_arguments() {
+        if let Some(long) = arg.get_long()
+            && HOST_OPTIONS.contains(&long)
+        {
+            options.push(long.to_string());
+        }
+    }
+    for sub in cmd.get_subcommands() {
+        collect_host_options(sub, options);
+    }
+}
+
+/// Wrap clap's bash function so host options and first arguments complete from the config
+fn bash_extension(bin: &str, dynamic: &DynamicArgs) -> String {
+    let options = dynamic.host_options.iter().map(|o| format!("--{}", o)).collect::<Vec<_>>().join("|");
+    format!(
+        r#"
+_{bin}_dynamic() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}" prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+    case "${{prev}}" in
+        {options})
+            COMPREPLY=( $(compgen -W "$({bin} {helper} hosts 2>/dev/null)" -- "${{cur}}") )
+            return 0
+            ;;
+    esac
+
+    _{bin} "$@"
+    if [[ ${{COMP_CWORD}} -eq 2 && "${{cur}}" != -* ]]; then
+        case "${{COMP_WORDS[1]}}" in
+            {hosts})
+                COMPREPLY=( $(compgen -W "$({bin} {helper} hosts 2>/dev/null)" -- "${{cur}}") )
+                ;;
+            {jobs})
+                COMPREPLY=( $(compgen -W "$({bin} {helper} aliases 2>/dev/null)" -- "${{cur}}") )
+                ;;
+        esac
+    fi
+}}
+
+complete -F _{bin}_dynamic -o bashdefault -o default {bin}
+"#,
+        bin = bin,
+        helper = HELPER,
+        options = options,
+        hosts = dynamic.host_commands.join("|"),
+        jobs = dynamic.job_commands.join("|"),
+    )
+}
+
+/// Put a wrapper in front of clap's zsh function, registered in its place
+fn zsh_with_extension(script: &str, bin: &str, dynamic: &DynamicArgs) -> String {
+    let options = dynamic.host_options.iter().map(|o| format!("--{}", o)).collect::<Vec<_>>().join("|");
+    let wrapper = format!(
+        r#"
+_{bin}_dynamic() {{
+    case "${{words[CURRENT-1]}}" in
+        {options})
+            compadd -- ${{(f)"$({bin} {helper} hosts 2>/dev/null)"}}
+            return
+            ;;
+    esac
+
+    if (( CURRENT == 3 )) && [[ "${{words[CURRENT]}}" != -* ]]; then
+        case "${{words[2]}}" in
+            {hosts})
+                compadd -- ${{(f)"$({bin} {helper} hosts 2>/dev/null)"}}
+                ;;
+            {jobs})
+                compadd -- ${{(f)"$({bin} {helper} aliases 2>/dev/null)"}}
+                ;;
+        esac
+    fi
+    _{bin} "$@"
+}}
+"#,
+        bin = bin,
+        helper = HELPER,
+        options = options,
+        hosts = dynamic.host_commands.join("|"),
+        jobs = dynamic.job_commands.join("|"),
+    );
+
+    let registration = format!("compdef _{bin} {bin}", bin = bin);
+    let script = script.replace(&registration, &format!("compdef _{bin}_dynamic {bin}", bin = bin));
+    let script = script.replace(
+        &format!("if [ \"$funcstack[1]\" = \"_{bin}\" ]; then\n    _{bin} \"$@\"", bin = bin),
+        // When autoloaded from fpath, later completions would call clap's function directly
+        &format!(
+            "if [ \"$funcstack[1]\" = \"_{bin}\" ]; then\n    compdef _{bin}_dynamic {bin}\n    _{bin}_dynamic \"$@\"",
+            bin = bin
+        ),
+    );
+    // The wrapper must be defined before the registration at the end of the script
+    match script.rfind("\nif [ \"$funcstack[1]\"") {
+        Some(index) => format!("{}{}{}", &script[..index], wrapper, &script[index..]),
+        None => format!("{}{}", script, wrapper),
+    }
+}
+
+/// Fish merges extra `complete` lines with clap's, so plain additions are enough
+fn fish_extension(bin: &str, dynamic: &DynamicArgs) -> String {
+    let mut lines = String::from("\n");
+    for option in &dynamic.host_options {
+        lines.push_str(&format!("complete -c {bin} -l {option} -x -a \"({bin} {HELPER} hosts 2>/dev/null)\"\n"));
+    }
+    let first_arg = |commands: &[String], values: &str| {
+        format!(
+            "complete -c {bin} -n \"__fish_seen_subcommand_from {} ; and test (count (commandline -opc)) -eq 2\" -f -a \"({bin} {HELPER} {values} 2>/dev/null)\"\n",
+            commands.join(" ")
+        )
+    };
+    lines.push_str(&first_arg(&dynamic.host_commands, "hosts"));
+    lines.push_str(&first_arg(&dynamic.job_commands, "aliases"));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dynamic_args() {
+        let dynamic = dynamic_args(&Cli::command());
+
+        assert_eq!(dynamic.host_options, vec!["jenkins", "other-jenkins"]);
+        assert_eq!(dynamic.host_commands, vec!["doctor", "ping", "load", "capacity", "recent"]);
+        assert!(dynamic.job_commands.contains(&"build".to_string()));
+        assert!(dynamic.job_commands.contains(&"monitor".to_string()));
+        assert!(!dynamic.job_commands.iter().any(|name| name == HELPER));
+    }
+
+    #[test]
+    fn test_zsh_wrapper_replaces_registration() {
+        let dynamic = dynamic_args(&Cli::command());
+        let mut script = Vec::new();
+        generate(CompletionShell::Zsh, &mut Cli::command(), "jenkins", &mut script);
+        let script = zsh_with_extension(&String::from_utf8(script).unwrap(), "jenkins", &dynamic);
+
+        assert!(script.contains("compdef _jenkins_dynamic jenkins"));
+        assert!(!script.contains("compdef _jenkins jenkins"));
+        assert!(script.find("_jenkins_dynamic() {").unwrap() < script.rfind("compdef").unwrap());
+    }
+}
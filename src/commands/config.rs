@@ -1,11 +1,61 @@
-use anyhow::Result;
-use crate::config::{Config, JenkinsHost};
-use crate::client::JenkinsClient;
+use anyhow::{Context, Result};
+use crate::config::{validate_config, Config, IssueSeverity, JenkinsHost};
+use crate::client::{GeneratedToken, JenkinsClient};
+use crate::helpers::errors::{kind_of, ErrorKind};
+use crate::helpers::formatting::{format_timestamp, now_millis};
+use crate::helpers::init::prompt_jenkins_selection;
+use crate::helpers::prompt;
 use crate::output;
-use inquire::{Text, Confirm, MultiSelect};
+use crate::state::State;
+use inquire::{Text, Confirm, MultiSelect, Password, PasswordDisplayMode};
 use url::Url;
 
-pub fn execute_add() -> Result<()> {
+/// Name given to API tokens generated by the CLI
+const GENERATED_TOKEN_NAME: &str = "jenkins-cli";
+
+pub fn execute_add(generate_token: bool) -> Result<()> {
+    prompt_and_add_host(generate_token)?;
+    Ok(())
+}
+
+/// First-run setup: add a host, then optionally create a first job alias
+/// Returns the name of the newly configured host
+pub fn run_setup_wizard() -> Result<String> {
+    output::header("Welcome to jenkins-cli");
+    output::dim("Let's connect to your Jenkins server. You'll need your username and an API token");
+    output::dim("(create one under your Jenkins user > Configure > API Token).");
+    output::newline();
+
+    let name = prompt_and_add_host(false)?
+        .ok_or_else(|| anyhow::anyhow!("Setup cancelled"))?;
+
+    let create_alias = Confirm::new("Create a job alias now?")
+        .with_default(false)
+        .with_help_message("Aliases let you type 'jenkins build api' instead of the full job path")
+        .prompt()?;
+
+    if create_alias {
+        let alias = Text::new("Alias name:").prompt()?;
+        let job_name = Text::new("Job name (full path, e.g. folder/job):").prompt()?;
+
+        if !alias.trim().is_empty() && !job_name.trim().is_empty() {
+            let mut config = Config::load()?;
+            config.add_job_alias(alias.trim().to_string(), job_name.trim().to_string(), Some(name.clone()));
+            config.save()?;
+            output::success(&format!("Alias '{}' created", alias.trim()));
+        }
+    }
+
+    output::newline();
+    Ok(name)
+}
+
+/// Prompt for a host, verify it and save it to the config
+/// With `generate_token`, the user's password is used once to create an API token and is never stored
+/// Returns None if the user declined to overwrite an existing host
+fn prompt_and_add_host(generate_token: bool) -> Result<Option<String>> {
+    prompt::ensure_interactive("host details", "Add the host to the config file directly.")?;
+
     let mut config = Config::load()?;
 
     // Prompt for name if not provided
@@ -28,7 +78,7 @@ pub fn execute_add() -> Result<()> {
             .prompt()?;
 
         if !overwrite {
-            return Ok(());
+            return Ok(None);
         }
     }
 
@@ -81,19 +131,14 @@ pub fn execute_add() -> Result<()> {
         })
         .prompt()?;
 
-    let token = Text::new("API Token:")
-        .with_validator(|input: &str| {
-            if input.trim().is_empty() {
-                Ok(inquire::validator::Validation::Invalid(
-                    "Token cannot be empty".into()
-                ))
-            } else {
-                Ok(inquire::validator::Validation::Valid)
-            }
-        })
-        .prompt()?;
+    let (token, token_uuid) = if generate_token {
+        let token = generate_api_token(&host, &user)?;
+        (token.value, Some(token.uuid))
+    } else {
+        (prompt_api_token()?, None)
+    };
 
-    let jenkins_host = JenkinsHost { host, user, token };
+    let jenkins_host = JenkinsHost { host, user, token, ..Default::default() };
 
     // Verify connection before saving
     let sp = output::spinner("Verifying connection to Jenkins...");
@@ -105,7 +150,10 @@ pub fn execute_add() -> Result<()> {
         }
         Err(e) => {
             output::finish_spinner_error(sp, "Connection failed");
-            anyhow::bail!("Connection failed: {}\nPlease check your configuration and try again.", e);
+            anyhow::bail!(
+                "Connection failed: {}\nPlease check your configuration and try again.",
+                scrub_secret(&format!("{:#}", e), &jenkins_host.token)
+            );
         }
     }
 
@@ -114,7 +162,74 @@ pub fn execute_add() -> Result<()> {
     config.save()?;
     output::success(&format!("Jenkins host '{}' added successfully!", name));
 
-    Ok(())
+    // 'config rotate-token' can only revoke tokens it knows the UUID of
+    let mut state = State::load()?;
+    state.hosts.entry(name.clone()).or_default().token_uuid = token_uuid;
+    if let Err(e) = state.save() {
+        output::warning(&format!("Could not record the token for 'jenkins config rotate-token': {}", e));
+    }
+
+    Ok(Some(name))
+}
+
+/// Masked, so the token never lands in the terminal's scrollback
+fn prompt_api_token() -> Result<String> {
+    let token = Password::new("API Token:")
+        .with_display_mode(PasswordDisplayMode::Masked)
+        .without_confirmation()
+        .with_validator(|input: &str| {
+            if input.trim().is_empty() {
+                Ok(inquire::validator::Validation::Invalid(
+                    "Token cannot be empty".into()
+                ))
+            } else {
+                Ok(inquire::validator::Validation::Valid)
+            }
+        })
+        .prompt()?;
+
+    Ok(token)
+}
+
+/// Replace a secret in a message that is about to be printed
+/// Servers and proxies sometimes echo credentials back in their error pages
+fn scrub_secret(message: &str, secret: &str) -> String {
+    if secret.is_empty() {
+        return message.to_string();
+    }
+    message.replace(secret, "****")
+}
+
+/// Log in with the user's password once to create an API token
+fn generate_api_token(host: &str, user: &str) -> Result<GeneratedToken> {
+    let password = Password::new("Password:")
+        .without_confirmation()
+        .with_help_message("Used once to create an API token - it is not saved")
+        .prompt()?;
+
+    let sp = output::spinner("Generating API token...");
+    let client = JenkinsClient::new(JenkinsHost {
+        host: host.to_string(),
+        user: user.to_string(),
+        token: password.clone(),
+        ..Default::default()
+    })?;
+
+    match client.generate_api_token(GENERATED_TOKEN_NAME) {
+        Ok(token) => {
+            output::finish_spinner_success(sp, &format!("Generated API token '{}'", token.name));
+            Ok(token)
+        }
+        Err(e) => {
+            output::finish_spinner_error(sp, "Failed to generate API token");
+            let message = scrub_secret(&format!("{:#}", e), &password);
+            // Keep the kind, so a wrong password still exits as an authentication failure
+            match kind_of(&e) {
+                Some(kind) => Err(kind.error(message)),
+                None => Err(anyhow::anyhow!(message)),
+            }
+        }
+    }
 }
 
 pub fn execute_list() -> Result<()> {
@@ -132,6 +247,21 @@ pub fn execute_list() -> Result<()> {
         output::highlight(name);
         output::list_item("Host:", &host.host);
         output::list_item("User:", &host.user);
+        if !host.identities.is_empty() {
+            let identities: Vec<String> = host
+                .identities
+                .iter()
+                .map(|(name, identity)| format!("{} ({})", name, identity.user))
+                .collect();
+            output::list_item("Identities:", &identities.join(", "));
+        }
+        if host.read_only {
+            output::list_item("Read-only:", "yes");
+        }
+        if !host.tags.is_empty() {
+            let tags: Vec<String> = host.tags.iter().map(|(key, value)| format!("{}={}", key, value)).collect();
+            output::list_item("Tags:", &tags.join(", "));
+        }
         output::newline();
     }
 
@@ -145,6 +275,8 @@ pub fn execute_remove() -> Result<()> {
         anyhow::bail!("No Jenkins hosts configured.\nUse 'jenkins config add' to add one.");
     }
 
+    prompt::ensure_interactive("hosts to remove", "Remove the host from the config file directly.")?;
+
     let hosts: Vec<String> = config.jenkins.keys().cloned().collect();
 
     // Use MultiSelect to allow selecting multiple hosts
@@ -190,10 +322,185 @@ pub fn execute_remove() -> Result<()> {
     Ok(())
 }
 
+/// Tokens that haven't been confirmed working for this long get a staleness warning
+const STALE_AFTER_MS: i64 = 90 * 24 * 60 * 60 * 1000;
+
+pub fn execute_verify(name: Option<String>) -> Result<()> {
+    let config = Config::load()?;
+
+    if config.jenkins.is_empty() {
+        anyhow::bail!("No Jenkins hosts configured.\nUse 'jenkins config add' to add one.");
+    }
+
+    let mut names: Vec<String> = match name {
+        Some(name) => {
+            config.get_jenkins(&name)?;
+            vec![name]
+        }
+        None => config.jenkins.keys().cloned().collect(),
+    };
+    names.sort();
+
+    let mut state = State::load()?;
+    let mut failures = 0;
+
+    for name in &names {
+        let host = config.get_jenkins(name)?.clone();
+        let sp = output::spinner(&format!("Verifying '{}'...", name));
+        let now = now_millis();
+        let previous_success = state.hosts.get(name).and_then(|s| s.last_success);
+
+        match JenkinsClient::new(host).and_then(|client| client.verify_connection()) {
+            Ok(_) => {
+                state.record_auth_success(name, now);
+                output::finish_spinner_success(sp, &format!("'{}' credentials are valid", name));
+            }
+            Err(e) if kind_of(&e) == Some(ErrorKind::Auth) => {
+                failures += 1;
+                state.record_auth_failure(name, now, &e.to_string());
+                output::finish_spinner_error(sp, &format!("'{}': {}", name, e));
+                match previous_success {
+                    Some(at) => output::dim(&format!("  Last worked {}", format_timestamp(at, now))),
+                    None => output::dim("  Never verified successfully"),
+                }
+                if previous_success.is_some_and(|at| now - at > STALE_AFTER_MS) {
+                    output::warning(&format!("  The token for '{}' may have expired or been revoked", name));
+                }
+            }
+            // The server never judged the credentials, so their recorded state stays as it was
+            Err(e) => {
+                failures += 1;
+                output::finish_spinner_error(sp, &format!("'{}': {:#}", name, e));
+                if kind_of(&e) == Some(ErrorKind::Network) {
+                    output::dim("  Could not reach the server; the credentials were not checked");
+                } else {
+                    output::dim("  The server did not answer like Jenkins; the credentials were not checked");
+                }
+            }
+        }
+    }
+
+    state.save()?;
+
+    if failures > 0 {
+        anyhow::bail!("{} of {} host(s) failed verification", failures, names.len());
+    }
+
+    Ok(())
+}
+
+/// Replace a host's API token with a freshly generated one, then revoke the old token
+/// The config only changes once the new token has been verified
+pub fn execute_rotate_token(name: Option<String>) -> Result<()> {
+    let mut config = Config::load()?;
+    let name = match name {
+        Some(name) => name,
+        None => prompt_jenkins_selection()?.ok_or_else(|| anyhow::anyhow!("No Jenkins host specified"))?,
+    };
+    let old_host = config.get_jenkins(&name)?.clone();
+    let tokens_page = format!("{}/me/configure", old_host.host.trim_end_matches('/'));
+
+    let sp = output::spinner(&format!("Generating a new API token for '{}'...", name));
+    let token = match JenkinsClient::new(old_host.clone())?.generate_api_token(GENERATED_TOKEN_NAME) {
+        Ok(token) => {
+            output::finish_spinner_success(sp, &format!("Generated API token '{}'", token.name));
+            token
+        }
+        Err(e) => {
+            output::finish_spinner_error(sp, "Failed to generate API token");
+            return Err(e);
+        }
+    };
+
+    let new_host = JenkinsHost { token: token.value.clone(), ..old_host.clone() };
+    let client = JenkinsClient::new(new_host.clone())?;
+    let sp = output::spinner("Verifying the new token...");
+    if let Err(e) = client.verify_connection() {
+        output::finish_spinner_error(sp, "The new token doesn't work");
+        anyhow::bail!(
+            "{}\nThe old token is still configured. Revoke the unused new token under {}",
+            scrub_secret(&format!("{:#}", e), &token.value),
+            tokens_page
+        );
+    }
+    output::finish_spinner_success(sp, "The new token works");
+
+    config.add_jenkins(name.clone(), new_host);
+    config.save()?;
+    output::success(&format!("Saved the new token for '{}'", name));
+
+    let mut state = State::load()?;
+    let previous = state.record_token_uuid(&name, &token.uuid);
+    state.record_auth_success(&name, now_millis());
+    state.save()?;
+
+    // Only tokens generated by the CLI have a known UUID
+    let Some(previous) = previous else {
+        output::warning("The previous token wasn't generated by jenkins-cli, so it can't be revoked automatically");
+        output::tip(&format!("Revoke it under {}", tokens_page));
+        return Ok(());
+    };
+    match client.revoke_api_token(&previous) {
+        Ok(()) => output::success("Revoked the previous token"),
+        Err(e) => {
+            output::warning(&format!("Could not revoke the previous token: {:#}", e));
+            output::tip(&format!("Revoke it under {}", tokens_page));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn execute_validate() -> Result<()> {
+    let config_path = Config::config_path()?;
+
+    if !config_path.exists() {
+        output::info(&format!("No config file found at {}", config_path.display()));
+        output::dim("Use 'jenkins config add' to create one.");
+        return Ok(());
+    }
+
+    let content = std::fs::read_to_string(&config_path)
+        .context("Failed to read config file")?;
+    let issues = validate_config(&content);
+
+    if issues.is_empty() {
+        output::success(&format!("{} is valid", config_path.display()));
+        return Ok(());
+    }
+
+    output::header(&format!("Problems in {}", config_path.display()));
+    for issue in &issues {
+        let location = issue.line.map(|l| format!("line {}: ", l)).unwrap_or_default();
+        let message = format!("{}{}", location, issue.message);
+        match issue.severity {
+            IssueSeverity::Error => output::error(&message),
+            IssueSeverity::Warning => output::warning(&message),
+        }
+    }
+
+    let errors = issues.iter().filter(|i| i.severity == IssueSeverity::Error).count();
+    if errors > 0 {
+        anyhow::bail!("Config file has {} error(s)", errors);
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
+    use super::scrub_secret;
     use url::Url;
 
+    #[test]
+    fn test_scrub_secret() {
+        assert_eq!(
+            scrub_secret("401 for admin:11a2b3c4 at https://jenkins.example.com", "11a2b3c4"),
+            "401 for admin:**** at https://jenkins.example.com"
+        );
+        assert_eq!(scrub_secret("Connection refused", ""), "Connection refused");
+    }
+
     #[test]
     fn test_url_validation_valid_http() {
         let result = Url::parse("http://jenkins.example.com");
@@ -0,0 +1,77 @@
+use anyhow::Result;
+use console::style;
+use crate::helpers::init::{create_client_for_job, resolve_build_number};
+use crate::helpers::url::to_human_job_path;
+use crate::interactive;
+use crate::output;
+
+pub fn execute(job_name: Option<String>, build_number: Option<i32>) -> Result<()> {
+    let client = create_client_for_job(job_name.as_deref(), None)?;
+
+    // Resolve the final job name (handle sub-jobs if present)
+    let final_job_name = interactive::resolve_job_name(&client, job_name.as_deref())?;
+    let build_num = resolve_build_number(&client, &final_job_name, build_number)?;
+
+    let sp = output::spinner(&format!("Fetching coverage for {}#{}...", to_human_job_path(&final_job_name), build_num));
+    let coverage = client.get_coverage(&final_job_name, build_num)?;
+    // Previous build is only used for deltas, so a missing report isn't an error
+    let previous = if build_num > 1 {
+        client.get_coverage(&final_job_name, build_num - 1).ok().flatten()
+    } else {
+        None
+    };
+    sp.finish_and_clear();
+
+    let coverage = coverage.ok_or_else(|| {
+        anyhow::anyhow!(
+            "No coverage report found for {}#{}. Is the JaCoCo or Cobertura plugin publishing results?",
+            final_job_name,
+            build_num
+        )
+    })?;
+
+    output::header(&format!("Coverage: {}#{} ({})", to_human_job_path(&final_job_name), build_num, coverage.source));
+    output::list_item(
+        "Lines:",
+        &format_coverage(coverage.line, previous.as_ref().and_then(|p| p.line)),
+    );
+    output::list_item(
+        "Branches:",
+        &format_coverage(coverage.branch, previous.as_ref().and_then(|p| p.branch)),
+    );
+
+    if previous.is_some() {
+        output::dim(&format!("Deltas compared to #{}", build_num - 1));
+    }
+
+    Ok(())
+}
+
+/// Format a coverage percentage with its change relative to the previous build
+fn format_coverage(current: Option<f64>, previous: Option<f64>) -> String {
+    let Some(current) = current else {
+        return style("n/a").dim().to_string();
+    };
+
+    match previous.map(|p| current - p) {
+        Some(delta) if delta >= 0.05 => format!("{:.1}% {}", current, style(format!("(+{:.1}%)", delta)).green()),
+        Some(delta) if delta <= -0.05 => format!("{:.1}% {}", current, style(format!("({:.1}%)", delta)).red()),
+        Some(_) => format!("{:.1}% {}", current, style("(±0.0%)").dim()),
+        None => format!("{:.1}%", current),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_coverage() {
+        let plain = |current, previous| console::strip_ansi_codes(&format_coverage(current, previous)).into_owned();
+        assert_eq!(plain(Some(82.5), None), "82.5%");
+        assert_eq!(plain(Some(82.5), Some(80.0)), "82.5% (+2.5%)");
+        assert_eq!(plain(Some(80.0), Some(82.5)), "80.0% (-2.5%)");
+        assert_eq!(plain(Some(80.0), Some(80.01)), "80.0% (±0.0%)");
+        assert_eq!(plain(None, Some(80.0)), "n/a");
+    }
+}
@@ -0,0 +1,247 @@
+use anyhow::Result;
+use console::{style, Term};
+use crate::client::{JenkinsClient, JobInfo};
+use crate::config::Config;
+use crate::helpers::formatting::{format_duration, format_job_color_styled, format_timestamp, now_millis};
+use crate::helpers::init::{create_client, prompt_jenkins_selection};
+use crate::helpers::interrupt;
+use crate::helpers::url::to_display_job_path;
+use crate::output::{self, Table};
+use std::collections::HashMap;
+use std::thread;
+use std::time::Duration;
+
+/// A job on a status table, with the host its status is read from
+pub struct JobTarget {
+    pub label: String,
+    pub job_name: String,
+    pub jenkins: String,
+}
+
+/// Show every alias and pinned job in one table, optionally refreshing it
+pub fn execute(watch: bool, interval: u64) -> Result<()> {
+    let config = Config::load()?;
+
+    let mut aliases: Vec<&String> = config.job_aliases.keys().collect();
+    aliases.sort();
+    let mut names: Vec<String> = aliases.into_iter().cloned().collect();
+    names.extend(config.pinned_jobs.iter().cloned());
+    if names.is_empty() {
+        output::info("Nothing on the dashboard yet.");
+        output::tip("Add aliases with 'jenkins alias add' or pin jobs with 'jenkins dash --pin <job>'");
+        return Ok(());
+    }
+
+    let targets = resolve_targets(&config, &names)?;
+    let clients = clients_for(&targets);
+
+    if !watch {
+        let sp = output::spinner(&format!("Fetching the status of {} job(s)...", targets.len()));
+        let jobs = fetch_summaries(&targets, &clients);
+        sp.finish_and_clear();
+        print_dashboard(&targets, &jobs);
+        return Ok(());
+    }
+
+    let term = Term::stdout();
+    let _ctrl_c = interrupt::catch_ctrl_c();
+    loop {
+        let jobs = fetch_summaries(&targets, &clients);
+        term.clear_screen()?;
+        print_dashboard(&targets, &jobs);
+        output::dim(&format!("Refreshing every {}s - press Ctrl+C to exit", interval));
+        if interrupt::sleep(Duration::from_secs(interval)) {
+            return Ok(());
+        }
+    }
+}
+
+/// Add a job (path or URL) to the dashboard
+pub fn execute_pin(job: String) -> Result<()> {
+    let mut config = Config::load()?;
+    if config.pinned_jobs.contains(&job) {
+        output::info(&format!("'{}' is already pinned", job));
+        return Ok(());
+    }
+    // Catch URLs of hosts that aren't configured now rather than on every refresh
+    config.resolve_job_name(&job)?;
+    config.pinned_jobs.push(job.clone());
+    config.save()?;
+    output::success(&format!("Pinned '{}' to the dashboard", job));
+    Ok(())
+}
+
+pub fn execute_unpin(job: String) -> Result<()> {
+    let mut config = Config::load()?;
+    let before = config.pinned_jobs.len();
+    config.pinned_jobs.retain(|pinned| *pinned != job);
+    if config.pinned_jobs.len() == before {
+        anyhow::bail!("'{}' is not pinned", job);
+    }
+    config.save()?;
+    output::success(&format!("Unpinned '{}'", job));
+    Ok(())
+}
+
+/// Resolve aliases, job paths and job URLs to the host each is read from
+/// Names without a host of their own share the one picked for this run
+pub fn resolve_targets(config: &Config, names: &[String]) -> Result<Vec<JobTarget>> {
+    let mut default_jenkins: Option<String> = None;
+    let mut targets = Vec::new();
+    for name in names {
+        let (job_name, is_alias, jenkins) = config.resolve_job_name(name)?;
+        let jenkins = match jenkins {
+            Some(jenkins) => jenkins,
+            None => {
+                if default_jenkins.is_none() {
+                    default_jenkins = prompt_jenkins_selection()?;
+                }
+                default_jenkins.clone().ok_or_else(|| anyhow::anyhow!("No Jenkins host specified"))?
+            }
+        };
+        let label = if is_alias { name.clone() } else { to_display_job_path(&job_name) };
+        targets.push(JobTarget { label, job_name, jenkins });
+    }
+    Ok(targets)
+}
+
+/// One client per host; a host that can't be set up only fails its own rows
+pub fn clients_for(targets: &[JobTarget]) -> HashMap<String, Result<JenkinsClient>> {
+    let mut clients = HashMap::new();
+    for target in targets {
+        clients
+            .entry(target.jenkins.clone())
+            .or_insert_with(|| create_client(Some(target.jenkins.clone())));
+    }
+    clients
+}
+
+/// Fetch every target's status at once
+pub fn fetch_summaries(targets: &[JobTarget], clients: &HashMap<String, Result<JenkinsClient>>) -> Vec<Result<JobInfo>> {
+    thread::scope(|scope| {
+        let handles: Vec<_> = targets
+            .iter()
+            .map(|target| {
+                let client = &clients[&target.jenkins];
+                scope.spawn(move || match client {
+                    Ok(client) => client.get_job_summary(&target.job_name),
+                    Err(e) => Err(anyhow::anyhow!("{:#}", e)),
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap_or_else(|_| Err(anyhow::anyhow!("The request thread panicked"))))
+            .collect()
+    })
+}
+
+fn print_dashboard(targets: &[JobTarget], jobs: &[Result<JobInfo>]) {
+    print_status("Dashboard", "JOB", targets, jobs);
+}
+
+/// Print the status table under a header, then why the jobs marked as errors couldn't be read
+pub fn print_status(title: &str, label: &str, targets: &[JobTarget], jobs: &[Result<JobInfo>]) {
+    output::header(title);
+    status_table(label, targets, jobs, now_millis()).print();
+    output::newline();
+    for (target, job) in targets.iter().zip(jobs) {
+        if let Err(e) = job {
+            output::warning(&format!("{}: {:#}", target.label, e));
+        }
+    }
+}
+
+/// One row per job with its latest build; `label` heads the column of target labels
+fn status_table(label: &str, targets: &[JobTarget], jobs: &[Result<JobInfo>], now: i64) -> Table {
+    let mut table = Table::new(&[label, "STATUS", "BUILD", "STARTED", "DURATION", "BY", "HOST"]);
+    for (target, job) in targets.iter().zip(jobs) {
+        let row = match job {
+            Ok(job) => {
+                let build = job.last_build.as_ref();
+                let duration = match build {
+                    Some(b) if b.building.unwrap_or(false) => style("running").cyan().to_string(),
+                    Some(b) => b.duration.map(format_duration).unwrap_or_default(),
+                    None => String::new(),
+                };
+                vec![
+                    target.label.clone(),
+                    format_job_color_styled(job.color.as_deref()),
+                    build.map(|b| format!("#{}", b.number)).unwrap_or_else(|| "-".to_string()),
+                    build.and_then(|b| b.timestamp).map(|t| format_timestamp(t, now)).unwrap_or_default(),
+                    duration,
+                    build.and_then(|b| b.triggered_by()).unwrap_or_default(),
+                    target.jenkins.clone(),
+                ]
+            }
+            // Details follow the table, so long messages don't stretch the columns
+            Err(_) => vec![
+                target.label.clone(),
+                style("error").red().to_string(),
+                "-".to_string(),
+                String::new(),
+                String::new(),
+                String::new(),
+                target.jenkins.clone(),
+            ],
+        };
+        table.add_row(row);
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::JenkinsHost;
+
+    #[test]
+    fn test_resolve_targets() {
+        let mut config = Config::default();
+        config.add_jenkins(
+            "prod".to_string(),
+            JenkinsHost { host: "https://jenkins.example.com".to_string(), ..Default::default() },
+        );
+        config.add_job_alias("api".to_string(), "team/api".to_string(), Some("prod".to_string()));
+
+        let names = vec!["api".to_string(), "https://jenkins.example.com/job/team/job/web/".to_string()];
+        let targets = resolve_targets(&config, &names).unwrap();
+        let resolved: Vec<(&str, &str, &str)> =
+            targets.iter().map(|t| (t.label.as_str(), t.job_name.as_str(), t.jenkins.as_str())).collect();
+        assert_eq!(resolved, vec![("api", "team/job/api", "prod"), ("team/web", "team/job/web", "prod")]);
+    }
+
+    #[test]
+    fn test_status_table() {
+        let now = 1_700_000_000_000;
+        let target = |label: &str, jenkins: &str| JobTarget {
+            label: label.to_string(),
+            job_name: label.to_string(),
+            jenkins: jenkins.to_string(),
+        };
+        let job: JobInfo = serde_json::from_value(serde_json::json!({
+            "name": "api",
+            "color": "blue",
+            "lastBuild": {
+                "number": 7,
+                "url": "",
+                "building": false,
+                "timestamp": now - 2 * 60 * 60 * 1000,
+                "duration": 95_000,
+                "actions": [{"causes": [{"shortDescription": "Started by timer"}]}]
+            }
+        }))
+        .unwrap();
+
+        let lines = status_table(
+            "JOB",
+            &[target("api", "prod"), target("web-frontend", "staging")],
+            &[Ok(job), Err(anyhow::anyhow!("Connection refused"))],
+            now,
+        )
+        .render_plain(None);
+        assert_eq!(lines[0], "JOB           STATUS   BUILD  STARTED      DURATION  BY     HOST");
+        assert_eq!(lines[1], "api           Success  #7     2 hours ago  1m 35s    timer  prod");
+        assert_eq!(lines[2], "web-frontend  error    -                                    staging");
+    }
+}
@@ -0,0 +1,302 @@
+use anyhow::Result;
+use crate::client::JenkinsClient;
+use crate::config::{validate_config, Config, IssueSeverity, CONFIG_ENV_VAR};
+use crate::helpers::errors::{kind_of, ErrorKind};
+use crate::helpers::formatting::{format_duration, now_millis};
+use crate::helpers::init::create_client;
+use crate::output;
+use crate::state::State;
+use reqwest::StatusCode;
+
+/// Clock differences beyond this make relative times and token checks misleading
+const MAX_CLOCK_SKEW_MS: i64 = 60_000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Outcome {
+    Pass,
+    Warn,
+    Fail,
+    Skip,
+}
+
+/// One line of the checklist
+struct Check {
+    name: &'static str,
+    outcome: Outcome,
+    detail: String,
+    tip: Option<String>,
+}
+
+impl Check {
+    fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+        Check { name, outcome: Outcome::Pass, detail: detail.into(), tip: None }
+    }
+
+    fn warn(name: &'static str, detail: impl Into<String>, tip: impl Into<String>) -> Self {
+        Check { name, outcome: Outcome::Warn, detail: detail.into(), tip: Some(tip.into()) }
+    }
+
+    fn fail(name: &'static str, detail: impl Into<String>, tip: impl Into<String>) -> Self {
+        Check { name, outcome: Outcome::Fail, detail: detail.into(), tip: Some(tip.into()) }
+    }
+
+    fn skip(name: &'static str, detail: impl Into<String>) -> Self {
+        Check { name, outcome: Outcome::Skip, detail: detail.into(), tip: None }
+    }
+
+    fn print(&self) {
+        let line = format!("{}: {}", self.name, self.detail);
+        match self.outcome {
+            Outcome::Pass => output::success(&line),
+            Outcome::Warn => output::warning(&line),
+            Outcome::Fail => output::error(&line),
+            Outcome::Skip => output::dim(&format!("- {}", line)),
+        }
+        if let Some(tip) = &self.tip {
+            output::dim(&format!("  → {}", tip));
+        }
+    }
+}
+
+pub fn execute(jenkins: Option<String>, job: Option<String>) -> Result<()> {
+    output::header("Jenkins CLI doctor");
+
+    let mut checks = vec![check_config_file(), check_state()];
+    checks.iter().for_each(Check::print);
+
+    // Host checks need a loadable config
+    if checks[0].outcome != Outcome::Fail {
+        let host_checks = match create_client(jenkins) {
+            Ok(client) => check_host(&client, job.as_deref()),
+            Err(e) => vec![Check::fail("Host", format!("{:#}", e), "Use 'jenkins config add' to add a Jenkins host")],
+        };
+        host_checks.iter().for_each(Check::print);
+        checks.extend(host_checks);
+    }
+
+    let count = |outcome| checks.iter().filter(|c| c.outcome == outcome).count();
+    let (passed, warnings, failed) = (count(Outcome::Pass), count(Outcome::Warn), count(Outcome::Fail));
+    output::newline();
+    output::info(&format!("{} passed, {} warning(s), {} failed", passed, warnings, failed));
+
+    if failed > 0 {
+        anyhow::bail!("{} check(s) failed", failed);
+    }
+    Ok(())
+}
+
+fn check_config_file() -> Check {
+    const NAME: &str = "Config file";
+
+    let path = match Config::config_path() {
+        Ok(path) => path,
+        Err(e) => return Check::fail(NAME, format!("{:#}", e), format!("Set {} to a config file path", CONFIG_ENV_VAR)),
+    };
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(_) if !path.exists() => {
+            return Check::fail(NAME, format!("{} not found", path.display()), "Run 'jenkins config add' to create it");
+        }
+        Err(e) => return Check::fail(NAME, format!("Failed to read {}: {}", path.display(), e), "Check the file permissions"),
+    };
+
+    let issues = validate_config(&content);
+    let errors = issues.iter().filter(|i| i.severity == IssueSeverity::Error).count();
+    let tip = "Run 'jenkins config validate' to see every problem";
+    match (errors, issues.len()) {
+        (0, 0) => Check::pass(NAME, format!("{} is valid", path.display())),
+        (0, warnings) => Check::warn(NAME, format!("{} has {} warning(s)", path.display(), warnings), tip),
+        (errors, _) => Check::fail(NAME, format!("{} has {} error(s)", path.display(), errors), tip),
+    }
+}
+
+fn check_state() -> Check {
+    const NAME: &str = "State files";
+
+    match State::check_health() {
+        Ok(dir) => Check::pass(NAME, format!("{} is writable", dir.display())),
+        Err(e) => Check::warn(NAME, format!("{:#}", e), "Fix the permissions or delete the file; it is recreated on demand"),
+    }
+}
+
+/// Reachability first, since every later check needs it, then credentials and permissions
+fn check_host(client: &JenkinsClient, job: Option<&str>) -> Vec<Check> {
+    let host = client.name().unwrap_or("host").to_string();
+    let mut checks = Vec::new();
+
+    let sp = output::spinner(&format!("Checking '{}'...", host));
+    let probe = client.probe_server();
+    sp.finish_and_clear();
+
+    let probe = match probe {
+        Ok(probe) if probe.status.is_server_error() => {
+            checks.push(Check::fail(
+                "Reachability",
+                format!("'{}' answered HTTP {}", host, probe.status),
+                "Check the controller's health and any proxy in front of it",
+            ));
+            return skip_rest(checks);
+        }
+        Ok(probe) => probe,
+        Err(e) => {
+            checks.push(Check::fail(
+                "Reachability",
+                format!("{:#}", e),
+                "Check the host URL with 'jenkins config list', and your network or VPN",
+            ));
+            return skip_rest(checks);
+        }
+    };
+    let reachability = match (&probe.version, probe.status) {
+        (Some(version), _) => Check::pass("Reachability", format!("'{}' runs Jenkins {}", host, version)),
+        (None, StatusCode::NOT_FOUND) => Check::fail(
+            "Reachability",
+            format!("'{}' answered, but no Jenkins was found at that URL", host),
+            "Include the context path in the host URL, e.g. https://ci.example.com/jenkins",
+        ),
+        (None, _) => Check::warn(
+            "Reachability",
+            format!("'{}' answered without an X-Jenkins header", host),
+            "A proxy may be hiding Jenkins headers or answering instead of Jenkins",
+        ),
+    };
+    let reachable = reachability.outcome != Outcome::Fail;
+    checks.push(reachability);
+    if !reachable {
+        return skip_rest(checks);
+    }
+
+    let who = match client.who_am_i() {
+        Ok(who) if !who.anonymous => {
+            checks.push(Check::pass("Authentication", format!("Authenticated as {}", who.name)));
+            who
+        }
+        Ok(_) => {
+            checks.push(Check::fail(
+                "Authentication",
+                "Jenkins treats the requests as anonymous",
+                "Check the user name; a token for another user is ignored",
+            ));
+            return skip_rest(checks);
+        }
+        Err(e) => {
+            checks.push(Check::fail(
+                "Authentication",
+                format!("{:#}", e),
+                "Create a new API token (User > Security > API Token) and update it with 'jenkins config add'",
+            ));
+            return skip_rest(checks);
+        }
+    };
+
+    checks.push(match client.crumb_issuer_enabled() {
+        Ok(true) => Check::pass("Crumb issuer", "Available (API token requests don't need crumbs)"),
+        Ok(false) => Check::warn(
+            "Crumb issuer",
+            "Disabled - the controller has no CSRF protection",
+            "Enable 'Prevent Cross Site Request Forgery exploits' in the security settings",
+        ),
+        Err(e) => Check::warn("Crumb issuer", format!("{:#}", e), "A proxy may be blocking /crumbIssuer"),
+    });
+
+    checks.push(match probe.date {
+        Some(server) => {
+            let skew = now_millis() - server;
+            if skew.abs() > MAX_CLOCK_SKEW_MS {
+                Check::warn(
+                    "Clock skew",
+                    format!("Local clock is {} {} the server", format_duration(skew.abs()), if skew > 0 { "ahead of" } else { "behind" }),
+                    "Sync the local clock (e.g. enable NTP); relative times and durations will be off",
+                )
+            } else {
+                Check::pass("Clock skew", format!("Within {}", format_duration(MAX_CLOCK_SKEW_MS)))
+            }
+        }
+        None => Check::skip("Clock skew", "The server sent no Date header"),
+    });
+
+    checks.push(permission_check(client, "Read permission", "api/json", "Ask an administrator for Overall/Read"));
+    checks.push(match job {
+        Some(job) => {
+            let job = Config::load().and_then(|config| config.resolve_job_name(job)).map(|(job, _, _)| job).unwrap_or_else(|_| job.to_string());
+            build_check(client, &job, &who.authorities)
+        }
+        None => Check::skip("Build permission", "Pass --job to check it on a job"),
+    });
+    checks.push(match client.can_access("scriptText") {
+        Ok(true) => Check::pass("Admin permission", "Granted"),
+        // Most commands work without it, so it is only informational
+        Ok(false) => Check::skip("Admin permission", "Not granted (needed for script, casc and plugins)"),
+        Err(e) => Check::warn("Admin permission", format!("{:#}", e), "Check the controller logs"),
+    });
+
+    checks
+}
+
+fn permission_check(client: &JenkinsClient, name: &'static str, path: &str, tip: &str) -> Check {
+    match client.can_access(path) {
+        Ok(true) => Check::pass(name, "Granted"),
+        Ok(false) => Check::fail(name, "Denied", tip),
+        Err(e) => Check::warn(name, format!("{:#}", e), "Check the controller logs"),
+    }
+}
+
+/// Jenkins has no read-only way to ask for Job/Build, and older controllers start a build on a GET
+/// of the job's build URL, so this only checks what reading the job shows
+fn build_check(client: &JenkinsClient, job: &str, authorities: &[String]) -> Check {
+    const NAME: &str = "Build permission";
+    match client.get_job(job) {
+        Ok(info) if info.buildable == Some(false) => {
+            Check::warn(NAME, "The job is disabled or can't be built", "Enable the job in Jenkins")
+        }
+        Ok(_) => {
+            let authorities = if authorities.is_empty() { "none".to_string() } else { authorities.join(", ") };
+            let detail = format!("The job is visible and buildable (authorities: {}); Jenkins checks Job/Build only on a build", authorities);
+            Check::pass(NAME, detail)
+        }
+        Err(e) if matches!(kind_of(&e), Some(ErrorKind::NotFound | ErrorKind::Auth)) => Check::fail(
+            NAME,
+            "The job doesn't exist or this user can't see it",
+            "Check the job path, or ask an administrator for Job/Read and Job/Build on this job",
+        ),
+        Err(e) => Check::warn(NAME, format!("{:#}", e), "Check the controller logs"),
+    }
+}
+
+/// Mark the checks that depend on a working connection as skipped
+fn skip_rest(mut checks: Vec<Check>) -> Vec<Check> {
+    let done: Vec<&str> = checks.iter().map(|c| c.name).collect();
+    for name in ["Authentication", "Crumb issuer", "Clock skew", "Read permission", "Build permission", "Admin permission"] {
+        if !done.contains(&name) {
+            checks.push(Check::skip(name, "Skipped"));
+        }
+    }
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skip_rest_keeps_finished_checks() {
+        let checks = skip_rest(vec![
+            Check::pass("Reachability", "up"),
+            Check::fail("Authentication", "401", "new token"),
+        ]);
+        let names: Vec<(&str, Outcome)> = checks.iter().map(|c| (c.name, c.outcome)).collect();
+
+        assert_eq!(
+            names,
+            vec![
+                ("Reachability", Outcome::Pass),
+                ("Authentication", Outcome::Fail),
+                ("Crumb issuer", Outcome::Skip),
+                ("Clock skew", Outcome::Skip),
+                ("Read permission", Outcome::Skip),
+                ("Build permission", Outcome::Skip),
+                ("Admin permission", Outcome::Skip),
+            ]
+        );
+    }
+}
@@ -0,0 +1,30 @@
+use anyhow::Result;
+use crate::helpers::init::{create_client_for_job, resolve_build_number};
+use crate::helpers::url::to_human_job_path;
+use crate::interactive;
+use crate::output;
+
+pub fn execute(job_name: Option<String>, build_number: Option<i32>) -> Result<()> {
+    let client = create_client_for_job(job_name.as_deref(), None)?;
+
+    // Resolve the final job name (handle sub-jobs if present)
+    let final_job_name = interactive::resolve_job_name(&client, job_name.as_deref())?;
+    let build_num = resolve_build_number(&client, &final_job_name, build_number)?;
+
+    let sp = output::spinner(&format!("Fetching environment for {}#{}...", to_human_job_path(&final_job_name), build_num));
+    let env_vars = client.get_injected_env_vars(&final_job_name, build_num)?;
+    sp.finish_and_clear();
+
+    output::header(&format!("Environment: {}#{}", to_human_job_path(&final_job_name), build_num));
+
+    if env_vars.is_empty() {
+        output::info("No environment variables recorded for this build");
+        return Ok(());
+    }
+
+    for (name, value) in &env_vars {
+        output::list_item(&format!("{}=", name), value);
+    }
+
+    Ok(())
+}
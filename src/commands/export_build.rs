@@ -0,0 +1,199 @@
+use anyhow::{Context, Result};
+use crate::client::{BuildArtifacts, BuildCause, BuildDetails, BuildParameter, ChangeSetItem, TestReport};
+use crate::helpers::errors::{kind_of, ErrorKind};
+use crate::helpers::formatting::now_millis;
+use crate::helpers::init::{create_client_for_job, resolve_build_number};
+use crate::helpers::url::{encode_path_segment, to_human_job_path};
+use crate::interactive;
+use crate::output;
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+
+/// Everything known about one build, as written by `jenkins export-build`
+#[derive(Debug, Serialize)]
+struct BuildExport<'a> {
+    /// Jenkins job path (`team/job/api`)
+    job: &'a str,
+    number: i32,
+    url: &'a str,
+    result: Option<&'a str>,
+    building: bool,
+    /// Start time in epoch milliseconds
+    timestamp: i64,
+    /// Duration in milliseconds
+    duration: i64,
+    parameters: Vec<&'a BuildParameter>,
+    causes: Vec<&'a BuildCause>,
+    revision: Option<Revision<'a>>,
+    changes: Vec<&'a ChangeSetItem>,
+    /// None when the build recorded no test report
+    tests: Option<TestSummary>,
+    artifacts: Vec<ExportedArtifact>,
+    /// When the document was written, in epoch milliseconds
+    exported_at: i64,
+}
+
+#[derive(Debug, Serialize)]
+struct Revision<'a> {
+    commit: &'a str,
+    remote: &'a str,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct TestSummary {
+    passed: i64,
+    failed: i64,
+    skipped: i64,
+    /// `ClassName.test` of every failed test
+    failures: Vec<String>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct ExportedArtifact {
+    path: String,
+    url: String,
+    /// MD5 Jenkins recorded when fingerprinting was enabled
+    md5: Option<String>,
+}
+
+pub fn execute(job_name: Option<String>, build_number: Option<i32>, output: Option<PathBuf>) -> Result<()> {
+    let client = create_client_for_job(job_name.as_deref(), None)?;
+    let final_job_name = interactive::resolve_job_name(&client, job_name.as_deref())?;
+    let build_num = resolve_build_number(&client, &final_job_name, build_number)?;
+
+    let sp = output::spinner(&format!("Collecting {}#{}...", to_human_job_path(&final_job_name), build_num));
+    let details = client.get_build(&final_job_name, build_num)?;
+    let tests = match client.get_test_report(&final_job_name, build_num) {
+        Ok(report) => Some(summarize_tests(&report)),
+        Err(e) if kind_of(&e) == Some(ErrorKind::NotFound) => None,
+        Err(e) => return Err(e),
+    };
+    let artifacts = client.get_artifacts(&final_job_name, build_num)?;
+    sp.finish_and_clear();
+
+    let export = build_export(&final_job_name, &details, tests, &artifacts, now_millis());
+    let json = serde_json::to_string_pretty(&export).context("Failed to serialize the build")?;
+
+    // Without --output the document goes to stdout, for piping into other tools
+    let Some(file) = output else {
+        println!("{}", json);
+        return Ok(());
+    };
+    fs::write(&file, format!("{}\n", json)).with_context(|| format!("Failed to write {}", file.display()))?;
+    output::success(&format!(
+        "Exported {}#{} to {}",
+        to_human_job_path(&final_job_name),
+        build_num,
+        file.display()
+    ));
+    Ok(())
+}
+
+fn build_export<'a>(
+    job_name: &'a str,
+    details: &'a BuildDetails,
+    tests: Option<TestSummary>,
+    artifacts: &BuildArtifacts,
+    now: i64,
+) -> BuildExport<'a> {
+    let base_url = details.url.trim_end_matches('/');
+    BuildExport {
+        job: job_name,
+        number: details.number,
+        url: &details.url,
+        result: details.result.as_deref(),
+        building: details.building,
+        timestamp: details.timestamp,
+        duration: details.duration,
+        parameters: details.parameters(),
+        causes: details.causes(),
+        revision: details.git_revision().map(|(commit, remote)| Revision { commit, remote }),
+        changes: details.changes(),
+        tests,
+        artifacts: artifacts
+            .artifacts
+            .iter()
+            .map(|artifact| ExportedArtifact {
+                path: artifact.relative_path.clone(),
+                url: format!(
+                    "{}/artifact/{}",
+                    base_url,
+                    artifact.relative_path.split('/').map(encode_path_segment).collect::<Vec<_>>().join("/")
+                ),
+                md5: artifacts.recorded_md5(artifact).map(str::to_string),
+            })
+            .collect(),
+        exported_at: now,
+    }
+}
+
+fn summarize_tests(report: &TestReport) -> TestSummary {
+    TestSummary {
+        passed: report.pass_count,
+        failed: report.fail_count,
+        skipped: report.skip_count,
+        failures: report
+            .failed_cases()
+            .iter()
+            .map(|case| format!("{}.{}", case.class_name, case.name))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_export() {
+        let details: BuildDetails = serde_json::from_value(serde_json::json!({
+            "number": 12,
+            "url": "https://jenkins.example.com/job/team/job/api/12/",
+            "result": "UNSTABLE",
+            "building": false,
+            "timestamp": 1_000,
+            "duration": 5_000,
+            "fullDisplayName": "team » api #12",
+            "actions": [
+                { "parameters": [{ "name": "ENV", "value": "prod" }] },
+                { "causes": [{ "shortDescription": "Started by user Ana", "userId": "ana", "userName": "Ana" }] },
+                { "lastBuiltRevision": { "SHA1": "3f2a9c1d" }, "remoteUrls": ["git@github.com:acme/api.git"] }
+            ],
+            "changeSets": [{ "kind": "git", "items": [{ "commitId": "3f2a9c1d", "msg": "Fix login" }] }]
+        }))
+        .unwrap();
+        let report: TestReport = serde_json::from_value(serde_json::json!({
+            "passCount": 40,
+            "failCount": 1,
+            "skipCount": 2,
+            "suites": [{ "name": "auth", "cases": [
+                { "className": "auth.LoginTest", "name": "rejects_bad_password", "status": "REGRESSION" },
+                { "className": "auth.LoginTest", "name": "accepts_token", "status": "PASSED" }
+            ] }]
+        }))
+        .unwrap();
+        let artifacts: BuildArtifacts = serde_json::from_value(serde_json::json!({
+            "artifacts": [{ "fileName": "api.jar", "relativePath": "target/api.jar" }],
+            "fingerprint": [{ "fileName": "api.jar", "hash": "d41d8cd9" }]
+        }))
+        .unwrap();
+
+        let export = build_export("team/job/api", &details, Some(summarize_tests(&report)), &artifacts, 9_000);
+        let json = serde_json::to_value(&export).unwrap();
+        assert_eq!(json["job"], "team/job/api");
+        assert_eq!(json["result"], "UNSTABLE");
+        assert_eq!(json["parameters"][0]["value"], "prod");
+        assert_eq!(json["causes"][0]["userId"], "ana");
+        assert_eq!(json["revision"]["commit"], "3f2a9c1d");
+        assert_eq!(json["changes"][0]["msg"], "Fix login");
+        assert_eq!(json["tests"]["failures"][0], "auth.LoginTest.rejects_bad_password");
+        assert_eq!(json["artifacts"][0]["url"], "https://jenkins.example.com/job/team/job/api/12/artifact/target/api.jar");
+        assert_eq!(json["artifacts"][0]["md5"], "d41d8cd9");
+        assert_eq!(json["exported_at"], 9_000);
+
+        // A build without a test report says so instead of reporting zero tests
+        let export = build_export("team/job/api", &details, None, &BuildArtifacts::default(), 9_000);
+        assert_eq!(serde_json::to_value(&export).unwrap()["tests"], serde_json::Value::Null);
+    }
+}
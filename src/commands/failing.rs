@@ -0,0 +1,136 @@
+use anyhow::Result;
+use crate::cli::JobFilter;
+use crate::client::DescendantJob;
+use crate::config::Config;
+use crate::commands::jobs::{matches_filter, status_rank};
+use crate::helpers::formatting::{format_job_color_styled, format_timestamp, now_millis};
+use crate::helpers::init::create_client_for_job;
+use crate::helpers::url::{to_display_job_path, to_human_job_path};
+use crate::output::{self, Table};
+
+/// A failing job with the build its failure streak started at
+#[derive(Debug, PartialEq)]
+struct FailingJob {
+    path: String,
+    color: String,
+    first_failing: Option<i32>,
+    last_failure: Option<i64>,
+}
+
+pub fn execute(folder: Option<String>, view: Option<String>, depth: usize) -> Result<()> {
+    let client = create_client_for_job(folder.as_deref(), None)?;
+
+    // Resolve alias if the folder was given as one
+    let config = Config::load()?;
+    let folder = folder.map(|name| config.resolve_job_name(&name).map(|(folder, _, _)| folder)).transpose()?;
+
+    let sp = output::spinner("Scanning jobs...");
+    let jobs = client.get_descendant_jobs_with_builds(folder.as_deref(), view.as_deref(), depth)?;
+    sp.finish_and_clear();
+
+    let scanned = jobs.len();
+    let failing = failing_jobs(jobs);
+    let location = match (&view, &folder) {
+        (Some(view), _) => format!("view '{}'", view),
+        (None, Some(folder)) => format!("'{}'", to_human_job_path(folder)),
+        (None, None) => "the instance".to_string(),
+    };
+    if failing.is_empty() {
+        output::success(&format!("All {} job(s) in {} are passing", scanned, location));
+        return Ok(());
+    }
+
+    output::header(&format!("Failing jobs in {} ({} of {})", location, failing.len(), scanned));
+    failing_table(&failing, now_millis()).print();
+    Ok(())
+}
+
+/// Failed and unstable jobs, failed first, each with where its failure streak started
+fn failing_jobs(jobs: Vec<DescendantJob>) -> Vec<FailingJob> {
+    let mut failing: Vec<FailingJob> = jobs
+        .into_iter()
+        .filter(|job| matches_filter(job.color.as_deref(), JobFilter::Failing))
+        .map(|job| {
+            let color = job.color.unwrap_or_default();
+            let builds = job.builds;
+            // Unstable builds count as successful in Jenkins, so a failed job's streak starts after
+            // the last successful build, an unstable job's after the last stable one
+            let (last_good, last_bad) = if color.starts_with("red") {
+                (builds.last_successful, builds.last_failed)
+            } else {
+                (builds.last_stable, builds.last_unstable)
+            };
+            let first_failing = match last_good {
+                Some(good) => Some(good.number + 1),
+                None => builds.first.map(|first| first.number),
+            };
+            FailingJob {
+                path: job.path,
+                color,
+                first_failing,
+                last_failure: last_bad.and_then(|bad| bad.timestamp),
+            }
+        })
+        .collect();
+    failing.sort_by(|a, b| {
+        status_rank(Some(&a.color)).cmp(&status_rank(Some(&b.color))).then_with(|| a.path.cmp(&b.path))
+    });
+    failing
+}
+
+/// One row per failing job: its status, the first build of the streak and when it last failed
+fn failing_table(failing: &[FailingJob], now: i64) -> Table {
+    let mut table = Table::new(&["JOB", "STATUS", "FAILING SINCE", "LAST FAILURE"]);
+    for job in failing {
+        table.add_row(vec![
+            to_display_job_path(&job.path),
+            format_job_color_styled(Some(&job.color)),
+            job.first_failing.map(|n| format!("#{}", n)).unwrap_or_else(|| "-".to_string()),
+            job.last_failure.map(|t| format_timestamp(t, now)).unwrap_or_default(),
+        ]);
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::{BuildMark, BuildMarks};
+
+    fn mark(number: i32, timestamp: Option<i64>) -> Option<BuildMark> {
+        Some(BuildMark { number, timestamp })
+    }
+
+    #[test]
+    fn test_failing_jobs() {
+        let job = |path: &str, color: &str, builds: BuildMarks| DescendantJob {
+            path: path.to_string(),
+            color: Some(color.to_string()),
+            builds,
+        };
+        let jobs = vec![
+            job("web", "yellow", BuildMarks {
+                last_unstable: mark(30, Some(5_000)),
+                last_successful: mark(30, None),
+                last_stable: mark(27, None),
+                ..Default::default()
+            }),
+            job("smoke", "blue", BuildMarks::default()),
+            job("team/job/api", "red_anime", BuildMarks {
+                first: mark(1, None),
+                last_failed: mark(12, Some(9_000)),
+                last_successful: mark(9, None),
+                ..Default::default()
+            }),
+            job("new", "red", BuildMarks { first: mark(1, None), last_failed: mark(2, Some(1_000)), ..Default::default() }),
+        ];
+
+        let failing = failing_jobs(jobs);
+        let summary: Vec<(&str, Option<i32>, Option<i64>)> =
+            failing.iter().map(|f| (f.path.as_str(), f.first_failing, f.last_failure)).collect();
+        assert_eq!(
+            summary,
+            vec![("new", Some(1), Some(1_000)), ("team/job/api", Some(10), Some(9_000)), ("web", Some(28), Some(5_000))]
+        );
+    }
+}
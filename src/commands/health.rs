@@ -0,0 +1,45 @@
+use anyhow::Result;
+use crate::client::worst_health;
+use crate::config::Config;
+use crate::helpers::formatting::format_health_score;
+use crate::helpers::init::create_client_for_job;
+use crate::output::{self, Table};
+
+pub fn execute(folder: Option<String>) -> Result<()> {
+    let client = create_client_for_job(folder.as_deref(), None)?;
+
+    // Resolve alias if the folder was given as one
+    let config = Config::load()?;
+    let folder = folder.map(|name| config.resolve_job_name(&name).map(|(folder, _, _)| folder)).transpose()?;
+
+    let sp = output::spinner("Fetching job health...");
+    let jobs = client.get_jobs_health(folder.as_deref())?;
+    sp.finish_and_clear();
+
+    let mut reports: Vec<_> = jobs
+        .iter()
+        .filter_map(|job| worst_health(job.health_report.as_ref()).map(|report| (job, report)))
+        .collect();
+
+    if reports.is_empty() {
+        output::info("No health reports found");
+        return Ok(());
+    }
+
+    // Sickest jobs first
+    reports.sort_by(|(a_job, a), (b_job, b)| a.score.cmp(&b.score).then_with(|| a_job.name.cmp(&b_job.name)));
+
+    let title = match folder.as_deref() {
+        Some(folder) => format!("Job health: {}", folder),
+        None => "Job health".to_string(),
+    };
+    output::header(&title);
+
+    let mut table = Table::new(&["HEALTH", "JOB", "REPORT"]).align_right(0);
+    for (job, report) in reports {
+        table.add_row(vec![format_health_score(report.score), job.name.clone(), report.description.clone()]);
+    }
+    table.print();
+
+    Ok(())
+}
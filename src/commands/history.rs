@@ -0,0 +1,103 @@
+use anyhow::Result;
+use console::style;
+use crate::helpers::formatting::{format_timestamp, now_millis};
+use crate::helpers::history::{self, HistoryEntry};
+use crate::output::{self, Table};
+use std::fmt::Write;
+
+/// List what this machine's CLI changed on the controllers, newest first
+pub fn execute(jenkins: Option<String>, job: Option<String>, last: usize) -> Result<()> {
+    let entries: Vec<HistoryEntry> = history::load()?
+        .into_iter()
+        .rev()
+        .filter(|entry| jenkins.as_ref().is_none_or(|host| &entry.host == host))
+        .filter(|entry| job.as_ref().is_none_or(|job| entry.display_target().contains(job.as_str())))
+        .take(last)
+        .collect();
+
+    if entries.is_empty() {
+        output::info("No recorded actions");
+        output::tip("Builds, cancellations, config and agent changes, scripts and non-GET api calls made through the CLI are recorded here");
+        return Ok(());
+    }
+
+    let now = now_millis();
+    let mut text = String::new();
+    let _ = writeln!(
+        text,
+        "\n{}",
+        style(format!("Local history ({})", history::history_path()?.display())).bold().underlined()
+    );
+    text.push_str(&history_table(&entries, now).to_text());
+    let failures: Vec<&HistoryEntry> = entries.iter().filter(|entry| entry.error.is_some()).collect();
+    if !failures.is_empty() {
+        text.push('\n');
+    }
+    for entry in failures {
+        let error = entry.error.as_deref().unwrap_or_default();
+        let _ = writeln!(
+            text,
+            "{} {} {} ({}): {}",
+            style("⚠").yellow().bold(),
+            entry.action.label(),
+            entry.display_target(),
+            format_timestamp(entry.at, now),
+            error
+        );
+    }
+    output::page(&text);
+    Ok(())
+}
+
+/// One row per entry: when, where, what, on which target, whether the controller accepted it and with which parameters
+fn history_table(entries: &[HistoryEntry], now: i64) -> Table {
+    let mut table = Table::new(&["WHEN", "HOST", "ACTION", "TARGET", "REQUEST", "PARAMETERS"]).max_width(5, 60);
+    for entry in entries {
+        let parameters: Vec<String> = entry.parameters.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        let request = if entry.accepted { style("accepted").green() } else { style("failed").red() };
+        table.add_row(vec![
+            format_timestamp(entry.at, now),
+            entry.host.clone(),
+            entry.action.label().to_string(),
+            entry.display_target(),
+            request.to_string(),
+            parameters.join(", "),
+        ]);
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::history::Action;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_history_table() {
+        let now = 1_700_000_000_000;
+        let build = HistoryEntry {
+            at: now - 2 * 60 * 60 * 1000,
+            host: "prod".to_string(),
+            target: "team/job/api".to_string(),
+            action: Action::Build,
+            parameters: BTreeMap::from([("BRANCH".to_string(), "main".to_string())]),
+            accepted: true,
+            error: None,
+        };
+        let update = HistoryEntry {
+            at: now - 30 * 1000,
+            host: "dr".to_string(),
+            action: Action::UpdateConfig,
+            parameters: BTreeMap::new(),
+            accepted: false,
+            error: Some("Request failed: 403".to_string()),
+            ..build.clone()
+        };
+
+        let lines = history_table(&[update, build], now).render_plain(None);
+        assert_eq!(lines[0], "WHEN         HOST  ACTION         TARGET    REQUEST   PARAMETERS");
+        assert_eq!(lines[1], "just now     dr    update config  team/api  failed");
+        assert_eq!(lines[2], "2 hours ago  prod  build          team/api  accepted  BRANCH=main");
+    }
+}
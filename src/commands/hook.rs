@@ -0,0 +1,227 @@
+use anyhow::{Context, Result};
+use crate::cli::HookTrigger;
+use crate::helpers::errors::ErrorKind;
+use crate::helpers::git;
+use crate::helpers::init::profile_default_job;
+use crate::helpers::params::ENV_PARAM_PREFIX;
+use crate::helpers::url::to_human_job_path;
+use crate::output;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Git has no post-push hook, so both triggers are installed as the pre-push hook
+const HOOK_NAME: &str = "pre-push";
+
+/// Marks hooks written by this command, which may be replaced or removed without --force
+const HOOK_MARKER: &str = "# Installed by jenkins-cli";
+
+/// Placeholders a parameter template can use; each is a variable set by the hook for every pushed branch
+const TEMPLATE_FIELDS: &[&str] = &["branch", "commit", "remote"];
+
+pub fn execute_install(
+    job: Option<String>,
+    jenkins: Option<String>,
+    params: Vec<(String, String)>,
+    trigger: HookTrigger,
+    force: bool,
+) -> Result<()> {
+    let job = match job {
+        Some(job) => job,
+        None => profile_default_job()?.ok_or_else(|| {
+            ErrorKind::Config.error("No job to build. Pass --job, or set `job` in a directory profile.")
+        })?,
+    };
+
+    let path = hook_path()?;
+    if let Ok(existing) = fs::read_to_string(&path)
+        && !is_ours(&existing)
+        && !force
+    {
+        anyhow::bail!(
+            "{} is a hook jenkins-cli didn't write. Use --force to replace it.",
+            path.display()
+        );
+    }
+
+    let exe = std::env::current_exe().context("Failed to locate the jenkins executable")?;
+    let script = hook_script(&exe.to_string_lossy(), &job, jenkins.as_deref(), &params, trigger)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    fs::write(&path, script).with_context(|| format!("Failed to write {}", path.display()))?;
+    make_executable(&path)?;
+
+    output::success(&format!("Installed {} to build '{}' on every push", path.display(), to_human_job_path(&job)));
+    match trigger {
+        HookTrigger::PostPush => {
+            output::tip("The build is triggered in the background once git push has updated the remote-tracking branch")
+        }
+        HookTrigger::PrePush => output::warning(
+            "The build starts before git sends the commits, so a job that checks out the branch builds the previous commit",
+        ),
+    }
+    Ok(())
+}
+
+pub fn execute_uninstall() -> Result<()> {
+    let path = hook_path()?;
+    let Ok(existing) = fs::read_to_string(&path) else {
+        output::info(&format!("No {} hook installed", HOOK_NAME));
+        return Ok(());
+    };
+    if !is_ours(&existing) {
+        anyhow::bail!("{} is a hook jenkins-cli didn't write, so it was left in place", path.display());
+    }
+
+    fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    output::success(&format!("Removed {}", path.display()));
+    Ok(())
+}
+
+/// The hook file of the repository in the current directory
+fn hook_path() -> Result<PathBuf> {
+    let cwd = std::env::current_dir().context("Failed to read the current directory")?;
+    git::hook_path(&cwd, HOOK_NAME).ok_or_else(|| anyhow::anyhow!("Not inside a git repository"))
+}
+
+fn is_ours(script: &str) -> bool {
+    script.lines().any(|line| line.starts_with(HOOK_MARKER))
+}
+
+/// A pre-push hook that builds `job` once per pushed branch, skipping tags and deleted branches
+/// Parameters reach the build as `JENKINS_PARAM_*` variables, so their templates are filled in by the shell
+fn hook_script(
+    exe: &str,
+    job: &str,
+    jenkins: Option<&str>,
+    params: &[(String, String)],
+    trigger: HookTrigger,
+) -> Result<String> {
+    let mut command = String::new();
+    for (key, template) in params {
+        if !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            anyhow::bail!("Parameter '{}' can't be passed from a git hook; use letters, digits and _ only", key);
+        }
+        command.push_str(&format!("{}{}={} ", ENV_PARAM_PREFIX, key, render_template(template)?));
+    }
+    command.push_str(&format!("{} build {} --non-interactive", shell_quote(exe), shell_quote(job)));
+    if let Some(jenkins) = jenkins {
+        command.push_str(&format!(" --jenkins {}", shell_quote(jenkins)));
+    }
+    command.push_str(" </dev/null");
+
+    let run = match trigger {
+        HookTrigger::PrePush => format!(
+            "    {} || echo \"jenkins-cli: the build of $branch was not triggered\" >&2\n",
+            command
+        ),
+        // The hook's parent is git push itself, so its exit marks the end of the push
+        // A rejected or failed push leaves the remote-tracking branch where it was, and then nothing is built
+        HookTrigger::PostPush => format!(
+            "    (\n        while kill -0 \"$PPID\" 2>/dev/null; do sleep 1; done\n        \
+             [ \"$(git rev-parse -q --verify \"refs/remotes/$remote/$branch\")\" = \"$commit\" ] || exit 0\n        \
+             {}\n    ) >/dev/null 2>&1 &\n",
+            command
+        ),
+    };
+
+    Ok(format!(
+        r#"#!/bin/sh
+{marker} - remove it with 'jenkins hook uninstall'
+# Builds {job} on Jenkins for every branch pushed
+remote="$1"
+while read -r local_ref commit remote_ref remote_commit; do
+    # Only branches, and not deleted ones
+    case "$remote_ref" in refs/heads/*) ;; *) continue ;; esac
+    case "$commit" in *[!0]*) ;; *) continue ;; esac
+    branch="${{remote_ref#refs/heads/}}"
+{run}done
+exit 0
+"#,
+        marker = HOOK_MARKER,
+        job = to_human_job_path(job),
+    ))
+}
+
+/// A template like `release-{branch}` as a double-quoted shell word, placeholders becoming hook variables
+fn render_template(template: &str) -> Result<String> {
+    let mut word = String::from("\"");
+    let mut rest = template;
+    while let Some(c) = rest.chars().next() {
+        if c == '{'
+            && let Some(end) = rest.find('}')
+        {
+            let field = &rest[1..end];
+            if !TEMPLATE_FIELDS.contains(&field) {
+                anyhow::bail!(
+                    "Unknown placeholder '{{{}}}' in '{}'; use {{branch}}, {{commit}} or {{remote}}",
+                    field,
+                    template
+                );
+            }
+            word.push_str(&format!("${{{}}}", field));
+            rest = &rest[end + 1..];
+            continue;
+        }
+        if matches!(c, '"' | '\\' | '$' | '`') {
+            word.push('\\');
+        }
+        word.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    word.push('"');
+    Ok(word)
+}
+
+/// Quote a value as a single shell word
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+        .with_context(|| format!("Failed to make {} executable", path.display()))
+}
+
+/// Git for Windows runs hooks through its own shell, so no permission bits are needed
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template() {
+        assert_eq!(render_template("{branch}").unwrap(), "\"${branch}\"");
+        assert_eq!(render_template("deploy-{branch}@{commit}").unwrap(), "\"deploy-${branch}@${commit}\"");
+        assert_eq!(render_template("say \"$HOME\"").unwrap(), "\"say \\\"\\$HOME\\\"\"");
+        assert_eq!(render_template("{").unwrap(), "\"{\"");
+        assert!(render_template("{tag}").unwrap_err().to_string().contains("Unknown placeholder '{tag}'"));
+    }
+
+    #[test]
+    fn test_hook_script() {
+        let params = vec![("BRANCH".to_string(), "{branch}".to_string())];
+        let script = hook_script("/usr/bin/jenkins", "team/job/api", Some("prod"), &params, HookTrigger::PrePush).unwrap();
+        assert!(script.starts_with("#!/bin/sh\n# Installed by jenkins-cli"));
+        assert!(is_ours(&script));
+        assert!(script.contains("# Builds team » api on Jenkins"));
+        assert!(script.contains(
+            "\n    JENKINS_PARAM_BRANCH=\"${branch}\" '/usr/bin/jenkins' build 'team/job/api' --non-interactive --jenkins 'prod' </dev/null ||"
+        ));
+
+        let script = hook_script("/usr/bin/jenkins", "api", None, &[], HookTrigger::PostPush).unwrap();
+        assert!(script.contains("while kill -0 \"$PPID\""));
+        assert!(script.contains("[ \"$(git rev-parse -q --verify \"refs/remotes/$remote/$branch\")\" = \"$commit\" ] || exit 0"));
+        assert!(script.contains(") >/dev/null 2>&1 &\n"));
+
+        let bad = vec![("DEPLOY-ENV".to_string(), "x".to_string())];
+        assert!(hook_script("jenkins", "api", None, &bad, HookTrigger::PrePush).is_err());
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+    }
+}
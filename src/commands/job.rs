@@ -0,0 +1,97 @@
+use anyhow::Result;
+use console::style;
+use crate::client::JenkinsClient;
+use crate::helpers::init::create_client_for_job;
+use crate::helpers::url::to_display_job_path;
+use crate::interactive;
+use crate::output;
+use similar::TextDiff;
+
+/// Lines of unchanged context shown around each change
+const DIFF_CONTEXT_LINES: usize = 3;
+
+pub fn execute_diff(
+    job_a: String,
+    job_b: Option<String>,
+    jenkins: Option<String>,
+    other_jenkins: Option<String>,
+) -> Result<()> {
+    if job_b.is_none() && other_jenkins.is_none() {
+        anyhow::bail!("Give a second job to compare with, or --other-jenkins to compare the same job on another host");
+    }
+
+    let client_a = create_client_for_job(Some(&job_a), jenkins)?;
+    let job_a = interactive::resolve_job_name(&client_a, Some(&job_a))?;
+
+    // The second job defaults to the same job, and the second host to the first one
+    let job_b = job_b.unwrap_or_else(|| job_a.clone());
+    let other_jenkins = other_jenkins.or_else(|| client_a.name().map(|name| name.to_string()));
+    let client_b = create_client_for_job(Some(&job_b), other_jenkins)?;
+    let job_b = interactive::resolve_job_name(&client_b, Some(&job_b))?;
+
+    let sp = output::spinner("Fetching job configurations...");
+    let config_a = client_a.get_job_config(&job_a)?;
+    let config_b = client_b.get_job_config(&job_b)?;
+    sp.finish_and_clear();
+
+    let label_a = label(&client_a, &job_a);
+    let label_b = label(&client_b, &job_b);
+
+    if config_a == config_b {
+        output::success(&format!("{} and {} have the same configuration", label_a, label_b));
+        return Ok(());
+    }
+
+    let unified = TextDiff::from_lines(&config_a, &config_b)
+        .unified_diff()
+        .context_radius(DIFF_CONTEXT_LINES)
+        .header(&label_a, &label_b)
+        .to_string();
+    output::page(&colorize_diff(&unified));
+
+    Ok(())
+}
+
+/// `host:job` when the host is known, so diffs across hosts stay readable
+fn label(client: &JenkinsClient, job_name: &str) -> String {
+    match client.name() {
+        Some(host) => format!("{}:{}", host, to_display_job_path(job_name)),
+        None => to_display_job_path(job_name),
+    }
+}
+
+/// Color a unified diff the way git does
+fn colorize_diff(diff: &str) -> String {
+    diff.lines()
+        .map(|line| {
+            let styled = if line.starts_with("+++") || line.starts_with("---") {
+                style(line).bold()
+            } else if line.starts_with("@@") {
+                style(line).cyan()
+            } else if line.starts_with('+') {
+                style(line).green()
+            } else if line.starts_with('-') {
+                style(line).red()
+            } else {
+                style(line)
+            };
+            format!("{}\n", styled)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_colorize_diff_keeps_lines() {
+        let a = "<project>\n  <disabled>false</disabled>\n</project>\n";
+        let b = "<project>\n  <disabled>true</disabled>\n</project>\n";
+        let unified = TextDiff::from_lines(a, b).unified_diff().header("prod:deploy", "dev:deploy").to_string();
+        let text = console::strip_ansi_codes(&colorize_diff(&unified)).into_owned();
+
+        assert!(text.starts_with("--- prod:deploy\n+++ dev:deploy\n@@ -1,3 +1,3 @@\n"));
+        assert!(text.contains("-  <disabled>false</disabled>\n+  <disabled>true</disabled>\n"));
+    }
+}
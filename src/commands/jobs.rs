@@ -0,0 +1,149 @@
+use anyhow::Result;
+use console::style;
+use crate::cli::{JobFilter, JobSort};
+use crate::client::SubJobInfo;
+use crate::config::Config;
+use crate::helpers::cache::CacheKind;
+use crate::helpers::formatting::{format_build_duration, format_job_color_styled, format_timestamp, now_millis};
+use crate::helpers::init::create_client_for_job;
+use crate::helpers::url::to_human_job_path;
+use crate::output::{self, Table};
+
+/// Jobs fetched per request while listing a folder
+const PAGE_SIZE: usize = 500;
+
+pub fn execute(folder: Option<String>, sort: JobSort, filter: Option<JobFilter>) -> Result<()> {
+    let client = create_client_for_job(folder.as_deref(), None)?;
+
+    // Resolve alias if the folder was given as one
+    let config = Config::load()?;
+    let folder = folder.map(|name| config.resolve_job_name(&name).map(|(folder, _, _)| folder)).transpose()?;
+
+    let sp = output::spinner("Fetching jobs...");
+    let mut jobs = Vec::new();
+    loop {
+        let page = client.get_jobs_page(folder.as_deref(), jobs.len(), PAGE_SIZE, CacheKind::Status)?;
+        let last_page = page.len() < PAGE_SIZE;
+        jobs.extend(page);
+        if last_page {
+            break;
+        }
+    }
+    sp.finish_and_clear();
+
+    let total = jobs.len();
+    let jobs = sort_and_filter(jobs, sort, filter);
+
+    let location = folder.as_deref().map(to_human_job_path).unwrap_or_else(|| "root".to_string());
+    output::header(&format!("Jobs in {} ({} of {})", location, jobs.len(), total));
+    if jobs.is_empty() {
+        output::dim(if total == 0 { "No jobs found" } else { "No jobs match the filter" });
+        return Ok(());
+    }
+    jobs_table(&jobs, now_millis()).print();
+    Ok(())
+}
+
+/// Keep the jobs matching `filter`, ordered by `sort`
+fn sort_and_filter(mut jobs: Vec<SubJobInfo>, sort: JobSort, filter: Option<JobFilter>) -> Vec<SubJobInfo> {
+    if let Some(filter) = filter {
+        jobs.retain(|job| matches_filter(job.color.as_deref(), filter));
+    }
+    match sort {
+        JobSort::Name => jobs.sort_by_key(|job| job.name.to_lowercase()),
+        JobSort::Status => jobs.sort_by_key(|job| (status_rank(job.color.as_deref()), job.name.to_lowercase())),
+        // Newest first, never-built jobs and folders last
+        JobSort::LastBuild => jobs.sort_by_key(|job| {
+            std::cmp::Reverse(job.last_build.as_ref().and_then(|b| b.timestamp).unwrap_or(i64::MIN))
+        }),
+    }
+    jobs
+}
+
+/// Whether a job's color puts it in the filtered group
+pub fn matches_filter(color: Option<&str>, filter: JobFilter) -> bool {
+    let Some(color) = color else {
+        return false;
+    };
+    match filter {
+        JobFilter::Failing => matches!(color.trim_end_matches("_anime"), "red" | "yellow"),
+        JobFilter::Building => color.ends_with("_anime"),
+        JobFilter::Disabled => color == "disabled",
+    }
+}
+
+/// Worst status first: failed, unstable, aborted, never built, passing, disabled, then folders
+pub fn status_rank(color: Option<&str>) -> u8 {
+    match color.map(|c| c.trim_end_matches("_anime")) {
+        Some("red") => 0,
+        Some("yellow") => 1,
+        Some("aborted") => 2,
+        Some("notbuilt") => 3,
+        Some("blue") => 4,
+        Some("disabled") => 5,
+        Some(_) => 6,
+        None => 7,
+    }
+}
+
+/// One row per job: status, its last build and when it ran
+fn jobs_table(jobs: &[SubJobInfo], now: i64) -> Table {
+    let mut table = Table::new(&["NAME", "STATUS", "BUILD", "STARTED", "DURATION"]);
+    for job in jobs {
+        let status = match job.color.as_deref() {
+            Some(color) => format_job_color_styled(Some(color)),
+            None => style("folder").dim().to_string(),
+        };
+        let build = job.last_build.as_ref();
+        table.add_row(vec![
+            job.name.clone(),
+            status,
+            build.map(|b| format!("#{}", b.number)).unwrap_or_else(|| "-".to_string()),
+            build.and_then(|b| b.timestamp).map(|t| format_timestamp(t, now)).unwrap_or_default(),
+            build
+                .map(|b| format_build_duration(b.building.unwrap_or(false), b.duration, b.timestamp, now))
+                .unwrap_or_default(),
+        ]);
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(name: &str, color: Option<&str>, timestamp: Option<i64>) -> SubJobInfo {
+        serde_json::from_value(serde_json::json!({
+            "name": name,
+            "url": "",
+            "color": color,
+            "lastBuild": timestamp.map(|t| serde_json::json!({"number": 1, "url": "", "timestamp": t, "duration": 1000})),
+        }))
+        .unwrap()
+    }
+
+    fn names(jobs: &[SubJobInfo]) -> Vec<&str> {
+        jobs.iter().map(|job| job.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_sort_and_filter() {
+        let jobs = || {
+            vec![
+                job("web", Some("blue"), Some(300)),
+                job("Api", Some("red_anime"), Some(100)),
+                job("tools", None, None),
+                job("docs", Some("yellow"), Some(200)),
+                job("legacy", Some("disabled"), Some(50)),
+            ]
+        };
+
+        assert_eq!(names(&sort_and_filter(jobs(), JobSort::Name, None)), vec!["Api", "docs", "legacy", "tools", "web"]);
+        assert_eq!(names(&sort_and_filter(jobs(), JobSort::Status, None)), vec!["Api", "docs", "web", "legacy", "tools"]);
+        assert_eq!(names(&sort_and_filter(jobs(), JobSort::LastBuild, None)), vec!["web", "docs", "Api", "legacy", "tools"]);
+
+        assert_eq!(names(&sort_and_filter(jobs(), JobSort::Name, Some(JobFilter::Failing))), vec!["Api", "docs"]);
+        assert_eq!(names(&sort_and_filter(jobs(), JobSort::Name, Some(JobFilter::Building))), vec!["Api"]);
+        assert_eq!(names(&sort_and_filter(jobs(), JobSort::Name, Some(JobFilter::Disabled))), vec!["legacy"]);
+    }
+}
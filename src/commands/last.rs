@@ -0,0 +1,65 @@
+use anyhow::Result;
+use crate::cli::LastAction;
+use crate::commands::{logs, open, status};
+use crate::helpers::formatting::{format_timestamp, now_millis};
+use crate::helpers::init::create_client;
+use crate::helpers::url::to_display_job_path;
+use crate::output;
+use crate::state::State;
+
+pub fn execute(action: Option<LastAction>) -> Result<()> {
+    let mut state = State::load()?;
+    let mut last = state.last_build.clone().ok_or_else(|| {
+        anyhow::anyhow!("No build has been triggered from this CLI yet.\nUse 'jenkins build' to trigger one.")
+    })?;
+
+    let client = create_client(Some(last.jenkins.clone()))?;
+
+    // Builds triggered without --follow only know their queue item until it starts
+    let build_number = match (last.number, last.queue_url.as_deref()) {
+        (Some(num), _) => num,
+        (None, Some(queue_url)) => {
+            let sp = output::spinner("Checking the queue...");
+            let number = client.get_build_number_from_queue(queue_url);
+            sp.finish_and_clear();
+
+            match number {
+                Ok(Some(num)) => {
+                    last.number = Some(num);
+                    state.last_build = Some(last.clone());
+                    state.save()?;
+                    num
+                }
+                Ok(None) => anyhow::bail!(
+                    "The last build of '{}' is still waiting in the queue.",
+                    last.job
+                ),
+                Err(_) => anyhow::bail!(
+                    "The queue item for the last build of '{}' has expired.\nUse 'jenkins status {}' to find the build.",
+                    last.job, last.job
+                ),
+            }
+        }
+        (None, None) => anyhow::bail!(
+            "The build number of the last build of '{}' is unknown.",
+            last.job
+        ),
+    };
+
+    output::dim(&format!(
+        "Last triggered: {}#{} on '{}' ({})",
+        to_display_job_path(&last.job),
+        build_number,
+        last.jenkins,
+        format_timestamp(last.triggered_at, now_millis())
+    ));
+
+    match action.unwrap_or(LastAction::Status) {
+        LastAction::Status => status::show_build(&client, &last.job, build_number),
+        LastAction::Logs { follow } => logs::show_logs(&client, &last.job, build_number, follow),
+        LastAction::Open => {
+            let url = format!("{}/{}", client.get_job_url(&last.job), build_number);
+            open::open_url(&url)
+        }
+    }
+}
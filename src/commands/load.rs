@@ -0,0 +1,97 @@
+use anyhow::Result;
+use crate::client::{LoadSeries, OverallLoad};
+use crate::helpers::init::create_client;
+use crate::output::{self, Table};
+
+/// Windows the load is averaged over, in minutes, like Unix load averages
+const WINDOWS: [usize; 3] = [1, 5, 15];
+
+/// Busy share of the executors from which a queue means the instance is short of capacity
+const SATURATED: f64 = 0.9;
+
+pub fn execute(jenkins: Option<String>) -> Result<()> {
+    let client = create_client(jenkins)?;
+
+    let sp = output::spinner("Fetching load statistics...");
+    let load = client.get_overall_load()?;
+    sp.finish_and_clear();
+
+    output::header(&format!("Load on {}", client.host_url()));
+    load_table(&load).print();
+
+    output::newline();
+    match assess(&load) {
+        Some(Capacity::Saturated) => {
+            output::warning("Executors are nearly all busy and builds are queueing");
+            output::tip("Add agents or executors, or look for stuck builds with 'jenkins running'");
+        }
+        Some(Capacity::Queueing) => {
+            output::info("Builds are queueing although executors are free - check the labels queued builds ask for");
+        }
+        None => output::success("No builds are waiting for an executor"),
+    }
+    Ok(())
+}
+
+/// A capacity problem the last minute's load points at
+#[derive(Debug, PartialEq)]
+enum Capacity {
+    /// Builds wait because the executors are taken
+    Saturated,
+    /// Builds wait with executors to spare, e.g. for a label no online node has
+    Queueing,
+}
+
+fn assess(load: &OverallLoad) -> Option<Capacity> {
+    let minute = |series: &LoadSeries| series.average(WINDOWS[0]).unwrap_or(0.0);
+    let queue = minute(&load.queue_length.min);
+    // Below half a build on average, the queue is just builds passing through
+    if queue < 0.5 {
+        return None;
+    }
+    let total = minute(&load.total_executors.min);
+    let busy = minute(&load.busy_executors.min);
+    if total <= 0.0 || busy >= total * SATURATED {
+        Some(Capacity::Saturated)
+    } else {
+        Some(Capacity::Queueing)
+    }
+}
+
+/// One row per statistic, averaged over each window
+fn load_table(load: &OverallLoad) -> Table {
+    let mut table = Table::new(&["STATISTIC", "1 MIN", "5 MIN", "15 MIN"]).align_right(1).align_right(2).align_right(3);
+    let rows = [
+        ("Queue length", &load.queue_length.min),
+        ("Busy executors", &load.busy_executors.min),
+        ("Total executors", &load.total_executors.min),
+    ];
+    for (label, series) in rows {
+        let mut row = vec![label.to_string()];
+        row.extend(WINDOWS.iter().map(|minutes| {
+            series.average(*minutes).map(|value| format!("{:.1}", value)).unwrap_or_else(|| "-".to_string())
+        }));
+        table.add_row(row);
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::LoadStatistic;
+
+    fn load(queue: f64, busy: f64, total: f64) -> OverallLoad {
+        let statistic = |latest: f64| LoadStatistic { min: LoadSeries { latest: Some(latest), history: vec![] } };
+        OverallLoad { queue_length: statistic(queue), busy_executors: statistic(busy), total_executors: statistic(total) }
+    }
+
+    #[test]
+    fn test_assess() {
+        assert_eq!(assess(&load(0.2, 8.0, 8.0)), None);
+        assert_eq!(assess(&load(3.0, 7.5, 8.0)), Some(Capacity::Saturated));
+        assert_eq!(assess(&load(3.0, 2.0, 8.0)), Some(Capacity::Queueing));
+        // No executors at all can only be a capacity problem
+        assert_eq!(assess(&load(1.0, 0.0, 0.0)), Some(Capacity::Saturated));
+    }
+}
@@ -1,43 +1,95 @@
 use anyhow::Result;
-use crate::helpers::init::create_client_for_job;
+use crate::client::JenkinsClient;
+use crate::helpers::init::{build_number_from_url, create_client_for_job, resolve_build_number};
+use crate::helpers::pipeline_log::{self, LogNode};
+use crate::helpers::stages::{StageTracker, STAGE_POLL_EVERY};
+use crate::helpers::url::to_human_job_path;
 use crate::interactive;
 use crate::output;
 use std::thread;
 use std::time::Duration;
 
-pub fn execute(job_name: Option<String>, build_number: Option<i32>, follow: bool) -> Result<()> {
+/// `fold` holds the stages to keep expanded when the log is shown folded
+/// With `branch`, the logs of the current git branch's job are shown
+pub fn execute(
+    job_name: Option<String>,
+    build_number: Option<i32>,
+    follow: bool,
+    axis: Option<String>,
+    fold: Option<Vec<String>>,
+    branch: bool,
+) -> Result<()> {
     let client = create_client_for_job(job_name.as_deref(), None)?;
+    let build_number = match build_number {
+        Some(num) => Some(num),
+        None => build_number_from_url(job_name.as_deref())?,
+    };
 
     // Resolve the final job name (handle sub-jobs if present)
-    let final_job_name = interactive::resolve_job_name(&client, job_name.as_deref())?;
-
-    let build_num = if let Some(num) = build_number {
-        num
+    let final_job_name = if branch {
+        interactive::resolve_current_branch_job(&client, job_name.as_deref())?
     } else {
-        let job = client.get_job(&final_job_name)?;
-        job.last_build
-            .map(|b| b.number)
-            .ok_or_else(|| anyhow::anyhow!("No builds found for job '{}'", final_job_name))?
+        interactive::resolve_job_name(&client, job_name.as_deref())?
+    };
+    let final_job_name = match axis {
+        Some(axes) => interactive::resolve_matrix_configuration(&client, &final_job_name, &axes)?,
+        None => final_job_name,
     };
 
+    let build_num = resolve_build_number(&client, &final_job_name, build_number)?;
+
+    if let Some(expand) = fold {
+        return show_folded_logs(&client, &final_job_name, build_num, &expand);
+    }
+
+    show_logs(&client, &final_job_name, build_num, follow)
+}
+
+/// Print a pipeline's console log with its stages folded
+fn show_folded_logs(client: &JenkinsClient, final_job_name: &str, build_num: i32, expand: &[String]) -> Result<()> {
+    let sp = output::spinner(&format!("Fetching console log for {}#{}...", to_human_job_path(final_job_name), build_num));
+    let log = client.get_console_log(final_job_name, build_num)?;
+    sp.finish_and_clear();
+
+    let nodes = pipeline_log::parse(&log);
+    if !nodes.iter().any(|node| matches!(node, LogNode::Stage { .. })) {
+        output::dim("No pipeline stages found in the log - showing it unfolded");
+    }
+
+    output::page(&format!("\n{}", pipeline_log::render_folded(&nodes, expand)));
+    Ok(())
+}
+
+/// Print a build's console log, optionally streaming it until the build finishes
+pub fn show_logs(client: &JenkinsClient, final_job_name: &str, build_num: i32, follow: bool) -> Result<()> {
     if !follow {
         // Original behavior - fetch full log once
-        let sp = output::spinner(&format!("Fetching console log for {}#{}...", final_job_name, build_num));
-        let log = client.get_console_log(&final_job_name, build_num)?;
+        let sp = output::spinner(&format!("Fetching console log for {}#{}...", to_human_job_path(final_job_name), build_num));
+        let log = client.get_console_log(final_job_name, build_num)?;
         sp.finish_and_clear();
 
-        output::newline();
-        println!("{}", log);
+        // Long logs open in the pager, like `git log`
+        output::page(&format!("\n{}\n", log));
     } else {
         // Follow mode - stream logs in real-time
-        output::header(&format!("Console Output for {}#{}", final_job_name, build_num));
+        output::header(&format!("Console Output for {}#{}", to_human_job_path(final_job_name), build_num));
         output::newline();
 
         let sp = output::spinner("Streaming build logs...");
         let mut offset = 0;
+        let mut stages = StageTracker::default();
+        let mut polls = 0;
         loop {
-            match client.get_console_log_progressive(&final_job_name, build_num, offset) {
+            match client.get_console_log_progressive(final_job_name, build_num, offset) {
                 Ok((text, new_offset, more_data)) => {
+                    // Announce stages ahead of the new text, which is most likely output of the new stage
+                    if polls % STAGE_POLL_EVERY == 0 {
+                        for stage in stages.poll_started(client, final_job_name, build_num) {
+                            sp.suspend(|| output::stage_marker(&stage.name));
+                        }
+                    }
+                    polls += 1;
+
                     if !text.is_empty() {
                         sp.suspend(|| print!("{}", text));
                     }
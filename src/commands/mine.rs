@@ -0,0 +1,103 @@
+use anyhow::Result;
+use console::style;
+use crate::client::BuildInfo;
+use crate::helpers::formatting::{format_build_result, format_timestamp, now_millis};
+use crate::helpers::init::{create_client, create_client_for_job};
+use crate::helpers::url::{to_display_job_path, to_human_job_path};
+use crate::interactive;
+use crate::output::{self, Table};
+
+pub fn execute(job_name: Option<String>, last: usize) -> Result<()> {
+    // Without a job, scan the whole instance rather than the profile's default job
+    let (client, final_job_name) = match job_name.as_deref() {
+        Some(name) => {
+            let client = create_client_for_job(Some(name), None)?;
+            let final_job_name = interactive::resolve_job_name(&client, Some(name))?;
+            (client, Some(final_job_name))
+        }
+        None => (create_client(None)?, None),
+    };
+
+    let sp = output::spinner(&match &final_job_name {
+        Some(job) => format!("Scanning recent builds of '{}'...", to_human_job_path(job)),
+        None => "Scanning recent builds of all jobs...".to_string(),
+    });
+    let builds = client.get_recent_builds_with_causes(final_job_name.as_deref(), last)?;
+    sp.finish_and_clear();
+
+    let user = client.user().to_string();
+    let mine = builds_started_by(builds, &user);
+
+    if mine.is_empty() {
+        output::info(&format!("No recent builds started by '{}'", user));
+        return Ok(());
+    }
+
+    output::header(&format!("Builds started by {}", user));
+
+    let now = now_millis();
+    let mut table = Table::new(&["BUILD", "RESULT", "STARTED"]);
+    for (job, build) in &mine {
+        let when = build.timestamp.map(|t| format_timestamp(t, now)).unwrap_or_default();
+        table.add_row(vec![
+            format!("{}#{}", to_display_job_path(job), build.number),
+            format_build_result(&build.result),
+            style(when).dim().to_string(),
+        ]);
+    }
+    table.print();
+
+    Ok(())
+}
+
+/// Keep builds started by the user, newest first
+fn builds_started_by(builds: Vec<(String, BuildInfo)>, user: &str) -> Vec<(String, BuildInfo)> {
+    let mut mine: Vec<(String, BuildInfo)> = builds
+        .into_iter()
+        .filter(|(_, build)| build.started_by(user))
+        .collect();
+    mine.sort_by_key(|(_, build)| std::cmp::Reverse(build.timestamp.unwrap_or(0)));
+    mine
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::{BuildAction, BuildCause};
+
+    fn build(number: i32, timestamp: i64, user_id: Option<&str>) -> BuildInfo {
+        BuildInfo {
+            number,
+            url: format!("https://jenkins.example.com/job/app/{}/", number),
+            result: Some("SUCCESS".to_string()),
+            building: Some(false),
+            timestamp: Some(timestamp),
+            duration: Some(1_000),
+            estimated_duration: None,
+            actions: vec![BuildAction {
+                causes: Some(vec![BuildCause {
+                    short_description: None,
+                    user_id: user_id.map(|u| u.to_string()),
+                    user_name: None,
+                    upstream_project: None,
+                    upstream_build: None,
+                }]),
+                ..Default::default()
+            }],
+        }
+    }
+
+    #[test]
+    fn test_builds_started_by() {
+        let builds = vec![
+            ("api".to_string(), build(1, 100, Some("jdoe"))),
+            ("api".to_string(), build(2, 300, None)),
+            ("web".to_string(), build(9, 200, Some("jdoe"))),
+            ("web".to_string(), build(10, 400, Some("alice"))),
+        ];
+
+        let mine = builds_started_by(builds, "jdoe");
+        let ids: Vec<(&str, i32)> = mine.iter().map(|(job, b)| (job.as_str(), b.number)).collect();
+        assert_eq!(ids, vec![("web", 9), ("api", 1)]);
+    }
+}
@@ -5,3 +5,39 @@ pub mod open;
 pub mod config;
 pub mod completion;
 pub mod alias;
+pub mod env;
+pub mod health;
+pub mod coverage;
+pub mod warnings;
+pub mod test_report;
+pub mod stats;
+pub mod monitor;
+pub mod shell;
+pub mod last;
+pub mod api;
+pub mod mine;
+pub mod running;
+pub mod replay;
+pub mod restart_from_stage;
+pub mod timeline;
+pub mod artifacts;
+pub mod export_build;
+pub mod job;
+pub mod backup;
+pub mod restore;
+pub mod casc;
+pub mod seed;
+pub mod script;
+pub mod node;
+pub mod plugins;
+pub mod doctor;
+pub mod ping;
+pub mod load;
+pub mod capacity;
+pub mod recent;
+pub mod history;
+pub mod dash;
+pub mod jobs;
+pub mod failing;
+pub mod hook;
+pub mod autobuild;
@@ -0,0 +1,418 @@
+use anyhow::{Context, Result};
+use console::{pad_str, style, Alignment, Term};
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+use crate::client::{JenkinsClient, JobInfo};
+use crate::config::Config;
+use crate::helpers::formatting::{
+    format_duration, format_job_color, format_job_color_styled as format_color, format_local_datetime, now_millis,
+    progress_bar,
+};
+use crate::helpers::init::{create_client, prompt_jenkins_selection};
+use crate::helpers::interrupt;
+use crate::helpers::notify::{self, Digest, StateChange};
+use crate::output;
+
+/// Status shown for a job whose summary couldn't be fetched
+const UNREACHABLE: &str = "Unreachable";
+
+/// Failed polls in a row before the daemon reports a job as unreachable, so one dropped request isn't two alerts
+const UNREACHABLE_AFTER: u32 = 2;
+
+/// A job being watched, with the Jenkins host it lives on
+struct MonitorTarget {
+    label: String,
+    job_name: String,
+    jenkins: String,
+}
+
+/// Where daemon digests go: stdout unless a file or webhook is given
+struct DigestSink {
+    file: Option<PathBuf>,
+    webhook: Option<String>,
+}
+
+/// A job's status at one poll of the daemon, with its latest build
+#[derive(Debug, Clone, PartialEq)]
+struct JobState {
+    status: String,
+    build: Option<i32>,
+    url: Option<String>,
+}
+
+pub fn execute(
+    mut jobs: Vec<String>,
+    view: Option<String>,
+    interval: Duration,
+    daemon: bool,
+    digest_file: Option<PathBuf>,
+    webhook: Option<String>,
+) -> Result<()> {
+    let config = Config::load()?;
+
+    // The daemon falls back to everything on the dashboard
+    if daemon && jobs.is_empty() && view.is_none() {
+        let mut aliases: Vec<&String> = config.job_aliases.keys().collect();
+        aliases.sort();
+        jobs = aliases.into_iter().cloned().collect();
+        jobs.extend(config.pinned_jobs.iter().cloned());
+    }
+    if jobs.is_empty() && view.is_none() {
+        anyhow::bail!("Specify at least one job or alias to monitor, or use --view <name>");
+    }
+
+    let mut default_jenkins: Option<String> = None;
+    let mut targets = Vec::new();
+
+    for name in &jobs {
+        let (job_name, _, alias_jenkins) = config.resolve_job_name(name)?;
+        let jenkins = match alias_jenkins {
+            Some(j) => j,
+            None => default_host(&mut default_jenkins)?,
+        };
+        targets.push(MonitorTarget { label: name.clone(), job_name, jenkins });
+    }
+
+    let mut clients: HashMap<String, JenkinsClient> = HashMap::new();
+
+    if let Some(view_name) = &view {
+        let jenkins = default_host(&mut default_jenkins)?;
+        let client = create_client(Some(jenkins.clone()))?;
+        let sp = output::spinner(&format!("Loading view '{}'...", view_name));
+        let view_jobs = client.get_view_jobs(view_name)?;
+        sp.finish_and_clear();
+        for job in view_jobs {
+            targets.push(MonitorTarget { label: job.name.clone(), job_name: job.name, jenkins: jenkins.clone() });
+        }
+        clients.insert(jenkins, client);
+    }
+
+    if targets.is_empty() {
+        anyhow::bail!("No jobs to monitor");
+    }
+
+    for target in &targets {
+        if !clients.contains_key(&target.jenkins) {
+            clients.insert(target.jenkins.clone(), create_client(Some(target.jenkins.clone()))?);
+        }
+    }
+
+    if daemon {
+        return run_daemon(&targets, &clients, interval, &DigestSink { file: digest_file, webhook });
+    }
+
+    let term = Term::stdout();
+    let label_width = targets.iter().map(|t| t.label.chars().count()).max().unwrap_or(0);
+    let mut previous: HashMap<String, String> = HashMap::new();
+
+    loop {
+        let rows: Vec<(String, Result<JobInfo>)> = targets
+            .iter()
+            .map(|t| (t.label.clone(), clients[&t.jenkins].get_job_summary(&t.job_name)))
+            .collect();
+
+        term.clear_screen()?;
+        output::header(&format!("Monitoring {} job(s)", targets.len()));
+        output::newline();
+
+        let now = now_millis();
+        for (label, result) in rows {
+            let label_cell = pad_str(&label, label_width, Alignment::Left, None).to_string();
+            match result {
+                Ok(job) => {
+                    let state = state_key(&job);
+                    let changed = previous
+                        .get(&label)
+                        .is_some_and(|prev| *prev != state);
+                    let marker = if changed {
+                        style("● changed").yellow().bold().to_string()
+                    } else {
+                        String::new()
+                    };
+                    println!("  {}  {}  {}", style(label_cell).bold(), describe_job(&job, now), marker);
+                    previous.insert(label, state);
+                }
+                Err(e) => {
+                    println!("  {}  {}", style(label_cell).bold(), style(format!("error: {}", e)).red());
+                }
+            }
+        }
+
+        output::newline();
+        output::dim(&format!("Refreshing every {} - press Ctrl+C to exit", format_duration(interval.as_millis() as i64)));
+        thread::sleep(interval);
+    }
+}
+
+/// Poll without a terminal UI, emitting a digest only when some job's status changed since the last poll
+/// The first poll just records where every job stands
+fn run_daemon(
+    targets: &[MonitorTarget],
+    clients: &HashMap<String, JenkinsClient>,
+    interval: Duration,
+    sink: &DigestSink,
+) -> Result<()> {
+    output::info(&format!(
+        "Monitoring {} job(s) every {} - press Ctrl+C to stop",
+        targets.len(),
+        format_duration(interval.as_millis() as i64)
+    ));
+    let _ctrl_c = interrupt::catch_ctrl_c();
+
+    let mut previous: Option<Vec<JobState>> = None;
+    let mut failures = vec![0; targets.len()];
+    loop {
+        let states: Vec<JobState> = targets
+            .iter()
+            .zip(&mut failures)
+            .enumerate()
+            .map(|(i, (t, failures))| {
+                let summary = clients[&t.jenkins].get_job_summary(&t.job_name);
+                settled_state(previous.as_ref().map(|states| &states[i]), &summary, failures)
+            })
+            .collect();
+        if let Some(previous) = &previous {
+            let changes = state_changes(targets, previous, &states);
+            if !changes.is_empty() {
+                // A sink that fails shouldn't stop the monitor, nor keep the digest from the other sinks
+                for e in sink.emit(&Digest::new(now_millis(), changes)) {
+                    output::warning(&format!("{:#}", e));
+                }
+            }
+        }
+        previous = Some(states);
+
+        if interrupt::sleep(interval) {
+            return Ok(());
+        }
+    }
+}
+
+impl DigestSink {
+    /// Send the digest to every sink, returning what went wrong with each one that failed
+    fn emit(&self, digest: &Digest) -> Vec<anyhow::Error> {
+        let mut text = format!("[{}] {} job(s) changed state\n", format_local_datetime(digest.timestamp), digest.changes.len());
+        for change in &digest.changes {
+            text.push_str(&format!("  {}\n", change.line()));
+        }
+
+        let mut errors = Vec::new();
+        if let Some(path) = &self.file
+            && let Err(e) = append_digest(path, &text)
+        {
+            errors.push(e);
+        }
+        if let Some(webhook) = &self.webhook
+            && let Err(e) = notify::send_digest(webhook, digest)
+        {
+            errors.push(e);
+        }
+        if self.file.is_none() && self.webhook.is_none() {
+            print!("{}", text);
+            std::io::stdout().flush().ok();
+        }
+        errors
+    }
+}
+
+fn append_digest(path: &Path, text: &str) -> Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    file.write_all(text.as_bytes()).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// The status a job is in for the daemon's purposes; a build in progress keeps its job's last result
+fn job_state(summary: &Result<JobInfo>) -> JobState {
+    match summary {
+        Ok(job) => JobState {
+            status: format_job_color(job.color.as_deref().map(|c| c.trim_end_matches("_anime"))),
+            build: job.last_build.as_ref().map(|b| b.number),
+            url: job.last_build.as_ref().map(|b| b.url.clone()).filter(|url| !url.is_empty()),
+        },
+        Err(_) => JobState { status: UNREACHABLE.to_string(), build: None, url: None },
+    }
+}
+
+/// The state to report for a poll: a job that stops answering keeps its last known state
+/// until it has failed UNREACHABLE_AFTER polls in a row
+fn settled_state(previous: Option<&JobState>, summary: &Result<JobInfo>, failures: &mut u32) -> JobState {
+    *failures = if summary.is_ok() { 0 } else { *failures + 1 };
+    match previous {
+        Some(previous) if summary.is_err() && *failures < UNREACHABLE_AFTER => previous.clone(),
+        _ => job_state(summary),
+    }
+}
+
+/// Jobs whose status differs between two polls; new builds with the same result aren't changes
+fn state_changes(targets: &[MonitorTarget], before: &[JobState], after: &[JobState]) -> Vec<StateChange> {
+    targets
+        .iter()
+        .zip(before.iter().zip(after))
+        .filter(|(_, (before, after))| before.status != after.status)
+        .map(|(target, (before, after))| StateChange {
+            job: target.label.clone(),
+            jenkins: target.jenkins.clone(),
+            from: before.status.clone(),
+            to: after.status.clone(),
+            build: after.build,
+            url: after.url.clone(),
+        })
+        .collect()
+}
+
+/// Prompt for the host used by non-aliased jobs (only once)
+fn default_host(default_jenkins: &mut Option<String>) -> Result<String> {
+    if default_jenkins.is_none() {
+        *default_jenkins = prompt_jenkins_selection()?;
+    }
+    default_jenkins
+        .clone()
+        .ok_or_else(|| anyhow::anyhow!("No Jenkins host specified"))
+}
+
+/// Identify the job's visible state so that transitions can be highlighted
+fn state_key(job: &JobInfo) -> String {
+    let build = job.last_build.as_ref().map(|b| b.number).unwrap_or(0);
+    format!("{}#{}", job.color.as_deref().unwrap_or(""), build)
+}
+
+/// Compact one-line description of a job's current state
+fn describe_job(job: &JobInfo, now_ms: i64) -> String {
+    let status = pad_str(&format_color(job.color.as_deref()), 18, Alignment::Left, None).to_string();
+
+    let Some(build) = &job.last_build else {
+        return format!("{} {}", status, style("no builds").dim());
+    };
+
+    match build.progress_percent(now_ms) {
+        Some(percent) => format!(
+            "{} #{} {} {}%",
+            status,
+            build.number,
+            style(progress_bar(percent, 10)).cyan(),
+            percent
+        ),
+        None if build.building.unwrap_or(false) => format!("{} #{} running", status, build.number),
+        None => format!("{} #{}", status, build.number),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::BuildInfo;
+
+    fn job(color: &str, build: Option<BuildInfo>) -> JobInfo {
+        JobInfo {
+            class: None,
+            name: Some("app".to_string()),
+            url: None,
+            color: Some(color.to_string()),
+            buildable: None,
+            last_build: build,
+            jobs: None,
+            property: None,
+            health_report: None,
+            active_configurations: None,
+        }
+    }
+
+    fn build(number: i32, building: bool) -> BuildInfo {
+        BuildInfo {
+            number,
+            url: String::new(),
+            result: None,
+            building: Some(building),
+            timestamp: Some(0),
+            duration: None,
+            estimated_duration: Some(1_000),
+            actions: vec![],
+        }
+    }
+
+    #[test]
+    fn test_state_key() {
+        assert_eq!(state_key(&job("blue", Some(build(3, false)))), "blue#3");
+        assert_eq!(state_key(&job("red", None)), "red#0");
+        assert_ne!(
+            state_key(&job("blue", Some(build(3, false)))),
+            state_key(&job("blue_anime", Some(build(4, true))))
+        );
+    }
+
+    #[test]
+    fn test_state_changes() {
+        let targets: Vec<MonitorTarget> = ["api", "web", "docs"]
+            .iter()
+            .map(|name| MonitorTarget { label: name.to_string(), job_name: name.to_string(), jenkins: "prod".to_string() })
+            .collect();
+        let before = vec![
+            job_state(&Ok(job("blue", Some(build(3, false))))),
+            job_state(&Ok(job("blue", Some(build(7, false))))),
+            job_state(&Err(anyhow::anyhow!("HTTP 503"))),
+        ];
+        // api starts a build and fails, web passes again on a new build, docs comes back
+        let after = vec![
+            job_state(&Ok(job("red_anime", Some(build(5, true))))),
+            job_state(&Ok(job("blue", Some(build(8, false))))),
+            job_state(&Ok(job("notbuilt", None))),
+        ];
+        assert_eq!(before[0].status, "Success");
+        assert_eq!(after[0].status, "Failed");
+
+        let changes: Vec<String> = state_changes(&targets, &before, &after).iter().map(StateChange::line).collect();
+        assert_eq!(changes, vec!["api: Success → Failed (#5)", "docs: Unreachable → Not Built"]);
+        assert!(state_changes(&targets, &after, &after).is_empty());
+    }
+
+    #[test]
+    fn test_settled_state_needs_consecutive_failures() {
+        let success = job_state(&Ok(job("blue", Some(build(3, false)))));
+        let mut failures = 0;
+
+        let once = settled_state(Some(&success), &Err(anyhow::anyhow!("timeout")), &mut failures);
+        assert_eq!(once, success);
+        let twice = settled_state(Some(&once), &Err(anyhow::anyhow!("timeout")), &mut failures);
+        assert_eq!(twice.status, UNREACHABLE);
+
+        // Answering again resets the count
+        settled_state(Some(&twice), &Ok(job("blue", Some(build(3, false)))), &mut failures);
+        assert_eq!(failures, 0);
+        assert_eq!(settled_state(Some(&success), &Err(anyhow::anyhow!("timeout")), &mut failures), success);
+    }
+
+    #[test]
+    fn test_digest_sinks_fail_independently() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("digest.log");
+        // Nothing listens on port 9, so the webhook fails but the file is still written
+        let sink = DigestSink { file: Some(path.clone()), webhook: Some("http://127.0.0.1:9/hook".to_string()) };
+        let change = StateChange {
+            job: "api".to_string(),
+            jenkins: "prod".to_string(),
+            from: "Success".to_string(),
+            to: "Failed".to_string(),
+            build: Some(5),
+            url: None,
+        };
+
+        let errors = sink.emit(&Digest::new(0, vec![change]));
+        assert_eq!(errors.len(), 1);
+        assert!(std::fs::read_to_string(&path).unwrap().contains("api: Success → Failed (#5)"));
+    }
+
+    #[test]
+    fn test_describe_job() {
+        let plain = |job: &JobInfo, now| console::strip_ansi_codes(&describe_job(job, now)).into_owned();
+        assert!(plain(&job("blue", Some(build(3, false))), 0).ends_with("#3"));
+        assert!(plain(&job("blue_anime", Some(build(4, true))), 500).contains("#4 ▰▰▰▰▰▱▱▱▱▱ 50%"));
+        assert!(plain(&job("notbuilt", None), 0).contains("no builds"));
+    }
+}
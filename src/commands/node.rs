@@ -0,0 +1,289 @@
+use anyhow::Result;
+use console::style;
+use crate::client::NodeInfo;
+use crate::helpers::errors::ErrorKind;
+use crate::helpers::formatting::format_duration;
+use crate::helpers::history::{self, Action};
+use crate::helpers::init::create_client;
+use crate::helpers::prompt;
+use crate::output::{self, Table};
+use indicatif::HumanBytes;
+use inquire::Confirm;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Agent root directory used when --remote-fs isn't given
+pub const DEFAULT_REMOTE_FS: &str = "/home/jenkins/agent";
+
+/// Free space below which Jenkins' own disk and temp space monitors take a node offline
+pub const DEFAULT_MIN_SPACE: &str = "1GB";
+
+/// Response times and clock differences beyond this are flagged; 5s is where Jenkins calls a clock difference dangerous
+pub const DEFAULT_MAX_DELAY: &str = "5s";
+
+/// Limits a node's monitor readings are checked against
+pub struct NodeThresholds {
+    pub min_disk: u64,
+    pub min_temp: u64,
+    /// Not checked unless given, since many agents run without swap
+    pub min_swap: Option<u64>,
+    pub max_response: Duration,
+    pub max_clock_skew: Duration,
+}
+
+pub fn execute_list(thresholds: NodeThresholds) -> Result<()> {
+    let client = create_client(None)?;
+
+    let sp = output::spinner("Fetching nodes...");
+    let nodes = client.get_nodes()?;
+    sp.finish_and_clear();
+
+    output::header(&format!("Nodes ({})", nodes.len()));
+    let problems: Vec<Vec<String>> = nodes.iter().map(|node| node_problems(node, &thresholds)).collect();
+    nodes_table(&nodes, &problems).print();
+
+    output::newline();
+    let mut flagged = 0;
+    for (node, problems) in nodes.iter().zip(&problems) {
+        if !problems.is_empty() {
+            flagged += 1;
+            output::warning(&format!("{}: {}", node.display_name, problems.join(", ")));
+        }
+    }
+    if flagged == 0 {
+        output::success("No node is below the thresholds");
+    }
+    Ok(())
+}
+
+pub fn execute_create(name: String, executors: u32, labels: Vec<String>, remote_fs: String) -> Result<()> {
+    if executors == 0 {
+        anyhow::bail!("An agent needs at least one executor");
+    }
+
+    let client = create_client(None)?;
+
+    let sp = output::spinner(&format!("Creating agent '{}'...", name));
+    let labels = labels.join(" ");
+    let created = client.create_inbound_agent(&name, executors, &labels, &remote_fs);
+    let settings = BTreeMap::from([
+        ("executors".to_string(), executors.to_string()),
+        ("labels".to_string(), labels),
+        ("remote_fs".to_string(), remote_fs.clone()),
+    ]);
+    history::record(&client, &name, Action::CreateNode, settings, &created);
+    created?;
+    output::finish_spinner_success(sp, &format!("Agent created => {}", client.get_node_url(&name)));
+
+    // The agent is useless until it connects, so show how right away
+    match client.get_agent_secret(&name) {
+        Ok(secret) => {
+            output::header("Connect the agent");
+            println!("{}", launch_command(client.host_url(), &name, &secret, &remote_fs));
+        }
+        Err(e) => {
+            output::warning(&format!("Could not read the agent secret: {:#}", e));
+            output::tip(&format!("The launch command is shown on {}", client.get_node_url(&name)));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn execute_delete(name: String, yes: bool) -> Result<()> {
+    let client = create_client(None)?;
+
+    if !yes {
+        prompt::ensure_interactive("confirming the deletion", "Pass --yes to delete the agent without confirming.")?;
+        let delete = Confirm::new(&format!("Delete agent '{}'?", name))
+            .with_default(false)
+            .prompt()
+            .unwrap_or(false);
+        if !delete {
+            return Err(ErrorKind::Cancelled.error("Deletion cancelled"));
+        }
+    }
+
+    let sp = output::spinner(&format!("Deleting agent '{}'...", name));
+    let deleted = client.delete_node(&name);
+    history::record(&client, &name, Action::DeleteNode, BTreeMap::new(), &deleted);
+    deleted?;
+    output::finish_spinner_success(sp, &format!("Agent '{}' deleted", name));
+
+    Ok(())
+}
+
+pub fn execute_logs(name: String) -> Result<()> {
+    let client = create_client(None)?;
+
+    let sp = output::spinner(&format!("Fetching the log of agent '{}'...", name));
+    let log = client.get_node_log(&name)?;
+    sp.finish_and_clear();
+
+    if log.trim().is_empty() {
+        // The built-in node and agents that were never launched have no launch log
+        output::info(&format!("Agent '{}' has no launch log", name));
+        return Ok(());
+    }
+
+    output::page(&format!("\n{}\n", log.trim_end()));
+    Ok(())
+}
+
+/// Readings past the thresholds, e.g. `disk space 512.00 MiB (below 1.00 GiB)`
+/// Offline nodes report no readings, so there's nothing to check on them
+fn node_problems(node: &NodeInfo, thresholds: &NodeThresholds) -> Vec<String> {
+    let data = &node.monitor_data;
+    let mut problems = Vec::new();
+
+    let spaces = [("disk space", &data.disk_space, thresholds.min_disk), ("temp space", &data.temp_space, thresholds.min_temp)];
+    for (what, space, min) in spaces {
+        if let Some(space) = space
+            && space.size >= 0
+            && (space.size as u64) < min
+        {
+            problems.push(format!("{} {} (below {})", what, HumanBytes(space.size as u64), HumanBytes(min)));
+        }
+    }
+    if let (Some(min), Some(swap)) = (thresholds.min_swap, &data.swap_space)
+        && swap.total_swap_space > 0
+        && swap.available_swap_space >= 0
+        && (swap.available_swap_space as u64) < min
+    {
+        problems.push(format!("free swap {} (below {})", HumanBytes(swap.available_swap_space as u64), HumanBytes(min)));
+    }
+    if let Some(response) = &data.response_time
+        && response.average > thresholds.max_response.as_millis() as i64
+    {
+        problems.push(format!(
+            "response time {} (over {})",
+            format_duration(response.average),
+            format_duration(thresholds.max_response.as_millis() as i64)
+        ));
+    }
+    if let Some(clock) = &data.clock
+        && clock.diff.abs() > thresholds.max_clock_skew.as_millis() as i64
+    {
+        problems.push(format!(
+            "clock {} (more than {} off)",
+            format_clock_diff(clock.diff),
+            format_duration(thresholds.max_clock_skew.as_millis() as i64)
+        ));
+    }
+    problems
+}
+
+/// How far a node's clock is off, e.g. `7s ahead`
+fn format_clock_diff(diff: i64) -> String {
+    match diff {
+        0 => "in sync".to_string(),
+        d if d > 0 => format!("{} ahead", format_duration(d)),
+        d => format!("{} behind", format_duration(-d)),
+    }
+}
+
+/// One row per node with its latest monitor readings; flagged nodes are marked
+fn nodes_table(nodes: &[NodeInfo], problems: &[Vec<String>]) -> Table {
+    let mut table = Table::new(&["NODE", "STATUS", "EXECUTORS", "DISK", "TEMP", "SWAP", "RESPONSE", "CLOCK"])
+        .align_right(2)
+        .align_right(3)
+        .align_right(4)
+        .align_right(5)
+        .align_right(6);
+    let space = |size: i64| if size >= 0 { HumanBytes(size as u64).to_string() } else { "-".to_string() };
+    for (node, problems) in nodes.iter().zip(problems) {
+        let data = &node.monitor_data;
+        let name = if problems.is_empty() {
+            node.display_name.clone()
+        } else {
+            style(format!("⚠ {}", node.display_name)).yellow().to_string()
+        };
+        let status = match (node.offline, node.temporarily_offline) {
+            (_, true) => style("Taken offline").dim().to_string(),
+            (true, false) => style("Offline").red().to_string(),
+            (false, false) => style("Online").green().to_string(),
+        };
+        let swap = match &data.swap_space {
+            Some(swap) if swap.total_swap_space == 0 => "none".to_string(),
+            Some(swap) => space(swap.available_swap_space),
+            None => "-".to_string(),
+        };
+        let dash = || "-".to_string();
+        table.add_row(vec![
+            name,
+            status,
+            node.num_executors.to_string(),
+            data.disk_space.as_ref().map(|s| space(s.size)).unwrap_or_else(dash),
+            data.temp_space.as_ref().map(|s| space(s.size)).unwrap_or_else(dash),
+            swap,
+            data.response_time.as_ref().map(|r| format_duration(r.average)).unwrap_or_else(dash),
+            data.clock.as_ref().map(|c| format_clock_diff(c.diff)).unwrap_or_else(dash),
+        ]);
+    }
+    table
+}
+
+/// Shell commands that fetch agent.jar and connect the agent to the controller
+fn launch_command(host_url: &str, name: &str, secret: &str, remote_fs: &str) -> String {
+    format!(
+        "curl -sO {host}/jnlpJars/agent.jar\njava -jar agent.jar -url {host}/ -secret {secret} -name \"{name}\" -workDir \"{remote_fs}\"",
+        host = host_url,
+        secret = secret,
+        name = name,
+        remote_fs = remote_fs
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_node_problems() {
+        let thresholds = NodeThresholds {
+            min_disk: 1 << 30,
+            min_temp: 1 << 30,
+            min_swap: None,
+            max_response: Duration::from_secs(5),
+            max_clock_skew: Duration::from_secs(5),
+        };
+        let node = |monitor_data: serde_json::Value| -> NodeInfo {
+            serde_json::from_value(serde_json::json!({ "displayName": "agent-1", "monitorData": monitor_data })).unwrap()
+        };
+
+        let healthy = node(serde_json::json!({
+            "hudson.node_monitors.DiskSpaceMonitor": { "size": 10_i64 << 30 },
+            "hudson.node_monitors.SwapSpaceMonitor": { "availableSwapSpace": 0, "totalSwapSpace": 1 << 30 },
+            "hudson.node_monitors.ClockMonitor": { "diff": -4_000 }
+        }));
+        assert!(node_problems(&healthy, &thresholds).is_empty());
+
+        let sick = node(serde_json::json!({
+            "hudson.node_monitors.DiskSpaceMonitor": { "size": 512 << 20 },
+            "hudson.node_monitors.TemporarySpaceMonitor": { "size": -1 },
+            "hudson.node_monitors.ResponseTimeMonitor": { "average": 7_000 },
+            "hudson.node_monitors.ClockMonitor": { "diff": 12_000 }
+        }));
+        assert_eq!(
+            node_problems(&sick, &thresholds),
+            vec![
+                "disk space 512.00 MiB (below 1.00 GiB)",
+                "response time 7s (over 5s)",
+                "clock 12s ahead (more than 5s off)",
+            ]
+        );
+
+        // Swap is only checked when asked for
+        let swap = NodeThresholds { min_swap: Some(1), ..thresholds };
+        assert_eq!(node_problems(&healthy, &swap), vec!["free swap 0 B (below 1 B)"]);
+    }
+
+    #[test]
+    fn test_launch_command() {
+        assert_eq!(
+            launch_command("https://jenkins.example.com", "linux-1", "8f2a4c", "/home/jenkins/agent"),
+            "curl -sO https://jenkins.example.com/jnlpJars/agent.jar\n\
+             java -jar agent.jar -url https://jenkins.example.com/ -secret 8f2a4c -name \"linux-1\" -workDir \"/home/jenkins/agent\""
+        );
+    }
+}
@@ -1,14 +1,30 @@
 use anyhow::Result;
-use crate::helpers::init::create_client_for_job;
+use crate::helpers::init::{build_number_from_url, create_client_for_job};
 use crate::interactive;
 use crate::output;
-use std::process::Command;
+use std::io::Write;
+use std::process::{Command, Stdio};
 
-pub fn execute(job_name: Option<String>, build_number: Option<i32>) -> Result<()> {
+/// With `branch`, the latest build of the current git branch's job is opened
+pub fn execute(job_name: Option<String>, build_number: Option<i32>, print: bool, copy: bool, branch: bool) -> Result<()> {
     let client = create_client_for_job(job_name.as_deref(), None)?;
 
-    // Resolve the job name (allow stopping at any level for open command)
-    let final_job_name = interactive::resolve_job_name_for_open(&client, job_name.as_deref())?;
+    let (final_job_name, build_number) = if branch {
+        let branch_job = interactive::resolve_current_branch_job(&client, job_name.as_deref())?;
+        // The branch's page until it has a build
+        let latest = match build_number {
+            Some(num) => Some(num),
+            None => client.get_job(&branch_job)?.last_build.map(|b| b.number),
+        };
+        (branch_job, latest)
+    } else {
+        let build_number = match build_number {
+            Some(num) => Some(num),
+            None => build_number_from_url(job_name.as_deref())?,
+        };
+        // Resolve the job name (allow stopping at any level for open command)
+        (interactive::resolve_job_name_for_open(&client, job_name.as_deref())?, build_number)
+    };
 
     let url = if let Some(build_num) = build_number {
         format!("{}/{}", client.get_job_url(&final_job_name), build_num)
@@ -16,18 +32,119 @@ pub fn execute(job_name: Option<String>, build_number: Option<i32>) -> Result<()
         client.get_job_url(&final_job_name)
     };
 
+    if !print && !copy {
+        return open_url(&url);
+    }
+
+    if print {
+        // Plain output so the URL can be piped into other tools
+        println!("{}", url);
+    }
+
+    if copy {
+        copy_to_clipboard(&url)?;
+        output::success("URL copied to clipboard");
+    }
+
+    Ok(())
+}
+
+/// True when running under Windows Subsystem for Linux
+#[cfg(target_os = "linux")]
+fn is_wsl() -> bool {
+    std::fs::read_to_string("/proc/version")
+        .map(|version| is_wsl_kernel(&version))
+        .unwrap_or(false)
+}
+
+/// WSL kernels identify themselves as Microsoft builds in /proc/version
+#[cfg(any(target_os = "linux", test))]
+fn is_wsl_kernel(version: &str) -> bool {
+    let version = version.to_lowercase();
+    version.contains("microsoft") || version.contains("wsl")
+}
+
+/// Open the URL in the Windows browser from inside WSL
+#[cfg(target_os = "linux")]
+fn open_url_wsl(url: &str) -> Result<()> {
+    // wslview (from wslu) handles the hand-off cleanly when installed
+    if Command::new("wslview").arg(url).spawn().is_ok() {
+        return Ok(());
+    }
+
+    // Explorer takes the URL as one argument; `cmd.exe /C start` would cut it at the first `&`
+    Command::new("explorer.exe")
+        .arg(url)
+        .spawn()
+        .map_err(|e| anyhow::anyhow!("Failed to open browser from WSL (install wslu for wslview): {}", e))?;
+
+    Ok(())
+}
+
+/// Copy text to the system clipboard using the platform's clipboard tool
+fn copy_to_clipboard(text: &str) -> Result<()> {
+    #[cfg(target_os = "macos")]
+    let candidates: &[(&str, &[&str])] = &[("pbcopy", &[])];
+
+    #[cfg(target_os = "windows")]
+    let candidates: &[(&str, &[&str])] = &[("clip", &[])];
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let candidates: &[(&str, &[&str])] = &[
+        ("wl-copy", &[]),
+        ("xclip", &["-selection", "clipboard"]),
+        ("xsel", &["--clipboard", "--input"]),
+        ("clip.exe", &[]),
+    ];
+
+    for (program, args) in candidates {
+        let Ok(mut child) = Command::new(program).args(*args).stdin(Stdio::piped()).spawn() else {
+            continue;
+        };
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes())?;
+        }
+
+        if child.wait()?.success() {
+            return Ok(());
+        }
+    }
+
+    let tools: Vec<&str> = candidates.iter().map(|(program, _)| *program).collect();
+    anyhow::bail!("No clipboard tool found (tried {})", tools.join(", "))
+}
+
+/// Open a URL in the default browser
+pub fn open_url(url: &str) -> Result<()> {
     output::info(&format!("Opening {}...", url));
 
     #[cfg(target_os = "macos")]
-    Command::new("open").arg(&url).spawn()?;
+    Command::new("open").arg(url).spawn()?;
 
     #[cfg(target_os = "linux")]
-    Command::new("xdg-open").arg(&url).spawn()?;
+    if is_wsl() {
+        open_url_wsl(url)?;
+    } else {
+        Command::new("xdg-open").arg(url).spawn()?;
+    }
 
     #[cfg(target_os = "windows")]
-    Command::new("cmd").args(&["/C", "start", &url]).spawn()?;
+    Command::new("cmd").args(&["/C", "start", url]).spawn()?;
 
     output::success("Browser opened successfully!");
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_wsl_kernel() {
+        assert!(is_wsl_kernel("Linux version 5.15.90.1-microsoft-standard-WSL2 (oe-user@oe-host)"));
+        assert!(is_wsl_kernel("Linux version 4.4.0-19041-Microsoft (Microsoft@Microsoft.com)"));
+        assert!(!is_wsl_kernel("Linux version 6.5.0-14-generic (buildd@lcy02-amd64-110)"));
+    }
+}
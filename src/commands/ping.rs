@@ -0,0 +1,155 @@
+use anyhow::Result;
+use crate::helpers::init::create_client;
+use crate::helpers::interrupt;
+use crate::output;
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_PING_COUNT: usize = 5;
+
+/// Pause between rounds, so the probe itself doesn't load the controller
+const PING_INTERVAL: Duration = Duration::from_secs(1);
+
+/// An API this much slower than a static file points at the controller rather than the network
+const SLOW_API_FACTOR: u32 = 4;
+const SLOW_API_MIN_GAP: Duration = Duration::from_millis(300);
+const SLOW_NETWORK: Duration = Duration::from_millis(500);
+
+/// Min, average and max of a set of timings
+#[derive(Debug, PartialEq)]
+struct Stats {
+    min: Duration,
+    avg: Duration,
+    max: Duration,
+}
+
+pub fn execute(jenkins: Option<String>, count: usize) -> Result<()> {
+    if count == 0 {
+        anyhow::bail!("--count must be at least 1");
+    }
+
+    let client = create_client(jenkins)?;
+    output::header(&format!("Pinging {} ({} round(s), Ctrl+C to stop)", client.host_url(), count));
+
+    // Each round times the root API (authentication + Jenkins work) and a static file (network only)
+    let _ctrl_c = interrupt::catch_ctrl_c();
+    let mut api_times = Vec::new();
+    let mut static_times = Vec::new();
+    let mut quieting_down = None;
+    let mut rounds = 0;
+    for round in 1..=count {
+        if round > 1 && interrupt::sleep(PING_INTERVAL) {
+            break;
+        }
+        rounds += 1;
+
+        let started = Instant::now();
+        let api = client.get_quieting_down();
+        let api_time = started.elapsed();
+
+        let started = Instant::now();
+        let static_file = client.fetch_static_file();
+        let static_time = started.elapsed();
+
+        let api_text = match api {
+            Ok(quieting) => {
+                quieting_down = Some(quieting);
+                api_times.push(api_time);
+                millis(api_time)
+            }
+            Err(e) => format!("failed ({:#})", e),
+        };
+        let static_text = match static_file {
+            Ok(()) => {
+                static_times.push(static_time);
+                millis(static_time)
+            }
+            Err(_) => "failed".to_string(),
+        };
+        output::list_item(&format!("#{}", round), &format!("api {}  static {}", api_text, static_text));
+    }
+
+    let Some(api) = stats(&api_times) else {
+        anyhow::bail!("All {} API request(s) failed", rounds);
+    };
+
+    output::header("Latency (min/avg/max)");
+    output::list_item("api:   ", &format!("{} / {} / {}", millis(api.min), millis(api.avg), millis(api.max)));
+    let baseline = stats(&static_times);
+    if let Some(baseline) = &baseline {
+        output::list_item(
+            "static:",
+            &format!("{} / {} / {}", millis(baseline.min), millis(baseline.avg), millis(baseline.max)),
+        );
+    }
+    output::newline();
+
+    let failed = rounds - api_times.len();
+    if failed > 0 {
+        output::warning(&format!("{} of {} API request(s) failed", failed, rounds));
+    }
+
+    if let Some(verdict) = baseline.and_then(|baseline| diagnose(&api, &baseline)) {
+        output::warning(verdict);
+    }
+
+    match quieting_down {
+        Some(true) => output::warning("Jenkins is quieting down - new builds won't start until it restarts or the shutdown is cancelled"),
+        _ => output::success("Jenkins is accepting new builds"),
+    }
+
+    Ok(())
+}
+
+fn stats(times: &[Duration]) -> Option<Stats> {
+    let min = *times.iter().min()?;
+    let max = *times.iter().max()?;
+    let avg = times.iter().sum::<Duration>() / times.len() as u32;
+    Some(Stats { min, avg, max })
+}
+
+/// Explain which side looks slow, comparing the API with the static-file baseline
+fn diagnose(api: &Stats, baseline: &Stats) -> Option<&'static str> {
+    if api.avg > baseline.avg * SLOW_API_FACTOR && api.avg - baseline.avg > SLOW_API_MIN_GAP {
+        Some("The API is much slower than the network round trip - the controller itself is slow")
+    } else if baseline.avg > SLOW_NETWORK {
+        Some("Even static files are slow - the network (or a proxy) is the bottleneck")
+    } else {
+        None
+    }
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{}ms", duration.as_millis())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|v| Duration::from_millis(*v)).collect()
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = stats(&ms(&[120, 80, 100])).unwrap();
+        assert_eq!(
+            stats,
+            Stats { min: Duration::from_millis(80), avg: Duration::from_millis(100), max: Duration::from_millis(120) }
+        );
+        assert_eq!(super::stats(&[]), None);
+    }
+
+    #[test]
+    fn test_diagnose() {
+        let fast = stats(&ms(&[40])).unwrap();
+        let slow_api = stats(&ms(&[900])).unwrap();
+        let slow_network = stats(&ms(&[800])).unwrap();
+
+        assert!(diagnose(&slow_api, &fast).unwrap().contains("controller"));
+        assert!(diagnose(&slow_network, &slow_network).unwrap().contains("network"));
+        assert_eq!(diagnose(&fast, &fast), None);
+        // Small absolute gaps aren't worth flagging, however large the ratio
+        assert_eq!(diagnose(&stats(&ms(&[200])).unwrap(), &fast), None);
+    }
+}
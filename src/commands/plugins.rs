@@ -0,0 +1,288 @@
+use anyhow::Result;
+use console::style;
+use crate::client::{InstalledPlugin, PluginUpdate, SecurityWarning};
+use crate::helpers::init::create_client;
+use crate::output::{self, Table};
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// An installed plugin with a newer version available
+#[derive(Debug, PartialEq)]
+struct Outdated<'a> {
+    plugin: &'a InstalledPlugin,
+    /// None when the plugin manager reports an update the update sites don't list
+    latest: Option<&'a str>,
+    warnings: &'a [SecurityWarning],
+}
+
+pub fn execute_outdated() -> Result<()> {
+    let client = create_client(None)?;
+
+    let sp = output::spinner("Checking for plugin updates...");
+    let plugins = client.get_plugins()?;
+    let updates = client.get_plugin_updates()?;
+    sp.finish_and_clear();
+
+    let outdated = find_outdated(&plugins, &updates);
+    if outdated.is_empty() {
+        output::success(&format!("All {} installed plugins are up to date", plugins.len()));
+        output::dim("Based on the update center data cached on the controller");
+        return Ok(());
+    }
+
+    output::header(&format!("Outdated plugins ({} of {})", outdated.len(), plugins.len()));
+    let mut table = Table::new(&["PLUGIN", "INSTALLED", "LATEST", ""]);
+    for entry in &outdated {
+        let flag = if entry.warnings.is_empty() {
+            String::new()
+        } else {
+            style("⚠ security advisory").red().bold().to_string()
+        };
+        table.add_row(vec![
+            entry.plugin.short_name.clone(),
+            entry.plugin.version.clone(),
+            style(entry.latest.unwrap_or("?")).green().to_string(),
+            flag,
+        ]);
+    }
+    table.print();
+
+    // Advisories follow the table, so long messages don't stretch the columns
+    for entry in outdated.iter().filter(|entry| !entry.warnings.is_empty()) {
+        output::newline();
+        output::highlight(&format!("{}:", entry.plugin.short_name));
+        for warning in entry.warnings {
+            let detail = [warning.message.as_deref(), warning.url.as_deref()]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(" - ");
+            output::dim(&format!("  {} {}", warning.id, detail));
+        }
+    }
+    output::newline();
+
+    let vulnerable = outdated.iter().filter(|entry| !entry.warnings.is_empty()).count();
+    if vulnerable > 0 {
+        output::warning(&format!("{} installed plugin(s) have published security advisories", vulnerable));
+    }
+
+    Ok(())
+}
+
+pub fn execute_tree(name: String, reverse: bool) -> Result<()> {
+    let client = create_client(None)?;
+
+    let sp = output::spinner("Fetching installed plugins...");
+    let plugins = client.get_plugins()?;
+    sp.finish_and_clear();
+
+    let Some(root) = plugins.iter().find(|plugin| plugin.short_name == name) else {
+        anyhow::bail!("Plugin '{}' is not installed", name);
+    };
+
+    if reverse && dependents(&plugins, &root.short_name).is_empty() {
+        output::success(&format!("No installed plugin depends on {} - it can be removed safely", name));
+        return Ok(());
+    }
+
+    print!("{}", render_tree(root, &plugins, reverse));
+    Ok(())
+}
+
+/// Render a plugin's dependencies (or dependents) as a tree
+/// Plugins already shown are marked instead of being expanded again, which also stops cycles
+fn render_tree(root: &InstalledPlugin, plugins: &[InstalledPlugin], reverse: bool) -> String {
+    let mut text = format!("{} {}\n", style(&root.short_name).bold(), style(&root.version).dim());
+    let mut seen = HashSet::from([root.short_name.as_str()]);
+    render_children(&root.short_name, plugins, reverse, "", &mut seen, &mut text);
+    text
+}
+
+fn render_children<'a>(
+    name: &str,
+    plugins: &'a [InstalledPlugin],
+    reverse: bool,
+    prefix: &str,
+    seen: &mut HashSet<&'a str>,
+    text: &mut String,
+) {
+    let children = if reverse { dependents(plugins, name) } else { dependencies(plugins, name) };
+
+    for (i, (child, optional)) in children.iter().enumerate() {
+        let last = i + 1 == children.len();
+        let installed = plugins.iter().find(|plugin| plugin.short_name == *child);
+
+        let mut line = child.to_string();
+        match installed {
+            Some(plugin) => line.push_str(&format!(" {}", style(&plugin.version).dim())),
+            None => line.push_str(&format!(" {}", style("(not installed)").yellow())),
+        }
+        if *optional {
+            line.push_str(&format!(" {}", style("(optional)").dim()));
+        }
+
+        let expand = installed.is_some() && seen.insert(child);
+        if installed.is_some() && !expand {
+            line.push_str(&format!(" {}", style("(…)").dim()));
+        }
+        let _ = writeln!(text, "{}{}{}", prefix, if last { "└── " } else { "├── " }, line);
+
+        if expand {
+            let prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+            render_children(child, plugins, reverse, &prefix, seen, text);
+        }
+    }
+}
+
+/// Plugins `name` depends on, with whether the dependency is optional
+fn dependencies<'a>(plugins: &'a [InstalledPlugin], name: &str) -> Vec<(&'a str, bool)> {
+    plugins
+        .iter()
+        .find(|plugin| plugin.short_name == name)
+        .map(|plugin| plugin.dependencies.iter().map(|dep| (dep.short_name.as_str(), dep.optional)).collect())
+        .unwrap_or_default()
+}
+
+/// Installed plugins that depend on `name`, sorted by name
+fn dependents<'a>(plugins: &'a [InstalledPlugin], name: &str) -> Vec<(&'a str, bool)> {
+    let mut dependents: Vec<(&str, bool)> = plugins
+        .iter()
+        .filter_map(|plugin| {
+            let dep = plugin.dependencies.iter().find(|dep| dep.short_name == name)?;
+            Some((plugin.short_name.as_str(), dep.optional))
+        })
+        .collect();
+    dependents.sort();
+    dependents
+}
+
+/// Installed plugins with a newer version, sorted by name; vulnerable ones come first
+fn find_outdated<'a>(plugins: &'a [InstalledPlugin], updates: &'a [PluginUpdate]) -> Vec<Outdated<'a>> {
+    let mut outdated: Vec<Outdated> = plugins
+        .iter()
+        .filter_map(|plugin| {
+            let update = updates.iter().find(|update| update.name == plugin.short_name);
+            match update {
+                Some(update) if update.version != plugin.version => Some(Outdated {
+                    plugin,
+                    latest: Some(&update.version),
+                    warnings: &update.warnings,
+                }),
+                None if plugin.has_update => Some(Outdated { plugin, latest: None, warnings: &[] }),
+                _ => None,
+            }
+        })
+        .collect();
+
+    outdated.sort_by(|a, b| {
+        (a.warnings.is_empty(), &a.plugin.short_name).cmp(&(b.warnings.is_empty(), &b.plugin.short_name))
+    });
+    outdated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::PluginDependency;
+
+    fn plugin(name: &str, version: &str, has_update: bool) -> InstalledPlugin {
+        InstalledPlugin {
+            short_name: name.to_string(),
+            long_name: name.to_string(),
+            version: version.to_string(),
+            has_update,
+            active: true,
+            dependencies: Vec::new(),
+        }
+    }
+
+    fn update(name: &str, version: &str, warnings: Vec<SecurityWarning>) -> PluginUpdate {
+        PluginUpdate { name: name.to_string(), version: version.to_string(), warnings }
+    }
+
+    fn with_deps(name: &str, deps: &[(&str, bool)]) -> InstalledPlugin {
+        InstalledPlugin {
+            dependencies: deps
+                .iter()
+                .map(|(dep, optional)| PluginDependency {
+                    short_name: dep.to_string(),
+                    version: "1.0".to_string(),
+                    optional: *optional,
+                })
+                .collect(),
+            ..plugin(name, "1.0", false)
+        }
+    }
+
+    fn tree_plugins() -> Vec<InstalledPlugin> {
+        vec![
+            with_deps("git", &[("credentials", false), ("scm-api", false), ("promoted-builds", true)]),
+            with_deps("credentials", &[("structs", false)]),
+            with_deps("scm-api", &[("structs", false)]),
+            with_deps("structs", &[]),
+        ]
+    }
+
+    #[test]
+    fn test_render_tree() {
+        let plugins = tree_plugins();
+        let text = console::strip_ansi_codes(&render_tree(&plugins[0], &plugins, false)).into_owned();
+
+        assert_eq!(
+            text,
+            "git 1.0\n\
+             ├── credentials 1.0\n\
+             │   └── structs 1.0\n\
+             ├── scm-api 1.0\n\
+             │   └── structs 1.0 (…)\n\
+             └── promoted-builds (not installed) (optional)\n"
+        );
+    }
+
+    #[test]
+    fn test_render_reverse_tree() {
+        let plugins = tree_plugins();
+        let text = console::strip_ansi_codes(&render_tree(&plugins[3], &plugins, true)).into_owned();
+
+        assert_eq!(
+            text,
+            "structs 1.0\n\
+             ├── credentials 1.0\n\
+             │   └── git 1.0\n\
+             └── scm-api 1.0\n    \
+                 └── git 1.0 (…)\n"
+        );
+        assert!(dependents(&plugins, "git").is_empty());
+    }
+
+    #[test]
+    fn test_find_outdated() {
+        let plugins = vec![
+            plugin("workflow-job", "1400.v7fd111b_ec82f", true),
+            plugin("git", "5.2.0", true),
+            plugin("matrix-auth", "3.2", false),
+            plugin("script-security", "1300.v1", true),
+        ];
+        let warning = SecurityWarning { id: "SECURITY-3000".to_string(), message: None, url: None };
+        let updates = vec![
+            update("git", "5.2.2", Vec::new()),
+            update("workflow-job", "1436.vfa_244484591f", vec![warning]),
+            update("ant", "500.v1", Vec::new()),
+        ];
+
+        let outdated = find_outdated(&plugins, &updates);
+        let names: Vec<(&str, Option<&str>)> =
+            outdated.iter().map(|entry| (entry.plugin.short_name.as_str(), entry.latest)).collect();
+
+        assert_eq!(
+            names,
+            vec![
+                ("workflow-job", Some("1436.vfa_244484591f")),
+                ("git", Some("5.2.2")),
+                ("script-security", None),
+            ]
+        );
+        assert_eq!(outdated[0].warnings.len(), 1);
+    }
+}
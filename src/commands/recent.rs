@@ -0,0 +1,52 @@
+use anyhow::Result;
+use crate::commands::{build, logs, status};
+use crate::helpers::errors::ErrorKind;
+use crate::helpers::formatting::{format_timestamp, now_millis};
+use crate::helpers::init::create_client;
+use crate::helpers::prompt;
+use crate::helpers::url::to_display_job_path;
+use crate::output;
+use crate::state::State;
+use inquire::Select;
+
+const ACTIONS: &[&str] = &["Status", "Build", "Logs"];
+
+pub fn execute(jenkins: Option<String>) -> Result<()> {
+    let client = create_client(jenkins)?;
+    let host = client.name().unwrap_or_default().to_string();
+
+    let recent = State::load()?.recent_jobs.remove(&host).unwrap_or_default();
+    if recent.is_empty() {
+        output::info(&format!("No jobs used on '{}' yet", host));
+        output::tip("Jobs show up here once a command like 'jenkins status' or 'jenkins build' has used them");
+        return Ok(());
+    }
+
+    let now = now_millis();
+    let width = recent.iter().map(|r| to_display_job_path(&r.job).chars().count()).max().unwrap_or(0);
+    let entries: Vec<String> = recent
+        .iter()
+        .map(|r| format!("{:<width$}  {}", to_display_job_path(&r.job), format_timestamp(r.used_at, now)))
+        .collect();
+
+    if prompt::is_non_interactive() {
+        output::header(&format!("Recent jobs on '{}'", host));
+        entries.iter().for_each(|entry| output::bullet(entry));
+        return Ok(());
+    }
+
+    let Ok(selection) = Select::new(&format!("Recent jobs on '{}':", host), entries).raw_prompt() else {
+        return Err(ErrorKind::Cancelled.error("Cancelled"));
+    };
+    let Ok(action) = Select::new("Run:", ACTIONS.to_vec()).prompt() else {
+        return Err(ErrorKind::Cancelled.error("Cancelled"));
+    };
+
+    // A full job URL pins the host, so the command doesn't ask for it again
+    let job = Some(client.get_job_url(&recent[selection.index].job));
+    match action {
+        "Build" => build::execute(job, None, false, None, false, None, false),
+        "Logs" => logs::execute(job, None, false, None, None, false),
+        _ => status::execute(job, None, None, false),
+    }
+}
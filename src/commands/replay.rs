@@ -0,0 +1,44 @@
+use anyhow::{Context, Result};
+use crate::helpers::history::{self, Action};
+use crate::helpers::init::{build_number_from_url, create_client_for_job, resolve_build_number};
+use crate::helpers::url::to_display_job_path;
+use crate::interactive;
+use crate::output;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+pub fn execute(job_name: Option<String>, build_number: Option<i32>, jenkinsfile: PathBuf) -> Result<()> {
+    // Read the script first so a wrong path fails before any prompts
+    let script = fs::read_to_string(&jenkinsfile)
+        .with_context(|| format!("Failed to read '{}'", jenkinsfile.display()))?;
+    if script.trim().is_empty() {
+        anyhow::bail!("'{}' is empty", jenkinsfile.display());
+    }
+
+    let client = create_client_for_job(job_name.as_deref(), None)?;
+    let build_number = match build_number {
+        Some(num) => Some(num),
+        None => build_number_from_url(job_name.as_deref())?,
+    };
+
+    // Resolve the final job name (handle sub-jobs if present)
+    let final_job_name = interactive::resolve_job_name(&client, job_name.as_deref())?;
+    let build_num = resolve_build_number(&client, &final_job_name, build_number)?;
+    let display_name = to_display_job_path(&final_job_name);
+
+    let sp = output::spinner(&format!(
+        "Replaying {}#{} with {}...",
+        display_name,
+        build_num,
+        jenkinsfile.display()
+    ));
+    let replayed = client.replay_build(&final_job_name, build_num, &script);
+    history::record(&client, &final_job_name, Action::Replay, BTreeMap::new(), &replayed);
+    replayed?;
+    output::finish_spinner_success(sp, &format!("Replay of #{} queued => {}", build_num, client.get_job_url(&final_job_name)));
+
+    output::tip(&format!("Use 'jenkins logs {} -f' to follow the new build", display_name));
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use anyhow::Result;
+use crate::client::PipelineStage;
+use crate::helpers::history::{self, Action};
+use crate::helpers::init::{build_number_from_url, create_client_for_job, resolve_build_number};
+use crate::helpers::prompt;
+use crate::helpers::url::to_display_job_path;
+use crate::interactive;
+use crate::output;
+use inquire::Select;
+use std::collections::BTreeMap;
+
+pub fn execute(job_name: Option<String>, build_number: Option<i32>, stage: Option<String>) -> Result<()> {
+    let client = create_client_for_job(job_name.as_deref(), None)?;
+    let build_number = match build_number {
+        Some(num) => Some(num),
+        None => build_number_from_url(job_name.as_deref())?,
+    };
+
+    // Resolve the final job name (handle sub-jobs if present)
+    let final_job_name = interactive::resolve_job_name(&client, job_name.as_deref())?;
+    let build_num = resolve_build_number(&client, &final_job_name, build_number)?;
+    let display_name = to_display_job_path(&final_job_name);
+
+    let sp = output::spinner("Loading pipeline stages...");
+    let stages = client.get_pipeline_stages(&final_job_name, build_num)?;
+    sp.finish_and_clear();
+
+    let stage_names = stages.as_deref().map(restartable_stages).unwrap_or_default();
+
+    let stage = match stage {
+        Some(stage) => {
+            // Catch typos locally when the stage list is available
+            if !stage_names.is_empty() && !stage_names.contains(&stage) {
+                anyhow::bail!(
+                    "Stage '{}' not found in {}#{}. Available stages: {}",
+                    stage,
+                    display_name,
+                    build_num,
+                    stage_names.join(", ")
+                );
+            }
+            stage
+        }
+        None => {
+            if stage_names.is_empty() {
+                anyhow::bail!("No stages found for {}#{}. Is it a declarative pipeline?", display_name, build_num);
+            }
+            prompt::ensure_interactive("a stage", "Pass it with --stage <name>.")?;
+            Select::new("Restart from stage:", stage_names)
+                .with_help_message("Use ↑↓ to navigate, type to search, Enter to select, ESC to cancel")
+                .prompt()?
+        }
+    };
+
+    let sp = output::spinner(&format!("Restarting {}#{} from stage '{}'...", display_name, build_num, stage));
+    let restarted = client.restart_from_stage(&final_job_name, build_num, &stage);
+    // The stage is what the restart was asked for, so it goes in with the parameters
+    let recorded = BTreeMap::from([("stage".to_string(), stage.clone())]);
+    history::record(&client, &final_job_name, Action::RestartFromStage, recorded, &restarted);
+    restarted?;
+    output::finish_spinner_success(sp, &format!("Restart queued => {}", client.get_job_url(&final_job_name)));
+
+    output::tip(&format!("Use 'jenkins logs {} -f' to follow the new build", display_name));
+
+    Ok(())
+}
+
+/// Stage names that can be restarted - the synthetic "Declarative: ..." stages can't
+fn restartable_stages(stages: &[PipelineStage]) -> Vec<String> {
+    stages
+        .iter()
+        .filter(|stage| !stage.name.starts_with("Declarative: "))
+        .map(|stage| stage.name.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restartable_stages() {
+        let stage = |name: &str| PipelineStage {
+            id: name.to_lowercase(),
+            name: name.to_string(),
+            status: "SUCCESS".to_string(),
+            start_time: None,
+            duration: None,
+        };
+        let stages = vec![
+            stage("Declarative: Checkout SCM"),
+            stage("Build"),
+            stage("Deploy"),
+            stage("Declarative: Post Actions"),
+        ];
+
+        assert_eq!(restartable_stages(&stages), vec!["Build", "Deploy"]);
+    }
+}
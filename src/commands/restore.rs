@@ -0,0 +1,213 @@
+use anyhow::{Context, Result};
+use console::style;
+use crate::client::JenkinsClient;
+use crate::helpers::errors::ErrorKind;
+use crate::helpers::history::{self, Action};
+use crate::helpers::init::create_client;
+use crate::helpers::prompt;
+use crate::helpers::url::to_display_job_path;
+use crate::output;
+use inquire::Confirm;
+use similar::{ChangeTag, TextDiff};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// What restoring one config.xml would do
+#[derive(Debug, Clone, PartialEq)]
+enum Plan {
+    Unchanged,
+    Update { added: usize, removed: usize },
+    Create,
+    /// The job doesn't exist and `--create-missing` wasn't given
+    Skip,
+}
+
+/// A config.xml found in the backup directory
+struct Entry {
+    /// Jenkins job path, e.g. `team/job/api`
+    job: String,
+    xml: String,
+    plan: Plan,
+}
+
+pub fn execute(dir: PathBuf, create_missing: bool, dry_run: bool, yes: bool) -> Result<()> {
+    let files = find_configs(&dir)?;
+    if files.is_empty() {
+        anyhow::bail!("No config.xml files found under {}", dir.display());
+    }
+
+    let client = create_client(None)?;
+    if !dry_run {
+        client.ensure_writable("restoring job configurations")?;
+    }
+
+    let sp = output::spinner("Comparing with the current job configurations...");
+    let mut entries = Vec::new();
+    for (job, path) in files {
+        let xml = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let current = client.find_job_config(&job)?;
+        let plan = plan(current.as_deref(), &xml, create_missing);
+        entries.push(Entry { job, xml, plan });
+    }
+    sp.finish_and_clear();
+
+    print_plan(&entries);
+
+    let changes = entries
+        .iter()
+        .filter(|e| matches!(e.plan, Plan::Update { .. } | Plan::Create))
+        .count();
+    if changes == 0 || dry_run {
+        return Ok(());
+    }
+
+    if !yes {
+        prompt::ensure_interactive("confirming the restore", "Pass --yes to apply it without confirming.")?;
+        let apply = Confirm::new(&format!("Apply {} change(s)?", changes))
+            .with_default(false)
+            .prompt()
+            .unwrap_or(false);
+        if !apply {
+            return Err(ErrorKind::Cancelled.error("Restore cancelled"));
+        }
+    }
+
+    apply(&client, &entries)
+}
+
+/// Upload the changed configurations; entries are ordered so folders exist before their jobs
+fn apply(client: &JenkinsClient, entries: &[Entry]) -> Result<()> {
+    let mut failed = 0;
+    for entry in entries {
+        let display = to_display_job_path(&entry.job);
+        let (result, action) = match entry.plan {
+            Plan::Update { .. } => (client.update_job_config(&entry.job, &entry.xml), Action::UpdateConfig),
+            Plan::Create => {
+                let (parent, name) = split_job_path(&entry.job);
+                (client.create_job(parent, name, &entry.xml), Action::CreateJob)
+            }
+            Plan::Unchanged | Plan::Skip => continue,
+        };
+        history::record(client, &entry.job, action, BTreeMap::new(), &result);
+
+        match result {
+            Ok(()) if entry.plan == Plan::Create => output::success(&format!("Created {}", display)),
+            Ok(()) => output::success(&format!("Updated {}", display)),
+            Err(e) => {
+                failed += 1;
+                output::error(&format!("{}: {:#}", display, e));
+            }
+        }
+    }
+
+    if failed > 0 {
+        anyhow::bail!("{} job configuration(s) could not be restored", failed);
+    }
+    Ok(())
+}
+
+fn plan(current: Option<&str>, backup: &str, create_missing: bool) -> Plan {
+    let Some(current) = current else {
+        return if create_missing { Plan::Create } else { Plan::Skip };
+    };
+
+    let diff = TextDiff::from_lines(current, backup);
+    let (added, removed) = diff.iter_all_changes().fold((0, 0), |(added, removed), change| match change.tag() {
+        ChangeTag::Insert => (added + 1, removed),
+        ChangeTag::Delete => (added, removed + 1),
+        ChangeTag::Equal => (added, removed),
+    });
+
+    if added == 0 && removed == 0 { Plan::Unchanged } else { Plan::Update { added, removed } }
+}
+
+fn print_plan(entries: &[Entry]) {
+    output::header("Restore plan");
+    for entry in entries {
+        let display = to_display_job_path(&entry.job);
+        let action = match entry.plan {
+            Plan::Unchanged => style("unchanged".to_string()).dim(),
+            Plan::Update { added, removed } => style(format!("update (+{} -{} lines)", added, removed)).yellow(),
+            Plan::Create => style("create".to_string()).green(),
+            Plan::Skip => style("missing - skipped (use --create-missing)".to_string()).dim(),
+        };
+        output::list_item(&format!("{}:", display), &action.to_string());
+    }
+}
+
+/// Find `config.xml` files below the backup directory, as (job path, file) pairs
+/// Parents come before their children so folders are created first
+fn find_configs(dir: &Path) -> Result<Vec<(String, PathBuf)>> {
+    let mut configs = Vec::new();
+    collect_configs(dir, &mut Vec::new(), &mut configs)?;
+    configs.sort_by(|(a, _), (b, _)| a.matches("/job/").count().cmp(&b.matches("/job/").count()).then_with(|| a.cmp(b)));
+    Ok(configs)
+}
+
+fn collect_configs(dir: &Path, names: &mut Vec<String>, configs: &mut Vec<(String, PathBuf)>) -> Result<()> {
+    let entries = fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_dir() {
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            names.push(name.to_string());
+            let config = path.join("config.xml");
+            if config.is_file() {
+                configs.push((names.join("/job/"), config));
+            }
+            collect_configs(&path, names, configs)?;
+            names.pop();
+        }
+    }
+    Ok(())
+}
+
+/// Split `a/job/b/job/c` into the parent folder `a/job/b` and the name `c`
+fn split_job_path(job: &str) -> (Option<&str>, &str) {
+    match job.rsplit_once("/job/") {
+        Some((parent, name)) => (Some(parent), name),
+        None => (None, job),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan() {
+        let current = "<project>\n  <disabled>false</disabled>\n</project>\n";
+        let backup = "<project>\n  <disabled>true</disabled>\n  <description/>\n</project>\n";
+
+        assert_eq!(plan(Some(current), current, false), Plan::Unchanged);
+        assert_eq!(plan(Some(current), backup, false), Plan::Update { added: 2, removed: 1 });
+        assert_eq!(plan(None, backup, true), Plan::Create);
+        assert_eq!(plan(None, backup, false), Plan::Skip);
+    }
+
+    #[test]
+    fn test_find_configs_orders_parents_first() {
+        let dir = tempfile::tempdir().unwrap();
+        for job in ["team/api", "team", "deploy", "team/api/main"] {
+            let path = dir.path().join(job);
+            fs::create_dir_all(&path).unwrap();
+            fs::write(path.join("config.xml"), "<project/>").unwrap();
+        }
+        // Directories without a config.xml still contribute to the path
+        fs::create_dir_all(dir.path().join("empty/nested")).unwrap();
+        fs::write(dir.path().join("empty/nested/config.xml"), "<project/>").unwrap();
+
+        let jobs: Vec<String> = find_configs(dir.path()).unwrap().into_iter().map(|(job, _)| job).collect();
+        assert_eq!(jobs, vec!["deploy", "team", "empty/job/nested", "team/job/api", "team/job/api/job/main"]);
+    }
+
+    #[test]
+    fn test_split_job_path() {
+        assert_eq!(split_job_path("team/job/api"), (Some("team"), "api"));
+        assert_eq!(split_job_path("a/job/b/job/c"), (Some("a/job/b"), "c"));
+        assert_eq!(split_job_path("deploy"), (None, "deploy"));
+    }
+}
@@ -0,0 +1,153 @@
+use anyhow::Result;
+use console::style;
+use crate::client::{QueuedItem, RunningBuild};
+use crate::helpers::formatting::{format_duration, now_millis, progress_bar};
+use crate::helpers::init::create_client;
+use crate::output::{self, Table};
+
+/// A running build together with every node it occupies
+#[derive(Debug, PartialEq)]
+struct RunningEntry {
+    name: String,
+    nodes: Vec<String>,
+    timestamp: Option<i64>,
+    estimated_duration: Option<i64>,
+}
+
+pub fn execute() -> Result<()> {
+    let client = create_client(None)?;
+
+    let sp = output::spinner("Fetching executors and queue...");
+    let builds = client.get_running_builds()?;
+    let queue = client.get_queue_items()?;
+    sp.finish_and_clear();
+
+    let now = now_millis();
+    let entries = group_by_build(builds);
+
+    output::header(&format!("Running builds ({})", entries.len()));
+    if entries.is_empty() {
+        output::dim("No builds running");
+    } else {
+        running_table(&entries, now).print();
+    }
+
+    if !queue.is_empty() {
+        output::header(&format!("Queued ({})", queue.len()));
+        queue_table(&queue, now).print();
+    }
+
+    Ok(())
+}
+
+/// One row per running build: how long it has run, its nodes and progress against the estimate
+fn running_table(entries: &[RunningEntry], now: i64) -> Table {
+    let mut table = Table::new(&["ELAPSED", "BUILD", "NODES", "PROGRESS"]).align_right(0);
+    for entry in entries {
+        let elapsed = entry.timestamp.map(|started| format_duration((now - started).max(0)));
+        let progress = match (entry.timestamp, entry.estimated_duration.filter(|e| *e > 0)) {
+            (Some(started), Some(estimate)) => {
+                let percent = ((now - started).max(0) * 100 / estimate).min(99) as u8;
+                format!("{} {}%", progress_bar(percent, 10), percent)
+            }
+            _ => String::new(),
+        };
+        table.add_row(vec![
+            elapsed.unwrap_or_else(|| "-".to_string()),
+            entry.name.clone(),
+            entry.nodes.join(", "),
+            progress,
+        ]);
+    }
+    table
+}
+
+/// One row per queued item: how long it has waited and why
+fn queue_table(queue: &[QueuedItem], now: i64) -> Table {
+    let mut table = Table::new(&["WAITING", "JOB", "REASON"]).align_right(0);
+    for item in queue {
+        let waiting = item.in_queue_since.map(|since| format_duration((now - since).max(0)));
+        let mut reason = item.why.clone().unwrap_or_default();
+        if item.stuck {
+            reason.push_str(&format!(" {}", style("(stuck)").red()));
+        }
+        table.add_row(vec![
+            waiting.unwrap_or_else(|| "-".to_string()),
+            item.task.name.clone().unwrap_or_else(|| "(unknown)".to_string()),
+            reason,
+        ]);
+    }
+    table
+}
+
+/// Merge executor entries belonging to the same build
+/// A pipeline shows up once as a flyweight on the controller plus once per `node` block on agents;
+/// agents are listed when present, the controller only for builds running nowhere else
+fn group_by_build(builds: Vec<RunningBuild>) -> Vec<RunningEntry> {
+    let mut groups: Vec<(String, Vec<RunningBuild>)> = Vec::new();
+    for build in builds {
+        let key = build.url.clone().unwrap_or_else(|| build.name.clone());
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, group)) => group.push(build),
+            None => groups.push((key, vec![build])),
+        }
+    }
+
+    let mut entries: Vec<RunningEntry> = groups
+        .into_iter()
+        .map(|(_, group)| {
+            let mut nodes: Vec<String> = group.iter().filter(|b| !b.flyweight).map(|b| b.node.clone()).collect();
+            if nodes.is_empty() {
+                nodes = group.iter().map(|b| b.node.clone()).collect();
+            }
+            nodes.dedup();
+
+            RunningEntry {
+                // Agent placeholders are named "job #1 (part of ...)" - the shortest name is the build's own
+                name: group.iter().map(|b| b.name.clone()).min_by_key(|n| n.len()).unwrap_or_default(),
+                nodes,
+                timestamp: group.iter().filter_map(|b| b.timestamp).min(),
+                estimated_duration: group.iter().filter_map(|b| b.estimated_duration).max(),
+            }
+        })
+        .collect();
+
+    // Longest-running first
+    entries.sort_by_key(|entry| entry.timestamp.unwrap_or(i64::MAX));
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn running(name: &str, url: &str, node: &str, flyweight: bool, timestamp: Option<i64>) -> RunningBuild {
+        RunningBuild {
+            name: name.to_string(),
+            url: Some(url.to_string()),
+            timestamp,
+            estimated_duration: None,
+            node: node.to_string(),
+            flyweight,
+        }
+    }
+
+    #[test]
+    fn test_group_by_build() {
+        let builds = vec![
+            running("api #12", "http://jenkins/job/api/12/", "Built-In Node", true, Some(2000)),
+            running("api #12 (part of api #12)", "http://jenkins/job/api/12/", "agent-1", false, None),
+            running("web #3", "http://jenkins/job/web/3/", "Built-In Node", true, Some(1000)),
+        ];
+
+        let entries = group_by_build(builds);
+        assert_eq!(entries.len(), 2);
+
+        assert_eq!(entries[0].name, "web #3");
+        assert_eq!(entries[0].nodes, vec!["Built-In Node"]);
+
+        assert_eq!(entries[1].name, "api #12");
+        assert_eq!(entries[1].nodes, vec!["agent-1"]);
+        assert_eq!(entries[1].timestamp, Some(2000));
+    }
+}
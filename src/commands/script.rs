@@ -0,0 +1,68 @@
+use anyhow::{Context, Result};
+use crate::helpers::history::{self, Action};
+use crate::helpers::init::create_client;
+use crate::output;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+
+pub fn execute(file: PathBuf, nodes: Vec<String>) -> Result<()> {
+    let script = fs::read_to_string(&file).with_context(|| format!("Failed to read '{}'", file.display()))?;
+    let client = create_client(None)?;
+
+    if nodes.is_empty() {
+        let sp = output::spinner("Running script on the controller...");
+        let result = client.run_script(None, &script);
+        sp.finish_and_clear();
+        history::record(&client, &file.display().to_string(), Action::RunScript, BTreeMap::new(), &result);
+        let text = result?;
+        print!("{}", text);
+        return Ok(());
+    }
+
+    // Every agent runs the script at the same time; the output is printed in the order given
+    let sp = output::spinner(&format!("Running script on {} agent(s)...", nodes.len()));
+    let results: Vec<Result<String>> = thread::scope(|scope| {
+        let handles: Vec<_> = nodes
+            .iter()
+            .map(|node| {
+                let (client, script) = (&client, &script);
+                scope.spawn(move || client.run_script(Some(node), script))
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap_or_else(|_| Err(anyhow::anyhow!("The request thread panicked"))))
+            .collect()
+    });
+    sp.finish_and_clear();
+
+    let mut failed = 0;
+    for (node, result) in nodes.iter().zip(results) {
+        let target = format!("{} on {}", file.display(), node);
+        history::record(&client, &target, Action::RunScript, BTreeMap::new(), &result);
+        output::header(node);
+        match result {
+            Ok(text) if text.trim().is_empty() => output::dim("(no output)"),
+            Ok(text) => print!("{}", ensure_trailing_newline(text)),
+            Err(e) => {
+                failed += 1;
+                output::error(&format!("{:#}", e));
+            }
+        }
+    }
+
+    if failed > 0 {
+        anyhow::bail!("The script failed on {} of {} agent(s)", failed, nodes.len());
+    }
+    Ok(())
+}
+
+/// Keep the next agent's header on its own line
+fn ensure_trailing_newline(mut text: String) -> String {
+    if !text.ends_with('\n') {
+        text.push('\n');
+    }
+    text
+}
@@ -0,0 +1,57 @@
+use anyhow::Result;
+use console::style;
+use crate::commands::build::{self, FinishedBuild};
+use crate::helpers::errors::ErrorKind;
+use crate::helpers::job_dsl::{self, Change, GeneratedItem};
+use crate::helpers::url::to_display_job_path;
+use crate::output;
+use std::time::Duration;
+
+pub fn execute(job_name: Option<String>, queue_timeout: Option<Duration>) -> Result<()> {
+    let Some(FinishedBuild { client, job_name, number, result }) = build::run(job_name, None, true, queue_timeout, false, None)? else {
+        // Not followed to the end (still queued or interrupted) - nothing to report yet
+        return Ok(());
+    };
+
+    let sp = output::spinner("Reading the Job DSL results...");
+    let log = client.get_console_log(&job_name, number)?;
+    sp.finish_and_clear();
+
+    let items = job_dsl::parse_generated_items(&log);
+    if items.is_empty() {
+        output::warning(&format!(
+            "No generated items found in the log of {} #{} - is it a Job DSL seed job?",
+            to_display_job_path(&job_name),
+            number
+        ));
+    } else {
+        print_items(&items);
+    }
+
+    match result.as_deref() {
+        Some("SUCCESS") | None => Ok(()),
+        Some(result) => Err(ErrorKind::BuildFailed.error(format!("Seed build #{} finished with {}", number, result))),
+    }
+}
+
+fn print_items(items: &[GeneratedItem]) {
+    for change in Change::ALL {
+        let group: Vec<&GeneratedItem> = items.iter().filter(|item| item.change == change).collect();
+        if group.is_empty() {
+            continue;
+        }
+
+        output::header(&format!("{} ({})", change.label(), group.len()));
+        for item in group {
+            let marker = match change {
+                Change::Created => style("+").green(),
+                Change::Removed => style("-").red(),
+                Change::Updated => style("~").yellow(),
+                Change::Unreferenced | Change::Disabled => style("!").dim(),
+            };
+            // Jobs are the common case, so only the other kinds are labelled
+            let kind = if item.kind == "job" { String::new() } else { format!(" {}", style(format!("({})", item.kind)).dim()) };
+            output::bullet(&format!("{} {}{}", marker, item.name, kind));
+        }
+    }
+}
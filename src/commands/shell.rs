@@ -0,0 +1,318 @@
+use anyhow::Result;
+use clap::{CommandFactory, Parser};
+use inquire::autocompletion::Replacement;
+use inquire::error::InquireError;
+use inquire::{Autocomplete, CustomUserError, Text};
+use crate::cli::{Cli, Commands};
+use crate::config::Config;
+use crate::helpers::{prompt, session};
+use crate::output;
+
+const BUILTINS: &[&str] = &["use", "host", ":history", "help", "exit", "quit"];
+
+pub fn execute() -> Result<()> {
+    if session::is_active() {
+        output::info("Already inside a jenkins shell.");
+        return Ok(());
+    }
+
+    prompt::ensure_interactive("shell input", "Run the commands directly, e.g. 'jenkins status <job>'.")?;
+
+    session::start();
+
+    output::header("Jenkins shell");
+    output::dim("Run commands without the 'jenkins' prefix, e.g. 'status', 'logs 123', 'build -f'.");
+    output::dim("Type 'help' for shell commands, Tab to complete, 'exit' or Ctrl-C to quit.");
+    output::newline();
+
+    let mut completer = ShellCompleter::new();
+
+    loop {
+        let line = match Text::new(&prompt_label())
+            .with_autocomplete(completer.clone())
+            .prompt()
+        {
+            Ok(line) => line,
+            // ESC clears the current line
+            Err(InquireError::OperationCanceled) => continue,
+            Err(InquireError::OperationInterrupted) => break,
+            Err(e) => return Err(e.into()),
+        };
+
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        completer.remember(line);
+
+        let args = match split_args(line) {
+            Ok(args) => args,
+            Err(e) => {
+                output::error(&e.to_string());
+                continue;
+            }
+        };
+
+        match args[0].as_str() {
+            "exit" | "quit" => break,
+            "help" => print_help(),
+            ":history" => {
+                for (i, entry) in completer.history.iter().enumerate() {
+                    println!("{:>4}  {}", i + 1, entry);
+                }
+            }
+            "use" => {
+                let job = args.get(1).cloned();
+                match &job {
+                    Some(job) => output::success(&format!("Using job '{}'", job)),
+                    None => output::info("Cleared the selected job"),
+                }
+                session::set_job(job);
+            }
+            "host" => {
+                let jenkins = args.get(1).cloned();
+                if let Some(name) = &jenkins {
+                    if let Err(e) = Config::load().and_then(|c| c.get_jenkins(name).map(|_| ())) {
+                        output::error(&e.to_string());
+                        continue;
+                    }
+                    output::success(&format!("Using Jenkins '{}'", name));
+                } else {
+                    output::info("Cleared the selected Jenkins host");
+                }
+                session::set_jenkins(jenkins);
+            }
+            _ => run_line(args),
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse a line as CLI arguments and run it, reporting errors without leaving the shell
+fn run_line(args: Vec<String>) {
+    let cli = match Cli::try_parse_from(std::iter::once("jenkins".to_string()).chain(args)) {
+        Ok(cli) => cli,
+        Err(e) => {
+            // Covers --help and --version as well as real parse errors
+            let _ = e.print();
+            return;
+        }
+    };
+
+    let fixed = session_wide_flags(&cli);
+    if !fixed.is_empty() {
+        output::warning(&format!(
+            "{} can't be changed inside the shell; restart it with them instead.",
+            fixed.join(", ")
+        ));
+        return;
+    }
+
+    if let Commands::Shell = cli.command {
+        output::info("Already inside a jenkins shell.");
+        return;
+    }
+
+    if let Err(e) = crate::dispatch(cli.command) {
+        output::error(&e.to_string());
+    }
+}
+
+/// Global flags on the line that only take effect when the process starts, so the shell can't honour them per command
+fn session_wide_flags(cli: &Cli) -> Vec<&'static str> {
+    [
+        ("--config", cli.config.is_some()),
+        ("--as", cli.identity.is_some()),
+        ("--non-interactive", cli.non_interactive),
+        ("--log-file", cli.log_file.is_some()),
+        ("--no-pager", cli.no_pager),
+        ("--no-cache", cli.no_cache),
+        ("--utc", cli.utc),
+    ]
+    .into_iter()
+    .filter_map(|(flag, given)| given.then_some(flag))
+    .collect()
+}
+
+fn prompt_label() -> String {
+    match (session::jenkins(), session::job()) {
+        (Some(jenkins), Some(job)) => format!("jenkins [{}:{}]", jenkins, job),
+        (Some(jenkins), None) => format!("jenkins [{}]", jenkins),
+        (None, Some(job)) => format!("jenkins [{}]", job),
+        (None, None) => "jenkins".to_string(),
+    }
+}
+
+fn print_help() {
+    output::header("Shell commands");
+    output::list_item("use <job>", "Default job for commands that take one ('use' alone clears it)");
+    output::list_item("host <name>", "Jenkins host to use without prompting ('host' alone clears it)");
+    output::list_item(":history", "Show commands entered in this session");
+    output::list_item("exit", "Leave the shell");
+    output::newline();
+    let _ = Cli::command().print_help();
+}
+
+/// Split a command line into arguments, honouring single/double quotes and backslash escapes
+fn split_args(line: &str) -> Result<Vec<String>> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"'), '\\') | (None, '\\') => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+                in_arg = true;
+            }
+            (Some(_), c) => current.push(c),
+            (None, '"') | (None, '\'') => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+
+    if quote.is_some() {
+        anyhow::bail!("Unterminated quote in command");
+    }
+    if in_arg {
+        args.push(current);
+    }
+
+    Ok(args)
+}
+
+/// Completes command names and previously entered lines
+#[derive(Clone)]
+struct ShellCompleter {
+    commands: Vec<String>,
+    history: Vec<String>,
+}
+
+impl ShellCompleter {
+    fn new() -> Self {
+        let mut commands: Vec<String> = Cli::command()
+            .get_subcommands()
+            .map(|c| c.get_name().to_string())
+            .filter(|name| name != "shell")
+            .collect();
+        commands.extend(BUILTINS.iter().map(|s| s.to_string()));
+        commands.sort();
+
+        ShellCompleter { commands, history: Vec::new() }
+    }
+
+    fn remember(&mut self, line: &str) {
+        if self.history.last().map(String::as_str) != Some(line) {
+            self.history.push(line.to_string());
+        }
+    }
+
+    fn suggestions(&self, input: &str) -> Vec<String> {
+        if input.trim().is_empty() {
+            return Vec::new();
+        }
+
+        let mut suggestions: Vec<String> = Vec::new();
+
+        // Most recent history first
+        for entry in self.history.iter().rev() {
+            if entry.starts_with(input) && entry != input && !suggestions.contains(entry) {
+                suggestions.push(entry.clone());
+            }
+        }
+
+        if !input.contains(' ') {
+            for command in &self.commands {
+                if command.starts_with(input) && command != input && !suggestions.contains(command) {
+                    suggestions.push(command.clone());
+                }
+            }
+        }
+
+        suggestions
+    }
+}
+
+impl Autocomplete for ShellCompleter {
+    fn get_suggestions(&mut self, input: &str) -> Result<Vec<String>, CustomUserError> {
+        Ok(self.suggestions(input))
+    }
+
+    fn get_completion(
+        &mut self,
+        input: &str,
+        highlighted_suggestion: Option<String>,
+    ) -> Result<Replacement, CustomUserError> {
+        if highlighted_suggestion.is_some() {
+            return Ok(highlighted_suggestion);
+        }
+
+        // Complete straight away when there's only one candidate
+        let suggestions = self.suggestions(input);
+        Ok(if suggestions.len() == 1 { suggestions.into_iter().next() } else { None })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_args() {
+        assert_eq!(split_args("logs 123").unwrap(), vec!["logs", "123"]);
+        assert_eq!(split_args("  build   -f  ").unwrap(), vec!["build", "-f"]);
+        assert_eq!(split_args(r#"status "folder/my job""#).unwrap(), vec!["status", "folder/my job"]);
+        assert_eq!(split_args("use 'a b' c").unwrap(), vec!["use", "a b", "c"]);
+        assert_eq!(split_args(r"status my\ job").unwrap(), vec!["status", "my job"]);
+        assert_eq!(split_args(r#"alias add x y -p MSG="""#).unwrap(), vec!["alias", "add", "x", "y", "-p", "MSG="]);
+        assert_eq!(split_args("").unwrap(), Vec::<String>::new());
+        assert!(split_args("status \"unterminated").is_err());
+    }
+
+    #[test]
+    fn test_session_wide_flags() {
+        let parse = |line: &str| Cli::try_parse_from(["jenkins"].into_iter().chain(line.split(' '))).unwrap();
+        assert!(session_wide_flags(&parse("status api")).is_empty());
+        assert_eq!(session_wide_flags(&parse("logs api --no-pager --utc")), vec!["--no-pager", "--utc"]);
+        assert_eq!(session_wide_flags(&parse("build api --as bot --no-cache")), vec!["--as", "--no-cache"]);
+    }
+
+    #[test]
+    fn test_completer_suggests_history_then_commands() {
+        let mut completer = ShellCompleter::new();
+        completer.remember("status api");
+        completer.remember("logs 12");
+        completer.remember("status web");
+
+        assert_eq!(completer.suggestions("sta"), vec!["status web", "status api", "stats", "status"]);
+        assert_eq!(completer.suggestions("status w"), vec!["status web"]);
+        assert!(completer.suggestions("").is_empty());
+        assert!(!completer.suggestions("s").contains(&"shell".to_string()));
+    }
+
+    #[test]
+    fn test_completer_skips_consecutive_duplicates() {
+        let mut completer = ShellCompleter::new();
+        completer.remember("status");
+        completer.remember("status");
+        assert_eq!(completer.history, vec!["status"]);
+    }
+}
@@ -0,0 +1,151 @@
+use anyhow::Result;
+use console::style;
+use crate::client::BuildInfo;
+use crate::helpers::formatting::format_duration;
+use crate::helpers::init::create_client_for_job;
+use crate::helpers::url::to_human_job_path;
+use crate::interactive;
+use crate::output;
+
+const SPARK_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+pub fn execute(job_name: Option<String>, last: usize) -> Result<()> {
+    let client = create_client_for_job(job_name.as_deref(), None)?;
+
+    // Resolve the final job name (handle sub-jobs if present)
+    let final_job_name = interactive::resolve_job_name(&client, job_name.as_deref())?;
+
+    let sp = output::spinner(&format!("Fetching last {} builds...", last));
+    let builds = client.get_build_history(&final_job_name, last)?;
+    sp.finish_and_clear();
+
+    let Some(stats) = BuildStats::from_builds(&builds) else {
+        output::info(&format!("No completed builds found for job '{}'", to_human_job_path(&final_job_name)));
+        return Ok(());
+    };
+
+    output::header(&format!("Build statistics: {} (last {} completed)", to_human_job_path(&final_job_name), stats.count));
+    output::list_item("Success rate:", &format!("{:.0}% ({}/{})", stats.success_rate(), stats.successes, stats.count));
+    output::list_item("Min duration:", &format_duration(stats.min));
+    output::list_item("Avg duration:", &format_duration(stats.avg));
+    output::list_item("P95 duration:", &format_duration(stats.p95));
+    output::list_item("Trend:", &format!("{} {}", style(sparkline(&stats.durations)).cyan(), style("(oldest → newest)").dim()));
+
+    Ok(())
+}
+
+/// Duration and result statistics over completed builds
+#[derive(Debug, PartialEq)]
+struct BuildStats {
+    count: usize,
+    successes: usize,
+    min: i64,
+    avg: i64,
+    p95: i64,
+    /// Durations in chronological order (oldest first)
+    durations: Vec<i64>,
+}
+
+impl BuildStats {
+    /// Compute statistics from builds listed newest first, skipping builds still running
+    fn from_builds(builds: &[BuildInfo]) -> Option<Self> {
+        let completed: Vec<&BuildInfo> = builds
+            .iter()
+            .filter(|b| !b.building.unwrap_or(false) && b.result.is_some())
+            .collect();
+
+        if completed.is_empty() {
+            return None;
+        }
+
+        let durations: Vec<i64> = completed.iter().rev().map(|b| b.duration.unwrap_or(0)).collect();
+        let successes = completed.iter().filter(|b| b.result.as_deref() == Some("SUCCESS")).count();
+
+        let mut sorted = durations.clone();
+        sorted.sort_unstable();
+        // Nearest-rank percentile
+        let p95_index = ((sorted.len() as f64 * 0.95).ceil() as usize).saturating_sub(1);
+
+        Some(BuildStats {
+            count: completed.len(),
+            successes,
+            min: sorted[0],
+            avg: sorted.iter().sum::<i64>() / sorted.len() as i64,
+            p95: sorted[p95_index],
+            durations,
+        })
+    }
+
+    fn success_rate(&self) -> f64 {
+        self.successes as f64 * 100.0 / self.count as f64
+    }
+}
+
+/// Render values as a unicode sparkline scaled between their min and max
+fn sparkline(values: &[i64]) -> String {
+    let (Some(&min), Some(&max)) = (values.iter().min(), values.iter().max()) else {
+        return String::new();
+    };
+    let range = (max - min).max(1) as f64;
+
+    values
+        .iter()
+        .map(|&v| {
+            let index = ((v - min) as f64 / range * (SPARK_CHARS.len() - 1) as f64).round() as usize;
+            SPARK_CHARS[index]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(number: i32, result: Option<&str>, duration: i64, building: bool) -> BuildInfo {
+        BuildInfo {
+            number,
+            url: format!("https://jenkins.example.com/job/app/{}/", number),
+            result: result.map(|r| r.to_string()),
+            building: Some(building),
+            timestamp: None,
+            duration: Some(duration),
+            estimated_duration: None,
+            actions: vec![],
+        }
+    }
+
+    #[test]
+    fn test_build_stats() {
+        // Newest first, as returned by the Jenkins API
+        let builds = vec![
+            build(5, None, 0, true),
+            build(4, Some("SUCCESS"), 4_000, false),
+            build(3, Some("FAILURE"), 1_000, false),
+            build(2, Some("SUCCESS"), 3_000, false),
+            build(1, Some("SUCCESS"), 2_000, false),
+        ];
+
+        let stats = BuildStats::from_builds(&builds).unwrap();
+        assert_eq!(stats.count, 4);
+        assert_eq!(stats.successes, 3);
+        assert_eq!(stats.min, 1_000);
+        assert_eq!(stats.avg, 2_500);
+        assert_eq!(stats.p95, 4_000);
+        assert_eq!(stats.durations, vec![2_000, 3_000, 1_000, 4_000]);
+        assert_eq!(stats.success_rate(), 75.0);
+    }
+
+    #[test]
+    fn test_build_stats_no_completed_builds() {
+        assert_eq!(BuildStats::from_builds(&[]), None);
+        assert_eq!(BuildStats::from_builds(&[build(1, None, 0, true)]), None);
+    }
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline(&[]), "");
+        assert_eq!(sparkline(&[1_000, 8_000]), "▁█");
+        assert_eq!(sparkline(&[5, 5, 5]), "▁▁▁");
+        assert_eq!(sparkline(&[0, 7, 14]).chars().count(), 3);
+    }
+}
@@ -1,42 +1,192 @@
 use anyhow::Result;
-use crate::helpers::formatting::{format_job_color_styled as format_color, format_build_result as format_result};
-use crate::helpers::init::create_client_for_job;
+use console::style;
+use crate::client::{worst_health, BuildInfo, JenkinsClient};
+use crate::config::Config;
+use crate::helpers::formatting::{format_job_color_styled as format_color, format_build_result as format_result, format_build_duration, format_health_score, format_local_datetime, format_timestamp, now_millis};
+use crate::helpers::init::{build_number_from_url, create_client, create_client_for_job, resolve_build_number};
+use crate::helpers::url::to_human_job_path;
 use crate::interactive;
-use crate::output;
+use crate::output::{self, Table};
+use std::collections::BTreeSet;
+use std::thread;
 
-pub fn execute(job_name: Option<String>, build_number: Option<i32>) -> Result<()> {
-    let client = create_client_for_job(job_name.as_deref(), None)?;
+/// With `branch`, the latest build of the current git branch's job is shown
+pub fn execute(job_name: Option<String>, build_number: Option<i32>, axis: Option<String>, branch: bool) -> Result<()> {
+    // Status is display-only, so a response from a few seconds ago is good enough
+    let client = create_client_for_job(job_name.as_deref(), None)?.with_cached_reads();
+    let build_number = match build_number {
+        Some(num) => Some(num),
+        None => build_number_from_url(job_name.as_deref())?,
+    };
+
+    if branch {
+        let branch_job = interactive::resolve_current_branch_job(&client, job_name.as_deref())?;
+        let build_num = resolve_build_number(&client, &branch_job, build_number)?;
+        return show_build(&client, &branch_job, build_num);
+    }
 
     // Resolve the final job name (handle sub-jobs if present)
     let final_job_name = interactive::resolve_job_name(&client, job_name.as_deref())?;
+    let final_job_name = match axis {
+        Some(axes) => interactive::resolve_matrix_configuration(&client, &final_job_name, &axes)?,
+        None => final_job_name,
+    };
 
     if let Some(build_num) = build_number {
-        let sp = output::spinner("Fetching build details...");
-        let build = client.get_build(&final_job_name, build_num)?;
-        sp.finish_and_clear();
-        print_build_details(&client, &final_job_name, &build);
+        show_build(&client, &final_job_name, build_num)?;
     } else {
         let sp = output::spinner("Fetching job information...");
         let job = client.get_job(&final_job_name)?;
+        // Triggers are supplementary - don't fail the status check if they can't be read
+        let triggers = client.get_job_triggers(&final_job_name).unwrap_or_default();
         sp.finish_and_clear();
-        print_job_info(&client, &final_job_name, &job);
+        print_job_info(&client, &final_job_name, &job, &triggers);
+    }
+
+    Ok(())
+}
+
+/// Latest build of the job on one host, for `--compare`
+type HostBuild = Result<Option<BuildInfo>>;
+
+/// Show the latest build of a job on several hosts side by side, to spot drift between environments
+pub fn execute_compare(job_name: Option<String>, hosts: Vec<String>) -> Result<()> {
+    let Some(job_name) = job_name else {
+        anyhow::bail!("Pass the job to compare; it can't be selected interactively.");
+    };
+
+    // The job path is shared between hosts, even if an alias names a host of its own
+    let config = Config::load()?;
+    let (final_job_name, _, _) = config.resolve_job_name(&job_name)?;
+    let hosts = config.expand_hosts(&hosts)?;
+    let clients = hosts
+        .iter()
+        .map(|host| create_client(Some(host.clone())))
+        .collect::<Result<Vec<_>>>()?;
+
+    let sp = output::spinner(&format!("Fetching '{}' from {} host(s)...", to_human_job_path(&final_job_name), hosts.len()));
+    let builds: Vec<HostBuild> = thread::scope(|scope| {
+        let handles: Vec<_> = clients
+            .iter()
+            .map(|client| {
+                let job = &final_job_name;
+                scope.spawn(move || client.get_build_history(job, 1).map(|builds| builds.into_iter().next()))
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap_or_else(|_| Err(anyhow::anyhow!("The request thread panicked"))))
+            .collect()
+    });
+    sp.finish_and_clear();
+
+    output::header(&format!("Job: {}", to_human_job_path(&final_job_name)));
+    comparison_table(&hosts, &builds, now_millis()).print();
+    output::newline();
+    for (host, build) in hosts.iter().zip(&builds) {
+        if let Err(e) = build {
+            output::warning(&format!("{}: {:#}", host, e));
+        }
+    }
+
+    let results: BTreeSet<Option<&str>> = builds
+        .iter()
+        .filter_map(|build| build.as_ref().ok())
+        .map(|build| build.as_ref().and_then(|b| b.result.as_deref()))
+        .collect();
+    if results.len() > 1 {
+        output::warning("The latest builds differ between hosts");
+    } else {
+        output::success("The latest builds agree on every host that answered");
+    }
+    Ok(())
+}
+
+/// One row per host with its latest build
+fn comparison_table(hosts: &[String], builds: &[HostBuild], now: i64) -> Table {
+    let mut table = Table::new(&["HOST", "BUILD", "RESULT", "STARTED"]);
+    for (host, build) in hosts.iter().zip(builds) {
+        let row = match build {
+            Ok(Some(build)) => vec![
+                host.clone(),
+                format!("#{}", build.number),
+                format_result(&build.result),
+                build.timestamp.map(|t| format_timestamp(t, now)).unwrap_or_default(),
+            ],
+            Ok(None) => vec![host.clone(), "-".to_string(), style("never built").dim().to_string(), String::new()],
+            // Details follow the table, so long messages don't stretch the columns
+            Err(_) => vec![host.clone(), "-".to_string(), style("error").red().to_string(), String::new()],
+        };
+        table.add_row(row);
     }
+    table
+}
 
+/// Fetch and print the details of a single build
+pub fn show_build(client: &JenkinsClient, job_name: &str, build_number: i32) -> Result<()> {
+    let sp = output::spinner("Fetching build details...");
+    let build = client.get_build(job_name, build_number)?;
+    sp.finish_and_clear();
+    print_build_details(client, job_name, &build);
     Ok(())
 }
 
-fn print_job_info(client: &crate::client::JenkinsClient, job_name: &str, job: &crate::client::JobInfo) {
-    output::header(&format!("Job: {}", job.name.as_deref().unwrap_or("Unknown")));
+fn print_job_info(
+    client: &crate::client::JenkinsClient,
+    job_name: &str,
+    job: &crate::client::JobInfo,
+    triggers: &[crate::client::JobTrigger],
+) {
+    output::header(&format!("Job: {}", to_human_job_path(job_name)));
     // Use configured host to build URL instead of API response URL
     output::list_item("URL:", &client.get_job_url(job_name));
     output::list_item("Status:", &format_color(job.color.as_deref()));
 
+    if let Some(health) = worst_health(job.health_report.as_ref()) {
+        output::list_item("Health:", &format!("{} {}", format_health_score(health.score), health.description));
+    }
+
+    if let Some(configurations) = job.active_configurations.as_ref().filter(|c| !c.is_empty()) {
+        output::newline();
+        output::highlight("Configurations:");
+        for configuration in configurations {
+            output::list_item(&format!("{}:", configuration.name), &format_color(configuration.color.as_deref()));
+        }
+        output::dim("Use --axis <AXES> to check a single configuration");
+    }
+
+    if !triggers.is_empty() {
+        output::newline();
+        output::highlight("Triggers:");
+        let now = now_millis();
+        for trigger in triggers {
+            let detail = match trigger.next_run(job_name, now) {
+                Some(next) => format!("{} (next: {})", trigger.detail(), format_local_datetime(next)),
+                None => trigger.detail(),
+            };
+            output::list_item(&format!("{}:", trigger.kind()), &detail);
+        }
+    }
+
     if let Some(last_build) = &job.last_build {
         output::newline();
         output::highlight("Last Build:");
         output::list_item("Number:", &format!("#{}", last_build.number));
         output::list_item("Result:", &format_result(&last_build.result));
         output::list_item("Building:", &last_build.building.unwrap_or(false).to_string());
+        let now = now_millis();
+        if let Some(timestamp) = last_build.timestamp {
+            output::list_item("Started:", &format_timestamp(timestamp, now));
+        }
+        let duration = format_build_duration(
+            last_build.building.unwrap_or(false),
+            last_build.duration,
+            last_build.timestamp,
+            now,
+        );
+        if !duration.is_empty() {
+            output::list_item("Duration:", &duration);
+        }
         // Use configured host to build build URL
         output::list_item("URL:", &format!("{}/{}", client.get_job_url(job_name), last_build.number));
     } else {
@@ -49,7 +199,52 @@ fn print_build_details(client: &crate::client::JenkinsClient, job_name: &str, bu
     output::list_item("Number:", &format!("#{}", build.number));
     output::list_item("Result:", &format_result(&build.result));
     output::list_item("Building:", &build.building.to_string());
-    output::list_item("Duration:", &format!("{} ms", build.duration));
+    let now = now_millis();
+    output::list_item("Started:", &format_timestamp(build.timestamp, now));
+    output::list_item(
+        "Duration:",
+        &format_build_duration(build.building, Some(build.duration), Some(build.timestamp), now),
+    );
     // Use configured host to build build URL
     output::list_item("URL:", &format!("{}/{}", client.get_job_url(job_name), build.number));
+
+    let parameters = build.parameters();
+    if !parameters.is_empty() {
+        output::newline();
+        output::highlight("Parameters:");
+        for param in parameters {
+            output::list_item(&format!("{}:", param.name), &param.display_value());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_comparison_table() {
+        let build = |number: i32, result: &str| BuildInfo {
+            number,
+            url: String::new(),
+            result: Some(result.to_string()),
+            building: Some(false),
+            timestamp: Some(0),
+            duration: Some(1_000),
+            estimated_duration: None,
+            actions: Vec::new(),
+        };
+        let hosts = vec!["prod".to_string(), "staging".to_string(), "dr".to_string()];
+        let builds = vec![Ok(Some(build(120, "SUCCESS"))), Ok(Some(build(87, "FAILURE"))), Ok(None)];
+
+        assert_eq!(
+            comparison_table(&hosts, &builds, 2 * 3_600_000).render_plain(None),
+            vec![
+                "HOST     BUILD  RESULT       STARTED",
+                "prod     #120   SUCCESS      2 hours ago",
+                "staging  #87    FAILURE      2 hours ago",
+                "dr       -      never built",
+            ]
+        );
+    }
 }
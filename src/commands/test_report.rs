@@ -0,0 +1,267 @@
+use anyhow::{Context, Result};
+use console::style;
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+use crate::client::{TestCase, TestReport};
+use crate::helpers::init::{create_client_for_job, resolve_build_number};
+use crate::helpers::url::to_human_job_path;
+use crate::interactive;
+use crate::output;
+
+pub fn execute(job_name: Option<String>, build_number: Option<i32>, failed: bool, junit_out: Option<PathBuf>) -> Result<()> {
+    let client = create_client_for_job(job_name.as_deref(), None)?;
+
+    // Resolve the final job name (handle sub-jobs if present)
+    let final_job_name = interactive::resolve_job_name(&client, job_name.as_deref())?;
+    let build_num = resolve_build_number(&client, &final_job_name, build_number)?;
+
+    let sp = output::spinner(&format!("Fetching test report for {}#{}...", to_human_job_path(&final_job_name), build_num));
+    let report = client.get_test_report(&final_job_name, build_num)?;
+    sp.finish_and_clear();
+
+    if let Some(path) = junit_out {
+        fs::write(&path, render_junit_xml(&report))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        output::success(&format!(
+            "Wrote {} test(s) from {}#{} to {}",
+            report.pass_count + report.fail_count + report.skip_count,
+            final_job_name,
+            build_num,
+            path.display()
+        ));
+        return Ok(());
+    }
+
+    if failed {
+        let failed_cases = report.failed_cases();
+        if failed_cases.is_empty() {
+            output::success(&format!("No failing tests in {}#{}", to_human_job_path(&final_job_name), build_num));
+            return Ok(());
+        }
+        output::page(&render_failed_cases(&to_human_job_path(&final_job_name), build_num, &failed_cases));
+        return Ok(());
+    }
+
+    // Builds with many failures produce a long list, so it goes through the pager too
+    output::page(&render_summary(&to_human_job_path(&final_job_name), build_num, &report));
+
+    Ok(())
+}
+
+/// Render the pass/fail counts and the list of failing tests
+fn render_summary(job_name: &str, build_number: i32, report: &TestReport) -> String {
+    let mut text = String::new();
+    let _ = writeln!(text, "\n{}", style(format!("Tests: {}#{}", job_name, build_number)).bold().underlined());
+    let _ = writeln!(text, "  {} {}", style("Passed:").dim(), style(report.pass_count).green());
+    let _ = writeln!(text, "  {} {}", style("Failed:").dim(), style(report.fail_count).red());
+    let _ = writeln!(text, "  {} {}", style("Skipped:").dim(), style(report.skip_count).dim());
+
+    let failed_cases = report.failed_cases();
+    if !failed_cases.is_empty() {
+        let _ = writeln!(text, "\n{}", style("Failing tests:").cyan().bold());
+        for case in failed_cases {
+            let _ = writeln!(text, "  • {}.{}", case.class_name, case.name);
+        }
+        let _ = writeln!(
+            text,
+            "\n{} {}",
+            style("💡").bold(),
+            style(format!("Use 'jenkins tests {} --build {} --failed' to see error details", job_name, build_number)).italic()
+        );
+    }
+
+    text
+}
+
+/// Render failing test cases with their error details and stack traces
+fn render_failed_cases(job_name: &str, build_number: i32, cases: &[&TestCase]) -> String {
+    let mut text = String::new();
+    let _ = writeln!(
+        text,
+        "{}\n",
+        style(format!("{} failing test(s) in {}#{}", cases.len(), job_name, build_number)).bold().underlined()
+    );
+
+    for case in cases {
+        let _ = writeln!(
+            text,
+            "{} {}.{} {}",
+            style("✗").red().bold(),
+            case.class_name,
+            style(&case.name).bold(),
+            style(format!("({:.2}s)", case.duration)).dim()
+        );
+        if let Some(details) = case.error_details.as_deref().filter(|d| !d.is_empty()) {
+            let _ = writeln!(text, "  {}", style(details).red());
+        }
+        if let Some(trace) = case.error_stack_trace.as_deref().filter(|t| !t.is_empty()) {
+            for line in trace.lines() {
+                let _ = writeln!(text, "    {}", style(line).dim());
+            }
+        }
+        text.push('\n');
+    }
+
+    text
+}
+
+/// Convert the Jenkins test report back into JUnit XML for local tooling
+fn render_junit_xml(report: &TestReport) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites tests=\"{}\" failures=\"{}\" skipped=\"{}\">",
+        report.pass_count + report.fail_count + report.skip_count,
+        report.fail_count,
+        report.skip_count
+    );
+
+    for suite in &report.suites {
+        let failures = suite.cases.iter().filter(|case| case.is_failed()).count();
+        let skipped = suite.cases.iter().filter(|case| case.status == "SKIPPED").count();
+        let time: f64 = suite.cases.iter().map(|case| case.duration).sum();
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.3}\">",
+            xml_escape(&suite.name),
+            suite.cases.len(),
+            failures,
+            skipped,
+            time
+        );
+
+        for case in &suite.cases {
+            let _ = write!(
+                xml,
+                "    <testcase classname=\"{}\" name=\"{}\" time=\"{:.3}\"",
+                xml_escape(&case.class_name),
+                xml_escape(&case.name),
+                case.duration
+            );
+            if case.is_failed() {
+                let _ = writeln!(
+                    xml,
+                    ">\n      <failure message=\"{}\">{}</failure>\n    </testcase>",
+                    xml_escape(case.error_details.as_deref().unwrap_or_default()),
+                    xml_escape(case.error_stack_trace.as_deref().unwrap_or_default())
+                );
+            } else if case.status == "SKIPPED" {
+                let _ = writeln!(xml, ">\n      <skipped/>\n    </testcase>");
+            } else {
+                let _ = writeln!(xml, "/>");
+            }
+        }
+
+        let _ = writeln!(xml, "  </testsuite>");
+    }
+
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// Escape text for XML, dropping control characters XML 1.0 can't represent
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::TestSuite;
+
+    fn failing_case() -> TestCase {
+        TestCase {
+            class_name: "com.example.ApiTest".to_string(),
+            name: "handlesTimeout".to_string(),
+            status: "FAILED".to_string(),
+            duration: 3.5,
+            error_details: Some("expected 200 but was 504".to_string()),
+            error_stack_trace: Some("java.lang.AssertionError\n\tat com.example.ApiTest".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_render_failed_cases() {
+        let case = failing_case();
+        let text = console::strip_ansi_codes(&render_failed_cases("api", 42, &[&case])).into_owned();
+
+        assert!(text.contains("1 failing test(s) in api#42"));
+        assert!(text.contains("com.example.ApiTest.handlesTimeout (3.50s)"));
+        assert!(text.contains("  expected 200 but was 504"));
+        assert!(text.contains("    java.lang.AssertionError"));
+    }
+
+    #[test]
+    fn test_render_failed_cases_without_details() {
+        let mut case = failing_case();
+        case.error_details = None;
+        case.error_stack_trace = Some(String::new());
+        let text = console::strip_ansi_codes(&render_failed_cases("api", 42, &[&case])).into_owned();
+
+        assert!(text.contains("com.example.ApiTest.handlesTimeout"));
+        assert!(!text.contains("expected 200"));
+    }
+
+    #[test]
+    fn test_render_summary() {
+        let report = TestReport {
+            fail_count: 1,
+            pass_count: 5,
+            skip_count: 0,
+            suites: vec![TestSuite { name: "com.example.ApiTest".to_string(), cases: vec![failing_case()] }],
+        };
+        let text = console::strip_ansi_codes(&render_summary("api", 42, &report)).into_owned();
+
+        assert!(text.contains("Tests: api#42"));
+        assert!(text.contains("  Passed: 5"));
+        assert!(text.contains("  • com.example.ApiTest.handlesTimeout"));
+        assert!(text.contains("jenkins tests api --build 42 --failed"));
+    }
+
+    #[test]
+    fn test_render_junit_xml() {
+        let mut passing = failing_case();
+        passing.name = "returns<200>".to_string();
+        passing.status = "PASSED".to_string();
+        let mut skipped = failing_case();
+        skipped.name = "skipsOnCi".to_string();
+        skipped.status = "SKIPPED".to_string();
+        let report = TestReport {
+            fail_count: 1,
+            pass_count: 1,
+            skip_count: 1,
+            suites: vec![TestSuite {
+                name: "com.example.ApiTest".to_string(),
+                cases: vec![failing_case(), passing, skipped],
+            }],
+        };
+
+        let xml = render_junit_xml(&report);
+        assert!(xml.contains("<testsuites tests=\"3\" failures=\"1\" skipped=\"1\">"));
+        assert!(xml.contains("<testsuite name=\"com.example.ApiTest\" tests=\"3\" failures=\"1\" skipped=\"1\" time=\"10.500\">"));
+        assert!(xml.contains("<failure message=\"expected 200 but was 504\">java.lang.AssertionError\n\tat com.example.ApiTest</failure>"));
+        assert!(xml.contains("name=\"returns&lt;200&gt;\" time=\"3.500\"/>"));
+        assert!(xml.contains("name=\"skipsOnCi\" time=\"3.500\">\n      <skipped/>"));
+        assert!(xml.ends_with("</testsuites>\n"));
+    }
+
+    #[test]
+    fn test_xml_escape() {
+        assert_eq!(xml_escape("a & \"b\" <c>"), "a &amp; &quot;b&quot; &lt;c&gt;");
+        assert_eq!(xml_escape("bell\u{7}\tend"), "bell\tend");
+    }
+}
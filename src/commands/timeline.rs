@@ -0,0 +1,205 @@
+use anyhow::Result;
+use console::style;
+use crate::client::PipelineStage;
+use crate::helpers::errors::ErrorKind;
+use crate::helpers::formatting::format_duration;
+use crate::helpers::init::{create_client_for_job, resolve_build_number};
+use crate::helpers::url::to_human_job_path;
+use crate::interactive;
+use crate::output::{self, Table};
+
+/// Characters the whole build takes up on the time axis
+const TIMELINE_WIDTH: usize = 40;
+
+/// Where a stage sits on the build's time axis, in milliseconds from the first stage's start
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Span {
+    offset: i64,
+    duration: i64,
+}
+
+impl Span {
+    fn end(self) -> i64 {
+        self.offset + self.duration
+    }
+}
+
+pub fn execute(job_name: Option<String>, build_number: Option<i32>) -> Result<()> {
+    let client = create_client_for_job(job_name.as_deref(), None)?;
+
+    // Resolve the final job name (handle sub-jobs if present)
+    let final_job_name = interactive::resolve_job_name(&client, job_name.as_deref())?;
+    let build_num = resolve_build_number(&client, &final_job_name, build_number)?;
+    let build_label = format!("{}#{}", to_human_job_path(&final_job_name), build_num);
+
+    let sp = output::spinner("Loading pipeline stages...");
+    let stages = client.get_pipeline_stages(&final_job_name, build_num)?;
+    sp.finish_and_clear();
+
+    let stages = stages.ok_or_else(|| {
+        ErrorKind::NotFound.error(format!(
+            "{} has no stage data. Timelines need a Pipeline job and the Pipeline Stage View plugin.",
+            build_label
+        ))
+    })?;
+    if stages.is_empty() {
+        output::info(&format!("{} has no stages yet", build_label));
+        return Ok(());
+    }
+
+    let spans = place_stages(&stages);
+    let total = spans.iter().flatten().map(|span| span.end()).max().unwrap_or(0);
+
+    output::header(&format!("Timeline: {} ({})", build_label, format_duration(total)));
+    timeline_table(&stages, &spans, total).print();
+
+    let longest = stages
+        .iter()
+        .zip(&spans)
+        .filter_map(|(stage, span)| Some((stage, (*span)?)))
+        .max_by_key(|(_, span)| span.duration);
+    if let Some((stage, span)) = longest
+        && total > 0
+    {
+        output::newline();
+        output::dim(&format!(
+            "Longest stage: {} - {} ({}% of the build)",
+            stage.name,
+            format_duration(span.duration),
+            span.duration * 100 / total
+        ));
+    }
+    Ok(())
+}
+
+/// Each stage's span, relative to the earliest start; None for stages that never ran
+fn place_stages(stages: &[PipelineStage]) -> Vec<Option<Span>> {
+    let started = |stage: &PipelineStage| stage.start_time.filter(|start| *start > 0);
+    let origin = stages.iter().filter_map(started).min().unwrap_or(0);
+    stages
+        .iter()
+        .map(|stage| {
+            let start = started(stage)?;
+            Some(Span { offset: start - origin, duration: stage.duration.unwrap_or(0).max(0) })
+        })
+        .collect()
+}
+
+/// Whether the stage at `index` ran at the same time as another, i.e. in a parallel block
+fn runs_in_parallel(spans: &[Option<Span>], index: usize) -> bool {
+    let Some(span) = spans[index].filter(|span| span.duration > 0) else {
+        return false;
+    };
+    spans.iter().enumerate().any(|(other_index, other)| {
+        other_index != index
+            && other.is_some_and(|other| other.duration > 0 && other.offset < span.end() && span.offset < other.end())
+    })
+}
+
+/// A stage's bar on a `width`-character axis covering `total` milliseconds; even the shortest stage gets one character
+fn bar(span: Span, total: i64, width: usize) -> String {
+    let column = |ms: i64| if total > 0 { (ms.clamp(0, total) * width as i64 / total) as usize } else { 0 };
+    let start = column(span.offset).min(width - 1);
+    let end = if total > 0 { ((span.end().clamp(0, total) * width as i64 + total - 1) / total) as usize } else { 0 };
+    let length = end.saturating_sub(start).max(1).min(width - start);
+    format!("{}{}{}", " ".repeat(start), "█".repeat(length), " ".repeat(width - start - length))
+}
+
+/// Color the text the way the stage's status reads
+fn paint(status: &str, text: &str) -> String {
+    match status {
+        "SUCCESS" => style(text).green().to_string(),
+        "FAILED" => style(text).red().to_string(),
+        "UNSTABLE" | "PAUSED_PENDING_INPUT" => style(text).yellow().to_string(),
+        "IN_PROGRESS" => style(text).cyan().to_string(),
+        _ => style(text).dim().to_string(),
+    }
+}
+
+fn status_label(status: &str) -> &str {
+    match status {
+        "SUCCESS" => "Success",
+        "FAILED" => "Failed",
+        "UNSTABLE" => "Unstable",
+        "ABORTED" => "Aborted",
+        "IN_PROGRESS" => "Running",
+        "NOT_EXECUTED" => "Skipped",
+        "PAUSED_PENDING_INPUT" => "Waiting for input",
+        other => other,
+    }
+}
+
+/// Start relative to the first stage, e.g. `+1m 43s`
+fn format_offset(offset: i64) -> String {
+    if offset == 0 {
+        return "+0s".to_string();
+    }
+    format!("+{}", format_duration(offset))
+}
+
+/// One row per stage: its bar, when it started relative to the first stage, and how long it took
+fn timeline_table(stages: &[PipelineStage], spans: &[Option<Span>], total: i64) -> Table {
+    let mut table = Table::new(&["STAGE", "TIMELINE", "START", "DURATION", "STATUS"]).align_right(2).align_right(3);
+    for (index, (stage, span)) in stages.iter().zip(spans).enumerate() {
+        let mut status = paint(&stage.status, status_label(&stage.status));
+        if runs_in_parallel(spans, index) {
+            status.push_str(&style(" ∥ parallel").dim().to_string());
+        }
+        let row = match span {
+            Some(span) => vec![
+                stage.name.clone(),
+                paint(&stage.status, &bar(*span, total, TIMELINE_WIDTH)),
+                format_offset(span.offset),
+                format_duration(span.duration),
+                status,
+            ],
+            None => vec![stage.name.clone(), " ".repeat(TIMELINE_WIDTH), "-".to_string(), "-".to_string(), status],
+        };
+        table.add_row(row);
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stage(name: &str, start_time: i64, duration: i64) -> PipelineStage {
+        PipelineStage {
+            id: name.to_lowercase(),
+            name: name.to_string(),
+            status: "SUCCESS".to_string(),
+            start_time: Some(start_time),
+            duration: Some(duration),
+        }
+    }
+
+    #[test]
+    fn test_place_stages() {
+        let stages = vec![
+            stage("Checkout", 1_000_000, 10_000),
+            stage("Unit", 1_010_000, 60_000),
+            stage("Lint", 1_010_500, 20_000),
+            stage("Deploy", 0, 0),
+        ];
+        let spans = place_stages(&stages);
+        assert_eq!(spans[0], Some(Span { offset: 0, duration: 10_000 }));
+        assert_eq!(spans[2], Some(Span { offset: 10_500, duration: 20_000 }));
+        assert_eq!(spans[3], None);
+
+        assert!(!runs_in_parallel(&spans, 0));
+        assert!(runs_in_parallel(&spans, 1));
+        assert!(runs_in_parallel(&spans, 2));
+        assert!(!runs_in_parallel(&spans, 3));
+    }
+
+    #[test]
+    fn test_bar() {
+        assert_eq!(bar(Span { offset: 0, duration: 50 }, 100, 10), "█████     ");
+        assert_eq!(bar(Span { offset: 50, duration: 50 }, 100, 10), "     █████");
+        // Short stages still show up, and a bar never runs past the axis
+        assert_eq!(bar(Span { offset: 99, duration: 1 }, 100, 10), "         █");
+        assert_eq!(bar(Span { offset: 0, duration: 0 }, 0, 4), "█   ");
+        assert_eq!(bar(Span { offset: 30, duration: 1 }, 100, 10), "   █      ");
+    }
+}
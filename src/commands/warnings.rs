@@ -0,0 +1,56 @@
+use anyhow::Result;
+use console::style;
+use crate::helpers::errors::ErrorKind;
+use crate::helpers::init::{create_client_for_job, resolve_build_number};
+use crate::helpers::url::to_human_job_path;
+use crate::interactive;
+use crate::output;
+
+pub fn execute(job_name: Option<String>, build_number: Option<i32>, fail_on_new: bool) -> Result<()> {
+    let client = create_client_for_job(job_name.as_deref(), None)?;
+
+    // Resolve the final job name (handle sub-jobs if present)
+    let final_job_name = interactive::resolve_job_name(&client, job_name.as_deref())?;
+    let build_num = resolve_build_number(&client, &final_job_name, build_number)?;
+
+    let sp = output::spinner(&format!("Fetching static analysis results for {}#{}...", to_human_job_path(&final_job_name), build_num));
+    let tools = client.get_warnings_tools(&final_job_name, build_num)?;
+    let mut summaries = Vec::new();
+    for tool in &tools {
+        let summary = client.get_warnings_summary(&final_job_name, build_num, &tool.id)?;
+        summaries.push((tool, summary));
+    }
+    sp.finish_and_clear();
+
+    output::header(&format!("Static analysis: {}#{}", to_human_job_path(&final_job_name), build_num));
+
+    if summaries.is_empty() {
+        output::info("No static analysis tools recorded for this build");
+        return Ok(());
+    }
+
+    let mut total_new = 0;
+    for (tool, summary) in &summaries {
+        total_new += summary.new_size;
+        let new = if summary.new_size > 0 {
+            style(format!("{} new", summary.new_size)).red().to_string()
+        } else {
+            style("0 new").dim().to_string()
+        };
+        let fixed = if summary.fixed_size > 0 {
+            style(format!("{} fixed", summary.fixed_size)).green().to_string()
+        } else {
+            style("0 fixed").dim().to_string()
+        };
+        output::list_item(
+            &format!("{}:", tool.name),
+            &format!("{}, {} outstanding, {}", new, summary.outstanding(), fixed),
+        );
+    }
+
+    if fail_on_new && total_new > 0 {
+        return Err(ErrorKind::BuildFailed.error(format!("{} new static analysis issue(s) found", total_new)));
+    }
+
+    Ok(())
+}
@@ -1,21 +1,98 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Deserializer, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+use crate::helpers::errors::ErrorKind;
+use crate::helpers::formatting::{is_valid_time_pattern, ISO_TIME_FORMAT, RELATIVE_TIME_FORMAT};
+use crate::helpers::params::parse_duration;
+use crate::helpers::url::{parse_job_url, to_jenkins_job_path};
+
+/// Environment variable that points at an alternate config file
+pub const CONFIG_ENV_VAR: &str = "JENKINS_CLI_CONFIG";
+
+/// Prefix of a host selector that picks hosts by tag (`tag:staging` or `tag:env=staging`)
+pub const TAG_SELECTOR_PREFIX: &str = "tag:";
+
+/// Config file path set by the global `--config` flag
+static CONFIG_PATH_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Use an alternate config file for the rest of the process
+pub fn set_config_path(path: PathBuf) {
+    let _ = CONFIG_PATH_OVERRIDE.set(path);
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct JenkinsHost {
     pub host: String,
+    /// Default identity
+    pub user: String,
+    pub token: String,
+    /// Additional named identities (e.g. `admin`, `readonly`) selectable with `--as`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub identities: BTreeMap<String, Identity>,
+    /// How long `build --follow` waits for a queued build to start (e.g. `15m`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue_timeout: Option<String>,
+    /// Free-form labels (e.g. `env: prod`, `team: payments`) for selecting hosts with `tag:`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tags: BTreeMap<String, String>,
+    /// Refuse builds, config changes and other writes through this host (e.g. shared observer credentials)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub read_only: bool,
+}
+
+/// Alternative credentials for a Jenkins host
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Identity {
     pub user: String,
     pub token: String,
 }
 
+impl JenkinsHost {
+    /// Copy of this host using the named identity's credentials
+    pub fn with_identity(&self, identity: &str) -> Result<JenkinsHost> {
+        let credentials = self.identities.get(identity).ok_or_else(|| {
+            let available: Vec<&str> = self.identities.keys().map(|k| k.as_str()).collect();
+            if available.is_empty() {
+                anyhow::anyhow!("Identity '{}' not found (this host has no extra identities)", identity)
+            } else {
+                anyhow::anyhow!("Identity '{}' not found (available: {})", identity, available.join(", "))
+            }
+        })?;
+
+        Ok(JenkinsHost {
+            host: self.host.clone(),
+            user: credentials.user.clone(),
+            token: credentials.token.clone(),
+            identities: BTreeMap::new(),
+            queue_timeout: self.queue_timeout.clone(),
+            tags: self.tags.clone(),
+            read_only: self.read_only,
+        })
+    }
+
+    /// `key=value` matches one tag exactly, a bare value matches any tag with that value
+    pub fn has_tag(&self, tag: &str) -> bool {
+        match tag.split_once('=') {
+            Some((key, value)) => self.tags.get(key).is_some_and(|v| v == value),
+            None => self.tags.values().any(|v| v == tag),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct JobAlias {
     pub job_name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub jenkins: Option<String>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub parameters: HashMap<String, String>,
+    /// Identity to use for this job (see `JenkinsHost::identities`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identity: Option<String>,
 }
 
 impl<'de> Deserialize<'de> for JobAlias {
@@ -31,6 +108,10 @@ impl<'de> Deserialize<'de> for JobAlias {
                 job_name: String,
                 #[serde(default)]
                 jenkins: Option<String>,
+                #[serde(default)]
+                parameters: HashMap<String, serde_yaml::Value>,
+                #[serde(default)]
+                identity: Option<String>,
             },
         }
 
@@ -38,8 +119,57 @@ impl<'de> Deserialize<'de> for JobAlias {
             JobAliasHelper::Simple(job_name) => Ok(JobAlias {
                 job_name,
                 jenkins: None,
+                parameters: HashMap::new(),
+                identity: None,
             }),
-            JobAliasHelper::Full { job_name, jenkins } => Ok(JobAlias { job_name, jenkins }),
+            JobAliasHelper::Full { job_name, jenkins, parameters, identity } => {
+                // Accept unquoted YAML scalars (e.g. `DEPLOY: true`) as parameter values
+                let parameters = parameters
+                    .into_iter()
+                    .map(|(name, value)| {
+                        let value = match value {
+                            serde_yaml::Value::String(s) => s,
+                            serde_yaml::Value::Bool(b) => b.to_string(),
+                            serde_yaml::Value::Number(n) => n.to_string(),
+                            serde_yaml::Value::Null => String::new(),
+                            _ => {
+                                return Err(serde::de::Error::custom(format!(
+                                    "parameter '{}' must be a string, number or boolean",
+                                    name
+                                )))
+                            }
+                        };
+                        Ok((name, value))
+                    })
+                    .collect::<Result<HashMap<_, _>, D::Error>>()?;
+
+                Ok(JobAlias { job_name, jenkins, parameters, identity })
+            }
+        }
+    }
+}
+
+/// Host and default job selected automatically when working inside a matching directory
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct DirectoryProfile {
+    /// Directory prefix (supports `~`) that the current directory must be inside
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Substring matched against the repository's git remote URLs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_remote: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jenkins: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub job: Option<String>,
+}
+
+impl DirectoryProfile {
+    fn expanded_path(&self) -> Option<PathBuf> {
+        let path = self.path.as_deref()?;
+        match path.strip_prefix("~") {
+            Some(rest) => dirs::home_dir().map(|home| home.join(rest.trim_start_matches('/'))),
+            None => Some(PathBuf::from(path)),
         }
     }
 }
@@ -50,6 +180,57 @@ pub struct Config {
     pub jenkins: HashMap<String, JenkinsHost>,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub job_aliases: HashMap<String, JobAlias>,
+    /// Jobs `jenkins dash` shows next to the aliases: job paths, or job URLs to read them from a specific host
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pinned_jobs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub profiles: Vec<DirectoryProfile>,
+    /// Debug log file used when `--log-file` isn't given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_file: Option<PathBuf>,
+    /// Report followed build results back to the commit on GitHub/GitLab
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit_status: Option<CommitStatusConfig>,
+    /// Webhook notified when a followed build finishes, unless `--notify` is given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notify_webhook: Option<String>,
+    /// How long read-only API responses are reused before asking the controller again
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache: Option<CacheConfig>,
+    /// How build times are shown: `relative` (default), `iso`, or a pattern like `%Y-%m-%d %H:%M`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_format: Option<String>,
+}
+
+/// TTLs of the response cache, as durations like `30s` or `5m` (`0s` disables a kind)
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct CacheConfig {
+    /// Job lists used by the selection prompts (defaults to 60s)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jobs_ttl: Option<String>,
+    /// Job and build status shown by `status` (defaults to 10s)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_ttl: Option<String>,
+}
+
+/// Where `build --follow` posts commit statuses for finished builds
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CommitStatusConfig {
+    pub provider: CommitStatusProvider,
+    pub token: String,
+    /// Status name shown on the commit/PR (defaults to `jenkins`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+    /// API base URL for self-hosted instances (e.g. `https://github.example.com/api/v3`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CommitStatusProvider {
+    Github,
+    Gitlab,
 }
 
 impl Config {
@@ -61,10 +242,10 @@ impl Config {
         }
 
         let content = fs::read_to_string(&config_path)
-            .context("Failed to read config file")?;
+            .context(ErrorKind::Config.tag("Failed to read config file"))?;
 
         let config: Config = serde_yaml::from_str(&content)
-            .context("Failed to parse config file")?;
+            .context(ErrorKind::Config.tag("Failed to parse config file (run 'jenkins config validate' for details)"))?;
 
         Ok(config)
     }
@@ -99,11 +280,60 @@ impl Config {
 
     pub fn get_jenkins(&self, name: &str) -> Result<&JenkinsHost> {
         self.jenkins.get(name)
-            .ok_or_else(|| anyhow::anyhow!("Jenkins '{}' not found", name))
+            .ok_or_else(|| ErrorKind::Config.error(format!("Jenkins '{}' not found", name)))
+    }
+
+    /// Host names a `--jenkins` value stands for: the name itself, or every host with a `tag:` (sorted)
+    pub fn select_hosts(&self, selector: &str) -> Result<Vec<String>> {
+        let Some(tag) = selector.strip_prefix(TAG_SELECTOR_PREFIX) else {
+            return Ok(vec![selector.to_string()]);
+        };
+        let mut names: Vec<String> = self
+            .jenkins
+            .iter()
+            .filter(|(_, host)| host.has_tag(tag))
+            .map(|(name, _)| name.clone())
+            .collect();
+        if names.is_empty() {
+            anyhow::bail!("No Jenkins host is tagged '{}'", tag);
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    /// Expand several selectors, keeping the first occurrence of each host
+    pub fn expand_hosts(&self, selectors: &[String]) -> Result<Vec<String>> {
+        let mut names = Vec::new();
+        for selector in selectors {
+            for name in self.select_hosts(selector)? {
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+        Ok(names)
     }
 
     pub fn add_job_alias(&mut self, alias: String, job_name: String, jenkins: Option<String>) {
-        self.job_aliases.insert(alias, JobAlias { job_name, jenkins });
+        self.add_job_alias_with_parameters(alias, job_name, jenkins, HashMap::new());
+    }
+
+    pub fn add_job_alias_with_parameters(
+        &mut self,
+        alias: String,
+        job_name: String,
+        jenkins: Option<String>,
+        parameters: HashMap<String, String>,
+    ) {
+        self.job_aliases.insert(alias, JobAlias { job_name, jenkins, parameters, identity: None });
+    }
+
+    /// Get the parameter overrides baked into an alias (empty if not an alias)
+    pub fn alias_parameters(&self, alias_or_name: &str) -> HashMap<String, String> {
+        self.job_aliases
+            .get(alias_or_name)
+            .map(|alias| alias.parameters.clone())
+            .unwrap_or_default()
     }
 
     pub fn remove_job_alias(&mut self, alias: &str) -> Result<()> {
@@ -113,6 +343,17 @@ impl Config {
         Ok(())
     }
 
+    /// Rename an alias, keeping its job binding and settings intact
+    pub fn rename_job_alias(&mut self, old: &str, new: &str) -> Result<()> {
+        if self.job_aliases.contains_key(new) {
+            anyhow::bail!("Job alias '{}' already exists", new);
+        }
+        let alias = self.job_aliases.remove(old)
+            .ok_or_else(|| anyhow::anyhow!("Job alias '{}' not found", old))?;
+        self.job_aliases.insert(new.to_string(), alias);
+        Ok(())
+    }
+
     #[allow(dead_code)]
     pub fn get_job_name(&self, alias_or_name: &str) -> String {
         self.job_aliases
@@ -121,30 +362,433 @@ impl Config {
             .unwrap_or_else(|| alias_or_name.to_string())
     }
 
-    pub fn resolve_job_name(&self, alias_or_name: &str) -> (String, bool, Option<String>) {
-        if let Some(alias) = self.job_aliases.get(alias_or_name) {
-            (alias.job_name.clone(), true, alias.jenkins.clone())
+    /// Resolve an alias, plain job path or pasted job URL to the Jenkins `folder/job/child` form
+    pub fn resolve_job_name(&self, alias_or_name: &str) -> Result<(String, bool, Option<String>)> {
+        if let Some((jenkins, job_name, _)) = self.match_job_url(alias_or_name)? {
+            return Ok((job_name, false, Some(jenkins)));
+        }
+
+        Ok(if let Some(alias) = self.job_aliases.get(alias_or_name) {
+            (to_jenkins_job_path(&alias.job_name), true, alias.jenkins.clone())
         } else {
-            (alias_or_name.to_string(), false, None)
+            (to_jenkins_job_path(alias_or_name), false, None)
+        })
+    }
+
+    /// Match a pasted Jenkins URL against the configured hosts
+    /// Returns the host name, job path and build number (if the URL points at a build), or None if the
+    /// input isn't a URL; a URL no configured host serves is an error rather than a job name
+    pub fn match_job_url(&self, input: &str) -> Result<Option<(String, String, Option<i32>)>> {
+        if !input.starts_with("http://") && !input.starts_with("https://") {
+            return Ok(None);
         }
+
+        // Prefer the longest host URL in case hosts share a prefix
+        let mut hosts: Vec<(&String, &JenkinsHost)> = self.jenkins.iter().collect();
+        hosts.sort_by_key(|(_, host)| std::cmp::Reverse(host.host.len()));
+
+        let matched = hosts.into_iter().find_map(|(name, host)| {
+            parse_job_url(input, &host.host).map(|(job, build)| (name.clone(), job, build))
+        });
+        if matched.is_none() {
+            let configured: Vec<&str> = self.jenkins.values().map(|host| host.host.as_str()).collect();
+            let configured = if configured.is_empty() { "none".to_string() } else { configured.join(", ") };
+            return Err(ErrorKind::Config.error(format!(
+                "URL does not match any configured Jenkins host (configured: {})",
+                configured
+            )));
+        }
+        Ok(matched)
+    }
+
+    /// Find the profile for the current directory, if any
+    pub fn active_profile(&self) -> Option<&DirectoryProfile> {
+        if self.profiles.is_empty() {
+            return None;
+        }
+        let cwd = std::env::current_dir().ok()?;
+        let remotes = crate::helpers::git::remote_urls(&cwd);
+        self.match_profile(&cwd, &remotes)
     }
 
-    fn config_path() -> Result<PathBuf> {
+    /// Match a profile by directory (most specific path wins), falling back to git remotes
+    pub fn match_profile(&self, cwd: &Path, remotes: &[String]) -> Option<&DirectoryProfile> {
+        let by_path = self
+            .profiles
+            .iter()
+            .filter_map(|profile| profile.expanded_path().map(|path| (profile, path)))
+            .filter(|(_, path)| cwd.starts_with(path))
+            .max_by_key(|(_, path)| path.components().count())
+            .map(|(profile, _)| profile);
+
+        by_path.or_else(|| {
+            self.profiles.iter().find(|profile| {
+                profile.git_remote.as_deref().is_some_and(|pattern| {
+                    remotes.iter().any(|remote| remote.contains(pattern))
+                })
+            })
+        })
+    }
+
+    /// Resolve the config file path
+    /// Priority: `--config` flag > JENKINS_CLI_CONFIG > ~/.config/jenkins-cli/config.yml
+    pub fn config_path() -> Result<PathBuf> {
+        if let Some(path) = CONFIG_PATH_OVERRIDE.get() {
+            return Ok(path.clone());
+        }
+
+        if let Some(path) = std::env::var_os(CONFIG_ENV_VAR).filter(|p| !p.is_empty()) {
+            return Ok(PathBuf::from(path));
+        }
+
         let home = dirs::home_dir()
             .context("Failed to get home directory")?;
         Ok(home.join(".config").join("jenkins-cli").join("config.yml"))
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IssueSeverity {
+    Error,
+    Warning,
+}
+
+/// A problem found while validating a config file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigIssue {
+    pub severity: IssueSeverity,
+    /// 1-based line number, when the problem can be located
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+const TOP_LEVEL_KEYS: &[&str] = &["jenkins", "job_aliases", "pinned_jobs", "profiles", "log_file", "commit_status", "notify_webhook", "cache", "time_format"];
+const HOST_KEYS: &[&str] = &["host", "user", "token"];
+const HOST_OPTIONAL_KEYS: &[&str] = &["identities", "queue_timeout", "tags", "read_only"];
+const IDENTITY_KEYS: &[&str] = &["user", "token"];
+const ALIAS_KEYS: &[&str] = &["job_name", "jenkins", "parameters", "identity"];
+const PROFILE_KEYS: &[&str] = &["path", "git_remote", "jenkins", "job"];
+const COMMIT_STATUS_KEYS: &[&str] = &["provider", "token"];
+const COMMIT_STATUS_OPTIONAL_KEYS: &[&str] = &["context", "api_url"];
+const CACHE_KEYS: &[&str] = &["jobs_ttl", "status_ttl"];
+
+/// Validate raw config file content, reporting every problem found
+pub fn validate_config(content: &str) -> Vec<ConfigIssue> {
+    let mut issues = Vec::new();
+
+    let error = |line: Option<usize>, message: String| ConfigIssue { severity: IssueSeverity::Error, line, message };
+    let warning = |line: Option<usize>, message: String| ConfigIssue { severity: IssueSeverity::Warning, line, message };
+
+    // Syntax errors (including duplicate keys) carry a location from the parser
+    let root: serde_yaml::Value = match serde_yaml::from_str(content) {
+        Ok(value) => value,
+        Err(e) => {
+            issues.push(error(e.location().map(|l| l.line()), e.to_string()));
+            return issues;
+        }
+    };
+
+    let root = match root {
+        serde_yaml::Value::Mapping(map) => map,
+        serde_yaml::Value::Null => return issues,
+        _ => {
+            issues.push(error(Some(1), "Config file must be a mapping of settings".to_string()));
+            return issues;
+        }
+    };
+
+    for key in root.keys().filter_map(|k| k.as_str()) {
+        if !TOP_LEVEL_KEYS.contains(&key) {
+            issues.push(warning(locate_key(content, &[key]), format!("Unknown key '{}'", key)));
+        }
+    }
+
+    let mut host_names: Vec<String> = Vec::new();
+    if let Some(hosts) = root.get("jenkins").and_then(|v| v.as_mapping()) {
+        for (name, host) in hosts {
+            let name = yaml_key(name);
+            let line = locate_key(content, &["jenkins", &name]);
+            host_names.push(name.clone());
+
+            let Some(host) = host.as_mapping() else {
+                issues.push(error(line, format!("Jenkins host '{}' must be a mapping with host, user and token", name)));
+                continue;
+            };
+            for field in HOST_KEYS {
+                if !host.contains_key(*field) {
+                    issues.push(error(line, format!("Jenkins host '{}' is missing required field '{}'", name, field)));
+                }
+            }
+            for key in host.keys().map(yaml_key) {
+                if !HOST_KEYS.contains(&key.as_str()) && !HOST_OPTIONAL_KEYS.contains(&key.as_str()) {
+                    issues.push(warning(
+                        locate_key(content, &["jenkins", &name, &key]),
+                        format!("Unknown key '{}' in Jenkins host '{}'", key, name),
+                    ));
+                }
+            }
+            if let Some(timeout) = host.get("queue_timeout")
+                && let Err(e) = parse_duration(&yaml_key(timeout))
+            {
+                issues.push(error(locate_key(content, &["jenkins", &name, "queue_timeout"]), e));
+            }
+            if let Some(tags) = host.get("tags")
+                && !tags.as_mapping().is_some_and(|tags| tags.values().all(|v| v.is_string()))
+            {
+                issues.push(error(
+                    locate_key(content, &["jenkins", &name, "tags"]),
+                    format!("Tags of Jenkins host '{}' must map names to text values (e.g. env: prod)", name),
+                ));
+            }
+            if let Some(identities) = host.get("identities").and_then(|v| v.as_mapping()) {
+                for (identity_name, identity) in identities {
+                    let identity_name = yaml_key(identity_name);
+                    let identity_line = locate_key(content, &["jenkins", &name, "identities", &identity_name]);
+                    for field in IDENTITY_KEYS {
+                        if !identity.as_mapping().is_some_and(|m| m.contains_key(*field)) {
+                            issues.push(error(
+                                identity_line,
+                                format!("Identity '{}' of Jenkins host '{}' is missing required field '{}'", identity_name, name, field),
+                            ));
+                        }
+                    }
+                }
+            }
+            if let Some(url) = host.get("host").and_then(|v| v.as_str())
+                && !url.starts_with("http://")
+                && !url.starts_with("https://")
+            {
+                issues.push(error(
+                    locate_key(content, &["jenkins", &name, "host"]),
+                    format!("Jenkins host '{}' URL must start with http:// or https://", name),
+                ));
+            }
+        }
+    }
+    report_case_duplicates(content, "jenkins", "Jenkins host", &host_names, &mut issues);
+
+    if let Some(aliases) = root.get("job_aliases").and_then(|v| v.as_mapping()) {
+        let mut alias_names = Vec::new();
+        for (name, alias) in aliases {
+            let name = yaml_key(name);
+            let line = locate_key(content, &["job_aliases", &name]);
+            alias_names.push(name.clone());
+
+            match alias {
+                serde_yaml::Value::String(_) => {}
+                serde_yaml::Value::Mapping(alias) => {
+                    if !alias.contains_key("job_name") {
+                        issues.push(error(line, format!("Job alias '{}' is missing required field 'job_name'", name)));
+                    }
+                    for key in alias.keys().map(yaml_key) {
+                        if !ALIAS_KEYS.contains(&key.as_str()) {
+                            issues.push(warning(
+                                locate_key(content, &["job_aliases", &name, &key]),
+                                format!("Unknown key '{}' in job alias '{}'", key, name),
+                            ));
+                        }
+                    }
+                    if let Some(jenkins) = alias.get("jenkins").and_then(|v| v.as_str())
+                        && !host_names.iter().any(|h| h == jenkins)
+                    {
+                        issues.push(error(
+                            locate_key(content, &["job_aliases", &name, "jenkins"]),
+                            format!("Job alias '{}' references unknown Jenkins host '{}'", name, jenkins),
+                        ));
+                    }
+                }
+                _ => issues.push(error(line, format!("Job alias '{}' must be a job name or a mapping", name))),
+            }
+        }
+        report_case_duplicates(content, "job_aliases", "Job alias", &alias_names, &mut issues);
+    }
+
+    if let Some(pinned) = root.get("pinned_jobs")
+        && !pinned.as_sequence().is_some_and(|jobs| jobs.iter().all(|job| job.is_string()))
+    {
+        issues.push(error(
+            locate_key(content, &["pinned_jobs"]),
+            "pinned_jobs must be a list of job paths or job URLs".to_string(),
+        ));
+    }
+
+    if let Some(time_format) = root.get("time_format") {
+        let line = locate_key(content, &["time_format"]);
+        match time_format.as_str() {
+            Some(RELATIVE_TIME_FORMAT | ISO_TIME_FORMAT) => {}
+            Some(pattern) if !is_valid_time_pattern(pattern) => issues.push(error(
+                line,
+                format!("time_format '{}' has an unknown %-field; see the strftime fields chrono supports", pattern),
+            )),
+            Some(pattern) if pattern.contains('%') => {}
+            Some(pattern) => issues.push(warning(
+                line,
+                format!("time_format '{}' has no %-fields; use 'relative', 'iso' or a pattern like '%Y-%m-%d %H:%M'", pattern),
+            )),
+            None => issues.push(error(line, "time_format must be a string".to_string())),
+        }
+    }
+
+    if let Some(profiles) = root.get("profiles").and_then(|v| v.as_sequence()) {
+        for (index, profile) in profiles.iter().enumerate() {
+            let Some(profile) = profile.as_mapping() else {
+                issues.push(error(locate_key(content, &["profiles"]), format!("Profile #{} must be a mapping", index + 1)));
+                continue;
+            };
+            if !profile.contains_key("path") && !profile.contains_key("git_remote") {
+                issues.push(error(
+                    locate_key(content, &["profiles"]),
+                    format!("Profile #{} needs a 'path' or 'git_remote' to match", index + 1),
+                ));
+            }
+            for key in profile.keys().map(yaml_key) {
+                if !PROFILE_KEYS.contains(&key.as_str()) {
+                    issues.push(warning(
+                        locate_key(content, &["profiles"]),
+                        format!("Unknown key '{}' in profile #{}", key, index + 1),
+                    ));
+                }
+            }
+            if let Some(jenkins) = profile.get("jenkins").and_then(|v| v.as_str())
+                && !host_names.iter().any(|h| h == jenkins)
+            {
+                issues.push(error(
+                    locate_key(content, &["profiles"]),
+                    format!("Profile #{} references unknown Jenkins host '{}'", index + 1, jenkins),
+                ));
+            }
+        }
+    }
+
+    if let Some(commit_status) = root.get("commit_status") {
+        let line = locate_key(content, &["commit_status"]);
+        match commit_status.as_mapping() {
+            Some(commit_status) => {
+                for field in COMMIT_STATUS_KEYS {
+                    if !commit_status.contains_key(*field) {
+                        issues.push(error(line, format!("commit_status is missing required field '{}'", field)));
+                    }
+                }
+                for key in commit_status.keys().map(yaml_key) {
+                    if !COMMIT_STATUS_KEYS.contains(&key.as_str()) && !COMMIT_STATUS_OPTIONAL_KEYS.contains(&key.as_str()) {
+                        issues.push(warning(
+                            locate_key(content, &["commit_status", &key]),
+                            format!("Unknown key '{}' in commit_status", key),
+                        ));
+                    }
+                }
+                if let Some(provider) = commit_status.get("provider").and_then(|v| v.as_str())
+                    && !matches!(provider, "github" | "gitlab")
+                {
+                    issues.push(error(
+                        locate_key(content, &["commit_status", "provider"]),
+                        format!("commit_status provider must be 'github' or 'gitlab', not '{}'", provider),
+                    ));
+                }
+            }
+            None => issues.push(error(line, "commit_status must be a mapping with provider and token".to_string())),
+        }
+    }
+
+    if let Some(cache) = root.get("cache") {
+        match cache.as_mapping() {
+            Some(cache) => {
+                for (key, value) in cache {
+                    let key = yaml_key(key);
+                    let line = locate_key(content, &["cache", &key]);
+                    if !CACHE_KEYS.contains(&key.as_str()) {
+                        issues.push(warning(line, format!("Unknown key '{}' in cache", key)));
+                    } else if let Err(e) = parse_duration(&yaml_key(value)) {
+                        issues.push(error(line, e));
+                    }
+                }
+            }
+            None => issues.push(error(locate_key(content, &["cache"]), "cache must be a mapping of TTLs".to_string())),
+        }
+    }
+
+    // Catch anything the structural checks missed (e.g. wrong value types)
+    if !issues.iter().any(|i| i.severity == IssueSeverity::Error)
+        && let Err(e) = serde_yaml::from_str::<Config>(content)
+    {
+        issues.push(error(e.location().map(|l| l.line()), e.to_string()));
+    }
+
+    issues
+}
+
+fn yaml_key(key: &serde_yaml::Value) -> String {
+    match key {
+        serde_yaml::Value::String(s) => s.clone(),
+        other => serde_yaml::to_string(other).unwrap_or_default().trim().to_string(),
+    }
+}
+
+/// Warn about names that only differ by case, which are easy to mix up
+fn report_case_duplicates(content: &str, section: &str, kind: &str, names: &[String], issues: &mut Vec<ConfigIssue>) {
+    let mut seen: HashMap<String, &String> = HashMap::new();
+    for name in names {
+        if let Some(first) = seen.insert(name.to_lowercase(), name) {
+            issues.push(ConfigIssue {
+                severity: IssueSeverity::Warning,
+                line: locate_key(content, &[section, name]),
+                message: format!("{} names '{}' and '{}' differ only by case", kind, first, name),
+            });
+        }
+    }
+}
+
+/// Find the 1-based line of a nested key path (e.g. `["jenkins", "prod", "host"]`)
+fn locate_key(content: &str, path: &[&str]) -> Option<usize> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut start = 0;
+    let mut parent_indent: Option<usize> = None;
+    let mut found = None;
+
+    for key in path {
+        let mut matched = None;
+        for (index, line) in lines.iter().enumerate().skip(start) {
+            let trimmed = line.trim_start();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let indent = line.len() - trimmed.len();
+            // Stop once we've left the parent's block
+            if let Some(parent) = parent_indent
+                && indent <= parent
+                && index > start
+            {
+                break;
+            }
+            let unquoted = trimmed.trim_start_matches("- ");
+            let key_matches = [format!("{}:", key), format!("\"{}\":", key), format!("'{}':", key)]
+                .iter()
+                .any(|candidate| unquoted.starts_with(candidate.as_str()));
+            if key_matches {
+                matched = Some((index, indent));
+                break;
+            }
+        }
+        let (index, indent) = matched?;
+        found = Some(index + 1);
+        start = index + 1;
+        parent_indent = Some(indent);
+    }
+
+    found
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::helpers::errors::kind_of;
 
     fn create_test_host(name: &str) -> JenkinsHost {
         JenkinsHost {
             host: format!("https://jenkins-{}.example.com", name),
             user: format!("user-{}", name),
             token: format!("token-{}", name),
+            ..Default::default()
         }
     }
 
@@ -197,6 +841,25 @@ mod tests {
         assert_eq!(result.unwrap().host, "https://jenkins-prod.example.com");
     }
 
+    #[test]
+    fn test_select_hosts_by_tag() {
+        let mut config = Config::default();
+        for (name, env) in [("prod-eu", "prod"), ("prod-us", "prod"), ("stage", "staging")] {
+            let mut host = create_test_host(name);
+            host.tags = BTreeMap::from([("env".to_string(), env.to_string()), ("team".to_string(), "payments".to_string())]);
+            config.add_jenkins(name.to_string(), host);
+        }
+
+        assert_eq!(config.select_hosts("stage").unwrap(), vec!["stage"]);
+        assert_eq!(config.select_hosts("tag:prod").unwrap(), vec!["prod-eu", "prod-us"]);
+        assert_eq!(config.select_hosts("tag:env=staging").unwrap(), vec!["stage"]);
+        assert!(config.select_hosts("tag:team=prod").is_err());
+        assert_eq!(
+            config.expand_hosts(&["stage".to_string(), "tag:payments".to_string()]).unwrap(),
+            vec!["stage", "prod-eu", "prod-us"]
+        );
+    }
+
     #[test]
     fn test_yaml_serialization() {
         let mut config = Config::default();
@@ -257,6 +920,44 @@ jenkins:
         assert_eq!(result.unwrap_err().to_string(), "Job alias 'nonexistent' not found");
     }
 
+    #[test]
+    fn test_rename_job_alias() {
+        let mut config = Config::default();
+        let mut params = HashMap::new();
+        params.insert("ENVIRONMENT".to_string(), "staging".to_string());
+        config.add_job_alias_with_parameters("old".to_string(), "deploy".to_string(), Some("dev".to_string()), params);
+
+        let result = config.rename_job_alias("old", "new");
+        assert!(result.is_ok());
+        assert!(!config.job_aliases.contains_key("old"));
+
+        let alias = config.job_aliases.get("new").unwrap();
+        assert_eq!(alias.job_name, "deploy");
+        assert_eq!(alias.jenkins, Some("dev".to_string()));
+        assert_eq!(alias.parameters.get("ENVIRONMENT"), Some(&"staging".to_string()));
+    }
+
+    #[test]
+    fn test_rename_nonexistent_job_alias() {
+        let mut config = Config::default();
+        let result = config.rename_job_alias("old", "new");
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err().to_string(), "Job alias 'old' not found");
+    }
+
+    #[test]
+    fn test_rename_job_alias_to_existing() {
+        let mut config = Config::default();
+        config.add_job_alias("old".to_string(), "job-a".to_string(), None);
+        config.add_job_alias("new".to_string(), "job-b".to_string(), None);
+
+        let result = config.rename_job_alias("old", "new");
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err().to_string(), "Job alias 'new' already exists");
+        assert_eq!(config.job_aliases.get("old").unwrap().job_name, "job-a");
+        assert_eq!(config.job_aliases.get("new").unwrap().job_name, "job-b");
+    }
+
     #[test]
     fn test_get_job_name_with_alias() {
         let mut config = Config::default();
@@ -271,12 +972,50 @@ jenkins:
         assert_eq!(config.get_job_name("actual-job-name"), "actual-job-name");
     }
 
+    #[test]
+    fn test_resolve_job_name_natural_path() {
+        let mut config = Config::default();
+        config.add_job_alias("api".to_string(), "team/backend/api".to_string(), None);
+
+        assert_eq!(config.resolve_job_name("team/backend/api").unwrap().0, "team/job/backend/job/api");
+        assert_eq!(config.resolve_job_name("team/job/api").unwrap().0, "team/job/api");
+        assert_eq!(config.resolve_job_name("api").unwrap().0, "team/job/backend/job/api");
+    }
+
+    #[test]
+    fn test_resolve_job_name_from_url() {
+        let mut config = Config::default();
+        config.add_jenkins("prod".to_string(), JenkinsHost {
+            host: "https://jenkins.example.com/".to_string(),
+            user: "user".to_string(),
+            token: "token".to_string(),
+            ..Default::default()
+        });
+
+        let (job_name, is_alias, jenkins) =
+            config.resolve_job_name("https://jenkins.example.com/job/team/job/api/42/").unwrap();
+        assert_eq!(job_name, "team/job/api");
+        assert!(!is_alias);
+        assert_eq!(jenkins, Some("prod".to_string()));
+
+        assert_eq!(
+            config.match_job_url("https://jenkins.example.com/job/api/7/").unwrap(),
+            Some(("prod".to_string(), "api".to_string(), Some(7)))
+        );
+        assert_eq!(config.match_job_url("team/api").unwrap(), None);
+
+        // A URL of some other server is not a job name
+        let error = config.resolve_job_name("https://unknown.example.com/job/api/").unwrap_err();
+        assert_eq!(kind_of(&error), Some(ErrorKind::Config));
+        assert!(error.to_string().contains("configured: https://jenkins.example.com/"));
+    }
+
     #[test]
     fn test_resolve_job_name_with_alias() {
         let mut config = Config::default();
         config.add_job_alias("my-job".to_string(), "my-very-long-job-name".to_string(), None);
 
-        let (job_name, is_alias, jenkins) = config.resolve_job_name("my-job");
+        let (job_name, is_alias, jenkins) = config.resolve_job_name("my-job").unwrap();
         assert_eq!(job_name, "my-very-long-job-name");
         assert!(is_alias);
         assert_eq!(jenkins, None);
@@ -285,7 +1024,7 @@ jenkins:
     #[test]
     fn test_resolve_job_name_without_alias() {
         let config = Config::default();
-        let (job_name, is_alias, jenkins) = config.resolve_job_name("actual-job-name");
+        let (job_name, is_alias, jenkins) = config.resolve_job_name("actual-job-name").unwrap();
         assert_eq!(job_name, "actual-job-name");
         assert!(!is_alias);
         assert_eq!(jenkins, None);
@@ -307,7 +1046,7 @@ jenkins:
         let mut config = Config::default();
         config.add_job_alias("my-job".to_string(), "my-very-long-job-name".to_string(), Some("dev".to_string()));
 
-        let (job_name, is_alias, jenkins) = config.resolve_job_name("my-job");
+        let (job_name, is_alias, jenkins) = config.resolve_job_name("my-job").unwrap();
         assert_eq!(job_name, "my-very-long-job-name");
         assert!(is_alias);
         assert_eq!(jenkins, Some("dev".to_string()));
@@ -383,6 +1122,129 @@ job_aliases:
         assert_eq!(alias2.jenkins, Some("dev".to_string()));
     }
 
+    #[test]
+    fn test_yaml_deserialization_with_alias_parameters() {
+        let yaml = r#"
+jenkins: {}
+job_aliases:
+  deploy-staging:
+    job_name: deploy
+    parameters:
+      ENVIRONMENT: staging
+      DEPLOY: true
+      REPLICAS: 3
+"#;
+
+        let config: Config = serde_yaml::from_str(yaml).unwrap();
+        let alias = config.job_aliases.get("deploy-staging").unwrap();
+        assert_eq!(alias.job_name, "deploy");
+        assert_eq!(alias.parameters.len(), 3);
+        assert_eq!(alias.parameters.get("ENVIRONMENT"), Some(&"staging".to_string()));
+        assert_eq!(alias.parameters.get("DEPLOY"), Some(&"true".to_string()));
+        assert_eq!(alias.parameters.get("REPLICAS"), Some(&"3".to_string()));
+    }
+
+    #[test]
+    fn test_yaml_deserialization_with_invalid_alias_parameter() {
+        let yaml = r#"
+job_aliases:
+  deploy-staging:
+    job_name: deploy
+    parameters:
+      ENVIRONMENT: [staging, prod]
+"#;
+
+        let result: Result<Config, _> = serde_yaml::from_str(yaml);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_alias_parameters() {
+        let mut config = Config::default();
+        let mut params = HashMap::new();
+        params.insert("ENVIRONMENT".to_string(), "staging".to_string());
+        config.add_job_alias_with_parameters("deploy-staging".to_string(), "deploy".to_string(), None, params);
+
+        let alias_params = config.alias_parameters("deploy-staging");
+        assert_eq!(alias_params.get("ENVIRONMENT"), Some(&"staging".to_string()));
+        assert!(config.alias_parameters("deploy").is_empty());
+    }
+
+    #[test]
+    fn test_yaml_serialization_with_alias_parameters() {
+        let mut config = Config::default();
+        let mut params = HashMap::new();
+        params.insert("ENVIRONMENT".to_string(), "staging".to_string());
+        config.add_job_alias_with_parameters("deploy-staging".to_string(), "deploy".to_string(), None, params);
+        config.add_job_alias("plain".to_string(), "plain-job".to_string(), None);
+
+        let yaml = serde_yaml::to_string(&config).unwrap();
+        assert!(yaml.contains("parameters:"));
+        assert!(yaml.contains("ENVIRONMENT: staging"));
+        // Aliases without parameters don't serialize an empty map
+        assert_eq!(yaml.matches("parameters:").count(), 1);
+    }
+
+    #[test]
+    fn test_yaml_deserialization_with_profiles() {
+        let yaml = r#"
+jenkins: {}
+profiles:
+  - path: /work/payments
+    jenkins: prod
+    job: payments/job/api
+  - git_remote: github.com/acme/web
+    jenkins: dev
+"#;
+
+        let config: Config = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.profiles.len(), 2);
+        assert_eq!(config.profiles[0].path, Some("/work/payments".to_string()));
+        assert_eq!(config.profiles[0].job, Some("payments/job/api".to_string()));
+        assert_eq!(config.profiles[1].git_remote, Some("github.com/acme/web".to_string()));
+        assert_eq!(config.profiles[1].job, None);
+    }
+
+    #[test]
+    fn test_match_profile_by_path() {
+        let mut config = Config::default();
+        config.profiles.push(DirectoryProfile {
+            path: Some("/work".to_string()),
+            jenkins: Some("dev".to_string()),
+            ..Default::default()
+        });
+        config.profiles.push(DirectoryProfile {
+            path: Some("/work/payments".to_string()),
+            jenkins: Some("prod".to_string()),
+            ..Default::default()
+        });
+
+        let profile = config.match_profile(Path::new("/work/payments/src"), &[]).unwrap();
+        assert_eq!(profile.jenkins, Some("prod".to_string()));
+
+        let profile = config.match_profile(Path::new("/work/web"), &[]).unwrap();
+        assert_eq!(profile.jenkins, Some("dev".to_string()));
+
+        // Prefix matching is per path component
+        assert!(config.match_profile(Path::new("/workspace"), &[]).is_none());
+    }
+
+    #[test]
+    fn test_match_profile_by_git_remote() {
+        let mut config = Config::default();
+        config.profiles.push(DirectoryProfile {
+            git_remote: Some("github.com/acme/web".to_string()),
+            job: Some("web".to_string()),
+            ..Default::default()
+        });
+
+        let remotes = vec!["git@github.com:acme/api.git".to_string(), "https://github.com/acme/web.git".to_string()];
+        let profile = config.match_profile(Path::new("/tmp/checkout"), &remotes).unwrap();
+        assert_eq!(profile.job, Some("web".to_string()));
+
+        assert!(config.match_profile(Path::new("/tmp/checkout"), &[]).is_none());
+    }
+
     #[test]
     fn test_yaml_serialization_with_jenkins_in_alias() {
         let mut config = Config::default();
@@ -396,4 +1258,249 @@ job_aliases:
         assert!(yaml.contains("job_name: dev-build-job"));
         assert!(yaml.contains("jenkins: dev"));
     }
+
+    #[test]
+    fn test_validate_config_valid() {
+        let yaml = r#"
+jenkins:
+  prod:
+    host: https://jenkins-prod.example.com
+    user: user-prod
+    token: token-prod
+job_aliases:
+  api: team/job/api
+  deploy:
+    job_name: deploy
+    jenkins: prod
+"#;
+        assert!(validate_config(yaml).is_empty());
+    }
+
+    #[test]
+    fn test_host_identities() {
+        let yaml = r#"
+jenkins:
+  prod:
+    host: https://jenkins-prod.example.com
+    user: admin
+    token: admin-token
+    identities:
+      readonly:
+        user: viewer
+        token: viewer-token
+job_aliases:
+  deploy:
+    job_name: deploy
+    jenkins: prod
+    identity: readonly
+"#;
+        assert!(validate_config(yaml).is_empty());
+
+        let config: Config = serde_yaml::from_str(yaml).unwrap();
+        let host = config.get_jenkins("prod").unwrap();
+        let readonly = host.with_identity("readonly").unwrap();
+        assert_eq!(readonly.host, "https://jenkins-prod.example.com");
+        assert_eq!(readonly.user, "viewer");
+        assert_eq!(readonly.token, "viewer-token");
+
+        let err = host.with_identity("missing").unwrap_err().to_string();
+        assert!(err.contains("available: readonly"));
+
+        assert_eq!(config.job_aliases["deploy"].identity, Some("readonly".to_string()));
+
+        // Hosts without identities serialize exactly as before
+        let plain = serde_yaml::to_string(&create_test_host("dev")).unwrap();
+        assert!(!plain.contains("identities"));
+    }
+
+    #[test]
+    fn test_validate_config_queue_timeout() {
+        let yaml = r#"
+jenkins:
+  prod:
+    host: https://jenkins-prod.example.com
+    user: admin
+    token: admin-token
+    queue_timeout: 15m
+  dev:
+    host: https://jenkins-dev.example.com
+    user: admin
+    token: admin-token
+    queue_timeout: soon
+"#;
+        let issues = validate_config(yaml);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, Some(12));
+        assert!(issues[0].message.contains("invalid duration 'soon'"));
+    }
+
+    #[test]
+    fn test_validate_config_cache() {
+        let yaml = "cache:\n  jobs_ttl: 5m\n  status_ttl: later\n  builds_ttl: 1m\n";
+        let issues = validate_config(yaml);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].line, Some(3));
+        assert!(issues[0].message.contains("invalid duration 'later'"));
+        assert_eq!(issues[1].severity, IssueSeverity::Warning);
+        assert!(issues[1].message.contains("Unknown key 'builds_ttl' in cache"));
+
+        let config: Config = serde_yaml::from_str("cache:\n  status_ttl: 0s\n").unwrap();
+        assert_eq!(config.cache.unwrap().status_ttl.as_deref(), Some("0s"));
+    }
+
+    #[test]
+    fn test_validate_config_time_format() {
+        assert!(validate_config("time_format: iso\n").is_empty());
+        assert!(validate_config("time_format: \"%d %b %H:%M\"\n").is_empty());
+
+        let issues = validate_config("time_format: local\n");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, IssueSeverity::Warning);
+        assert!(issues[0].message.contains("time_format 'local' has no %-fields"));
+
+        let issues = validate_config("time_format: 24\n");
+        assert_eq!(issues[0].severity, IssueSeverity::Error);
+
+        let issues = validate_config("time_format: \"%Y-%m-%d %K\"\n");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, IssueSeverity::Error);
+        assert!(issues[0].message.contains("unknown %-field"));
+    }
+
+    #[test]
+    fn test_validate_config_tags() {
+        let base = "jenkins:\n  prod:\n    host: https://jenkins.example.com\n    user: admin\n    token: t\n";
+        assert!(validate_config(&format!("{}    tags:\n      env: prod\n", base)).is_empty());
+
+        let issues = validate_config(&format!("{}    tags: [prod]\n", base));
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, Some(6));
+        assert!(issues[0].message.contains("Tags of Jenkins host 'prod'"));
+    }
+
+    #[test]
+    fn test_validate_config_incomplete_identity() {
+        let yaml = r#"
+jenkins:
+  prod:
+    host: https://jenkins-prod.example.com
+    user: admin
+    token: admin-token
+    identities:
+      readonly:
+        user: viewer
+"#;
+        let issues = validate_config(yaml);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, Some(8));
+        assert!(issues[0].message.contains("Identity 'readonly' of Jenkins host 'prod' is missing required field 'token'"));
+    }
+
+    #[test]
+    fn test_validate_config_commit_status() {
+        let yaml = r#"
+commit_status:
+  provider: bitbucket
+  context: ci/jenkins
+"#;
+        let issues = validate_config(yaml);
+        assert_eq!(issues.len(), 2);
+        assert!(issues[0].message.contains("missing required field 'token'"));
+        assert_eq!(issues[1].line, Some(3));
+        assert!(issues[1].message.contains("'github' or 'gitlab'"));
+
+        let valid = "commit_status:\n  provider: gitlab\n  token: glpat-123\n";
+        assert!(validate_config(valid).is_empty());
+        let config: Config = serde_yaml::from_str(valid).unwrap();
+        assert_eq!(config.commit_status.unwrap().provider, CommitStatusProvider::Gitlab);
+    }
+
+    #[test]
+    fn test_validate_config_syntax_error() {
+        let yaml = "jenkins:\n  prod: [unclosed\n";
+        let issues = validate_config(yaml);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, IssueSeverity::Error);
+        assert!(issues[0].line.is_some());
+    }
+
+    #[test]
+    fn test_validate_config_duplicate_keys() {
+        let yaml = r#"
+jenkins:
+  prod:
+    host: https://a.example.com
+    user: a
+    token: a
+  prod:
+    host: https://b.example.com
+    user: b
+    token: b
+"#;
+        let issues = validate_config(yaml);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, IssueSeverity::Error);
+        assert!(issues[0].message.contains("duplicate"));
+    }
+
+    #[test]
+    fn test_validate_config_reports_problems_with_lines() {
+        let yaml = r#"current: prod
+jenkins:
+  prod:
+    host: jenkins-prod.example.com
+    user: user-prod
+    tokn: token-prod
+  Prod:
+    host: https://jenkins.example.com
+    user: u
+    token: t
+job_aliases:
+  deploy:
+    job_name: deploy
+    jenkins: staging
+  broken:
+    jenkins: prod
+"#;
+        let issues = validate_config(yaml);
+        let find = |needle: &str| issues.iter().find(|i| i.message.contains(needle)).unwrap();
+
+        let unknown_top = find("Unknown key 'current'");
+        assert_eq!(unknown_top.severity, IssueSeverity::Warning);
+        assert_eq!(unknown_top.line, Some(1));
+
+        let missing_token = find("missing required field 'token'");
+        assert_eq!(missing_token.severity, IssueSeverity::Error);
+        assert_eq!(missing_token.line, Some(3));
+
+        assert_eq!(find("Unknown key 'tokn'").line, Some(6));
+        assert_eq!(find("must start with http").line, Some(4));
+        assert_eq!(find("differ only by case").line, Some(7));
+
+        let unknown_host = find("unknown Jenkins host 'staging'");
+        assert_eq!(unknown_host.severity, IssueSeverity::Error);
+        assert_eq!(unknown_host.line, Some(14));
+
+        assert_eq!(find("Job alias 'broken' is missing required field 'job_name'").line, Some(15));
+    }
+
+    #[test]
+    fn test_validate_config_profiles() {
+        let yaml = r#"
+jenkins: {}
+profiles:
+  - jenkins: prod
+"#;
+        let issues = validate_config(yaml);
+        assert!(issues.iter().any(|i| i.message.contains("needs a 'path' or 'git_remote'")));
+        assert!(issues.iter().any(|i| i.message.contains("unknown Jenkins host 'prod'")));
+    }
+
+    #[test]
+    fn test_locate_key() {
+        let yaml = "jenkins:\n  prod:\n    host: a\n  dev:\n    host: b\n";
+        assert_eq!(locate_key(yaml, &["jenkins"]), Some(1));
+        assert_eq!(locate_key(yaml, &["jenkins", "dev", "host"]), Some(5));
+        assert_eq!(locate_key(yaml, &["jenkins", "staging"]), None);
+    }
 }
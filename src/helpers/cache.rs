@@ -0,0 +1,196 @@
+use crate::config::Config;
+use crate::helpers::formatting::now_millis;
+use crate::helpers::params::parse_duration;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime};
+
+/// Job lists change rarely, so selection prompts can reuse them for a while
+pub const DEFAULT_JOBS_TTL: Duration = Duration::from_secs(60);
+/// Job and build status goes stale quickly
+pub const DEFAULT_STATUS_TTL: Duration = Duration::from_secs(10);
+
+/// Set by `--no-cache`: every read goes to the controller
+static DISABLED: AtomicBool = AtomicBool::new(false);
+
+static TTLS: OnceLock<(Duration, Duration)> = OnceLock::new();
+
+/// Kinds of cached responses, each with its own TTL
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CacheKind {
+    Jobs,
+    Status,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    url: String,
+    stored_at: i64,
+    body: String,
+}
+
+/// Never read or write cached responses from now on
+pub fn disable() {
+    DISABLED.store(true, Ordering::Relaxed);
+}
+
+/// Return a cached response body for `url` if it is younger than the kind's TTL
+/// `scope` separates hosts and users, so one user never sees another's responses
+pub fn load(scope: &str, url: &str, kind: CacheKind) -> Option<String> {
+    let ttl = ttl(kind)?;
+    let body = read_entry(&entry_path(scope, url)?, url, ttl, now_millis());
+    if body.is_some() {
+        tracing::debug!(%url, "response cache hit");
+    }
+    body
+}
+
+/// Remember a response body; failures only cost a request next time
+pub fn store(scope: &str, url: &str, body: &str, kind: CacheKind) {
+    if ttl(kind).is_none() {
+        return;
+    }
+    if let Some(path) = entry_path(scope, url)
+        && let Err(e) = write_entry(&path, url, body, now_millis())
+    {
+        tracing::debug!(path = %path.display(), error = %e, "failed to write response cache");
+    }
+    // Every URL gets its own file, so without pruning the directory only ever grows
+    if let Some(dir) = cache_dir() {
+        let (jobs, status) = ttls();
+        prune(&dir, jobs.max(status), SystemTime::now());
+    }
+}
+
+/// Directory holding cached responses
+pub fn cache_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("jenkins-cli").join("responses"))
+}
+
+/// TTL for a kind of response, or None when caching it is disabled
+fn ttl(kind: CacheKind) -> Option<Duration> {
+    if DISABLED.load(Ordering::Relaxed) {
+        return None;
+    }
+    let (jobs, status) = ttls();
+    let ttl = match kind {
+        CacheKind::Jobs => jobs,
+        CacheKind::Status => status,
+    };
+    (!ttl.is_zero()).then_some(ttl)
+}
+
+/// Configured (jobs, status) TTLs
+fn ttls() -> (Duration, Duration) {
+    *TTLS.get_or_init(|| {
+        let cache = Config::load().ok().and_then(|config| config.cache).unwrap_or_default();
+        let ttl = |value: Option<String>, default| value.and_then(|v| parse_duration(&v).ok()).unwrap_or(default);
+        (ttl(cache.jobs_ttl, DEFAULT_JOBS_TTL), ttl(cache.status_ttl, DEFAULT_STATUS_TTL))
+    })
+}
+
+fn entry_path(scope: &str, url: &str) -> Option<PathBuf> {
+    let key = md5::compute(format!("{} {}", scope, url));
+    Some(cache_dir()?.join(format!("{:x}.json", key)))
+}
+
+fn read_entry(path: &Path, url: &str, ttl: Duration, now: i64) -> Option<String> {
+    let content = fs::read_to_string(path).ok()?;
+    let entry: Entry = serde_json::from_str(&content).ok()?;
+    let age = now - entry.stored_at;
+    // A clock that went backwards makes the age meaningless, so treat the entry as stale
+    (entry.url == url && age >= 0 && (age as u128) < ttl.as_millis()).then_some(entry.body)
+}
+
+fn write_entry(path: &Path, url: &str, body: &str, now: i64) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let entry = Entry { url: url.to_string(), stored_at: now, body: body.to_string() };
+    // Write then rename, so a concurrent reader never sees half an entry
+    let tmp = path.with_extension("tmp");
+    create_private(&tmp)?.write_all(serde_json::to_string(&entry)?.as_bytes())?;
+    fs::rename(&tmp, path)
+}
+
+/// Responses can hold anything the user may read on the controller, so only the user may read them here
+#[cfg(unix)]
+fn create_private(path: &Path) -> std::io::Result<File> {
+    use std::os::unix::fs::OpenOptionsExt;
+    OpenOptions::new().write(true).create(true).truncate(true).mode(0o600).open(path)
+}
+
+/// The cache directory is already per-user on Windows
+#[cfg(not(unix))]
+fn create_private(path: &Path) -> std::io::Result<File> {
+    File::create(path)
+}
+
+/// Delete cached responses no kind would still serve, including leftovers of interrupted writes
+fn prune(dir: &Path, max_age: Duration, now: SystemTime) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let expired = entry
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .is_ok_and(|modified| now.duration_since(modified).is_ok_and(|age| age >= max_age));
+        if expired {
+            fs::remove_file(entry.path()).ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_expires_after_ttl() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("entry.json");
+        let url = "https://jenkins.example.com/job/api/api/json";
+        write_entry(&path, url, "{\"name\":\"api\"}", 1_000).unwrap();
+
+        let ttl = Duration::from_secs(10);
+        assert_eq!(read_entry(&path, url, ttl, 5_000).as_deref(), Some("{\"name\":\"api\"}"));
+        assert_eq!(read_entry(&path, url, ttl, 11_000), None);
+        assert_eq!(read_entry(&path, url, ttl, 500), None);
+        // A hash collision must not return another URL's response
+        assert_eq!(read_entry(&path, "https://jenkins.example.com/api/json", ttl, 5_000), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_entry_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("entry.json");
+        write_entry(&path, "https://jenkins.example.com/api/json", "{}", 1_000).unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+    }
+
+    #[test]
+    fn test_prune_removes_expired_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("entry.json");
+        write_entry(&path, "https://jenkins.example.com/api/json", "{}", 1_000).unwrap();
+
+        prune(dir.path(), Duration::from_secs(60), SystemTime::now());
+        assert!(path.exists());
+        prune(dir.path(), Duration::from_secs(60), SystemTime::now() + Duration::from_secs(61));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_entry_path_depends_on_scope() {
+        let url = "https://jenkins.example.com/api/json";
+        assert_ne!(entry_path("alice@https://jenkins.example.com", url), entry_path("bob@https://jenkins.example.com", url));
+        assert_eq!(entry_path("alice@https://jenkins.example.com", url), entry_path("alice@https://jenkins.example.com", url));
+    }
+}
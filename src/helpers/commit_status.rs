@@ -0,0 +1,128 @@
+use anyhow::{Context, Result};
+use crate::config::{CommitStatusConfig, CommitStatusProvider};
+use reqwest::blocking::Client;
+use std::time::Duration;
+
+/// Status name used when the config doesn't set a `context`
+const DEFAULT_CONTEXT: &str = "jenkins";
+
+/// A finished build to report on its commit
+pub struct CommitStatus<'a> {
+    pub sha: &'a str,
+    pub remote_url: &'a str,
+    pub result: Option<&'a str>,
+    pub build_url: &'a str,
+    pub description: String,
+}
+
+/// Post the build result as a commit status on GitHub or GitLab
+pub fn report(config: &CommitStatusConfig, status: &CommitStatus) -> Result<()> {
+    let repository = repository_path(status.remote_url)
+        .ok_or_else(|| anyhow::anyhow!("Could not determine the repository from remote '{}'", status.remote_url))?;
+    let context = config.context.as_deref().unwrap_or(DEFAULT_CONTEXT);
+    let state = status_state(config.provider, status.result);
+
+    let client = Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .context("Failed to create HTTP client")?;
+
+    let request = match config.provider {
+        CommitStatusProvider::Github => {
+            let api = config.api_url.as_deref().unwrap_or("https://api.github.com").trim_end_matches('/');
+            client
+                .post(format!("{}/repos/{}/statuses/{}", api, repository, status.sha))
+                .bearer_auth(&config.token)
+                .header(reqwest::header::USER_AGENT, "jenkins-cli")
+                .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+                .json(&serde_json::json!({
+                    "state": state,
+                    "target_url": status.build_url,
+                    "description": status.description,
+                    "context": context,
+                }))
+        }
+        CommitStatusProvider::Gitlab => {
+            let api = config.api_url.as_deref().unwrap_or("https://gitlab.com/api/v4").trim_end_matches('/');
+            // Nested group paths are a single URL-encoded project id
+            let project = url::form_urlencoded::byte_serialize(repository.as_bytes()).collect::<String>();
+            client
+                .post(format!("{}/projects/{}/statuses/{}", api, project, status.sha))
+                .header("PRIVATE-TOKEN", &config.token)
+                .form(&[
+                    ("state", state),
+                    ("target_url", status.build_url),
+                    ("description", status.description.as_str()),
+                    ("name", context),
+                ])
+        }
+    };
+
+    let response = request.send().context("Failed to send commit status")?;
+    let code = response.status();
+    tracing::debug!(%repository, sha = status.sha, state, status = code.as_u16(), "commit status posted");
+    if !code.is_success() {
+        let body = response.text().unwrap_or_default();
+        anyhow::bail!("Commit status rejected: HTTP {} ({})", code, body.trim());
+    }
+
+    Ok(())
+}
+
+/// Map a Jenkins build result onto the provider's commit states
+fn status_state(provider: CommitStatusProvider, result: Option<&str>) -> &'static str {
+    match (provider, result) {
+        (CommitStatusProvider::Github, Some("SUCCESS")) => "success",
+        (CommitStatusProvider::Github, Some("FAILURE" | "UNSTABLE")) => "failure",
+        (CommitStatusProvider::Github, _) => "error",
+        (CommitStatusProvider::Gitlab, Some("SUCCESS")) => "success",
+        (CommitStatusProvider::Gitlab, Some("FAILURE" | "UNSTABLE")) => "failed",
+        (CommitStatusProvider::Gitlab, _) => "canceled",
+    }
+}
+
+/// `owner/repo` (or `group/subgroup/project`) from an SSH or HTTPS remote URL
+fn repository_path(remote_url: &str) -> Option<String> {
+    let remote = remote_url.trim().trim_end_matches('/');
+    let path = if let Some((_, rest)) = remote.split_once("://") {
+        // https://host/owner/repo or ssh://git@host:22/owner/repo
+        rest.split_once('/')?.1
+    } else {
+        // scp-like syntax: git@host:owner/repo
+        remote.split_once(':')?.1
+    };
+
+    let path = path.trim_start_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    if !path.contains('/') {
+        return None;
+    }
+    Some(path.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repository_path() {
+        assert_eq!(repository_path("git@github.com:acme/web.git").as_deref(), Some("acme/web"));
+        assert_eq!(repository_path("https://github.com/acme/web.git").as_deref(), Some("acme/web"));
+        assert_eq!(repository_path("https://github.com/acme/web/").as_deref(), Some("acme/web"));
+        assert_eq!(
+            repository_path("ssh://git@gitlab.example.com:2222/platform/tools/cli.git").as_deref(),
+            Some("platform/tools/cli")
+        );
+        assert_eq!(repository_path("https://github.com/acme"), None);
+        assert_eq!(repository_path("/srv/git/web"), None);
+    }
+
+    #[test]
+    fn test_status_state() {
+        assert_eq!(status_state(CommitStatusProvider::Github, Some("SUCCESS")), "success");
+        assert_eq!(status_state(CommitStatusProvider::Github, Some("UNSTABLE")), "failure");
+        assert_eq!(status_state(CommitStatusProvider::Github, Some("ABORTED")), "error");
+        assert_eq!(status_state(CommitStatusProvider::Gitlab, Some("FAILURE")), "failed");
+        assert_eq!(status_state(CommitStatusProvider::Gitlab, None), "canceled");
+    }
+}
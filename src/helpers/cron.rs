@@ -0,0 +1,233 @@
+use chrono::{DateTime, Datelike, NaiveDate, TimeZone};
+
+/// Lowest and highest value of each field: minute, hour, day of month, month, day of week
+const LOWER_BOUNDS: [u32; 5] = [0, 0, 1, 1, 0];
+const UPPER_BOUNDS: [u32; 5] = [59, 23, 31, 12, 7];
+
+/// How far ahead to look for a matching day; a February 29th on a given weekday can be 28 years off
+const SEARCH_DAYS: usize = 28 * 366;
+
+/// The next time after `after` that a Jenkins cron spec (schedule or SCM polling) fires
+/// `H` is hashed from the job's full name (`team/api`) the way Jenkins does it, so it lands on the same minute
+/// The spec is read in the zone of `after`, which is only right when the controller runs in that zone
+/// None when the spec can't be read, never fires or sets a zone of its own (`TZ=...`)
+pub fn next_run<Tz: TimeZone>(spec: &str, job_full_name: &str, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+    // Jenkins hashes every line of the spec with the same generator, in order
+    let mut hash = Hash::from(job_full_name);
+    let mut lines = Vec::new();
+    for line in spec.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')) {
+        if line.starts_with("TZ=") {
+            return None;
+        }
+        lines.push(CronLine::parse(line, &mut hash)?);
+    }
+    lines.iter().filter_map(|line| line.next_after(after)).min()
+}
+
+/// One line of a spec, as the set of allowed values of each field
+#[derive(Debug, PartialEq)]
+struct CronLine {
+    bits: [u64; 5],
+}
+
+impl CronLine {
+    fn parse(line: &str, hash: &mut Hash) -> Option<Self> {
+        let expanded = match line {
+            "@yearly" | "@annually" => "H H H H *",
+            "@monthly" => "H H H * *",
+            "@weekly" => "H H * * H",
+            "@daily" => "H H * * *",
+            "@midnight" => "H H(0-2) * * *",
+            "@hourly" => "H * * * *",
+            line => line,
+        };
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        if fields.len() != 5 {
+            return None;
+        }
+
+        let mut bits = [0; 5];
+        for (field, text) in fields.iter().enumerate() {
+            bits[field] = text.split(',').try_fold(0, |bits, term| Some(bits | parse_term(term, field, hash)?))?;
+        }
+        // Both 0 and 7 are Sunday
+        if bits[4] & (1 << 7) != 0 {
+            bits[4] |= 1;
+        }
+        Some(CronLine { bits })
+    }
+
+    fn next_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let zone = after.timezone();
+        for date in after.naive_local().date().iter_days().take(SEARCH_DAYS) {
+            if !self.matches_day(date) {
+                continue;
+            }
+            for hour in (0..24).filter(|hour| has(self.bits[1], *hour)) {
+                for minute in (0..60).filter(|minute| has(self.bits[0], *minute)) {
+                    // Times skipped by a daylight saving change don't fire
+                    let Some(at) = zone.from_local_datetime(&date.and_hms_opt(hour, minute, 0)?).earliest() else {
+                        continue;
+                    };
+                    if at > *after {
+                        return Some(at);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Jenkins requires both the day of month and the day of week to match, unlike Unix cron
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        has(self.bits[2], date.day())
+            && has(self.bits[3], date.month())
+            && has(self.bits[4], date.weekday().num_days_from_sunday())
+    }
+}
+
+fn has(bits: u64, value: u32) -> bool {
+    bits & (1 << value) != 0
+}
+
+/// `*`, `H`, `H(1-5)`, `1-5` or `3`, the first three optionally with a `/step`
+fn parse_term(term: &str, field: usize, hash: &mut Hash) -> Option<u64> {
+    let (lower, upper) = (LOWER_BOUNDS[field], UPPER_BOUNDS[field]);
+    let (range, step) = match term.split_once('/') {
+        Some((range, step)) => (range, Some(step.parse::<u32>().ok().filter(|step| *step > 0)?)),
+        None => (term, None),
+    };
+    let in_bounds = |value: u32| (lower..=upper).contains(&value).then_some(value);
+    let parse_range = |range: &str| -> Option<(u32, u32)> {
+        let (start, end) = range.split_once('-')?;
+        Some((in_bounds(start.parse().ok()?)?, in_bounds(end.parse().ok()?)?))
+    };
+
+    let bits = if range == "*" {
+        range_bits(lower, upper, step.unwrap_or(1))
+    } else if let Some(hashed) = range.strip_prefix('H') {
+        let (start, end) = match hashed {
+            // Days of month stop at 28 so every month has them, and Sunday is only 0
+            "" => (lower, match field {
+                2 => 28,
+                4 => 6,
+                _ => upper,
+            }),
+            _ => parse_range(hashed.strip_prefix('(')?.strip_suffix(')')?)?,
+        };
+        if start > end {
+            return None;
+        }
+        match step.unwrap_or(1) {
+            step if step > end - start + 1 => return None,
+            1 => 1 << (start + hash.next(end - start + 1)),
+            step => range_bits(start + hash.next(step), end, step),
+        }
+    } else if range.contains('-') {
+        let (start, end) = parse_range(range)?;
+        range_bits(start, end, step.unwrap_or(1))
+    } else if step.is_none() {
+        1 << in_bounds(range.parse().ok()?)?
+    } else {
+        return None;
+    };
+    (bits != 0).then_some(bits)
+}
+
+fn range_bits(start: u32, end: u32, step: u32) -> u64 {
+    (start..=end).step_by(step as usize).fold(0, |bits, value| bits | 1 << value)
+}
+
+/// Jenkins' `hudson.scheduler.Hash`: a `java.util.Random` seeded from the MD5 of the job's full name
+struct Hash {
+    seed: i64,
+}
+
+impl Hash {
+    const MULTIPLIER: i64 = 0x5DEECE66D;
+    const MASK: i64 = (1 << 48) - 1;
+
+    fn from(name: &str) -> Self {
+        let mut digest = md5::compute(name.as_bytes()).0;
+        for i in 8..digest.len() {
+            digest[i % 8] ^= digest[i];
+        }
+        let mut seed = [0; 8];
+        seed.copy_from_slice(&digest[..8]);
+        Self::with_seed(i64::from_be_bytes(seed))
+    }
+
+    fn with_seed(seed: i64) -> Self {
+        Hash { seed: (seed ^ Self::MULTIPLIER) & Self::MASK }
+    }
+
+    /// `Random.next(bits)`
+    fn next_bits(&mut self, bits: u32) -> i32 {
+        self.seed = self.seed.wrapping_mul(Self::MULTIPLIER).wrapping_add(0xB) & Self::MASK;
+        (self.seed >> (48 - bits)) as i32
+    }
+
+    /// `Random.nextInt(bound)`: a value in `0..bound`
+    fn next(&mut self, bound: u32) -> u32 {
+        let bound = bound as i32;
+        if bound & -bound == bound {
+            return ((bound as i64 * self.next_bits(31) as i64) >> 31) as u32;
+        }
+        loop {
+            let bits = self.next_bits(31);
+            let value = bits % bound;
+            if bits.wrapping_sub(value).wrapping_add(bound - 1) >= 0 {
+                return value as u32;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn at(text: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(text).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_hash_matches_java_random() {
+        // new java.util.Random(42): nextInt() is -1170105035, then nextInt(10) 3, nextInt(16) 10, nextInt(60) 44
+        let mut hash = Hash::with_seed(42);
+        assert_eq!(hash.next_bits(32), -1170105035);
+        assert_eq!(hash.next(10), 3);
+        assert_eq!(hash.next(16), 10);
+        assert_eq!(hash.next(60), 44);
+    }
+
+    #[test]
+    fn test_next_run() {
+        let now = at("2024-05-03T10:15:30Z"); // a Friday
+
+        assert_eq!(next_run("30 2 * * 1-5", "api", &now), Some(at("2024-05-06T02:30:00Z")));
+        assert_eq!(next_run("*/15 * * * *", "api", &now), Some(at("2024-05-03T10:30:00Z")));
+        assert_eq!(next_run("# nightly\n0 22 * * *\n0 12 * * 0,7", "api", &now), Some(at("2024-05-03T22:00:00Z")));
+        // Both the day of month and the day of week have to match
+        assert_eq!(next_run("0 0 1 * 3", "api", &now), Some(at("2025-01-01T00:00:00Z")));
+
+        assert_eq!(next_run("0 0 30 2 *", "api", &now), None);
+        assert_eq!(next_run("TZ=Europe/Paris\n0 2 * * *", "api", &now), None);
+        assert_eq!(next_run("0 25 * * *", "api", &now), None);
+        assert_eq!(next_run("not a spec", "api", &now), None);
+    }
+
+    #[test]
+    fn test_hashed_fields_follow_the_job_name() {
+        let now = at("2024-05-03T10:15:30Z");
+        // Jenkins draws 56 of 60 and then 3 of 4 for team/api
+        assert_eq!(next_run("H H(0-3) * * *", "team/api", &now), Some(at("2024-05-04T03:56:00Z")));
+        assert_ne!(next_run("H H(0-3) * * *", "team/web", &now), next_run("H H(0-3) * * *", "team/api", &now));
+
+        let mut hash = Hash::from("team/api");
+        let line = CronLine::parse("H/15 * * * *", &mut hash).unwrap();
+        assert_eq!(line.bits[0].count_ones(), 4);
+        assert!(CronLine::parse("H/61 * * * *", &mut hash).is_none());
+    }
+}
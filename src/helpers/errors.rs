@@ -0,0 +1,97 @@
+use std::fmt;
+
+/// Exit code of failures that fit no other class
+pub const GENERIC_EXIT_CODE: i32 = 1;
+
+/// Failure classes wrapper scripts can tell apart by the exit code
+/// 2 is left to clap, which uses it for usage errors
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ErrorKind {
+    Config,
+    Auth,
+    Network,
+    NotFound,
+    BuildFailed,
+    Cancelled,
+}
+
+impl ErrorKind {
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ErrorKind::Config => 3,
+            ErrorKind::Auth => 4,
+            ErrorKind::Network => 5,
+            ErrorKind::NotFound => 6,
+            ErrorKind::BuildFailed => 7,
+            // Same as a shell reports for Ctrl+C
+            ErrorKind::Cancelled => 130,
+        }
+    }
+
+    /// An error of this kind; use it with `map_err` or `Err(...)?`, or as `context` to classify a lower-level error
+    pub fn error(self, message: impl Into<String>) -> anyhow::Error {
+        anyhow::Error::new(self.tag(message))
+    }
+
+    /// The message as a typed error, for `context`
+    pub fn tag(self, message: impl Into<String>) -> KindedError {
+        KindedError { kind: self, message: message.into() }
+    }
+}
+
+/// An error message tagged with its failure class
+#[derive(Debug)]
+pub struct KindedError {
+    kind: ErrorKind,
+    message: String,
+}
+
+impl fmt::Display for KindedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for KindedError {}
+
+/// The failure class of an error: the outermost tagged one in its chain, or a network error
+/// raised by the HTTP client
+pub fn kind_of(error: &anyhow::Error) -> Option<ErrorKind> {
+    if let Some(tagged) = error.downcast_ref::<KindedError>() {
+        return Some(tagged.kind);
+    }
+    error.chain().find_map(|cause| {
+        if let Some(tagged) = cause.downcast_ref::<KindedError>() {
+            return Some(tagged.kind);
+        }
+        cause
+            .downcast_ref::<reqwest::Error>()
+            .filter(|e| e.is_connect() || e.is_timeout() || e.is_request())
+            .map(|_| ErrorKind::Network)
+    })
+}
+
+/// Process exit code for a failed command
+pub fn exit_code(error: &anyhow::Error) -> i32 {
+    kind_of(error).map_or(GENERIC_EXIT_CODE, ErrorKind::exit_code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_exit_code_follows_the_tagged_kind() {
+        assert_eq!(exit_code(&ErrorKind::NotFound.error("Job 'api' not found")), 6);
+        assert_eq!(exit_code(&anyhow::anyhow!("something else")), GENERIC_EXIT_CODE);
+
+        // Context added on top keeps the class, and a tag given as context classifies what it wraps
+        let wrapped = Err::<(), _>(ErrorKind::Auth.error("HTTP 401")).context("Failed to trigger build").unwrap_err();
+        assert_eq!(exit_code(&wrapped), 4);
+        let io = std::fs::read("/nonexistent/config.yml").context(ErrorKind::Config.tag("Failed to read config file"));
+        let io = io.context("Loading settings").unwrap_err();
+        assert_eq!(exit_code(&io), 3);
+        assert_eq!(format!("{:#}", io).split(": ").nth(1), Some("Failed to read config file"));
+    }
+}
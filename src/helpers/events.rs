@@ -0,0 +1,91 @@
+use crate::helpers::formatting::now_millis;
+use serde::Serialize;
+use std::io::Write;
+
+/// Build progress event for `build --follow --events`, printed as one JSON line on stdout
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub enum BuildEvent {
+    Queued { job: String, queue_url: String },
+    Started { job: String, build: i32 },
+    LogChunk { job: String, build: i32, text: String },
+    StageChanged { job: String, build: i32, stage: String, status: String },
+    /// Always the last event; `build` is null when the build never left the queue
+    Finished { job: String, build: Option<i32>, result: Option<String>, outcome: Outcome },
+}
+
+/// How following the build ended
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Outcome {
+    /// The build ran to completion; `result` holds its result
+    Completed,
+    /// Ctrl+C stopped following, the build (or queue item) keeps going
+    Interrupted,
+    /// The queued build was cancelled
+    QueueCancelled,
+    /// The build didn't start within the queue timeout
+    QueueTimeout,
+    /// The log couldn't be fetched any more
+    LogFailed,
+}
+
+#[derive(Serialize)]
+struct EventLine<'a> {
+    timestamp: i64,
+    #[serde(flatten)]
+    event: &'a BuildEvent,
+}
+
+/// Print an event as a newline-delimited JSON record
+pub fn emit(event: &BuildEvent) {
+    let line = EventLine { timestamp: now_millis(), event };
+    if let Ok(json) = serde_json::to_string(&line) {
+        let mut stdout = std::io::stdout().lock();
+        let _ = writeln!(stdout, "{}", json);
+        // Consumers read the stream live, so don't let lines sit in the buffer
+        let _ = stdout.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_event_serialization() {
+        let event = BuildEvent::StageChanged {
+            job: "api".to_string(),
+            build: 42,
+            stage: "Deploy".to_string(),
+            status: "IN_PROGRESS".to_string(),
+        };
+        let line = serde_json::to_value(EventLine { timestamp: 1, event: &event }).unwrap();
+
+        assert_eq!(
+            line,
+            serde_json::json!({
+                "timestamp": 1,
+                "event": "stage-changed",
+                "job": "api",
+                "build": 42,
+                "stage": "Deploy",
+                "status": "IN_PROGRESS"
+            })
+        );
+
+        let event = BuildEvent::Finished { job: "api".to_string(), build: None, result: None, outcome: Outcome::QueueTimeout };
+        let line = serde_json::to_value(EventLine { timestamp: 1, event: &event }).unwrap();
+        assert_eq!(
+            line,
+            serde_json::json!({
+                "timestamp": 1,
+                "event": "finished",
+                "job": "api",
+                "build": null,
+                "result": null,
+                "outcome": "queue-timeout"
+            })
+        );
+    }
+}
@@ -1,4 +1,53 @@
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local, Utc};
 use console::style;
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// `time_format` value that keeps timestamps relative (the default)
+pub const RELATIVE_TIME_FORMAT: &str = "relative";
+/// `time_format` shorthand for ISO 8601 timestamps
+pub const ISO_TIME_FORMAT: &str = "iso";
+/// Pattern used when `--utc` asks for absolute times but no pattern is configured
+const DEFAULT_TIME_PATTERN: &str = "%Y-%m-%d %H:%M:%S";
+
+/// How `format_timestamp` renders build times, set once at startup
+static TIME_SETTINGS: OnceLock<TimeSettings> = OnceLock::new();
+
+#[derive(Debug, Clone, PartialEq)]
+struct TimeSettings {
+    /// strftime-style pattern, or `None` for relative times
+    pattern: Option<String>,
+    utc: bool,
+}
+
+/// Render timestamps with the configured `time_format`, in UTC instead of the local zone when `utc` is set
+/// `--utc` with relative times switches to absolute ones, since relative times have no zone
+/// An invalid pattern (reported by `jenkins config check`) keeps times relative
+pub fn set_time_format(time_format: Option<&str>, utc: bool) {
+    let pattern = match time_format.unwrap_or(RELATIVE_TIME_FORMAT) {
+        RELATIVE_TIME_FORMAT if utc => Some(DEFAULT_TIME_PATTERN.to_string()),
+        RELATIVE_TIME_FORMAT => None,
+        ISO_TIME_FORMAT => Some("%Y-%m-%dT%H:%M:%S%:z".to_string()),
+        pattern if is_valid_time_pattern(pattern) => Some(pattern.to_string()),
+        _ if utc => Some(DEFAULT_TIME_PATTERN.to_string()),
+        _ => None,
+    };
+    let _ = TIME_SETTINGS.set(TimeSettings { pattern, utc });
+}
+
+/// True if every `%` field of a strftime pattern is one chrono knows
+pub fn is_valid_time_pattern(pattern: &str) -> bool {
+    StrftimeItems::new(pattern).all(|item| !matches!(item, Item::Error))
+}
+
+/// Current time in epoch milliseconds (the unit Jenkins uses for timestamps)
+pub fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
 
 /// Format Jenkins job color/status for plain text display
 pub fn format_job_color(color: Option<&str>) -> String {
@@ -48,6 +97,121 @@ pub fn format_build_result(result: &Option<String>) -> String {
     }
 }
 
+/// Format a duration in milliseconds as a compact human-readable string (e.g. `12m 34s`)
+pub fn format_duration(ms: i64) -> String {
+    let total_secs = ms.max(0) / 1000;
+    let (hours, minutes, seconds) = (total_secs / 3600, (total_secs % 3600) / 60, total_secs % 60);
+
+    if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m {}s", minutes, seconds)
+    } else if total_secs > 0 {
+        format!("{}s", seconds)
+    } else {
+        format!("{}ms", ms.max(0))
+    }
+}
+
+/// Format an epoch-millis timestamp relative to now (e.g. `25 minutes ago`)
+pub fn format_relative_time(timestamp_ms: i64, now_ms: i64) -> String {
+    let secs = (now_ms - timestamp_ms) / 1000;
+    if secs < 0 {
+        return "in the future".to_string();
+    }
+
+    let (value, unit) = match secs {
+        0..=59 => return "just now".to_string(),
+        60..=3_599 => (secs / 60, "minute"),
+        3_600..=86_399 => (secs / 3_600, "hour"),
+        86_400..=2_591_999 => (secs / 86_400, "day"),
+        2_592_000..=31_535_999 => (secs / 2_592_000, "month"),
+        _ => (secs / 31_536_000, "year"),
+    };
+
+    if value == 1 {
+        format!("1 {} ago", unit)
+    } else {
+        format!("{} {}s ago", value, unit)
+    }
+}
+
+/// Format an epoch-millis timestamp the way the user configured (`25 minutes ago` by default)
+pub fn format_timestamp(timestamp_ms: i64, now_ms: i64) -> String {
+    match TIME_SETTINGS.get() {
+        Some(TimeSettings { pattern: Some(pattern), utc }) => format_datetime(timestamp_ms, pattern, *utc),
+        _ => format_relative_time(timestamp_ms, now_ms),
+    }
+}
+
+/// Time of day (`14:03:22`) for running logs, in the local time zone unless `--utc` was given
+pub fn format_clock_time(timestamp_ms: i64) -> String {
+    format_datetime(timestamp_ms, "%H:%M:%S", is_utc())
+}
+
+/// Date and time (`2024-05-01 14:03:22`) for log files, in the local time zone unless `--utc` was given
+pub fn format_local_datetime(timestamp_ms: i64) -> String {
+    format_datetime(timestamp_ms, DEFAULT_TIME_PATTERN, is_utc())
+}
+
+fn is_utc() -> bool {
+    TIME_SETTINGS.get().is_some_and(|settings| settings.utc)
+}
+
+/// Render a timestamp with a strftime pattern that passed `is_valid_time_pattern`, in UTC or the local time zone
+fn format_datetime(timestamp_ms: i64, pattern: &str, utc: bool) -> String {
+    let Some(at) = DateTime::<Utc>::from_timestamp_millis(timestamp_ms) else {
+        return String::new();
+    };
+    if utc {
+        at.format(pattern).to_string()
+    } else {
+        at.with_timezone(&Local).format(pattern).to_string()
+    }
+}
+
+/// How long a build took, or has been running so far (e.g. `12m 34s`, `running for 3m 2s`)
+pub fn format_build_duration(building: bool, duration: Option<i64>, timestamp: Option<i64>, now_ms: i64) -> String {
+    match (building, timestamp, duration) {
+        (true, Some(started), _) => format!("running for {}", format_duration(now_ms - started)),
+        (true, None, _) => "running".to_string(),
+        (false, _, Some(duration)) => format_duration(duration),
+        (false, _, None) => String::new(),
+    }
+}
+
+/// When a build started and how long it took (e.g. `started 25 minutes ago, took 12m 34s`)
+pub fn format_build_timing(building: bool, duration: Option<i64>, timestamp: Option<i64>, now_ms: i64) -> String {
+    let started = timestamp.map(|t| format!("started {}", format_timestamp(t, now_ms)));
+    let took = match format_build_duration(building, duration, timestamp, now_ms) {
+        text if text.is_empty() => None,
+        text if building => Some(text),
+        text => Some(format!("took {}", text)),
+    };
+    started.into_iter().chain(took).collect::<Vec<_>>().join(", ")
+}
+
+/// Render a small progress bar for a percentage (e.g. `▰▰▰▱▱`)
+pub fn progress_bar(percent: u8, width: usize) -> String {
+    let filled = (percent.min(100) as usize * width).div_ceil(100).min(width);
+    format!("{}{}", "▰".repeat(filled), "▱".repeat(width - filled))
+}
+
+/// Format a job health score (0-100) with console styling
+pub fn format_health_score(score: i32) -> String {
+    let text = format!("{:>3}%", score);
+    match score {
+        80.. => style(text).green().to_string(),
+        40..=79 => style(text).yellow().to_string(),
+        _ => style(text).red().to_string(),
+    }
+}
+
+/// Parse an HTTP `Date` header (e.g. `Sun, 06 Nov 1994 08:49:37 GMT`) into epoch millis
+pub fn parse_http_date(value: &str) -> Option<i64> {
+    DateTime::parse_from_rfc2822(value).ok().map(|date| date.timestamp_millis())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -74,4 +238,82 @@ mod tests {
         format_build_result(&Some("ABORTED".to_string()));
         format_build_result(&None);
     }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(0), "0ms");
+        assert_eq!(format_duration(450), "450ms");
+        assert_eq!(format_duration(5_000), "5s");
+        assert_eq!(format_duration(754_123), "12m 34s");
+        assert_eq!(format_duration(3_600_000), "1h 0m");
+        assert_eq!(format_duration(5_430_000), "1h 30m");
+        assert_eq!(format_duration(-5), "0ms");
+    }
+
+    #[test]
+    fn test_format_relative_time() {
+        let now = 1_700_000_000_000;
+        assert_eq!(format_relative_time(now - 5_000, now), "just now");
+        assert_eq!(format_relative_time(now - 60_000, now), "1 minute ago");
+        assert_eq!(format_relative_time(now - 25 * 60_000, now), "25 minutes ago");
+        assert_eq!(format_relative_time(now - 3 * 3_600_000, now), "3 hours ago");
+        assert_eq!(format_relative_time(now - 2 * 86_400_000, now), "2 days ago");
+        assert_eq!(format_relative_time(now - 90 * 86_400_000, now), "3 months ago");
+        assert_eq!(format_relative_time(now - 400 * 86_400_000, now), "1 year ago");
+        assert_eq!(format_relative_time(now + 60_000, now), "in the future");
+    }
+
+    #[test]
+    fn test_format_build_timing() {
+        let now = 1_700_000_000_000;
+        let started = Some(now - 25 * 60_000);
+        assert_eq!(format_build_duration(false, Some(754_123), started, now), "12m 34s");
+        assert_eq!(format_build_duration(true, Some(0), started, now), "running for 25m 0s");
+        assert_eq!(format_build_timing(false, Some(754_123), started, now), "started 25 minutes ago, took 12m 34s");
+        assert_eq!(format_build_timing(true, None, started, now), "started 25 minutes ago, running for 25m 0s");
+        assert_eq!(format_build_timing(false, None, None, now), "");
+    }
+
+    #[test]
+    fn test_format_datetime() {
+        let at = 1_700_000_000_000;
+        assert_eq!(format_datetime(at, "%Y-%m-%d %H:%M:%S", true), "2023-11-14 22:13:20");
+        assert_eq!(format_datetime(at, "%d %b %Y %H:%M %:z", true), "14 Nov 2023 22:13 +00:00");
+        assert_eq!(format_datetime(0, "%Y-%m-%dT%H:%M:%S", true), "1970-01-01T00:00:00");
+        assert_eq!(format_datetime(1_709_208_000_000, "%m/%d 100%%", true), "02/29 100%");
+    }
+
+    #[test]
+    fn test_is_valid_time_pattern() {
+        assert!(is_valid_time_pattern("%Y-%m-%d %H:%M"));
+        assert!(is_valid_time_pattern("%a %e %b, %I:%M %p"));
+        assert!(!is_valid_time_pattern("%Y %K"));
+        assert!(!is_valid_time_pattern("%"));
+    }
+
+    #[test]
+    fn test_progress_bar() {
+        assert_eq!(progress_bar(0, 5), "▱▱▱▱▱");
+        assert_eq!(progress_bar(50, 4), "▰▰▱▱");
+        assert_eq!(progress_bar(99, 5), "▰▰▰▰▰");
+        assert_eq!(progress_bar(100, 5), "▰▰▰▰▰");
+    }
+
+    #[test]
+    fn test_format_health_score() {
+        let plain = |score| console::strip_ansi_codes(&format_health_score(score)).into_owned();
+        assert_eq!(plain(100), "100%");
+        assert_eq!(plain(60), " 60%");
+        assert_eq!(plain(0), "  0%");
+    }
+
+    #[test]
+    fn test_parse_http_date() {
+        assert_eq!(parse_http_date("Thu, 01 Jan 1970 00:00:00 GMT"), Some(0));
+        assert_eq!(parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT"), Some(784_111_777_000));
+        assert_eq!(parse_http_date("Tue, 14 Nov 2023 22:13:20 GMT"), Some(1_700_000_000_000));
+        assert_eq!(parse_http_date("Thu, 29 Feb 2024 12:00:00 GMT"), Some(1_709_208_000_000));
+        assert_eq!(parse_http_date("yesterday"), None);
+        assert_eq!(parse_http_date("Sun, 06 Foo 1994 08:49:37 GMT"), None);
+    }
 }
@@ -0,0 +1,83 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Run a git command in `dir` and return its trimmed stdout, or None if git fails
+fn git_output(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// List the URLs of all git remotes for the repository containing `dir`
+pub fn remote_urls(dir: &Path) -> Vec<String> {
+    git_output(dir, &["config", "--get-regexp", r"^remote\..*\.url$"])
+        .map(|out| parse_remote_urls(&out))
+        .unwrap_or_default()
+}
+
+/// Name of the branch checked out in the repository containing `dir`, or None if HEAD is detached
+pub fn current_branch(dir: &Path) -> Option<String> {
+    git_output(dir, &["symbolic-ref", "--quiet", "--short", "HEAD"]).filter(|branch| !branch.is_empty())
+}
+
+/// Commit the current branch's upstream (e.g. `origin/main`) points at, which moves when the branch is pushed
+/// Returns None outside a repository or if the branch has no upstream
+pub fn upstream_commit(dir: &Path) -> Option<String> {
+    git_output(dir, &["rev-parse", "--verify", "--quiet", "@{upstream}"]).filter(|commit| !commit.is_empty())
+}
+
+/// Files of the repository containing `dir`, tracked or not, leaving out ignored ones
+/// Paths are relative to `dir`; returns None outside a repository
+pub fn listed_files(dir: &Path) -> Option<Vec<PathBuf>> {
+    let output = git_output(dir, &["ls-files", "--cached", "--others", "--exclude-standard", "-z"])?;
+    Some(output.split('\0').filter(|path| !path.is_empty()).map(PathBuf::from).collect())
+}
+
+/// Where git looks for the hook `name` of the repository containing `dir` (honors core.hooksPath)
+/// Returns None outside a repository
+pub fn hook_path(dir: &Path, name: &str) -> Option<PathBuf> {
+    git_output(dir, &["rev-parse", "--git-path", &format!("hooks/{}", name)]).map(|path| dir.join(path))
+}
+
+/// Parse `git config --get-regexp` output (`remote.<name>.url <url>` per line)
+fn parse_remote_urls(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.split_once(' ').map(|(_, url)| url.trim().to_string()))
+        .filter(|url| !url.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_remote_urls() {
+        let output = "remote.origin.url git@github.com:acme/web.git\nremote.upstream.url https://github.com/acme/web.git\n";
+        assert_eq!(
+            parse_remote_urls(output),
+            vec!["git@github.com:acme/web.git", "https://github.com/acme/web.git"]
+        );
+        assert!(parse_remote_urls("").is_empty());
+    }
+
+    #[test]
+    fn test_current_branch() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(current_branch(dir.path()), None);
+
+        let git = |args: &[&str]| git_output(dir.path(), args);
+        git(&["init", "--quiet"]).unwrap();
+        git(&["checkout", "--quiet", "-b", "feature/foo"]).unwrap();
+        assert_eq!(current_branch(dir.path()).as_deref(), Some("feature/foo"));
+    }
+}
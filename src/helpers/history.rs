@@ -0,0 +1,222 @@
+use anyhow::{Context, Result};
+use crate::client::{JenkinsClient, ParameterDefinition, ParameterValue};
+use crate::helpers::formatting::now_millis;
+use crate::helpers::url::to_display_job_path;
+use crate::output;
+use crate::state::State;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Recorded in place of the value of a password parameter
+const REDACTED: &str = "****";
+
+/// Changes the CLI makes on a controller
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Build,
+    Replay,
+    RestartFromStage,
+    CancelQueued,
+    UpdateConfig,
+    CreateJob,
+    CreateNode,
+    DeleteNode,
+    RunScript,
+    ApiRequest,
+}
+
+impl Action {
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::Build => "build",
+            Action::Replay => "replay",
+            Action::RestartFromStage => "restart from stage",
+            Action::CancelQueued => "cancel queued build",
+            Action::UpdateConfig => "update config",
+            Action::CreateJob => "create job",
+            Action::CreateNode => "create agent",
+            Action::DeleteNode => "delete agent",
+            Action::RunScript => "run script",
+            Action::ApiRequest => "api request",
+        }
+    }
+
+    /// Whether the entry's target is a job path (as opposed to an agent, a script or a request line)
+    pub fn on_job(self) -> bool {
+        !matches!(self, Action::CreateNode | Action::DeleteNode | Action::RunScript | Action::ApiRequest)
+    }
+}
+
+/// One line of the local audit history
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct HistoryEntry {
+    /// Epoch millis
+    pub at: i64,
+    pub host: String,
+    /// The job path, or the agent, script or `METHOD path` the action was about
+    #[serde(alias = "job")]
+    pub target: String,
+    pub action: Action,
+    /// Parameter values sent with the request, passwords redacted
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub parameters: BTreeMap<String, String>,
+    /// Whether the controller accepted the request; for builds that is the trigger, not the build's result
+    #[serde(alias = "ok")]
+    pub accepted: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl HistoryEntry {
+    /// The target as shown to people: job paths without their `job/` segments
+    pub fn display_target(&self) -> String {
+        if self.action.on_job() { to_display_job_path(&self.target) } else { self.target.clone() }
+    }
+}
+
+/// Names of the parameters whose values must never be written down
+pub fn secret_parameters(definitions: &[ParameterDefinition]) -> Vec<String> {
+    definitions
+        .iter()
+        .filter(|d| d.class.contains("PasswordParameterDefinition"))
+        .map(|d| d.name.clone())
+        .collect()
+}
+
+/// Parameter values as they go into the history: passwords redacted, uploads by file path
+pub fn recorded_parameters(values: Option<&[ParameterValue]>, secrets: &[String]) -> BTreeMap<String, String> {
+    values
+        .unwrap_or_default()
+        .iter()
+        .map(|p| {
+            let value = match &p.file {
+                _ if secrets.contains(&p.name) => REDACTED.to_string(),
+                Some(path) => path.display().to_string(),
+                None => p.value.clone(),
+            };
+            (p.name.clone(), value)
+        })
+        .collect()
+}
+
+/// Append an action and its outcome to the history
+/// The action already happened, so a history that can't be written only costs a warning
+pub fn record<T>(
+    client: &JenkinsClient,
+    target: &str,
+    action: Action,
+    parameters: BTreeMap<String, String>,
+    outcome: &Result<T>,
+) {
+    let entry = HistoryEntry {
+        at: now_millis(),
+        host: client.name().unwrap_or(client.host_url()).to_string(),
+        target: target.to_string(),
+        action,
+        parameters,
+        accepted: outcome.is_ok(),
+        error: outcome.as_ref().err().map(|e| format!("{:#}", e)),
+    };
+    if let Err(e) = history_path().and_then(|path| append(&path, &entry)) {
+        output::warning(&format!("Could not record the action in the local history: {:#}", e));
+    }
+}
+
+/// Every recorded entry, oldest first
+pub fn load() -> Result<Vec<HistoryEntry>> {
+    read(&history_path()?)
+}
+
+pub fn history_path() -> Result<PathBuf> {
+    Ok(State::data_dir()?.join("history.jsonl"))
+}
+
+fn append(path: &Path, entry: &HistoryEntry) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context("Failed to create data directory")?;
+    }
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+    // One write per entry, so concurrent CLIs (and fanned-out builds) don't interleave lines
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn read(path: &Path) -> Result<Vec<HistoryEntry>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    // A line cut short by a crash shouldn't hide the rest of the history
+    Ok(content.lines().filter_map(|line| serde_json::from_str(line).ok()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value(name: &str, value: &str) -> ParameterValue {
+        ParameterValue { name: name.to_string(), value: value.to_string(), file: None }
+    }
+
+    #[test]
+    fn test_recorded_parameters_redacts_passwords() {
+        let definitions: Vec<ParameterDefinition> = serde_json::from_str(
+            r#"[
+                {"_class": "hudson.model.StringParameterDefinition", "name": "BRANCH", "type": "StringParameterDefinition"},
+                {"_class": "hudson.model.PasswordParameterDefinition", "name": "DEPLOY_KEY", "type": "PasswordParameterDefinition"}
+            ]"#,
+        )
+        .unwrap();
+        let values = vec![value("BRANCH", "main"), value("DEPLOY_KEY", "hunter2")];
+
+        let recorded = recorded_parameters(Some(&values), &secret_parameters(&definitions));
+        assert_eq!(recorded["BRANCH"], "main");
+        assert_eq!(recorded["DEPLOY_KEY"], REDACTED);
+        assert!(recorded_parameters(None, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_history_roundtrip_skips_broken_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.jsonl");
+        let entry = HistoryEntry {
+            at: 1_700_000_000_000,
+            host: "prod".to_string(),
+            target: "team/job/api".to_string(),
+            action: Action::Build,
+            parameters: BTreeMap::from([("BRANCH".to_string(), "main".to_string())]),
+            accepted: true,
+            error: None,
+        };
+        append(&path, &entry).unwrap();
+        fs::write(&path, format!("{}{{\"at\": 17\n", fs::read_to_string(&path).unwrap())).unwrap();
+        append(&path, &HistoryEntry { action: Action::CancelQueued, accepted: false, ..entry.clone() }).unwrap();
+
+        let entries = read(&path).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0], entry);
+        assert_eq!(entries[1].action, Action::CancelQueued);
+        assert!(fs::read_to_string(&path).unwrap().contains("\"action\":\"cancel_queued\""));
+    }
+
+    #[test]
+    fn test_history_reads_entries_written_before_the_rename() {
+        let entry: HistoryEntry =
+            serde_json::from_str(r#"{"at": 1, "host": "prod", "job": "team/job/api", "action": "build", "ok": true}"#).unwrap();
+        assert_eq!(entry.target, "team/job/api");
+        assert!(entry.accepted);
+        assert_eq!(entry.display_target(), "team/api");
+
+        let api = HistoryEntry { target: "POST /job/api/disable".to_string(), action: Action::ApiRequest, ..entry };
+        assert_eq!(api.display_target(), "POST /job/api/disable");
+    }
+}
@@ -1,24 +1,120 @@
 use anyhow::Result;
 use crate::client::JenkinsClient;
-use crate::config::{Config, JenkinsHost};
-use inquire::Select;
+use crate::config::{Config, JenkinsHost, TAG_SELECTOR_PREFIX};
+use crate::output;
+use crate::state::State;
+use crate::commands;
+use crate::helpers::errors::ErrorKind;
+use crate::helpers::{prompt, session};
+use crate::helpers::url::to_display_job_path;
+use inquire::{Confirm, Select};
+use std::sync::OnceLock;
+
+/// Identity selected with the global `--as` flag
+static IDENTITY_OVERRIDE: OnceLock<String> = OnceLock::new();
+
+/// Use the named identity for every host (set from the `--as` flag)
+pub fn set_identity(identity: String) {
+    let _ = IDENTITY_OVERRIDE.set(identity);
+}
 
 /// Create a JenkinsClient with the specified or current host
 pub fn create_client(jenkins_name: Option<String>) -> Result<JenkinsClient> {
-    let host = resolve_jenkins_host(jenkins_name)?;
-    JenkinsClient::new(host)
+    create_client_as(jenkins_name, None)
+}
+
+/// Create a JenkinsClient, using a named identity if one is given (`--as` takes precedence)
+fn create_client_as(jenkins_name: Option<String>, identity: Option<&str>) -> Result<JenkinsClient> {
+    let jenkins_to_use = match jenkins_name {
+        Some(name) => Some(resolve_host_selector(name)?),
+        None => prompt_jenkins_selection()?,
+    };
+    let identity = IDENTITY_OVERRIDE.get().map(|s| s.as_str()).or(identity);
+
+    // Clients are cached per host and identity
+    let cache_key = jenkins_to_use.as_deref().map(|name| match identity {
+        Some(identity) => format!("{}:{}", name, identity),
+        None => name.to_string(),
+    });
+
+    if let (Some(name), Some(key)) = (jenkins_to_use.as_deref(), cache_key.as_deref()) {
+        // Reuse the connection inside `jenkins shell`
+        if let Some(client) = session::cached_client(key) {
+            tracing::debug!(client = key, "client cache hit");
+            return Ok(client);
+        }
+        warn_if_credentials_failing(name);
+    }
+
+    let mut host = resolve_jenkins_host(jenkins_to_use.clone())?;
+    if let Some(identity) = identity {
+        host = host.with_identity(identity)?;
+    }
+
+    let mut client = JenkinsClient::new(host)?;
+    if let (Some(name), Some(key)) = (jenkins_to_use.as_deref(), cache_key.as_deref()) {
+        client = client.with_name(name);
+        session::cache_client(key, &client);
+    }
+    Ok(client)
+}
+
+/// Turn a `tag:` selector into the one host it stands for, asking when several hosts carry the tag
+/// Plain host names pass through unchanged
+fn resolve_host_selector(selector: String) -> Result<String> {
+    if !selector.starts_with(TAG_SELECTOR_PREFIX) {
+        return Ok(selector);
+    }
+
+    let mut names = Config::load()?.select_hosts(&selector)?;
+    if names.len() == 1 {
+        return Ok(names.remove(0));
+    }
+    prompt::ensure_interactive(
+        &format!("one of the hosts matching '{}' ({})", selector, names.join(", ")),
+        "Pass a host name instead of a tag.",
+    )?;
+    Ok(Select::new("Select Jenkins:", names)
+        .with_help_message("Use ↑↓ to navigate, type to search, Enter to select, ESC to cancel")
+        .prompt()?)
+}
+
+/// Print a banner if the last credential check for this host failed
+fn warn_if_credentials_failing(jenkins_name: &str) {
+    let Ok(state) = State::load() else {
+        return;
+    };
+
+    if let Some(host_state) = state.hosts.get(jenkins_name).filter(|s| s.is_failing()) {
+        let reason = host_state.failure_reason.as_deref().unwrap_or("unknown error");
+        output::warning(&format!(
+            "Credentials for '{}' failed their last check: {}",
+            jenkins_name, reason
+        ));
+        output::dim(&format!("Run 'jenkins config verify {}' after updating the token.", jenkins_name));
+    }
 }
 
 /// Create a JenkinsClient for a specific job, using alias jenkins if available
-/// Priority: explicit jenkins_name > alias jenkins > prompt selection (if multiple) > single jenkins
+/// Priority: explicit jenkins_name > alias jenkins > directory profile > prompt selection (if multiple) > single jenkins
 pub fn create_client_for_job(job_name: Option<&str>, jenkins_name: Option<String>) -> Result<JenkinsClient> {
+    // Fall back to the directory profile's default job
+    let profile_job = if job_name.is_none() { profile_default_job()? } else { None };
+    let job_name = job_name.or(profile_job.as_deref());
+
+    // Aliases can pin the identity used for their job
+    let alias_identity = match job_name {
+        Some(job) => Config::load()?.job_aliases.get(job).and_then(|alias| alias.identity.clone()),
+        None => None,
+    };
+
     let jenkins_to_use = if jenkins_name.is_some() {
         // User explicitly specified jenkins
         jenkins_name
     } else if let Some(job) = job_name {
         // Check if job_name is an alias with jenkins info
         let config = Config::load()?;
-        let (_, _, alias_jenkins) = config.resolve_job_name(job);
+        let (_, _, alias_jenkins) = config.resolve_job_name(job)?;
         if alias_jenkins.is_some() {
             alias_jenkins
         } else {
@@ -30,16 +126,52 @@ pub fn create_client_for_job(job_name: Option<&str>, jenkins_name: Option<String
         prompt_jenkins_selection()?
     };
 
-    create_client(jenkins_to_use)
+    create_client_as(jenkins_to_use, alias_identity.as_deref())
+}
+
+/// Default job from the shell session or the directory profile matching the current directory
+pub fn profile_default_job() -> Result<Option<String>> {
+    if let Some(job) = session::job() {
+        return Ok(Some(job));
+    }
+
+    let config = Config::load()?;
+    Ok(config.active_profile().and_then(|profile| profile.job.clone()))
 }
 
 /// Prompt user to select a Jenkins host if multiple are configured
+/// A host selected in the shell session or a matching directory profile is used without prompting
 /// Returns None if only one host exists (will use it automatically)
 pub fn prompt_jenkins_selection() -> Result<Option<String>> {
+    if let Some(jenkins) = session::jenkins() {
+        return Ok(Some(jenkins));
+    }
+
     let config = Config::load()?;
 
+    if let Some(jenkins) = config.active_profile().and_then(|profile| profile.jenkins.clone()) {
+        config.get_jenkins(&jenkins)?;
+        return Ok(Some(jenkins));
+    }
+
     match config.jenkins.len() {
-        0 => anyhow::bail!("No Jenkins configured. Use 'jenkins config add' to add one."),
+        0 => {
+            // Offer guided setup on first run instead of failing outright
+            if prompt::is_non_interactive() {
+                return Err(ErrorKind::Config.error("No Jenkins configured. Use 'jenkins config add' to add one."));
+            }
+
+            let setup = Confirm::new("No Jenkins configured yet. Set one up now?")
+                .with_default(true)
+                .prompt()?;
+
+            if !setup {
+                return Err(ErrorKind::Config.error("No Jenkins configured. Use 'jenkins config add' to add one."));
+            }
+
+            let name = commands::config::run_setup_wizard()?;
+            Ok(Some(name))
+        }
         1 => {
             // Only one jenkins, use it automatically
             let name = config.jenkins.keys().next().unwrap().clone();
@@ -47,6 +179,10 @@ pub fn prompt_jenkins_selection() -> Result<Option<String>> {
         }
         _ => {
             // Multiple jenkins hosts, prompt user to select
+            prompt::ensure_interactive(
+                "a Jenkins host",
+                "Bind the job to a host with 'jenkins alias add', use a directory profile, or pass a full job URL.",
+            )?;
             let mut jenkins_names: Vec<String> = config.jenkins.keys().cloned().collect();
             jenkins_names.sort();
 
@@ -79,3 +215,26 @@ pub fn resolve_jenkins_host(jenkins_name: Option<String>) -> Result<JenkinsHost>
 
     Ok(host)
 }
+
+/// Build number from a pasted build URL (e.g. `.../job/api/42/`), used when --build isn't given
+pub fn build_number_from_url(job_name: Option<&str>) -> Result<Option<i32>> {
+    let Some(job_name) = job_name else {
+        return Ok(None);
+    };
+    Ok(Config::load()?.match_job_url(job_name)?.and_then(|(_, _, build)| build))
+}
+
+/// Use the given build number, or fall back to the job's last build
+pub fn resolve_build_number(client: &JenkinsClient, job_name: &str, build_number: Option<i32>) -> Result<i32> {
+    if let Some(num) = build_number {
+        return Ok(num);
+    }
+
+    let job = client.get_job(job_name)?;
+    if job.is_folder() {
+        anyhow::bail!(job.folder_explanation(&to_display_job_path(job_name)));
+    }
+    job.last_build
+        .map(|b| b.number)
+        .ok_or_else(|| anyhow::anyhow!("No builds found for job '{}'", job_name))
+}
@@ -0,0 +1,68 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Once;
+use std::thread;
+use std::time::{Duration, Instant};
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+/// Number of live `CtrlC` guards
+static CATCHING: AtomicUsize = AtomicUsize::new(0);
+static INSTALL: Once = Once::new();
+
+/// Exit code of a process ended by Ctrl+C
+const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+/// While alive, Ctrl+C is caught instead of terminating the process
+#[must_use = "Ctrl+C is only caught while the guard is alive"]
+pub struct CtrlC(());
+
+impl Drop for CtrlC {
+    fn drop(&mut self) {
+        if CATCHING.fetch_sub(1, Ordering::SeqCst) == 1 {
+            INTERRUPTED.store(false, Ordering::SeqCst);
+        }
+    }
+}
+
+/// Catch Ctrl+C until the returned guard is dropped
+/// Callers poll `take()` (or sleep with `sleep()`) to react to it
+pub fn catch_ctrl_c() -> CtrlC {
+    CATCHING.fetch_add(1, Ordering::SeqCst);
+    INSTALL.call_once(|| {
+        thread::spawn(|| {
+            let Ok(runtime) = tokio::runtime::Builder::new_current_thread().enable_all().build() else {
+                return;
+            };
+            runtime.block_on(async {
+                while tokio::signal::ctrl_c().await.is_ok() {
+                    // The handler can't be removed again, so outside a guard it ends the process as Ctrl+C would
+                    if CATCHING.load(Ordering::SeqCst) == 0 {
+                        std::process::exit(INTERRUPTED_EXIT_CODE);
+                    }
+                    INTERRUPTED.store(true, Ordering::SeqCst);
+                }
+            });
+        });
+    });
+    CtrlC(())
+}
+
+/// True if Ctrl+C was pressed since the last call
+pub fn take() -> bool {
+    INTERRUPTED.swap(false, Ordering::SeqCst)
+}
+
+/// Sleep for the duration, waking early if Ctrl+C is pressed
+/// Returns true if interrupted
+pub fn sleep(duration: Duration) -> bool {
+    let deadline = Instant::now() + duration;
+    loop {
+        if take() {
+            return true;
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return false;
+        }
+        thread::sleep((deadline - now).min(Duration::from_millis(100)));
+    }
+}
@@ -0,0 +1,144 @@
+/// What a Job DSL run did to a generated item, from the headings it logs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Created,
+    Updated,
+    Removed,
+    /// No longer generated, but kept because the seed job ignores removed items
+    Unreferenced,
+    Disabled,
+}
+
+impl Change {
+    pub const ALL: [Change; 5] = [Change::Created, Change::Updated, Change::Removed, Change::Unreferenced, Change::Disabled];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Change::Created => "Created",
+            Change::Updated => "Updated",
+            Change::Removed => "Removed",
+            Change::Unreferenced => "Unreferenced",
+            Change::Disabled => "Disabled",
+        }
+    }
+
+    /// `Added items:` / `Existing views:` ... as printed by the Job DSL plugin
+    fn from_heading(heading: &str) -> Option<Change> {
+        let (verb, kind) = heading.strip_suffix(':')?.split_once(' ')?;
+        if !matches!(kind, "items" | "views" | "config files" | "user content") {
+            return None;
+        }
+        match verb {
+            "Added" => Some(Change::Created),
+            "Existing" => Some(Change::Updated),
+            "Removed" => Some(Change::Removed),
+            "Unreferenced" => Some(Change::Unreferenced),
+            "Disabled" => Some(Change::Disabled),
+            _ => None,
+        }
+    }
+}
+
+/// A job, view, config file or user content file listed by a Job DSL run
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedItem {
+    /// `job`, `view`, `configfile` or `usercontent`
+    pub kind: String,
+    pub name: String,
+    pub change: Change,
+}
+
+/// Collect the generated items from a seed job's console log
+///
+/// The plugin lists them after the DSL scripts ran, e.g.
+/// `Added items:` followed by indented `GeneratedJob{name='team/api'}` lines.
+pub fn parse_generated_items(log: &str) -> Vec<GeneratedItem> {
+    let mut items = Vec::new();
+    let mut section = None;
+
+    for line in log.lines() {
+        // Timestamps may precede the heading, so look for the known phrases anywhere in the line
+        if let Some(change) = heading(line) {
+            section = Some(change);
+            continue;
+        }
+        match (section, generated_item(line)) {
+            (Some(change), Some((kind, name))) => items.push(GeneratedItem { kind, name, change }),
+            _ => section = None,
+        }
+    }
+
+    items
+}
+
+fn heading(line: &str) -> Option<Change> {
+    let line = line.trim_end();
+    let start = ["Added ", "Existing ", "Removed ", "Unreferenced ", "Disabled "]
+        .iter()
+        .filter_map(|verb| line.find(verb))
+        .min()?;
+    Change::from_heading(&line[start..])
+}
+
+/// `GeneratedJob{name='team/api'}` -> ("job", "team/api")
+fn generated_item(line: &str) -> Option<(String, String)> {
+    let rest = &line[line.find("Generated")? + "Generated".len()..];
+    let (kind, rest) = rest.split_once("{name='")?;
+    let name = rest.trim_end().strip_suffix("'}")?;
+    if kind.is_empty() || name.is_empty() {
+        return None;
+    }
+    Some((kind.to_ascii_lowercase(), name.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOG: &str = "Started by user admin
+Processing DSL script jobs.groovy
+Added items:
+    GeneratedJob{name='team'}
+    GeneratedJob{name='team/api'}
+Existing items:
+    GeneratedJob{name='deploy'}
+Removed items:
+    GeneratedJob{name='legacy'}
+Added views:
+    GeneratedView{name='Team'}
+Finished: SUCCESS";
+
+    #[test]
+    fn test_parse_generated_items() {
+        let items = parse_generated_items(LOG);
+        let summary: Vec<(&str, &str, Change)> =
+            items.iter().map(|i| (i.kind.as_str(), i.name.as_str(), i.change)).collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("job", "team", Change::Created),
+                ("job", "team/api", Change::Created),
+                ("job", "deploy", Change::Updated),
+                ("job", "legacy", Change::Removed),
+                ("view", "Team", Change::Created),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_generated_items_with_timestamps() {
+        let log = "[2024-05-01T10:00:00.000Z] Unreferenced items:\n[2024-05-01T10:00:00.000Z]     GeneratedJob{name='old'}\n";
+        let items = parse_generated_items(log);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].change, Change::Unreferenced);
+        assert_eq!(items[0].name, "old");
+    }
+
+    #[test]
+    fn test_parse_ignores_unrelated_lines() {
+        assert!(parse_generated_items("Added items to the cart:\nGeneratedJob{name='x'}").is_empty());
+        // Items only count directly below a heading
+        assert!(parse_generated_items("Added items:\nsomething else\n    GeneratedJob{name='x'}").is_empty());
+    }
+}
@@ -0,0 +1,59 @@
+use anyhow::{Context, Result};
+use std::fs::{self, File, OpenOptions};
+use std::path::Path;
+use std::sync::Mutex;
+use tracing::{Level, Subscriber};
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::fmt;
+use tracing_subscriber::prelude::*;
+
+/// Write internal diagnostics (request timings, cache hits, retries, errors) to a file
+/// Nothing is logged unless this is called, so user-facing output is unaffected
+pub fn init(path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).context("Failed to create log directory")?;
+    }
+
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open log file '{}'", path.display()))?;
+
+    tracing::subscriber::set_global_default(file_subscriber(file)).context("Failed to install logger")?;
+
+    Ok(())
+}
+
+/// One line per event in the usual tracing layout:
+/// `2026-01-31T09:15:02.120345Z DEBUG jenkins::client: request finished method=GET status=200`
+fn file_subscriber(file: File) -> impl Subscriber + Send + Sync {
+    // Only this crate's events - dependencies' internals are too noisy
+    let filter = Targets::new().with_target(env!("CARGO_CRATE_NAME"), Level::DEBUG);
+    tracing_subscriber::registry()
+        .with(fmt::layer().with_writer(Mutex::new(file)).with_ansi(false))
+        .with(filter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_subscriber() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("jenkins.log");
+        let file = File::create(&path).unwrap();
+
+        tracing::subscriber::with_default(file_subscriber(file), || {
+            tracing::debug!(status = 200, "request finished");
+            tracing::trace!("too detailed");
+            tracing::debug!(target: "hyper::client", "not ours");
+        });
+
+        let log = fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = log.lines().collect();
+        assert_eq!(lines.len(), 1, "{}", log);
+        assert!(lines[0].contains(" DEBUG jenkins::helpers::logging::tests: request finished status=200"), "{}", log);
+    }
+}
@@ -1,3 +1,19 @@
 pub mod url;
 pub mod formatting;
 pub mod init;
+pub mod params;
+pub mod git;
+pub mod session;
+pub mod interrupt;
+pub mod prompt;
+pub mod events;
+pub mod logging;
+pub mod stages;
+pub mod commit_status;
+pub mod notify;
+pub mod pipeline_log;
+pub mod job_dsl;
+pub mod cache;
+pub mod history;
+pub mod errors;
+pub mod cron;
@@ -0,0 +1,160 @@
+use anyhow::{Context, Result};
+use crate::helpers::formatting::format_duration;
+use reqwest::StatusCode;
+use reqwest::blocking::Client;
+use serde::Serialize;
+use std::time::Duration;
+
+/// Summary of a finished build, POSTed as JSON to the notification webhook
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct BuildSummary {
+    /// Ready-made message so Slack incoming webhooks can display it as-is
+    pub text: String,
+    pub job: String,
+    pub build: i32,
+    pub result: Option<String>,
+    /// Build duration in milliseconds
+    pub duration: i64,
+    pub url: String,
+}
+
+impl BuildSummary {
+    pub fn new(job: &str, build: i32, result: Option<String>, duration: i64, url: &str) -> Self {
+        let text = format!(
+            "{} #{} finished: {} in {} - {}",
+            job,
+            build,
+            result.as_deref().unwrap_or("UNKNOWN"),
+            format_duration(duration),
+            url
+        );
+        Self { text, job: job.to_string(), build, result, duration, url: url.to_string() }
+    }
+}
+
+/// A job whose status changed between two polls of `jenkins monitor --daemon`
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct StateChange {
+    pub job: String,
+    pub jenkins: String,
+    pub from: String,
+    pub to: String,
+    /// Latest build when the change was seen
+    pub build: Option<i32>,
+    pub url: Option<String>,
+}
+
+impl StateChange {
+    /// One line for the digest, e.g. `api: Success → Failed (#13) <url>`
+    pub fn line(&self) -> String {
+        let mut line = format!("{}: {} → {}", self.job, self.from, self.to);
+        if let Some(build) = self.build {
+            line.push_str(&format!(" (#{})", build));
+        }
+        if let Some(url) = &self.url {
+            line.push_str(&format!(" {}", url));
+        }
+        line
+    }
+}
+
+/// Jobs that changed status since the previous poll, POSTed as JSON to the monitor's webhook
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct Digest {
+    /// Ready-made message so Slack incoming webhooks can display it as-is
+    pub text: String,
+    /// When the changes were seen, in epoch milliseconds
+    pub timestamp: i64,
+    pub changes: Vec<StateChange>,
+}
+
+impl Digest {
+    pub fn new(timestamp: i64, changes: Vec<StateChange>) -> Self {
+        let mut text = format!("{} job(s) changed state", changes.len());
+        for change in &changes {
+            text.push_str(&format!("\n{}", change.line()));
+        }
+        Self { text, timestamp, changes }
+    }
+}
+
+/// POST the build summary to a Slack or generic webhook
+pub fn send(webhook_url: &str, summary: &BuildSummary) -> Result<()> {
+    let status = post(webhook_url, summary)?;
+    tracing::debug!(job = %summary.job, build = summary.build, status = status.as_u16(), "notification sent");
+    if !status.is_success() {
+        anyhow::bail!("Notification webhook returned HTTP {}", status);
+    }
+
+    Ok(())
+}
+
+/// POST a monitor digest to a Slack or generic webhook
+pub fn send_digest(webhook_url: &str, digest: &Digest) -> Result<()> {
+    let status = post(webhook_url, digest)?;
+    tracing::debug!(changes = digest.changes.len(), status = status.as_u16(), "digest sent");
+    if !status.is_success() {
+        anyhow::bail!("Digest webhook returned HTTP {}", status);
+    }
+
+    Ok(())
+}
+
+fn post(webhook_url: &str, payload: &impl Serialize) -> Result<StatusCode> {
+    let client = Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .context("Failed to create HTTP client")?;
+
+    let response = client.post(webhook_url).json(payload).send().context("Failed to send notification")?;
+    Ok(response.status())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_summary_payload() {
+        let summary = BuildSummary::new(
+            "team/deploy",
+            42,
+            Some("FAILURE".to_string()),
+            95_000,
+            "https://jenkins.example.com/job/team/job/deploy/42/",
+        );
+        assert_eq!(
+            summary.text,
+            format!("team/deploy #42 finished: FAILURE in {} - https://jenkins.example.com/job/team/job/deploy/42/", format_duration(95_000))
+        );
+
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["job"], "team/deploy");
+        assert_eq!(json["build"], 42);
+        assert_eq!(json["result"], "FAILURE");
+        assert_eq!(json["duration"], 95_000);
+    }
+
+    #[test]
+    fn test_digest_payload() {
+        let change = |job: &str, build: Option<i32>| StateChange {
+            job: job.to_string(),
+            jenkins: "prod".to_string(),
+            from: "Success".to_string(),
+            to: "Failed".to_string(),
+            build,
+            url: build.map(|n| format!("https://jenkins.example.com/job/{}/{}/", job, n)),
+        };
+        let digest = Digest::new(1_000, vec![change("api", Some(13)), change("web", None)]);
+        assert_eq!(
+            digest.text,
+            "2 job(s) changed state\napi: Success → Failed (#13) https://jenkins.example.com/job/api/13/\nweb: Success → Failed"
+        );
+
+        let json = serde_json::to_value(&digest).unwrap();
+        assert_eq!(json["timestamp"], 1_000);
+        assert_eq!(json["changes"][0]["job"], "api");
+        assert_eq!(json["changes"][0]["to"], "Failed");
+        assert_eq!(json["changes"][1]["build"], serde_json::Value::Null);
+    }
+}
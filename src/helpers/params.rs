@@ -0,0 +1,257 @@
+use crate::client::ParameterDefinition;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Parse a `KEY=VALUE` pair from the command line
+pub fn parse_key_value(input: &str) -> Result<(String, String), String> {
+    match input.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.to_string()))
+        }
+        _ => Err(format!("invalid parameter '{}': expected KEY=VALUE", input)),
+    }
+}
+
+/// Parse a duration like `90`, `30s`, `15m` or `1h` (plain numbers are seconds)
+pub fn parse_duration(input: &str) -> Result<Duration, String> {
+    let input = input.trim();
+    let split = input.find(|c: char| !c.is_ascii_digit()).unwrap_or(input.len());
+    let (number, unit) = input.split_at(split);
+
+    let value: u64 = number
+        .parse()
+        .map_err(|_| format!("invalid duration '{}': expected e.g. 30s, 15m or 1h", input))?;
+
+    let multiplier: u64 = match unit.trim() {
+        "" | "s" | "sec" | "secs" => 1,
+        "m" | "min" | "mins" => 60,
+        "h" | "hr" | "hrs" => 3600,
+        _ => return Err(format!("invalid duration '{}': expected e.g. 30s, 15m or 1h", input)),
+    };
+    let seconds = value.checked_mul(multiplier).ok_or_else(|| format!("invalid duration '{}': too large", input))?;
+
+    Ok(Duration::from_secs(seconds))
+}
+
+/// Parse a polling interval: a duration as for `parse_duration`, but never zero
+pub fn parse_interval(input: &str) -> Result<Duration, String> {
+    match parse_duration(input)? {
+        interval if interval.is_zero() => Err(format!("invalid interval '{}': must be at least 1s", input.trim())),
+        interval => Ok(interval),
+    }
+}
+
+/// Parse a size like `512MB`, `1GB` or `2048` (bytes); units are binary, as Jenkins' node monitors use
+pub fn parse_size(input: &str) -> Result<u64, String> {
+    let input = input.trim();
+    let split = input.find(|c: char| !c.is_ascii_digit()).unwrap_or(input.len());
+    let (number, unit) = input.split_at(split);
+
+    let value: u64 = number
+        .parse()
+        .map_err(|_| format!("invalid size '{}': expected e.g. 512MB or 1GB", input))?;
+
+    let multiplier: u64 = match unit.trim().to_ascii_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KB" | "KIB" => 1 << 10,
+        "M" | "MB" | "MIB" => 1 << 20,
+        "G" | "GB" | "GIB" => 1 << 30,
+        "T" | "TB" | "TIB" => 1 << 40,
+        _ => return Err(format!("invalid size '{}': expected e.g. 512MB or 1GB", input)),
+    };
+
+    value.checked_mul(multiplier).ok_or_else(|| format!("invalid size '{}': too large", input))
+}
+
+/// Prefix of environment variables that supply build parameters, e.g. `JENKINS_PARAM_BRANCH=main`
+pub const ENV_PARAM_PREFIX: &str = "JENKINS_PARAM_";
+
+/// Build parameters given as `JENKINS_PARAM_*` environment variables, matched to the job's
+/// parameter names case-insensitively (`JENKINS_PARAM_BRANCH` sets `branch` if that's the only match)
+/// Returns the values and the variables that match no parameter
+pub fn env_parameters(
+    vars: impl IntoIterator<Item = (String, String)>,
+    definitions: &[ParameterDefinition],
+) -> (HashMap<String, String>, Vec<String>) {
+    let mut values = HashMap::new();
+    let mut unmatched = Vec::new();
+
+    for (var, value) in vars {
+        let Some(name) = var.strip_prefix(ENV_PARAM_PREFIX).filter(|name| !name.is_empty()) else {
+            continue;
+        };
+        let definition = definitions
+            .iter()
+            .find(|d| d.name == name)
+            .or_else(|| definitions.iter().find(|d| d.name.eq_ignore_ascii_case(name)));
+        match definition {
+            Some(definition) => {
+                values.insert(definition.name.clone(), value);
+            }
+            None => unmatched.push(var),
+        }
+    }
+
+    unmatched.sort();
+    (values, unmatched)
+}
+
+/// Check parameter values given up front (e.g. from an alias) against the job's definitions
+/// so mistakes are reported before the build is triggered
+pub fn validate_parameter_values(
+    definitions: &[ParameterDefinition],
+    values: &HashMap<String, String>,
+) -> anyhow::Result<()> {
+    let mut problems = Vec::new();
+
+    let mut names: Vec<&String> = values.keys().collect();
+    names.sort();
+
+    for name in names {
+        let value = &values[name];
+        let Some(def) = definitions.iter().find(|d| &d.name == name) else {
+            let known: Vec<&str> = definitions.iter().map(|d| d.name.as_str()).collect();
+            if known.is_empty() {
+                problems.push(format!("'{}': the job has no parameters", name));
+            } else {
+                problems.push(format!("'{}': unknown parameter (expected one of: {})", name, known.join(", ")));
+            }
+            continue;
+        };
+
+        if def.class.contains("BooleanParameterDefinition") && value != "true" && value != "false" {
+            problems.push(format!("'{}': expected true or false, got '{}'", name, value));
+        } else if def.class.contains("ChoiceParameterDefinition")
+            && let Some(choices) = &def.choices
+            && !choices.contains(value)
+        {
+            problems.push(format!("'{}': '{}' is not a valid choice (expected one of: {})", name, value, choices.join(", ")));
+        }
+    }
+
+    if !problems.is_empty() {
+        anyhow::bail!("Invalid build parameters:\n  {}", problems.join("\n  "));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn definition(class: &str, name: &str, choices: Option<Vec<&str>>) -> ParameterDefinition {
+        ParameterDefinition {
+            class: format!("hudson.model.{}", class),
+            name: name.to_string(),
+            param_type: class.trim_end_matches("Definition").to_string(),
+            description: None,
+            default_value: None,
+            choices: choices.map(|c| c.into_iter().map(String::from).collect()),
+            project_name: None,
+            filter: None,
+            credential_type: None,
+        }
+    }
+
+    #[test]
+    fn test_env_parameters() {
+        let defs = vec![
+            definition("StringParameterDefinition", "branch", None),
+            definition("BooleanParameterDefinition", "DRY_RUN", None),
+        ];
+        let vars = [
+            ("JENKINS_PARAM_BRANCH", "main"),
+            ("JENKINS_PARAM_DRY_RUN", "true"),
+            ("JENKINS_PARAM_REGION", "eu"),
+            ("JENKINS_PARAM_", "x"),
+            ("HOME", "/home/ci"),
+        ]
+        .map(|(k, v)| (k.to_string(), v.to_string()));
+
+        let (values, unmatched) = env_parameters(vars, &defs);
+        assert_eq!(values.len(), 2);
+        assert_eq!(values["branch"], "main");
+        assert_eq!(values["DRY_RUN"], "true");
+        assert_eq!(unmatched, vec!["JENKINS_PARAM_REGION"]);
+    }
+
+    #[test]
+    fn test_validate_parameter_values() {
+        let defs = vec![
+            definition("StringParameterDefinition", "BRANCH", None),
+            definition("BooleanParameterDefinition", "DRY_RUN", None),
+            definition("ChoiceParameterDefinition", "ENV", Some(vec!["dev", "prod"])),
+        ];
+        let values = |pairs: &[(&str, &str)]| -> HashMap<String, String> {
+            pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+        };
+
+        assert!(validate_parameter_values(&defs, &values(&[("BRANCH", "main"), ("DRY_RUN", "true"), ("ENV", "prod")])).is_ok());
+
+        let err = validate_parameter_values(&defs, &values(&[("BRNCH", "main")])).unwrap_err().to_string();
+        assert!(err.contains("'BRNCH': unknown parameter (expected one of: BRANCH, DRY_RUN, ENV)"));
+
+        let err = validate_parameter_values(&defs, &values(&[("DRY_RUN", "yes"), ("ENV", "staging")])).unwrap_err().to_string();
+        assert!(err.contains("'DRY_RUN': expected true or false, got 'yes'"));
+        assert!(err.contains("'ENV': 'staging' is not a valid choice (expected one of: dev, prod)"));
+
+        let err = validate_parameter_values(&[], &values(&[("BRANCH", "main")])).unwrap_err().to_string();
+        assert!(err.contains("the job has no parameters"));
+    }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("2048"), Ok(2048));
+        assert_eq!(parse_size("512MB"), Ok(512 << 20));
+        assert_eq!(parse_size("1 GiB"), Ok(1 << 30));
+        assert_eq!(parse_size("10g"), Ok(10 << 30));
+        assert!(parse_size("").is_err());
+        assert!(parse_size("1PB").is_err());
+        assert!(parse_size("-1GB").is_err());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("30s"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_duration("15m"), Ok(Duration::from_secs(900)));
+        assert_eq!(parse_duration("1h"), Ok(Duration::from_secs(3600)));
+        assert_eq!(parse_duration(" 2 min "), Ok(Duration::from_secs(120)));
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("m").is_err());
+        assert!(parse_duration("10d").is_err());
+        assert!(parse_duration("-5s").is_err());
+        assert!(parse_duration("18446744073709551615h").is_err());
+    }
+
+    #[test]
+    fn test_parse_interval() {
+        assert_eq!(parse_interval("5m"), Ok(Duration::from_secs(300)));
+        assert_eq!(parse_interval("0"), Err("invalid interval '0': must be at least 1s".to_string()));
+        assert!(parse_interval("0s").is_err());
+    }
+
+    #[test]
+    fn test_parse_key_value() {
+        assert_eq!(
+            parse_key_value("BRANCH=main"),
+            Ok(("BRANCH".to_string(), "main".to_string()))
+        );
+        assert_eq!(
+            parse_key_value("EXPR=a=b"),
+            Ok(("EXPR".to_string(), "a=b".to_string()))
+        );
+        assert_eq!(
+            parse_key_value("EMPTY="),
+            Ok(("EMPTY".to_string(), String::new()))
+        );
+    }
+
+    #[test]
+    fn test_parse_key_value_invalid() {
+        assert!(parse_key_value("BRANCH").is_err());
+        assert!(parse_key_value("=main").is_err());
+        assert!(parse_key_value("").is_err());
+    }
+}
@@ -0,0 +1,231 @@
+use console::style;
+use std::fmt::Write;
+
+/// Prefix Jenkins puts on the lines it writes for pipeline steps
+const PIPELINE_MARKER: &str = "[Pipeline] ";
+
+/// Steps that only structure the log and aren't worth listing in a folded stage
+const STRUCTURAL_STEPS: &[&str] = &["stage", "parallel"];
+
+/// A console log split along its `[Pipeline] { (Stage)` ... `[Pipeline] }` blocks
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogNode {
+    Line(String),
+    Stage { name: String, children: Vec<LogNode> },
+}
+
+/// One open block while parsing; anonymous blocks (node, withEnv, ...) have no name
+struct Frame {
+    name: Option<String>,
+    children: Vec<LogNode>,
+}
+
+/// Parse a console log into stages; lines outside any stage stay at the top level
+pub fn parse(log: &str) -> Vec<LogNode> {
+    let mut stack = vec![Frame { name: None, children: Vec::new() }];
+
+    for line in log.lines() {
+        match pipeline_step(line) {
+            Some(step) if step.starts_with("{ (") && step.ends_with(')') => {
+                let name = step[3..step.len() - 1].to_string();
+                stack.push(Frame { name: Some(name), children: Vec::new() });
+            }
+            Some("{") => {
+                stack.push(Frame { name: None, children: vec![LogNode::Line(line.to_string())] });
+            }
+            Some("}") if stack.len() > 1 => {
+                if let Some(mut frame) = stack.pop() {
+                    if frame.name.is_none() {
+                        frame.children.push(LogNode::Line(line.to_string()));
+                    }
+                    close(&mut stack, frame);
+                }
+            }
+            _ => push_line(&mut stack, line),
+        }
+    }
+
+    // Blocks left open by a running or truncated build
+    while stack.len() > 1 {
+        if let Some(frame) = stack.pop() {
+            close(&mut stack, frame);
+        }
+    }
+    stack.pop().map(|frame| frame.children).unwrap_or_default()
+}
+
+fn push_line(stack: &mut [Frame], line: &str) {
+    if let Some(frame) = stack.last_mut() {
+        frame.children.push(LogNode::Line(line.to_string()));
+    }
+}
+
+/// Attach a closed block to its parent: stages become nodes, anonymous blocks are transparent
+fn close(stack: &mut [Frame], frame: Frame) {
+    let Some(parent) = stack.last_mut() else {
+        return;
+    };
+    match frame.name {
+        Some(name) => parent.children.push(LogNode::Stage { name, children: frame.children }),
+        None => parent.children.extend(frame.children),
+    }
+}
+
+/// The step part of a `[Pipeline] ...` line (timestamps may precede the marker)
+fn pipeline_step(line: &str) -> Option<&str> {
+    line.find(PIPELINE_MARKER).map(|i| line[i + PIPELINE_MARKER.len()..].trim())
+}
+
+/// Render the log with every stage folded to a one-line summary, except the stages named in `expand`
+pub fn render_folded(nodes: &[LogNode], expand: &[String]) -> String {
+    let mut text = String::new();
+    render(nodes, expand, 0, false, &mut text);
+    text
+}
+
+fn render(nodes: &[LogNode], expand: &[String], depth: usize, expanded: bool, text: &mut String) {
+    let indent = "  ".repeat(depth);
+    for node in nodes {
+        match node {
+            LogNode::Line(line) => {
+                // Top-level block markers only frame the folded stages, so they go too
+                if expanded || (depth == 0 && !is_structural(line)) {
+                    let _ = writeln!(text, "{}", line);
+                }
+            }
+            LogNode::Stage { name, children } => {
+                if expanded || expand.iter().any(|e| e.eq_ignore_ascii_case(name)) {
+                    let _ = writeln!(text, "{}{}", indent, style(format!("▾ {}", name)).cyan().bold());
+                    render(children, expand, depth + 1, true, text);
+                    continue;
+                }
+
+                let steps = stage_steps(children);
+                let summary = if steps.is_empty() { String::new() } else { format!(" — {}", steps.join(", ")) };
+                let _ = writeln!(
+                    text,
+                    "{}{}{} {}",
+                    indent,
+                    style(format!("▸ {}", name)).cyan().bold(),
+                    summary,
+                    style(format!("({} line(s) folded)", folded_lines(children))).dim()
+                );
+                // Nested stages (e.g. parallel branches) keep their own headings
+                render(children, expand, depth + 1, false, text);
+            }
+        }
+    }
+}
+
+/// Names of the steps run directly in a stage, in order of first use
+fn stage_steps(children: &[LogNode]) -> Vec<&str> {
+    let mut steps: Vec<&str> = Vec::new();
+    for node in children {
+        let LogNode::Line(line) = node else {
+            continue;
+        };
+        let Some(step) = pipeline_step(line).and_then(|s| s.split_whitespace().next()) else {
+            continue;
+        };
+        if !is_structural(line) && !steps.contains(&step) {
+            steps.push(step);
+        }
+    }
+    steps
+}
+
+/// Block delimiters (`{`, `}`, `// stage`) and steps like `stage` that only open blocks
+fn is_structural(line: &str) -> bool {
+    pipeline_step(line)
+        .and_then(|s| s.split_whitespace().next())
+        .is_some_and(|step| step.starts_with(['{', '}', '/']) || STRUCTURAL_STEPS.contains(&step))
+}
+
+/// Lines hidden by folding a stage (nested stages report their own)
+fn folded_lines(children: &[LogNode]) -> usize {
+    children.iter().filter(|node| matches!(node, LogNode::Line(_))).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOG: &str = "Started by user admin
+[Pipeline] Start of Pipeline
+[Pipeline] node
+Running on agent-1
+[Pipeline] {
+[Pipeline] stage
+[Pipeline] { (Build)
+[Pipeline] sh
++ make
+compiled 12 files
+[Pipeline] }
+[Pipeline] // stage
+[Pipeline] stage
+[Pipeline] { (Test)
+[Pipeline] parallel
+[Pipeline] { (Branch: unit)
+[Pipeline] sh
++ make test
+[Pipeline] }
+[Pipeline] }
+[Pipeline] // stage
+[Pipeline] }
+[Pipeline] // node
+[Pipeline] End of Pipeline
+Finished: SUCCESS";
+
+    #[test]
+    fn test_parse_stages() {
+        let nodes = parse(LOG);
+        let stages: Vec<&str> = nodes
+            .iter()
+            .filter_map(|node| match node {
+                LogNode::Stage { name, .. } => Some(name.as_str()),
+                LogNode::Line(_) => None,
+            })
+            .collect();
+        // The anonymous node block is transparent
+        assert_eq!(stages, vec!["Build", "Test"]);
+        assert_eq!(nodes.first(), Some(&LogNode::Line("Started by user admin".to_string())));
+        assert_eq!(nodes.last(), Some(&LogNode::Line("Finished: SUCCESS".to_string())));
+
+        let Some(LogNode::Stage { children, .. }) = nodes.iter().find(|n| matches!(n, LogNode::Stage { name, .. } if name == "Test")) else {
+            panic!("Test stage missing");
+        };
+        assert!(children.iter().any(|n| matches!(n, LogNode::Stage { name, .. } if name == "Branch: unit")));
+    }
+
+    #[test]
+    fn test_render_folded() {
+        let text = console::strip_ansi_codes(&render_folded(&parse(LOG), &[])).into_owned();
+
+        assert!(text.contains("Started by user admin\n[Pipeline] Start of Pipeline\n[Pipeline] node\nRunning on agent-1\n▸ Build"));
+        assert!(text.contains("▸ Build — sh (3 line(s) folded)"));
+        assert!(!text.contains("compiled 12 files"));
+        assert!(text.contains("  ▸ Branch: unit — sh (2 line(s) folded)"));
+        assert!(text.ends_with("Finished: SUCCESS\n"));
+    }
+
+    #[test]
+    fn test_render_folded_expands_named_stages() {
+        let text = console::strip_ansi_codes(&render_folded(&parse(LOG), &["build".to_string()])).into_owned();
+
+        assert!(text.contains("▾ Build\n[Pipeline] sh\n+ make\ncompiled 12 files\n"));
+        assert!(text.contains("▸ Test"));
+        assert!(!text.contains("+ make test"));
+    }
+
+    #[test]
+    fn test_parse_unclosed_and_plain_logs() {
+        let nodes = parse("[Pipeline] { (Deploy)\ndeploying...");
+        assert_eq!(
+            nodes,
+            vec![LogNode::Stage { name: "Deploy".to_string(), children: vec![LogNode::Line("deploying...".to_string())] }]
+        );
+
+        let plain = parse("line 1\n[Pipeline] }\nline 2");
+        assert_eq!(plain.len(), 3);
+    }
+}
@@ -0,0 +1,58 @@
+use anyhow::Result;
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Environment variable that turns on non-interactive mode, e.g. in CI
+pub const NON_INTERACTIVE_ENV: &str = "JENKINS_CLI_NONINTERACTIVE";
+
+/// Set from the global `--non-interactive` flag
+static NON_INTERACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Fail at every prompt site instead of waiting for input
+pub fn set_non_interactive() {
+    NON_INTERACTIVE.store(true, Ordering::Relaxed);
+}
+
+/// True if prompting is disabled by the flag or the environment, or there is no terminal to answer on
+/// Piping only stdout (e.g. into `tee`) keeps prompts, which are drawn on stderr
+pub fn is_non_interactive() -> bool {
+    requested() || !std::io::stdin().is_terminal()
+}
+
+/// Bail with an actionable message if prompting is disabled
+/// `what` names the missing input, `hint` says how to provide it without a prompt
+pub fn ensure_interactive(what: &str, hint: &str) -> Result<()> {
+    if requested() {
+        anyhow::bail!("Cannot prompt for {} in non-interactive mode.\n{}", what, hint);
+    }
+    if !std::io::stdin().is_terminal() {
+        anyhow::bail!("Cannot prompt for {} without a terminal to answer on.\n{}", what, hint);
+    }
+    Ok(())
+}
+
+/// True if non-interactive mode was asked for explicitly, by the flag or the environment
+/// Only then may a prompt's answer be assumed, e.g. a parameter's default
+pub fn requested() -> bool {
+    NON_INTERACTIVE.load(Ordering::Relaxed)
+        || std::env::var(NON_INTERACTIVE_ENV).is_ok_and(|value| is_enabled(&value))
+}
+
+fn is_enabled(value: &str) -> bool {
+    matches!(value.trim().to_lowercase().as_str(), "1" | "true" | "yes" | "on")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_enabled() {
+        assert!(is_enabled("1"));
+        assert!(is_enabled("true"));
+        assert!(is_enabled(" YES "));
+        assert!(!is_enabled("0"));
+        assert!(!is_enabled("false"));
+        assert!(!is_enabled(""));
+    }
+}
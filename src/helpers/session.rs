@@ -0,0 +1,57 @@
+use crate::client::JenkinsClient;
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+
+/// In-memory selections that persist across commands run inside `jenkins shell`
+#[derive(Default)]
+struct Session {
+    jenkins: Option<String>,
+    job: Option<String>,
+    clients: HashMap<String, JenkinsClient>,
+}
+
+static SESSION: Mutex<Option<Session>> = Mutex::new(None);
+
+fn lock() -> MutexGuard<'static, Option<Session>> {
+    SESSION.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Start keeping selections and clients in memory
+pub fn start() {
+    *lock() = Some(Session::default());
+}
+
+pub fn is_active() -> bool {
+    lock().is_some()
+}
+
+pub fn jenkins() -> Option<String> {
+    lock().as_ref().and_then(|s| s.jenkins.clone())
+}
+
+pub fn set_jenkins(name: Option<String>) {
+    if let Some(session) = lock().as_mut() {
+        session.jenkins = name;
+    }
+}
+
+pub fn job() -> Option<String> {
+    lock().as_ref().and_then(|s| s.job.clone())
+}
+
+pub fn set_job(job: Option<String>) {
+    if let Some(session) = lock().as_mut() {
+        session.job = job;
+    }
+}
+
+/// Client previously created for this host during the session
+pub fn cached_client(jenkins_name: &str) -> Option<JenkinsClient> {
+    lock().as_ref().and_then(|s| s.clients.get(jenkins_name).cloned())
+}
+
+pub fn cache_client(jenkins_name: &str, client: &JenkinsClient) {
+    if let Some(session) = lock().as_mut() {
+        session.clients.insert(jenkins_name.to_string(), client.clone());
+    }
+}
@@ -0,0 +1,103 @@
+use crate::client::{JenkinsClient, PipelineStage};
+
+/// Log polls (every 500ms) between stage checks while following a build, i.e. ~2s
+pub const STAGE_POLL_EVERY: u32 = 4;
+
+/// Tracks pipeline stage statuses between polls of the wfapi endpoint
+#[derive(Default)]
+pub struct StageTracker {
+    stages: Vec<PipelineStage>,
+    polled: bool,
+    unsupported: bool,
+}
+
+impl StageTracker {
+    /// Fetch the current stages and return those that are new or changed status since the last poll
+    /// Non-pipeline builds (and failed requests) yield nothing
+    pub fn poll(&mut self, client: &JenkinsClient, job_name: &str, build_number: i32) -> Vec<PipelineStage> {
+        if self.unsupported {
+            return Vec::new();
+        }
+
+        match client.get_pipeline_stages(job_name, build_number) {
+            Ok(Some(stages)) => {
+                let changed = changed_stages(&self.stages, &stages);
+                self.stages = stages;
+                self.polled = true;
+                changed
+            }
+            Ok(None) => {
+                self.unsupported = true;
+                Vec::new()
+            }
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Like `poll`, but only the stages that weren't seen before (i.e. that have started)
+    /// On the first poll only the running stage counts - earlier stages are already in the log
+    pub fn poll_started(&mut self, client: &JenkinsClient, job_name: &str, build_number: i32) -> Vec<PipelineStage> {
+        let previous = self.polled.then(|| self.stages.clone());
+        let changed = self.poll(client, job_name, build_number);
+        started_stages(previous.as_deref(), changed)
+    }
+}
+
+/// Newly started stages among the changed ones (`previous` is None before the first poll)
+fn started_stages(previous: Option<&[PipelineStage]>, changed: Vec<PipelineStage>) -> Vec<PipelineStage> {
+    match previous {
+        Some(previous) => changed
+            .into_iter()
+            .filter(|stage| !previous.iter().any(|prev| prev.id == stage.id))
+            .collect(),
+        None => changed.into_iter().filter(|stage| stage.status == "IN_PROGRESS").collect(),
+    }
+}
+
+/// Stages whose status differs from the previous list, including stages that weren't there yet
+fn changed_stages(previous: &[PipelineStage], current: &[PipelineStage]) -> Vec<PipelineStage> {
+    current
+        .iter()
+        .filter(|stage| {
+            !previous
+                .iter()
+                .any(|prev| prev.id == stage.id && prev.status == stage.status)
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stage(id: &str, name: &str, status: &str) -> PipelineStage {
+        PipelineStage {
+            id: id.to_string(),
+            name: name.to_string(),
+            status: status.to_string(),
+            start_time: None,
+            duration: None,
+        }
+    }
+
+    #[test]
+    fn test_changed_stages() {
+        let previous = vec![stage("6", "Build", "IN_PROGRESS")];
+        let current = vec![stage("6", "Build", "SUCCESS"), stage("14", "Test", "IN_PROGRESS")];
+
+        let changed = changed_stages(&previous, &current);
+        assert_eq!(changed, current);
+
+        assert!(changed_stages(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn test_started_stages() {
+        let first = vec![stage("6", "Build", "SUCCESS"), stage("14", "Test", "IN_PROGRESS")];
+        assert_eq!(started_stages(None, first.clone()), vec![stage("14", "Test", "IN_PROGRESS")]);
+
+        let changed = vec![stage("14", "Test", "SUCCESS"), stage("20", "Deploy", "IN_PROGRESS")];
+        assert_eq!(started_stages(Some(&first), changed), vec![stage("20", "Deploy", "IN_PROGRESS")]);
+    }
+}
@@ -3,9 +3,162 @@ pub fn normalize_host_url(host: &str) -> &str {
     host.trim_end_matches('/')
 }
 
+/// Percent-encode a single URL path segment (everything except RFC 3986 unreserved characters)
+pub fn encode_path_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Percent-encode each segment of a job path like `folder/job/My Service`
+pub fn encode_job_path(job_name: &str) -> String {
+    job_name
+        .split('/')
+        .map(encode_path_segment)
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Decode `%XX` escapes in a URL path segment
+pub fn decode_path_segment(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && i + 2 < bytes.len()
+            && let Some(byte) = std::str::from_utf8(&bytes[i + 1..i + 3])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            decoded.push(byte);
+            i += 3;
+            continue;
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Extract the job path and build number from a Jenkins URL on the given host
+/// e.g. `https://jenkins.example.com/job/team/job/api/42/console` → (`team/job/api`, Some(42))
+pub fn parse_job_url(url: &str, host: &str) -> Option<(String, Option<i32>)> {
+    let host = normalize_host_url(host);
+    let rest = url.strip_prefix(host)?;
+    if !rest.is_empty() && !rest.starts_with('/') {
+        return None;
+    }
+
+    let mut segments = rest.split('/').filter(|s| !s.is_empty()).peekable();
+    let mut names = Vec::new();
+
+    // Skip any view prefix (`/view/<name>`) before the job segments
+    while segments.peek() == Some(&"view") {
+        segments.next();
+        segments.next();
+    }
+
+    while segments.peek() == Some(&"job") {
+        segments.next();
+        names.push(decode_path_segment(segments.next()?));
+    }
+
+    if names.is_empty() {
+        return None;
+    }
+
+    let build = segments.next().and_then(|s| s.parse::<i32>().ok());
+    Some((names.join("/job/"), build))
+}
+
+/// Extract the queue item id from a queue Location URL (e.g. `.../queue/item/123/`)
+pub fn queue_item_id(queue_url: &str) -> Option<i64> {
+    let mut segments = queue_url.trim_end_matches('/').rsplit('/');
+    let id = segments.next()?.parse().ok()?;
+    (segments.next()? == "item").then_some(id)
+}
+
+/// Separator Jenkins puts between folder names in breadcrumbs and full display names
+pub const FOLDER_SEPARATOR: &str = " » ";
+
+/// Convert a natural `folder/subfolder/job` path into Jenkins' `folder/job/subfolder/job/job` form
+/// Paths already in the Jenkins form are returned unchanged, human `folder » job` paths are converted too
+/// In the Jenkins form each name sits between `/job/` separators, so a slash inside one is part of a
+/// branch name (`my-service/job/feature/foo` is the branch `feature/foo`)
+pub fn to_jenkins_job_path(path: &str) -> String {
+    if path.contains(FOLDER_SEPARATOR.trim()) {
+        return from_human_job_path(path);
+    }
+    let path = path.trim_matches('/');
+    if path.contains("/job/") {
+        return path.split("/job/").map(encode_branch_name).collect::<Vec<_>>().join("/job/");
+    }
+    path.split('/').collect::<Vec<_>>().join("/job/")
+}
+
+/// How multibranch projects encode a slash in branch job names
+const BRANCH_SLASH: &str = "%2F";
+
+/// Name of the job a multibranch project creates for a branch: Jenkins encodes the slashes
+/// (`feature/foo` → `feature%2Ffoo`), and the job URL encodes that name once more
+pub fn encode_branch_name(branch: &str) -> String {
+    branch.replace('/', BRANCH_SLASH)
+}
+
+/// The branch name of a multibranch branch job (`feature%2Ffoo` → `feature/foo`)
+pub fn decode_branch_name(name: &str) -> String {
+    name.replace(BRANCH_SLASH, "/").replace("%2f", "/")
+}
+
+/// Readings of a path whose trailing names may be one branch name containing slashes, longest
+/// folder prefix first: `svc/job/feature/job/foo` → `svc/job/feature%2Ffoo`
+/// The path itself isn't included, and paths with fewer than three names have no other reading
+pub fn branch_job_candidates(job_name: &str) -> Vec<String> {
+    let path = to_jenkins_job_path(job_name);
+    let names: Vec<&str> = path.split("/job/").collect();
+    (1..names.len().saturating_sub(1))
+        .rev()
+        .map(|split| format!("{}/job/{}", names[..split].join("/job/"), names[split..].join(BRANCH_SLASH)))
+        .collect()
+}
+
+/// Convert a Jenkins `folder/job/child` path into the natural `folder/child` form for display
+pub fn to_display_job_path(path: &str) -> String {
+    path.split("/job/").collect::<Vec<_>>().join("/")
+}
+
+/// Convert a job path in either form into the human `team » api » deploy` form for headers and prompts
+/// Branch names are shown as typed, with their slashes
+pub fn to_human_job_path(path: &str) -> String {
+    to_jenkins_job_path(path)
+        .split("/job/")
+        .filter(|name| !name.is_empty())
+        .map(decode_branch_name)
+        .collect::<Vec<_>>()
+        .join(FOLDER_SEPARATOR)
+}
+
+/// Convert a human `team » api » deploy` path (as copied from a Jenkins page) into the Jenkins form
+pub fn from_human_job_path(path: &str) -> String {
+    path.split(FOLDER_SEPARATOR.trim())
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(encode_branch_name)
+        .collect::<Vec<_>>()
+        .join("/job/")
+}
+
 /// Build a Jenkins job URL
 pub fn build_job_url(host: &str, job_name: &str) -> String {
-    format!("{}/job/{}", normalize_host_url(host), job_name)
+    format!("{}/job/{}", normalize_host_url(host), encode_job_path(job_name))
 }
 
 /// Build a Jenkins API URL
@@ -18,11 +171,16 @@ pub fn build_build_url(host: &str, job_name: &str, build_number: i32) -> String
     format!(
         "{}/job/{}/{}",
         normalize_host_url(host),
-        job_name,
+        encode_job_path(job_name),
         build_number
     )
 }
 
+/// Build the URL of an agent (node) page
+pub fn build_computer_url(host: &str, node: &str) -> String {
+    format!("{}/computer/{}", normalize_host_url(host), encode_path_segment(node))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -55,6 +213,135 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_encode_path_segment() {
+        assert_eq!(encode_path_segment("my-job_1.0~x"), "my-job_1.0~x");
+        assert_eq!(encode_path_segment("My Service (prod)"), "My%20Service%20%28prod%29");
+        assert_eq!(encode_path_segment("a#b?c"), "a%23b%3Fc");
+        assert_eq!(encode_path_segment("ünïcode"), "%C3%BCn%C3%AFcode");
+        // Multibranch names are already encoded by Jenkins and need a second pass in URLs
+        assert_eq!(encode_path_segment("feature%2Ffoo"), "feature%252Ffoo");
+    }
+
+    #[test]
+    fn test_build_job_url_encodes_segments() {
+        assert_eq!(
+            build_job_url("https://jenkins.example.com", "team/job/My Service (prod)"),
+            "https://jenkins.example.com/job/team/job/My%20Service%20%28prod%29"
+        );
+        assert_eq!(
+            build_build_url("https://jenkins.example.com", "My Service", 7),
+            "https://jenkins.example.com/job/My%20Service/7"
+        );
+    }
+
+    #[test]
+    fn test_to_jenkins_job_path() {
+        assert_eq!(to_jenkins_job_path("my-job"), "my-job");
+        assert_eq!(to_jenkins_job_path("team/api"), "team/job/api");
+        assert_eq!(to_jenkins_job_path("team/backend/api"), "team/job/backend/job/api");
+        assert_eq!(to_jenkins_job_path("/team/api/"), "team/job/api");
+        // Already in Jenkins form
+        assert_eq!(to_jenkins_job_path("team/job/api"), "team/job/api");
+        assert_eq!(to_jenkins_job_path("team/job/backend/job/api"), "team/job/backend/job/api");
+    }
+
+    #[test]
+    fn test_to_display_job_path() {
+        assert_eq!(to_display_job_path("my-job"), "my-job");
+        assert_eq!(to_display_job_path("team/job/backend/job/api"), "team/backend/api");
+        assert_eq!(to_display_job_path(&to_jenkins_job_path("team/backend/api")), "team/backend/api");
+    }
+
+    #[test]
+    fn test_branch_names_with_slashes() {
+        // A slash between `/job/` separators belongs to a branch name
+        assert_eq!(to_jenkins_job_path("my-service/job/feature/foo"), "my-service/job/feature%2Ffoo");
+        assert_eq!(to_jenkins_job_path("my-service/job/feature%2Ffoo"), "my-service/job/feature%2Ffoo");
+        assert_eq!(to_jenkins_job_path("my-service » feature/foo"), "my-service/job/feature%2Ffoo");
+        assert_eq!(to_human_job_path("my-service/job/feature%2Ffoo"), "my-service » feature/foo");
+        assert_eq!(to_display_job_path("my-service/job/feature%2Ffoo"), "my-service/feature%2Ffoo");
+
+        // The server sees the name encoded once more
+        assert_eq!(
+            build_job_url("https://jenkins.example.com", "my-service/job/feature%2Ffoo"),
+            "https://jenkins.example.com/job/my-service/job/feature%252Ffoo"
+        );
+        assert_eq!(
+            parse_job_url("https://jenkins.example.com/job/my-service/job/feature%252Ffoo/3/", "https://jenkins.example.com"),
+            Some(("my-service/job/feature%2Ffoo".to_string(), Some(3)))
+        );
+
+        assert_eq!(
+            branch_job_candidates("team/my-service/feature/foo"),
+            vec!["team/job/my-service/job/feature%2Ffoo", "team/job/my-service%2Ffeature%2Ffoo"]
+        );
+        assert!(branch_job_candidates("team/api").is_empty());
+    }
+
+    #[test]
+    fn test_human_job_path() {
+        assert_eq!(to_human_job_path("team/job/api/job/deploy"), "team » api » deploy");
+        assert_eq!(to_human_job_path("team/api/deploy"), "team » api » deploy");
+        assert_eq!(to_human_job_path("deploy"), "deploy");
+
+        assert_eq!(from_human_job_path("team » api » deploy"), "team/job/api/job/deploy");
+        assert_eq!(from_human_job_path(" team»api "), "team/job/api");
+        assert_eq!(to_jenkins_job_path("team » api"), "team/job/api");
+        assert_eq!(to_jenkins_job_path(&to_human_job_path("team/job/My Service")), "team/job/My Service");
+    }
+
+    #[test]
+    fn test_decode_path_segment() {
+        assert_eq!(decode_path_segment("My%20Service%20%28prod%29"), "My Service (prod)");
+        assert_eq!(decode_path_segment("feature%252Ffoo"), "feature%2Ffoo");
+        assert_eq!(decode_path_segment("plain"), "plain");
+        assert_eq!(decode_path_segment("bad%zz%"), "bad%zz%");
+        assert_eq!(decode_path_segment("%ü"), "%ü");
+        assert_eq!(decode_path_segment(&encode_path_segment("ünïcode job")), "ünïcode job");
+    }
+
+    #[test]
+    fn test_parse_job_url() {
+        let host = "https://jenkins.example.com";
+        assert_eq!(
+            parse_job_url("https://jenkins.example.com/job/team/job/api/42/", host),
+            Some(("team/job/api".to_string(), Some(42)))
+        );
+        assert_eq!(
+            parse_job_url("https://jenkins.example.com/job/api/", host),
+            Some(("api".to_string(), None))
+        );
+        assert_eq!(
+            parse_job_url("https://jenkins.example.com/job/api/17/console", host),
+            Some(("api".to_string(), Some(17)))
+        );
+        assert_eq!(
+            parse_job_url("https://jenkins.example.com/view/All/job/My%20Service/", host),
+            Some(("My Service".to_string(), None))
+        );
+        assert_eq!(
+            parse_job_url("https://jenkins.example.com/job/api/lastBuild/", host),
+            Some(("api".to_string(), None))
+        );
+        // Hosts served under a path prefix
+        assert_eq!(
+            parse_job_url("https://ci.example.com/jenkins/job/api/3/", "https://ci.example.com/jenkins/"),
+            Some(("api".to_string(), Some(3)))
+        );
+        assert_eq!(parse_job_url("https://other.example.com/job/api/", host), None);
+        assert_eq!(parse_job_url("https://jenkins.example.com.evil/job/api/", host), None);
+        assert_eq!(parse_job_url("https://jenkins.example.com/manage/", host), None);
+    }
+
+    #[test]
+    fn test_queue_item_id() {
+        assert_eq!(queue_item_id("https://jenkins.example.com/queue/item/123/"), Some(123));
+        assert_eq!(queue_item_id("https://jenkins.example.com/queue/item/42"), Some(42));
+        assert_eq!(queue_item_id("https://jenkins.example.com/job/api/12/"), None);
+        assert_eq!(queue_item_id("https://jenkins.example.com/queue/item/abc/"), None);
+    }
+
     #[test]
     fn test_build_api_url() {
         assert_eq!(
@@ -78,4 +365,12 @@ mod tests {
             "https://jenkins.example.com/job/my-job/123"
         );
     }
+
+    #[test]
+    fn test_build_computer_url() {
+        assert_eq!(
+            build_computer_url("https://jenkins.example.com/", "linux agent"),
+            "https://jenkins.example.com/computer/linux%20agent"
+        );
+    }
 }
@@ -1,10 +1,20 @@
 use anyhow::{Context, Result};
-use inquire::{Confirm, InquireError, Select, Text};
+use inquire::validator::Validation;
+use inquire::{Confirm, Editor, InquireError, Select, Text};
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
 
-use crate::client::{JenkinsClient, ParameterDefinition, ParameterValue};
+use crate::client::{BuildInfo, JenkinsClient, ParameterDefinition, ParameterValue, SubJobInfo};
 use crate::config::Config;
-use crate::helpers::formatting::format_job_color as format_color;
+use crate::helpers::cache::CacheKind;
+use crate::helpers::formatting::{format_build_timing, format_duration, format_job_color as format_color, format_timestamp, now_millis};
+use crate::helpers::errors::{kind_of, ErrorKind};
+use crate::helpers::git;
+use crate::helpers::init::profile_default_job;
+use crate::helpers::prompt;
+use crate::helpers::url::{encode_branch_name, FOLDER_SEPARATOR, to_display_job_path, to_human_job_path, to_jenkins_job_path};
 use crate::output;
+use crate::state::State;
 
 /// Handle inquire errors and convert to user-friendly messages
 fn handle_inquire_error<T>(result: Result<T, InquireError>) -> Result<T> {
@@ -12,196 +22,398 @@ fn handle_inquire_error<T>(result: Result<T, InquireError>) -> Result<T> {
         Ok(value) => Ok(value),
         Err(InquireError::OperationCanceled) => {
             output::cancelled("Operation cancelled by user");
-            std::process::exit(0);
+            std::process::exit(ErrorKind::Cancelled.exit_code());
         }
         Err(InquireError::OperationInterrupted) => {
             output::cancelled("Operation interrupted by user");
-            std::process::exit(0);
+            std::process::exit(ErrorKind::Cancelled.exit_code());
         }
         Err(e) => Err(e).context("Failed to get user input"),
     }
 }
 
-/// Resolves the final job name by interactively selecting from sub-jobs if present
-pub fn resolve_job_name(client: &JenkinsClient, initial_job_name: Option<&str>) -> Result<String> {
-    let mut current_job_name = match initial_job_name {
-        Some(name) => {
-            // Resolve alias if present
-            let config = Config::load()?;
-            let (job_name, is_alias, jenkins) = config.resolve_job_name(name);
-            if is_alias {
-                if let Some(j) = jenkins {
-                    output::dim(&format!("Using alias '{}' → '{}' (Jenkins: {})", name, job_name, j));
-                } else {
-                    output::dim(&format!("Using alias '{}' → '{}'", name, job_name));
-                }
-            }
-            job_name
-        },
-        None => {
-            // No job name provided, start from root
-            let sp = output::spinner("Loading jobs...");
-            let root_jobs = client.get_root_jobs()?;
-            sp.finish_and_clear();
-
-            if root_jobs.is_empty() {
-                anyhow::bail!("No jobs found on this Jenkins instance");
-            }
+/// Number of jobs fetched per request when browsing large folders
+const JOBS_PAGE_SIZE: usize = 100;
+const LOAD_MORE_OPTION: &str = "[Load more…]";
+const OPEN_CURRENT_OPTION: &str = "[Open this job/folder]";
+const GO_UP_OPTION: &str = "[.. go up]";
+const SEARCH_OPTION: &str = "[Search all sub-folders…]";
+/// Folder levels searched below the current folder
+const SEARCH_DEPTH: usize = 4;
+
+/// Jobs of a folder (or the root), loaded one page at a time
+struct JobPager<'a> {
+    client: &'a JenkinsClient,
+    folder: Option<String>,
+    jobs: Vec<SubJobInfo>,
+    exhausted: bool,
+}
 
-            // Create display options with job name and status
-            let options: Vec<String> = root_jobs
-                .iter()
-                .map(|job| format!("{} [{}]", job.name, format_color(job.color.as_deref())))
-                .collect();
-
-            let selection = handle_inquire_error(
-                Select::new("Select a job:", options)
-                    .with_help_message("Use ↑↓ to navigate, type to search, Enter to select, ESC to cancel")
-                    .prompt()
-            )?;
-
-            // Extract job name from selection (remove the status part)
-            let job_name = selection.split(" [").next().unwrap().to_string();
-            job_name
-        }
-    };
+impl<'a> JobPager<'a> {
+    fn new(client: &'a JenkinsClient, folder: Option<&str>) -> Result<Self> {
+        let mut pager = JobPager {
+            client,
+            folder: folder.map(|f| f.to_string()),
+            jobs: Vec::new(),
+            exhausted: false,
+        };
+        pager.load_more()?;
+        Ok(pager)
+    }
 
-    loop {
-        let sp = output::spinner("Loading job details...");
-        let job_info = client.get_job(&current_job_name)?;
-        sp.finish_and_clear();
+    fn load_more(&mut self) -> Result<()> {
+        let page = self.client.get_jobs_page(self.folder.as_deref(), self.jobs.len(), JOBS_PAGE_SIZE, CacheKind::Jobs)?;
+        self.exhausted = page.len() < JOBS_PAGE_SIZE;
+        self.jobs.extend(page);
+        Ok(())
+    }
 
-        // If no sub-jobs, return the current job name
-        if job_info.jobs.is_none() || job_info.jobs.as_ref().unwrap().is_empty() {
-            return Ok(current_job_name);
+    /// Number of jobs for display, e.g. `100+` while more pages remain
+    fn count_label(&self) -> String {
+        if self.exhausted {
+            self.jobs.len().to_string()
+        } else {
+            format!("{}+", self.jobs.len())
         }
+    }
+}
 
-        // Display sub-jobs and let user select
-        let sub_jobs = job_info.jobs.unwrap();
+/// Let the user pick a job from the pager, loading further pages on request
+/// Returns the selected job name, or one of `leading_options` if that was chosen
+fn select_job(pager: &mut JobPager, leading_options: &[&str]) -> Result<String> {
+    prompt::ensure_interactive("a job", "Pass the full job path (e.g. 'folder/job') or a job alias.")?;
 
-        // Create display options with job name and status
-        let options: Vec<String> = sub_jobs
-            .iter()
-            .map(|job| format!("{} [{}]", job.name, format_color(job.color.as_deref())))
-            .collect();
+    // Start on the first real choice rather than on navigation entries
+    let mut cursor = leading_options.iter().filter(|o| [GO_UP_OPTION, SEARCH_OPTION].contains(o)).count();
+
+    loop {
+        let mut options: Vec<String> = leading_options.iter().map(|o| o.to_string()).collect();
+        options.extend(job_options(&pager.jobs, now_millis()));
+        if !pager.exhausted {
+            options.push(LOAD_MORE_OPTION.to_string());
+        }
 
-        output::dim(&format!("'{}' contains {} sub-job(s).", current_job_name, sub_jobs.len()));
         let selection = handle_inquire_error(
             Select::new("Select a job:", options)
+                .with_starting_cursor(cursor)
                 .with_help_message("Use ↑↓ to navigate, type to search, Enter to select, ESC to cancel")
-                .prompt()
+                .raw_prompt()
         )?;
 
-        // Extract job name from selection (remove the status part)
-        let selected_job_name = selection.split(" [").next().unwrap().to_string();
-
-        // Find the original job info
-        let selected_job = sub_jobs
-            .iter()
-            .find(|job| job.name == selected_job_name)
-            .unwrap();
+        if let Some(option) = leading_options.get(selection.index) {
+            return Ok(option.to_string());
+        }
+        if let Some(job) = pager.jobs.get(selection.index - leading_options.len()) {
+            return Ok(job.name.clone());
+        }
 
-        // Build the full job path
-        // Jenkins uses the format: parent/job/child
-        current_job_name = format!("{}/job/{}", current_job_name, selected_job.name);
+        // Only "Load more" is left
+        {
+            // Keep the cursor on the first newly loaded job
+            cursor = leading_options.len() + pager.jobs.len();
+            let sp = output::spinner("Loading more jobs...");
+            pager.load_more()?;
+            sp.finish_and_clear();
+        }
     }
 }
 
-/// Resolves the job name for the open command, allowing to stop at any level
-pub fn resolve_job_name_for_open(client: &JenkinsClient, initial_job_name: Option<&str>) -> Result<String> {
-    let mut current_job_name = match initial_job_name {
-        Some(name) => {
-            // Resolve alias if present
-            let config = Config::load()?;
-            let (job_name, is_alias, jenkins) = config.resolve_job_name(name);
-            if is_alias {
-                if let Some(j) = jenkins {
-                    output::dim(&format!("Using alias '{}' → '{}' (Jenkins: {})", name, job_name, j));
-                } else {
-                    output::dim(&format!("Using alias '{}' → '{}'", name, job_name));
+/// Select entries for jobs, in columns: name and status, when the last build ran, and how long it took
+fn job_options(jobs: &[SubJobInfo], now: i64) -> Vec<String> {
+    let rows: Vec<(String, String, String)> = jobs
+        .iter()
+        .map(|job| {
+            let name = format!("{} [{}]", job.name, format_color(job.color.as_deref()));
+            let Some(build) = &job.last_build else {
+                return (name, "never built".to_string(), String::new());
+            };
+            let when = build.timestamp.map(|t| format_timestamp(t, now)).unwrap_or_default();
+            let took = match (build.building, build.duration) {
+                (Some(true), _) => "running".to_string(),
+                (_, Some(duration)) => format!("took {}", format_duration(duration)),
+                _ => String::new(),
+            };
+            (name, when, took)
+        })
+        .collect();
+
+    let name_width = rows.iter().map(|(name, _, _)| name.chars().count()).max().unwrap_or(0);
+    let when_width = rows.iter().map(|(_, when, _)| when.chars().count()).max().unwrap_or(0);
+    rows.into_iter()
+        .map(|(name, when, took)| {
+            format!("{:<name_width$}  {:<when_width$}  {}", name, when, took).trim_end().to_string()
+        })
+        .collect()
+}
+
+/// Walk the folder tree from `start` (the root if None) until a job without sub-jobs is selected
+/// With `open_folders`, the folder being shown can be chosen as well
+fn browse_jobs(client: &JenkinsClient, start: Option<String>, open_folders: bool) -> Result<String> {
+    let mut current = start;
+
+    loop {
+        let sp = output::spinner(if current.is_some() { "Loading job details..." } else { "Loading jobs..." });
+        let pager = JobPager::new(client, current.as_deref());
+        sp.finish_and_clear();
+        let mut pager = match (pager, current.as_deref()) {
+            // Branch names with slashes are typed as they are, e.g. `my-service/feature/foo`
+            (Err(e), Some(job)) if kind_of(&e) == Some(ErrorKind::NotFound) => match client.find_branch_job(job)? {
+                Some(branch_job) => {
+                    current = Some(branch_job);
+                    continue;
                 }
-            }
-            job_name
-        },
-        None => {
-            // No job name provided, start from root
-            let sp = output::spinner("Loading jobs...");
-            let root_jobs = client.get_root_jobs()?;
-            sp.finish_and_clear();
+                None => return Err(e),
+            },
+            (pager, _) => pager?,
+        };
 
-            if root_jobs.is_empty() {
+        let Some(folder) = current else {
+            if pager.jobs.is_empty() {
                 anyhow::bail!("No jobs found on this Jenkins instance");
             }
+            let selection = select_job(&mut pager, &[SEARCH_OPTION])?;
+            if selection == SEARCH_OPTION {
+                match search_jobs(client, None)? {
+                    Some(job) => return Ok(job),
+                    None => continue,
+                }
+            }
+            current = Some(selection);
+            continue;
+        };
 
-            // Create display options with job name and status
-            let options: Vec<String> = root_jobs
-                .iter()
-                .map(|job| format!("{} [{}]", job.name, format_color(job.color.as_deref())))
-                .collect();
-
-            let selection = handle_inquire_error(
-                Select::new("Select a job:", options)
-                    .with_help_message("Use ↑↓ to navigate, type to search, Enter to select, ESC to cancel")
-                    .prompt()
-            )?;
-
-            // Extract job name from selection (remove the status part)
-            let job_name = selection.split(" [").next().unwrap().to_string();
-            job_name
+        // If no sub-jobs, return the current job name
+        if pager.jobs.is_empty() {
+            return Ok(folder);
         }
+
+        if prompt::is_non_interactive() {
+            // Folders open as-is without prompting
+            if open_folders {
+                return Ok(folder);
+            }
+            anyhow::bail!(folder_contents_error(&folder, &pager.jobs));
+        }
+
+        output::dim(&format!("{} ({} sub-job(s))", breadcrumb(&folder), pager.count_label()));
+        let options: &[&str] = if open_folders {
+            &[GO_UP_OPTION, SEARCH_OPTION, OPEN_CURRENT_OPTION]
+        } else {
+            &[GO_UP_OPTION, SEARCH_OPTION]
+        };
+        let selection = select_job(&mut pager, options)?;
+
+        current = match selection.as_str() {
+            GO_UP_OPTION => parent_folder(&folder),
+            OPEN_CURRENT_OPTION => return Ok(folder),
+            SEARCH_OPTION => match search_jobs(client, Some(&folder))? {
+                Some(job) => return Ok(job),
+                None => Some(folder),
+            },
+            // Jenkins uses the format: parent/job/child
+            _ => Some(format!("{}/job/{}", folder, selection)),
+        };
+    }
+}
+
+/// Pick a job from everything below `folder`, matching the typed text against full paths
+/// Returns None (back to browsing) when nothing is found or ESC is pressed
+fn search_jobs(client: &JenkinsClient, folder: Option<&str>) -> Result<Option<String>> {
+    let sp = output::spinner("Searching sub-folders...");
+    let jobs = client.get_descendant_jobs(folder, SEARCH_DEPTH)?;
+    sp.finish_and_clear();
+
+    let location = breadcrumb(folder.unwrap_or_default());
+    if jobs.is_empty() {
+        output::warning(&format!("No jobs found below {}", location));
+        return Ok(None);
+    }
+
+    let options: Vec<String> = jobs
+        .iter()
+        .map(|job| format!("{} [{}]", to_human_job_path(&job.path), format_color(job.color.as_deref())))
+        .collect();
+    let selection = Select::new(&format!("Search jobs below {}:", location), options)
+        .with_help_message("Type any part of the path, Enter to select, ESC to go back to browsing")
+        .raw_prompt();
+    let index = match selection {
+        Ok(selection) => selection.index,
+        Err(InquireError::OperationCanceled) => return Ok(None),
+        Err(e) => return handle_inquire_error(Err(e)),
     };
 
-    loop {
-        let sp = output::spinner("Loading job details...");
-        let job_info = client.get_job(&current_job_name)?;
-        sp.finish_and_clear();
+    let path = &jobs[index].path;
+    Ok(Some(match folder {
+        Some(folder) => format!("{}/job/{}", folder, path),
+        None => path.clone(),
+    }))
+}
 
-        // If no sub-jobs, return the current job name
-        if job_info.jobs.is_none() || job_info.jobs.as_ref().unwrap().is_empty() {
-            return Ok(current_job_name);
+/// Folder containing `job`, or None for top-level jobs
+fn parent_folder(job: &str) -> Option<String> {
+    to_jenkins_job_path(job).rsplit_once("/job/").map(|(parent, _)| parent.to_string())
+}
+
+/// Path from the root to `folder`, e.g. `Jenkins » team » api` (just `Jenkins` for the root)
+fn breadcrumb(folder: &str) -> String {
+    let path = to_human_job_path(folder);
+    if path.is_empty() {
+        return "Jenkins".to_string();
+    }
+    format!("Jenkins{}{}", FOLDER_SEPARATOR, path)
+}
+
+/// Resolve an alias to its job name, noting the substitution
+fn resolve_alias(name: &str) -> Result<String> {
+    let config = Config::load()?;
+    let (job_name, is_alias, jenkins) = config.resolve_job_name(name)?;
+    if is_alias {
+        if let Some(j) = jenkins {
+            output::dim(&format!("Using alias '{}' → '{}' (Jenkins: {})", name, to_human_job_path(&job_name), j));
+        } else {
+            output::dim(&format!("Using alias '{}' → '{}'", name, to_human_job_path(&job_name)));
         }
+    }
+    Ok(job_name)
+}
 
-        // Display options: open current or select sub-job
-        let sub_jobs = job_info.jobs.unwrap();
+/// Resolves the final job name by interactively selecting from sub-jobs if present
+pub fn resolve_job_name(client: &JenkinsClient, initial_job_name: Option<&str>) -> Result<String> {
+    // Fall back to the directory profile's default job
+    let profile_job = if initial_job_name.is_none() { profile_default_job()? } else { None };
+    let initial_job_name = initial_job_name.or(profile_job.as_deref());
 
-        // Create display options with "Open this job/folder" as first option
-        let mut options: Vec<String> = vec!["[Open this job/folder]".to_string()];
-        options.extend(
-            sub_jobs
-                .iter()
-                .map(|job| format!("{} [{}]", job.name, format_color(job.color.as_deref())))
-        );
+    let start = initial_job_name.map(resolve_alias).transpose()?;
+    remember_job(client, browse_jobs(client, start, false)?)
+}
 
-        output::dim(&format!("'{}' contains {} sub-job(s).", current_job_name, sub_jobs.len()));
-        let selection = handle_inquire_error(
-            Select::new("Select a job:", options)
-                .with_help_message("Use ↑↓ to navigate, type to search, Enter to select, ESC to cancel")
-                .prompt()
-        )?;
+/// Descend from a folder until a job without sub-jobs is selected
+/// Returns `folder` itself if it has no sub-jobs
+pub fn select_sub_job(client: &JenkinsClient, folder: &str) -> Result<String> {
+    remember_job(client, browse_jobs(client, Some(folder.to_string()), false)?)
+}
 
-        // If user selected "Open this job/folder", return current job
-        if selection == "[Open this job/folder]" {
-            return Ok(current_job_name);
-        }
+/// The job of the checked-out git branch in a multibranch project, e.g. `my-service/job/feature%2Ffoo`
+/// The project is `project` (a path or alias), or else the default job of the shell session or directory profile
+pub fn resolve_current_branch_job(client: &JenkinsClient, project: Option<&str>) -> Result<String> {
+    let cwd = std::env::current_dir().context("Failed to read the current directory")?;
+    let branch = git::current_branch(&cwd)
+        .ok_or_else(|| anyhow::anyhow!("No git branch is checked out here, so there is no branch job to look up"))?;
+
+    let profile_job = if project.is_none() { profile_default_job()? } else { None };
+    let Some(project) = project.or(profile_job.as_deref()) else {
+        return Err(ErrorKind::Config.error(
+            "No multibranch project set for this directory.\nPass it as the job, or set `job` in a directory profile.",
+        ));
+    };
+    let project = resolve_alias(project)?;
 
-        // Extract job name from selection (remove the status part)
-        let selected_job_name = selection.split(" [").next().unwrap().to_string();
+    let sp = output::spinner(&format!("Looking up branch '{}'...", branch));
+    let info = client.get_job(&project)?;
+    sp.finish_and_clear();
+    if !info.is_multibranch() {
+        anyhow::bail!("'{}' is not a multibranch project, so it has no job per branch", to_human_job_path(&project));
+    }
+    let name = encode_branch_name(&branch);
+    if !info.jobs.iter().flatten().any(|job| job.name == name) {
+        return Err(ErrorKind::NotFound.error(format!(
+            "'{}' has no job for branch '{}' yet. Push the branch and let Jenkins scan the project.",
+            to_human_job_path(&project),
+            branch
+        )));
+    }
+
+    let job = format!("{}/job/{}", project, name);
+    output::dim(&format!("Using branch '{}' → '{}'", branch, to_human_job_path(&job)));
+    remember_job(client, job)
+}
+
+/// Add a job to the host's recently used list for `jenkins recent`
+fn remember_job(client: &JenkinsClient, job: String) -> Result<String> {
+    if let Some(host) = client.name()
+        && let Err(e) = State::remember_job(host, &job, now_millis())
+    {
+        // Only a convenience, so never fail the command over it
+        tracing::debug!(error = %format!("{:#}", e), "failed to record recent job");
+    }
+    Ok(job)
+}
+
+/// Number of sub-jobs listed when a folder is given where a job is needed
+const FOLDER_JOBS_LISTED: usize = 10;
+
+/// Without prompts, name the folder's jobs so the right path can be passed instead
+fn folder_contents_error(folder: &str, jobs: &[SubJobInfo]) -> String {
+    let folder = to_display_job_path(folder);
+    let mut message = format!("'{}' is a folder, not a job. Pass one of its jobs instead:", folder);
+    for job in jobs.iter().take(FOLDER_JOBS_LISTED) {
+        message.push_str(&format!("\n  {}/{}", folder, job.name));
+    }
+    if jobs.len() > FOLDER_JOBS_LISTED {
+        message.push_str(&format!("\n  … and {} more", jobs.len() - FOLDER_JOBS_LISTED));
+    }
+    message
+}
+
+/// Path of one configuration of a matrix job, given as `AXIS=value,...` in any axis order
+pub fn resolve_matrix_configuration(client: &JenkinsClient, job_name: &str, axes: &str) -> Result<String> {
+    let sp = output::spinner("Loading matrix configurations...");
+    let job = client.get_job(job_name)?;
+    sp.finish_and_clear();
 
-        // Find the original job info
-        let selected_job = sub_jobs
-            .iter()
-            .find(|job| job.name == selected_job_name)
-            .unwrap();
+    if !job.is_matrix() {
+        anyhow::bail!("'{}' is not a matrix (multi-configuration) job", to_display_job_path(job_name));
+    }
 
-        // Build the full job path
-        // Jenkins uses the format: parent/job/child
-        current_job_name = format!("{}/job/{}", current_job_name, selected_job.name);
+    let names: Vec<String> = job
+        .active_configurations
+        .unwrap_or_default()
+        .into_iter()
+        .map(|configuration| configuration.name)
+        .collect();
+
+    match match_configuration(&names, axes) {
+        // Configurations live directly under the matrix job, without a `job/` segment
+        Some(name) => Ok(format!("{}/{}", job_name, name)),
+        None => anyhow::bail!(
+            "No configuration '{}' in '{}'. Available configurations:\n  {}",
+            axes,
+            to_display_job_path(job_name),
+            names.join("\n  ")
+        ),
     }
 }
 
+fn match_configuration<'a>(names: &'a [String], axes: &str) -> Option<&'a String> {
+    let wanted = axis_values(axes);
+    names.iter().find(|name| axis_values(name) == wanted)
+}
+
+/// `JDK=17,OS=linux` as a set of (axis, value) pairs
+fn axis_values(axes: &str) -> BTreeSet<(String, String)> {
+    axes.split(',')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(axis, value)| (axis.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+/// Resolves the job name for the open command, allowing to stop at any level
+pub fn resolve_job_name_for_open(client: &JenkinsClient, initial_job_name: Option<&str>) -> Result<String> {
+    // Fall back to the directory profile's default job
+    let profile_job = if initial_job_name.is_none() { profile_default_job()? } else { None };
+    let initial_job_name = initial_job_name.or(profile_job.as_deref());
+
+    let start = initial_job_name.map(resolve_alias).transpose()?;
+    remember_job(client, browse_jobs(client, start, true)?)
+}
+
 /// Prompt user to input values for job parameters
+/// Parameters present in `preset` are used as-is and not prompted for
 pub fn collect_parameters(
-    parameter_definitions: Vec<ParameterDefinition>
+    client: &JenkinsClient,
+    job_name: &str,
+    parameter_definitions: Vec<ParameterDefinition>,
+    preset: &HashMap<String, String>,
 ) -> Result<Vec<ParameterValue>> {
     let mut parameter_values = Vec::new();
 
@@ -213,16 +425,56 @@ pub fn collect_parameters(
     output::info(&format!("This job requires {} parameter(s):", parameter_definitions.len()));
     output::newline();
 
+    let mut missing = Vec::new();
+
     for param_def in parameter_definitions {
-        let param_value = prompt_for_parameter(&param_def)?;
+        if let Some(value) = preset.get(&param_def.name) {
+            output::dim(&format!("{}: {} (from alias)", param_def.name, value));
+            // A file parameter's value names the local file to upload
+            let param_value = if param_def.class.contains("FileParameterDefinition") {
+                file_parameter(&param_def, value)?
+            } else {
+                ParameterValue { name: param_def.name.clone(), value: value.clone(), file: None }
+            };
+            parameter_values.push(param_value);
+            continue;
+        }
+
+        // Without prompts, fall back to the job's defaults and report what's left
+        // Defaults are only assumed when asked for, never just because there's no terminal
+        if prompt::is_non_interactive() {
+            match default_value(&param_def).filter(|_| prompt::requested()) {
+                Some(value) => {
+                    output::dim(&format!("{}: {} (default)", param_def.name, value));
+                    parameter_values.push(ParameterValue {
+                        name: param_def.name.clone(),
+                        value,
+                        file: None,
+                    });
+                }
+                None => missing.push(param_def.name.clone()),
+            }
+            continue;
+        }
+
+        let param_value = prompt_for_parameter(client, job_name, &param_def)?;
         parameter_values.push(param_value);
     }
 
+    if !missing.is_empty() {
+        let hint = if prompt::requested() {
+            "Set them on a job alias: 'jenkins alias add <alias> <job> -p NAME=VALUE'."
+        } else {
+            "Set them on a job alias: 'jenkins alias add <alias> <job> -p NAME=VALUE', or pass --non-interactive to use the job's defaults."
+        };
+        prompt::ensure_interactive(&format!("parameter(s) {}", missing.join(", ")), hint)?;
+    }
+
     Ok(parameter_values)
 }
 
 /// Prompt for a single parameter based on its type
-fn prompt_for_parameter(param_def: &ParameterDefinition) -> Result<ParameterValue> {
+fn prompt_for_parameter(client: &JenkinsClient, job_name: &str, param_def: &ParameterDefinition) -> Result<ParameterValue> {
     let description = param_def.description.as_deref().unwrap_or("");
     let help_message = if description.is_empty() {
         format!("Type: {}", param_def.param_type)
@@ -230,11 +482,22 @@ fn prompt_for_parameter(param_def: &ParameterDefinition) -> Result<ParameterValu
         format!("{} (Type: {})", description, param_def.param_type)
     };
 
+    // File parameters carry the file itself rather than a value
+    if param_def.class.contains("FileParameterDefinition") {
+        return prompt_file_parameter(param_def, &help_message);
+    }
+
     // Determine parameter type from class name
     let value = if param_def.class.contains("BooleanParameterDefinition") {
         prompt_boolean_parameter(param_def, &help_message)?
     } else if param_def.class.contains("ChoiceParameterDefinition") {
         prompt_choice_parameter(param_def, &help_message)?
+    } else if param_def.class.contains("TextParameterDefinition") {
+        prompt_text_parameter(param_def, &help_message)?
+    } else if param_def.class.contains("RunParameterDefinition") {
+        prompt_run_parameter(client, param_def, &help_message)?
+    } else if param_def.class.contains("CredentialsParameterDefinition") {
+        prompt_credentials_parameter(client, job_name, param_def, &help_message)?
     } else {
         // Default to string parameter (covers StringParameterDefinition and others)
         prompt_string_parameter(param_def, &help_message)?
@@ -243,9 +506,58 @@ fn prompt_for_parameter(param_def: &ParameterDefinition) -> Result<ParameterValu
     Ok(ParameterValue {
         name: param_def.name.clone(),
         value,
+        file: None,
     })
 }
 
+fn prompt_file_parameter(param_def: &ParameterDefinition, help: &str) -> Result<ParameterValue> {
+    let path = handle_inquire_error(
+        Text::new(&format!("{} (path to file):", param_def.name))
+            .with_help_message(help)
+            .with_validator(|input: &str| {
+                if Path::new(&expand_home(input)).is_file() {
+                    Ok(Validation::Valid)
+                } else {
+                    Ok(Validation::Invalid("File not found".into()))
+                }
+            })
+            .prompt()
+    )?;
+
+    file_parameter(param_def, &path)
+}
+
+/// The upload for a file parameter, from a local path (`~/` allowed)
+fn file_parameter(param_def: &ParameterDefinition, path: &str) -> Result<ParameterValue> {
+    let path = PathBuf::from(expand_home(path));
+    if !path.is_file() {
+        anyhow::bail!(
+            "'{}' is a file parameter, but '{}' is not a file to upload",
+            param_def.name,
+            path.display()
+        );
+    }
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    Ok(ParameterValue {
+        name: param_def.name.clone(),
+        value: file_name,
+        file: Some(path),
+    })
+}
+
+/// Expand a leading `~/` to the home directory
+fn expand_home(path: &str) -> String {
+    let path = path.trim();
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest).to_string_lossy().into_owned(),
+        _ => path.to_string(),
+    }
+}
+
 fn prompt_string_parameter(param_def: &ParameterDefinition, help: &str) -> Result<String> {
     let default_value = extract_default_string(param_def);
     let prompt_message = format!("{}:", param_def.name);
@@ -262,6 +574,22 @@ fn prompt_string_parameter(param_def: &ParameterDefinition, help: &str) -> Resul
     Ok(value)
 }
 
+/// Multi-line values (YAML, JSON, ...) are edited in $VISUAL / $EDITOR
+fn prompt_text_parameter(param_def: &ParameterDefinition, help: &str) -> Result<String> {
+    let default_value = extract_default_string(param_def).unwrap_or_default();
+    let prompt_message = format!("{}:", param_def.name);
+
+    let value = handle_inquire_error(
+        Editor::new(&prompt_message)
+            .with_predefined_text(&default_value)
+            .with_help_message(help)
+            .prompt()
+    )?;
+
+    // Editors append a trailing newline on save
+    Ok(value.strip_suffix('\n').unwrap_or(&value).to_string())
+}
+
 fn prompt_boolean_parameter(param_def: &ParameterDefinition, help: &str) -> Result<String> {
     let default_value = extract_default_bool(param_def);
     let prompt_message = format!("{}?", param_def.name);
@@ -298,6 +626,103 @@ fn prompt_choice_parameter(param_def: &ParameterDefinition, help: &str) -> Resul
     Ok(selection)
 }
 
+/// Number of recent builds offered for a run parameter
+const RUN_PARAMETER_BUILDS: usize = 20;
+
+/// Select a build of the referenced job; Jenkins expects `<job>#<number>`
+fn prompt_run_parameter(client: &JenkinsClient, param_def: &ParameterDefinition, help: &str) -> Result<String> {
+    let Some(project) = param_def.project_name.as_deref() else {
+        return prompt_string_parameter(param_def, help);
+    };
+
+    let sp = output::spinner(&format!("Loading recent builds of '{}'...", project));
+    let builds = client.get_build_history(&to_jenkins_job_path(project), RUN_PARAMETER_BUILDS);
+    sp.finish_and_clear();
+
+    let builds: Vec<BuildInfo> = match builds {
+        Ok(builds) => builds
+            .into_iter()
+            .filter(|b| param_def.accepts_run_result(b.result.as_deref()))
+            .collect(),
+        Err(e) => {
+            output::warning(&format!("Could not load builds of '{}': {}", project, e));
+            return prompt_string_parameter(param_def, help);
+        }
+    };
+
+    if builds.is_empty() {
+        output::warning(&format!("No matching builds of '{}' to choose from", project));
+        return prompt_string_parameter(param_def, help);
+    }
+
+    let now = now_millis();
+    let options: Vec<String> = builds
+        .iter()
+        .map(|b| {
+            let timing = format_build_timing(b.building.unwrap_or(false), b.duration, b.timestamp, now);
+            format!("#{} [{}]  {}", b.number, b.result.as_deref().unwrap_or("IN_PROGRESS"), timing)
+        })
+        .collect();
+
+    let selection = handle_inquire_error(
+        Select::new(&format!("{} (build of {}):", param_def.name, project), options)
+            .with_help_message(help)
+            .prompt()
+    )?;
+
+    // "#42 [SUCCESS]" -> "42"
+    let number = selection.trim_start_matches('#').split(' ').next().unwrap_or_default();
+    Ok(format!("{}#{}", project, number))
+}
+
+/// Select a credential ID of the parameter's type from the stores the job can use, falling back to free text
+fn prompt_credentials_parameter(
+    client: &JenkinsClient,
+    job_name: &str,
+    param_def: &ParameterDefinition,
+    help: &str,
+) -> Result<String> {
+    let sp = output::spinner("Loading credentials...");
+    let credentials = client.get_credentials(job_name);
+    sp.finish_and_clear();
+
+    let credentials = match credentials {
+        Ok(mut credentials) if credentials.iter().any(|c| param_def.accepts_credential(c)) => {
+            credentials.retain(|c| param_def.accepts_credential(c));
+            credentials
+        }
+        Ok(_) => return prompt_string_parameter(param_def, help),
+        Err(e) => {
+            output::warning(&format!("Could not list credentials: {}", e));
+            return prompt_string_parameter(param_def, help);
+        }
+    };
+
+    let options: Vec<String> = credentials
+        .iter()
+        .map(|c| match (&c.display_name, &c.type_name) {
+            (Some(display), Some(kind)) => format!("{} [{} - {}]", c.id, display, kind),
+            (Some(display), None) => format!("{} [{}]", c.id, display),
+            _ => c.id.clone(),
+        })
+        .collect();
+
+    let default_id = extract_default_string(param_def);
+    let starting_cursor = default_id
+        .and_then(|id| credentials.iter().position(|c| c.id == id))
+        .unwrap_or(0);
+
+    let selection = handle_inquire_error(
+        Select::new(&format!("{}:", param_def.name), options)
+            .with_starting_cursor(starting_cursor)
+            .with_help_message(help)
+            .prompt()
+    )?;
+
+    // Extract the credential ID (remove the description part)
+    Ok(selection.split(" [").next().unwrap().to_string())
+}
+
 // Helper functions to extract default values
 fn extract_default_string(param_def: &ParameterDefinition) -> Option<String> {
     param_def.default_value.as_ref()
@@ -311,6 +736,19 @@ fn extract_default_string(param_def: &ParameterDefinition) -> Option<String> {
         })
 }
 
+/// The value Jenkins would use if the parameter were left untouched
+fn default_value(param_def: &ParameterDefinition) -> Option<String> {
+    if param_def.class.contains("BooleanParameterDefinition") {
+        return Some(extract_default_bool(param_def).unwrap_or(false).to_string());
+    }
+    if param_def.class.contains("FileParameterDefinition") {
+        return None;
+    }
+    extract_default_string(param_def).or_else(|| {
+        param_def.choices.as_ref().and_then(|choices| choices.first().cloned())
+    })
+}
+
 fn extract_default_bool(param_def: &ParameterDefinition) -> Option<bool> {
     param_def.default_value.as_ref()
         .and_then(|dv| dv.value.as_ref())
@@ -320,6 +758,119 @@ fn extract_default_bool(param_def: &ParameterDefinition) -> Option<bool> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::JenkinsHost;
+
+    #[test]
+    fn test_expand_home() {
+        assert_eq!(expand_home("/tmp/build.zip"), "/tmp/build.zip");
+        assert_eq!(expand_home("  artifact.tar.gz "), "artifact.tar.gz");
+        if let Some(home) = dirs::home_dir() {
+            assert_eq!(expand_home("~/build.zip"), home.join("build.zip").to_string_lossy());
+        }
+    }
+
+    #[test]
+    fn test_job_pager_count_label() {
+        let client = JenkinsClient::new(JenkinsHost {
+            host: "https://jenkins.example.com".to_string(),
+            user: "user".to_string(),
+            token: "token".to_string(),
+            ..Default::default()
+        }).unwrap();
+        let job = |name: &str| SubJobInfo {
+            name: name.to_string(),
+            url: format!("https://jenkins.example.com/job/{}/", name),
+            color: Some("blue".to_string()),
+            health_report: None,
+            last_build: None,
+        };
+
+        let mut pager = JobPager {
+            client: &client,
+            folder: None,
+            jobs: vec![job("a"), job("b")],
+            exhausted: false,
+        };
+        assert_eq!(pager.count_label(), "2+");
+
+        pager.exhausted = true;
+        assert_eq!(pager.count_label(), "2");
+    }
+
+    #[test]
+    fn test_job_options_columns() {
+        let job = |name: &str, color: &str, last_build: Option<(i64, i64, bool)>| SubJobInfo {
+            name: name.to_string(),
+            url: String::new(),
+            color: Some(color.to_string()),
+            health_report: None,
+            last_build: last_build.map(|(timestamp, duration, building)| BuildInfo {
+                number: 1,
+                url: String::new(),
+                result: None,
+                building: Some(building),
+                timestamp: Some(timestamp),
+                duration: Some(duration),
+                estimated_duration: None,
+                actions: Vec::new(),
+            }),
+        };
+        let now = 10 * 3_600_000;
+        let jobs = vec![
+            job("api", "blue", Some((now - 2 * 3_600_000, 192_000, false))),
+            job("api-deploy", "red_anime", Some((now - 5 * 60_000, 0, true))),
+            job("docs", "notbuilt", None),
+        ];
+
+        assert_eq!(
+            job_options(&jobs, now),
+            vec![
+                "api [Success]                2 hours ago    took 3m 12s",
+                "api-deploy [Building (red)]  5 minutes ago  running",
+                "docs [Not Built]             never built",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parent_folder_and_breadcrumb() {
+        assert_eq!(parent_folder("team/job/api/job/deploy"), Some("team/job/api".to_string()));
+        assert_eq!(parent_folder("team/api"), Some("team".to_string()));
+        assert_eq!(parent_folder("deploy"), None);
+
+        assert_eq!(breadcrumb("team/job/api"), "Jenkins » team » api");
+        assert_eq!(breadcrumb("deploy"), "Jenkins » deploy");
+        assert_eq!(breadcrumb(""), "Jenkins");
+    }
+
+    #[test]
+    fn test_match_configuration() {
+        let names = vec!["JDK=17,OS=linux".to_string(), "JDK=17,OS=windows".to_string()];
+
+        assert_eq!(match_configuration(&names, "JDK=17,OS=linux"), Some(&names[0]));
+        assert_eq!(match_configuration(&names, "OS=windows, JDK=17"), Some(&names[1]));
+        assert_eq!(match_configuration(&names, "OS=windows"), None);
+        assert_eq!(match_configuration(&names, "JDK=21,OS=linux"), None);
+    }
+
+    #[test]
+    fn test_folder_contents_error() {
+        let jobs: Vec<SubJobInfo> = (1..=12)
+            .map(|i| SubJobInfo {
+                name: format!("svc-{}", i),
+                url: format!("https://jenkins.example.com/job/team/job/svc-{}/", i),
+                color: None,
+                health_report: None,
+                last_build: None,
+            })
+            .collect();
+
+        let message = folder_contents_error("team", &jobs);
+        assert!(message.starts_with("'team' is a folder, not a job."));
+        assert!(message.contains("\n  team/svc-1\n"));
+        assert!(!message.contains("team/svc-11"));
+        assert!(message.ends_with("… and 2 more"));
+    }
 
     #[test]
     fn test_format_color() {
@@ -373,6 +924,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_file_parameter() {
+        let param_def: ParameterDefinition = serde_json::from_value(serde_json::json!({
+            "_class": "hudson.model.FileParameterDefinition",
+            "name": "MANIFEST",
+            "type": "FileParameterDefinition"
+        }))
+        .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("manifest.yml");
+        std::fs::write(&path, "replicas: 2\n").unwrap();
+
+        let value = file_parameter(&param_def, path.to_str().unwrap()).unwrap();
+        assert_eq!(value.value, "manifest.yml");
+        assert_eq!(value.file, Some(path));
+
+        let missing = dir.path().join("missing.yml");
+        let error = file_parameter(&param_def, missing.to_str().unwrap()).unwrap_err();
+        assert!(error.to_string().contains("'MANIFEST' is a file parameter"));
+    }
+
     #[test]
     fn test_extract_default_string_from_string_value() {
         use crate::client::{DefaultParameterValue, ParameterDefinition};
@@ -386,6 +958,9 @@ mod tests {
                 value: Some(serde_json::Value::String("main".to_string())),
             }),
             choices: None,
+            project_name: None,
+            filter: None,
+            credential_type: None,
         };
 
         let result = extract_default_string(&param_def);
@@ -405,6 +980,9 @@ mod tests {
                 value: Some(serde_json::Value::Number(42.into())),
             }),
             choices: None,
+            project_name: None,
+            filter: None,
+            credential_type: None,
         };
 
         let result = extract_default_string(&param_def);
@@ -424,6 +1002,9 @@ mod tests {
                 value: Some(serde_json::Value::Bool(true)),
             }),
             choices: None,
+            project_name: None,
+            filter: None,
+            credential_type: None,
         };
 
         let result = extract_default_string(&param_def);
@@ -441,6 +1022,9 @@ mod tests {
             description: None,
             default_value: None,
             choices: None,
+            project_name: None,
+            filter: None,
+            credential_type: None,
         };
 
         let result = extract_default_string(&param_def);
@@ -460,6 +1044,9 @@ mod tests {
                 value: Some(serde_json::Value::Bool(true)),
             }),
             choices: None,
+            project_name: None,
+            filter: None,
+            credential_type: None,
         };
 
         let result = extract_default_bool(&param_def);
@@ -479,6 +1066,9 @@ mod tests {
                 value: Some(serde_json::Value::Bool(false)),
             }),
             choices: None,
+            project_name: None,
+            filter: None,
+            credential_type: None,
         };
 
         let result = extract_default_bool(&param_def);
@@ -498,6 +1088,9 @@ mod tests {
                 value: Some(serde_json::Value::String("true".to_string())),
             }),
             choices: None,
+            project_name: None,
+            filter: None,
+            credential_type: None,
         };
 
         let result = extract_default_bool(&param_def);
@@ -515,6 +1108,9 @@ mod tests {
             description: None,
             default_value: None,
             choices: None,
+            project_name: None,
+            filter: None,
+            credential_type: None,
         };
 
         let result = extract_default_bool(&param_def);
@@ -531,12 +1127,15 @@ mod tests {
             param_type: "StringParameterDefinition".to_string(),
             description: None,
             default_value: Some(DefaultParameterValue {
-                value: Some(serde_json::json!(3.14)),
+                value: Some(serde_json::json!(2.75)),
             }),
             choices: None,
+            project_name: None,
+            filter: None,
+            credential_type: None,
         };
 
         let result = extract_default_string(&param_def);
-        assert_eq!(result, Some("3.14".to_string()));
+        assert_eq!(result, Some("2.75".to_string()));
     }
 }
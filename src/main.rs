@@ -5,50 +5,230 @@ mod commands;
 mod helpers;
 mod interactive;
 mod output;
+mod state;
 
 use anyhow::Result;
 use clap::Parser;
-use cli::{Cli, Commands, ConfigAction, AliasAction};
+use cli::{Cli, Commands, ConfigAction, AliasAction, ArtifactsAction, JobAction, CascAction, NodeAction, PluginsAction, HookAction};
 use std::process;
 
 fn main() {
     if let Err(e) = run() {
+        tracing::error!(error = %format!("{:#}", e), "command failed");
         eprintln!("{}", e);
-        process::exit(1);
+        process::exit(helpers::errors::exit_code(&e));
     }
 }
 
 fn run() -> Result<()> {
     let cli = Cli::parse();
 
-    match cli.command {
+    if let Some(path) = cli.config {
+        config::set_config_path(path);
+    }
+
+    let loaded = config::Config::load().ok();
+
+    // Logging is best-effort: a bad path shouldn't stop the command
+    let log_file = cli.log_file.or_else(|| loaded.as_ref().and_then(|c| c.log_file.clone()));
+    if let Some(path) = log_file
+        && let Err(e) = helpers::logging::init(&path)
+    {
+        output::warning(&format!("{:#}", e));
+    }
+    tracing::debug!(version = env!("CARGO_PKG_VERSION"), "starting");
+
+    if let Some(identity) = cli.identity {
+        helpers::init::set_identity(identity);
+    }
+
+    if cli.non_interactive {
+        helpers::prompt::set_non_interactive();
+    }
+
+    if cli.no_pager {
+        output::disable_pager();
+    }
+
+    helpers::formatting::set_time_format(loaded.as_ref().and_then(|c| c.time_format.as_deref()), cli.utc);
+
+    if cli.no_cache {
+        helpers::cache::disable();
+    }
+
+    // Piped output (e.g. `jenkins logs job | grep ERROR`) gets no colors, spinners or prompts
+    if !output::is_terminal() {
+        output::disable_decorations();
+    }
+
+    dispatch(cli.command)
+}
+
+/// Run a parsed command (shared with `jenkins shell`)
+fn dispatch(command: Commands) -> Result<()> {
+    match command {
         Commands::Config { action } => match action {
-            ConfigAction::Add => commands::config::execute_add()?,
+            ConfigAction::Add { generate_token } => commands::config::execute_add(generate_token)?,
             ConfigAction::List => commands::config::execute_list()?,
             ConfigAction::Remove => commands::config::execute_remove()?,
+            ConfigAction::Validate => commands::config::execute_validate()?,
+            ConfigAction::Verify { name } => commands::config::execute_verify(name)?,
+            ConfigAction::RotateToken { name } => commands::config::execute_rotate_token(name)?,
         },
         Commands::Alias { action } => match action {
-            AliasAction::Add { alias, job_name } => {
-                commands::alias::execute_add(alias, job_name)?;
+            AliasAction::Add { alias, job_name, params } => {
+                commands::alias::execute_add(alias, job_name, params)?;
             }
             AliasAction::List => commands::alias::execute_list()?,
+            AliasAction::Status => commands::alias::execute_status()?,
             AliasAction::Remove { alias } => commands::alias::execute_remove(alias)?,
+            AliasAction::Rename { old, new } => commands::alias::execute_rename(old, new)?,
         },
-        Commands::Build { job_name, follow } => {
-            commands::build::execute(job_name, follow)?;
+        Commands::Build { job_name, follow, queue_timeout, events, notify, follow_downstream, jenkins, all_hosts } => {
+            // A tag may stand for several hosts, so it always goes through the fan-out
+            if all_hosts || jenkins.len() > 1 || jenkins.iter().any(|j| j.starts_with(config::TAG_SELECTOR_PREFIX)) {
+                commands::build::execute_fan_out(job_name, jenkins, all_hosts, follow, queue_timeout)?;
+            } else {
+                commands::build::execute(
+                    job_name,
+                    jenkins.into_iter().next(),
+                    follow,
+                    queue_timeout,
+                    events,
+                    notify,
+                    follow_downstream,
+                )?;
+            }
+        }
+        Commands::Status { job_name, build, axis, compare, branch } => {
+            if compare.is_empty() {
+                commands::status::execute(job_name, build, axis, branch)?;
+            } else {
+                commands::status::execute_compare(job_name, compare)?;
+            }
+        }
+        Commands::Logs { job_name, build, follow, axis, fold, expand, branch } => {
+            let fold = fold.then_some(expand);
+            commands::logs::execute(job_name, build, follow, axis, fold, branch)?;
+        }
+        Commands::Coverage { job_name, build } => {
+            commands::coverage::execute(job_name, build)?;
+        }
+        Commands::Tests { job_name, build, failed, junit_out } => {
+            commands::test_report::execute(job_name, build, failed, junit_out)?;
+        }
+        Commands::Stats { job_name, last } => {
+            commands::stats::execute(job_name, last)?;
+        }
+        Commands::Warnings { job_name, build, fail_on_new } => {
+            commands::warnings::execute(job_name, build, fail_on_new)?;
+        }
+        Commands::Jobs { folder, sort, filter } => {
+            commands::jobs::execute(folder, sort, filter)?;
         }
-        Commands::Status { job_name, build } => {
-            commands::status::execute(job_name, build)?;
+        Commands::Failing { folder, view, depth } => {
+            commands::failing::execute(folder, view, depth)?;
         }
-        Commands::Logs { job_name, build, follow } => {
-            commands::logs::execute(job_name, build, follow)?;
+        Commands::Health { folder } => {
+            commands::health::execute(folder)?;
         }
-        Commands::Open { job_name, build } => {
-            commands::open::execute(job_name, build)?;
+        Commands::Env { job_name, build } => {
+            commands::env::execute(job_name, build)?;
+        }
+        Commands::Open { job_name, build, print, copy, branch } => {
+            commands::open::execute(job_name, build, print, copy, branch)?;
+        }
+        Commands::Hook { action } => match action {
+            HookAction::Install { job, jenkins, params, when, force } => {
+                commands::hook::execute_install(job, jenkins, params, when, force)?;
+            }
+            HookAction::Uninstall => commands::hook::execute_uninstall()?,
+        },
+        Commands::Autobuild { job, on, interval, debounce } => {
+            commands::autobuild::execute(job, on, interval, debounce)?;
+        }
+        Commands::ExportBuild { job_name, build, output } => {
+            commands::export_build::execute(job_name, build, output)?;
+        }
+        Commands::Artifacts { action } => match action {
+            ArtifactsAction::List { job_name, build } => commands::artifacts::execute_list(job_name, build)?,
+            ArtifactsAction::Download { job_name, build, output, patterns, parallel, resume } => {
+                commands::artifacts::execute_download(job_name, build, output, patterns, parallel, resume)?;
+            }
+        },
+        Commands::Job { action } => match action {
+            JobAction::Diff { job_a, job_b, jenkins, other_jenkins } => {
+                commands::job::execute_diff(job_a, job_b, jenkins, other_jenkins)?;
+            }
+        },
+        Commands::Backup { folder, dest } => commands::backup::execute(folder, dest)?,
+        Commands::Restore { dir, create_missing, dry_run, yes } => {
+            commands::restore::execute(dir, create_missing, dry_run, yes)?;
+        }
+        Commands::Seed { job_name, queue_timeout } => commands::seed::execute(job_name, queue_timeout)?,
+        Commands::Casc { action } => match action {
+            CascAction::Export { output } => commands::casc::execute_export(output)?,
+        },
+        Commands::Script { file, nodes } => commands::script::execute(file, nodes)?,
+        Commands::Node { action } => match action {
+            NodeAction::Create { name, executors, labels, remote_fs } => {
+                commands::node::execute_create(name, executors, labels, remote_fs)?;
+            }
+            NodeAction::List { min_disk, min_temp, min_swap, max_response, max_clock_skew } => {
+                let thresholds = commands::node::NodeThresholds { min_disk, min_temp, min_swap, max_response, max_clock_skew };
+                commands::node::execute_list(thresholds)?;
+            }
+            NodeAction::Logs { name } => commands::node::execute_logs(name)?,
+            NodeAction::Delete { name, yes } => commands::node::execute_delete(name, yes)?,
+        },
+        Commands::Plugins { action } => match action {
+            PluginsAction::Outdated => commands::plugins::execute_outdated()?,
+            PluginsAction::Tree { name, reverse } => commands::plugins::execute_tree(name, reverse)?,
+        },
+        Commands::Doctor { jenkins, job } => commands::doctor::execute(jenkins, job)?,
+        Commands::Ping { jenkins, count } => commands::ping::execute(jenkins, count)?,
+        Commands::Load { jenkins } => commands::load::execute(jenkins)?,
+        Commands::Capacity { jenkins, output } => commands::capacity::execute(jenkins, output)?,
+        Commands::Recent { jenkins } => {
+            commands::recent::execute(jenkins)?;
+        }
+        Commands::History { jenkins, job, last } => {
+            commands::history::execute(jenkins, job, last)?;
+        }
+        Commands::Dash { watch, interval, pin, unpin } => match (pin, unpin) {
+            (Some(job), _) => commands::dash::execute_pin(job)?,
+            (_, Some(job)) => commands::dash::execute_unpin(job)?,
+            _ => commands::dash::execute(watch, interval)?,
+        },
+        Commands::Monitor { jobs, view, interval, daemon, digest_file, webhook } => {
+            commands::monitor::execute(jobs, view, interval, daemon, digest_file, webhook)?;
+        }
+        Commands::Running => commands::running::execute()?,
+        Commands::RestartFromStage { job_name, build, stage } => {
+            commands::restart_from_stage::execute(job_name, build, stage)?;
+        }
+        Commands::Timeline { job_name, build } => commands::timeline::execute(job_name, build)?,
+        Commands::Replay { job_name, build, jenkinsfile } => {
+            commands::replay::execute(job_name, build, jenkinsfile)?;
+        }
+        Commands::Mine { job_name, last } => {
+            commands::mine::execute(job_name, last)?;
+        }
+        Commands::Api { method, path, data } => {
+            commands::api::execute(method, path, data)?;
+        }
+        Commands::Last { action } => {
+            commands::last::execute(action)?;
+        }
+        Commands::Shell => {
+            commands::shell::execute()?;
         }
         Commands::Completion { shell } => {
             commands::completion::execute(shell)?;
         }
+        Commands::CompleteValues { values } => {
+            commands::completion::execute_values(values)?;
+        }
     }
 
     Ok(())
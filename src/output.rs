@@ -1,71 +1,117 @@
-use console::style;
-use indicatif::{ProgressBar, ProgressStyle};
+use console::{measure_text_width, pad_str, style, truncate_str, Alignment, Term};
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use std::env;
+use std::fmt::Display;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
+/// Set when stdout is reserved for machine-readable output (e.g. `build --events`)
+static MESSAGES_TO_STDERR: AtomicBool = AtomicBool::new(false);
+
+/// Set by `--no-pager`: long output is printed directly
+static PAGER_DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// Never pipe output through the pager from now on
+pub fn disable_pager() {
+    PAGER_DISABLED.store(true, Ordering::Relaxed);
+}
+
+/// Send all human-readable messages to stderr from now on
+pub fn messages_to_stderr() {
+    MESSAGES_TO_STDERR.store(true, Ordering::Relaxed);
+}
+
+/// Print a message line to stdout, or stderr if stdout is reserved
+fn out(line: impl Display) {
+    if MESSAGES_TO_STDERR.load(Ordering::Relaxed) {
+        eprintln!("{}", line);
+    } else {
+        println!("{}", line);
+    }
+}
+
 /// Print a success message with a green checkmark
 pub fn success(msg: &str) {
-    println!("{} {}", style("✓").green().bold(), msg);
+    out(format_args!("{} {}", style("✓").green().bold(), msg));
 }
 
 /// Print an info message with a blue icon
 pub fn info(msg: &str) {
-    println!("{} {}", style("ℹ").blue().bold(), msg);
+    out(format_args!("{} {}", style("ℹ").blue().bold(), msg));
 }
 
 /// Print a warning message with a yellow icon
 pub fn warning(msg: &str) {
-    println!("{} {}", style("⚠").yellow().bold(), msg);
+    out(format_args!("{} {}", style("⚠").yellow().bold(), msg));
 }
 
 /// Print an error message with a red cross
-#[allow(dead_code)]
 pub fn error(msg: &str) {
     eprintln!("{} {}", style("✗").red().bold(), msg);
 }
 
 /// Print a section header
 pub fn header(msg: &str) {
-    println!("\n{}", style(msg).bold().underlined());
+    out(format_args!("\n{}", style(msg).bold().underlined()));
 }
 
 /// Print a list item
 pub fn list_item(key: &str, value: &str) {
-    println!("  {} {}", style(key).dim(), value);
+    out(format_args!("  {} {}", style(key).dim(), value));
 }
 
 /// Print a highlighted value
 pub fn highlight(msg: &str) {
-    println!("{}", style(msg).cyan().bold());
+    out(style(msg).cyan().bold());
 }
 
 /// Print a dim/secondary message
 pub fn dim(msg: &str) {
-    println!("{}", style(msg).dim());
+    out(style(msg).dim());
 }
 
 /// Print a tip message
 pub fn tip(msg: &str) {
-    println!("\n{} {}", style("💡").bold(), style(msg).italic());
+    out(format_args!("\n{} {}", style("💡").bold(), style(msg).italic()));
 }
 
-/// Print a plain message without icons
-#[allow(dead_code)]
-pub fn plain(msg: &str) {
-    println!("{}", msg);
+/// Print a marker for a pipeline stage starting, between lines of a streamed log
+pub fn stage_marker(stage: &str) {
+    out(style(format!("── Stage: {} ──", stage)).cyan().bold());
 }
 
 /// Print a bullet list item
 pub fn bullet(msg: &str) {
-    println!("  • {}", msg);
+    out(format_args!("  • {}", msg));
 }
 
 /// Print an empty line
 pub fn newline() {
-    println!();
+    out("");
+}
+
+/// True when stdout is a terminal rather than a pipe or file
+pub fn is_terminal() -> bool {
+    Term::stdout().is_term()
+}
+
+/// Plain output for pipes and files: no colors (spinners and prompts check `is_terminal` themselves)
+pub fn disable_decorations() {
+    console::set_colors_enabled(false);
+    console::set_colors_enabled_stderr(false);
 }
 
 /// Create a spinner with elapsed time for all operations
+/// The spinner is hidden when stdout is piped so it can't interleave with the output
 pub fn spinner(msg: &str) -> ProgressBar {
+    if !is_terminal() {
+        let pb = ProgressBar::hidden();
+        pb.set_message(msg.to_string());
+        return pb;
+    }
+
     let pb = ProgressBar::new_spinner();
     pb.set_style(
         ProgressStyle::default_spinner()
@@ -77,22 +123,274 @@ pub fn spinner(msg: &str) -> ProgressBar {
     pb
 }
 
+/// Byte progress bar for a transfer, drawn below the other bars of `multi`
+/// Hidden like spinners when stdout is piped
+pub fn transfer_bar(multi: &MultiProgress, msg: &str) -> ProgressBar {
+    if !is_terminal() {
+        let pb = ProgressBar::hidden();
+        pb.set_message(msg.to_string());
+        return pb;
+    }
+
+    let pb = multi.add(ProgressBar::new(0));
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("{spinner:.blue} {msg} {bar:30.cyan/blue} {bytes}/{total_bytes} {bytes_per_sec:.dim}")
+            .unwrap()
+            .progress_chars("=> ")
+    );
+    pb.set_message(msg.to_string());
+    pb.enable_steady_tick(Duration::from_millis(80));
+    pb
+}
+
+/// Replace a transfer bar with a success line
+pub fn finish_transfer_success(pb: ProgressBar, msg: &str) {
+    pb.set_style(ProgressStyle::default_spinner().template("{msg}").unwrap());
+    finish_spinner_success(pb, msg);
+}
+
+/// Replace a transfer bar with an error line
+pub fn finish_transfer_error(pb: ProgressBar, msg: &str) {
+    pb.set_style(ProgressStyle::default_spinner().template("{msg}").unwrap());
+    finish_spinner_error(pb, msg);
+}
+
 /// Finish spinner with success message
 pub fn finish_spinner_success(pb: ProgressBar, msg: &str) {
-    pb.finish_with_message(format!("{} {}", style("✓").green().bold(), msg));
+    finish_spinner(pb, format!("{} {}", style("✓").green().bold(), msg));
 }
 
 /// Finish spinner with error message
 pub fn finish_spinner_error(pb: ProgressBar, msg: &str) {
-    pb.finish_with_message(format!("{} {}", style("✗").red().bold(), msg));
+    finish_spinner(pb, format!("{} {}", style("✗").red().bold(), msg));
 }
 
 /// Finish spinner with warning message
 pub fn finish_spinner_warning(pb: ProgressBar, msg: &str) {
-    pb.finish_with_message(format!("{} {}", style("⚠").yellow().bold(), msg));
+    finish_spinner(pb, format!("{} {}", style("⚠").yellow().bold(), msg));
+}
+
+/// A hidden spinner's final message goes to stderr so piped stdout stays clean
+fn finish_spinner(pb: ProgressBar, msg: String) {
+    if pb.is_hidden() {
+        pb.finish_and_clear();
+        eprintln!("{}", msg);
+    } else {
+        pb.finish_with_message(msg);
+    }
 }
 
 /// Print a cancellation message
 pub fn cancelled(msg: &str) {
-    println!("\n{} {}", style("✗").dim(), style(msg).dim());
+    out(format_args!("\n{} {}", style("✗").dim(), style(msg).dim()));
+}
+
+/// Columns never shrink below this when a table is fitted to the terminal
+const MIN_COLUMN_WIDTH: usize = 6;
+
+/// Gap between columns
+const COLUMN_SEPARATOR: &str = "  ";
+
+/// Rows of cells printed in aligned columns, header first
+/// Cells may carry colors; widths are measured without escape codes
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+    alignments: Vec<Alignment>,
+    max_widths: Vec<Option<usize>>,
+}
+
+impl Table {
+    pub fn new(headers: &[&str]) -> Self {
+        Table {
+            headers: headers.iter().map(|h| h.to_string()).collect(),
+            rows: Vec::new(),
+            alignments: vec![Alignment::Left; headers.len()],
+            max_widths: vec![None; headers.len()],
+        }
+    }
+
+    /// Right-align a column, e.g. one of counts or durations
+    pub fn align_right(mut self, column: usize) -> Self {
+        self.alignments[column] = Alignment::Right;
+        self
+    }
+
+    /// Cut the cells of a column longer than `width`, ending them with an ellipsis
+    pub fn max_width(mut self, column: usize, width: usize) -> Self {
+        self.max_widths[column] = Some(width);
+        self
+    }
+
+    /// Missing trailing cells are left empty
+    pub fn add_row(&mut self, mut row: Vec<String>) {
+        row.resize(self.headers.len(), String::new());
+        self.rows.push(row);
+    }
+
+    /// Lines of the table, narrowing the widest columns until it fits in `width` when given
+    pub fn render(&self, width: Option<usize>) -> Vec<String> {
+        let widths = self.column_widths(width);
+        let separator = style(COLUMN_SEPARATOR).dim().to_string();
+
+        let line = |cells: &[String]| {
+            // Trailing empty cells are left out, since their dimmed separators would survive `trim_end`
+            let used = cells.iter().rposition(|cell| !cell.is_empty()).map_or(0, |last| last + 1);
+            cells[..used]
+                .iter()
+                .zip(&widths)
+                .zip(&self.alignments)
+                .map(|((cell, width), alignment)| {
+                    // `truncate_str` also cuts cells that exactly fit
+                    let cell = if measure_text_width(cell) > *width { truncate_str(cell, *width, "…") } else { cell.into() };
+                    pad_str(&cell, *width, *alignment, None).into_owned()
+                })
+                .collect::<Vec<_>>()
+                .join(&separator)
+                .trim_end()
+                .to_string()
+        };
+
+        let headers: Vec<String> = self.headers.iter().map(|h| style(h).dim().to_string()).collect();
+        let mut lines = vec![line(&headers)];
+        lines.extend(self.rows.iter().map(|row| line(row)));
+        lines
+    }
+
+    /// `render` without colors, so tests can compare the text whatever the terminal supports
+    #[cfg(test)]
+    pub fn render_plain(&self, width: Option<usize>) -> Vec<String> {
+        self.render(width).iter().map(|line| console::strip_ansi_codes(line).into_owned()).collect()
+    }
+
+    /// Print the table indented like list items, fitted to the terminal when there is one
+    pub fn print(&self) {
+        for line in self.render(Self::terminal_width()) {
+            out(format_args!("  {}", line));
+        }
+    }
+
+    /// The printed table as text, for output that goes through `page`
+    pub fn to_text(&self) -> String {
+        self.render(Self::terminal_width()).iter().map(|line| format!("  {}\n", line)).collect()
+    }
+
+    fn terminal_width() -> Option<usize> {
+        let term = Term::stdout();
+        term.is_term().then(|| (term.size().1 as usize).saturating_sub(2))
+    }
+
+    fn column_widths(&self, limit: Option<usize>) -> Vec<usize> {
+        let mut widths: Vec<usize> = (0..self.headers.len())
+            .map(|column| {
+                let widest = std::iter::once(&self.headers[column])
+                    .chain(self.rows.iter().map(|row| &row[column]))
+                    .map(|cell| measure_text_width(cell))
+                    .max()
+                    .unwrap_or(0);
+                self.max_widths[column].map_or(widest, |max| widest.min(max))
+            })
+            .collect();
+
+        let Some(limit) = limit else {
+            return widths;
+        };
+        let gaps = COLUMN_SEPARATOR.len() * widths.len().saturating_sub(1);
+        while widths.iter().sum::<usize>() + gaps > limit {
+            let Some(widest) = (0..widths.len()).filter(|c| widths[*c] > MIN_COLUMN_WIDTH).max_by_key(|c| widths[*c])
+            else {
+                break;
+            };
+            widths[widest] -= 1;
+        }
+        widths
+    }
+}
+
+/// Show long output through the user's pager ($PAGER, defaulting to `less`)
+/// Falls back to printing directly when stdout is not a terminal, the text fits on one screen,
+/// `--no-pager` was given or no pager is available
+pub fn page(text: &str) {
+    let term = Term::stdout();
+    if PAGER_DISABLED.load(Ordering::Relaxed) || !term.is_term() {
+        print!("{}", text);
+        return;
+    }
+
+    let (rows, columns) = term.size();
+    if screen_lines(text, columns as usize) < rows as usize {
+        print!("{}", text);
+        return;
+    }
+
+    let pager = env::var("PAGER").unwrap_or_else(|_| "less".to_string());
+    let mut parts = pager.split_whitespace();
+    let Some(program) = parts.next() else {
+        print!("{}", text);
+        return;
+    };
+
+    let mut command = Command::new(program);
+    command.args(parts).stdin(Stdio::piped());
+    // Same defaults as git: quit if one screen, keep colors, don't clear the screen
+    if env::var_os("LESS").is_none() {
+        command.env("LESS", "FRX");
+    }
+
+    match command.spawn() {
+        Ok(mut child) => {
+            if let Some(mut stdin) = child.stdin.take() {
+                // The pager may exit early (e.g. user pressed q) - ignore broken pipes
+                let _ = stdin.write_all(text.as_bytes());
+            }
+            let _ = child.wait();
+        }
+        Err(_) => print!("{}", text),
+    }
+}
+
+/// Number of terminal rows the text takes up, counting wrapped lines
+fn screen_lines(text: &str, columns: usize) -> usize {
+    let columns = columns.max(1);
+    text.lines()
+        .map(|line| console::measure_text_width(line).div_ceil(columns).max(1))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_screen_lines() {
+        assert_eq!(screen_lines("one\ntwo\n", 80), 2);
+        assert_eq!(screen_lines(&"x".repeat(200), 80), 3);
+        // Empty lines still take a row, escape codes take none
+        assert_eq!(screen_lines("a\n\n\u{1b}[31mb\u{1b}[0m", 1), 3);
+        assert_eq!(screen_lines("", 80), 0);
+    }
+
+    #[test]
+    fn test_table_aligns_columns() {
+        let mut table = Table::new(&["JOB", "BUILD", "RESULT"]).align_right(1);
+        table.add_row(vec!["api".to_string(), "#7".to_string(), "SUCCESS".to_string()]);
+        table.add_row(vec!["web-frontend".to_string(), "#120".to_string()]);
+
+        assert_eq!(
+            table.render_plain(None),
+            vec!["JOB           BUILD  RESULT", "api              #7  SUCCESS", "web-frontend   #120"]
+        );
+    }
+
+    #[test]
+    fn test_table_truncates_to_fit() {
+        let mut table = Table::new(&["JOB", "HOST"]).max_width(1, 7);
+        table.add_row(vec!["team/backend/payments-service".to_string(), "production-eu".to_string()]);
+
+        assert_eq!(table.render_plain(None)[1], "team/backend/payments-service  produc…");
+        assert_eq!(table.render_plain(Some(24))[1], "team/backend/p…  produc…");
+        // Columns stop shrinking at the minimum width rather than disappearing
+        assert_eq!(table.render_plain(Some(5))[1], "team/…  produ…");
+    }
 }
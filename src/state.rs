@@ -0,0 +1,250 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Result of the most recent credential checks for a host
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct HostState {
+    /// Epoch millis when the credentials last worked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_success: Option<i64>,
+    /// Epoch millis when the credentials last failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_failure: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_reason: Option<String>,
+    /// UUID of the API token the CLI generated for this host, so rotation can revoke it later
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_uuid: Option<String>,
+}
+
+impl HostState {
+    /// True if the most recent check failed
+    pub fn is_failing(&self) -> bool {
+        match (self.last_failure, self.last_success) {
+            (Some(failure), Some(success)) => failure > success,
+            (Some(_), None) => true,
+            _ => false,
+        }
+    }
+}
+
+/// The most recent build triggered through the CLI
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LastBuild {
+    pub jenkins: String,
+    pub job: String,
+    /// Build number, once known (builds triggered without --follow only have a queue item)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub number: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue_url: Option<String>,
+    pub triggered_at: i64,
+}
+
+/// Jobs kept in each host's recently used list
+const RECENT_JOBS_LIMIT: usize = 20;
+
+/// A job recently used by any command
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RecentJob {
+    pub job: String,
+    pub used_at: i64,
+}
+
+/// Runtime state the CLI records between invocations (kept separate from the user's config)
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct State {
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub hosts: HashMap<String, HostState>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_build: Option<LastBuild>,
+    /// Most recently used jobs per host, newest first
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub recent_jobs: HashMap<String, Vec<RecentJob>>,
+}
+
+impl State {
+    pub fn load() -> Result<Self> {
+        let state_path = Self::state_path()?;
+
+        if !state_path.exists() {
+            return Ok(State::default());
+        }
+
+        let content = fs::read_to_string(&state_path)
+            .context("Failed to read state file")?;
+
+        // State is disposable - start fresh rather than failing on a corrupt file
+        Ok(serde_yaml::from_str(&content).unwrap_or_else(|e| {
+            tracing::warn!(error = %e, path = %state_path.display(), "ignoring unreadable state file");
+            State::default()
+        }))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let state_path = Self::state_path()?;
+
+        if let Some(parent) = state_path.parent() {
+            fs::create_dir_all(parent)
+                .context("Failed to create state directory")?;
+        }
+
+        let content = serde_yaml::to_string(self)
+            .context("Failed to serialize state")?;
+
+        fs::write(&state_path, content)
+            .context("Failed to write state file")?;
+
+        Ok(())
+    }
+
+    pub fn record_auth_success(&mut self, host: &str, at: i64) {
+        let entry = self.hosts.entry(host.to_string()).or_default();
+        entry.last_success = Some(at);
+        entry.failure_reason = None;
+    }
+
+    pub fn record_auth_failure(&mut self, host: &str, at: i64, reason: &str) {
+        let entry = self.hosts.entry(host.to_string()).or_default();
+        entry.last_failure = Some(at);
+        entry.failure_reason = Some(reason.to_string());
+    }
+
+    /// Remember the UUID of a token the CLI generated, returning the one it replaces
+    pub fn record_token_uuid(&mut self, host: &str, uuid: &str) -> Option<String> {
+        self.hosts.entry(host.to_string()).or_default().token_uuid.replace(uuid.to_string())
+    }
+
+    /// Move a job to the front of its host's recently used list
+    pub fn record_recent_job(&mut self, host: &str, job: &str, at: i64) {
+        let jobs = self.recent_jobs.entry(host.to_string()).or_default();
+        jobs.retain(|recent| recent.job != job);
+        jobs.insert(0, RecentJob { job: job.to_string(), used_at: at });
+        jobs.truncate(RECENT_JOBS_LIMIT);
+    }
+
+    /// Record that a command used a job
+    pub fn remember_job(host: &str, job: &str, at: i64) -> Result<()> {
+        let mut state = Self::load()?;
+        state.record_recent_job(host, job, at);
+        state.save()
+    }
+
+    /// Record a triggered build, then let later updates fill in its number
+    pub fn record_last_build(last_build: LastBuild) -> Result<()> {
+        let mut state = Self::load()?;
+        state.last_build = Some(last_build);
+        state.save()
+    }
+
+    /// Check that the data directory is writable and the state file parses, returning the directory
+    pub fn check_health() -> Result<PathBuf> {
+        let dir = Self::data_dir()?;
+        fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+
+        let probe = dir.join(".write-test");
+        fs::write(&probe, b"").with_context(|| format!("{} is not writable", dir.display()))?;
+        let _ = fs::remove_file(&probe);
+
+        let state_path = Self::state_path()?;
+        if state_path.exists() {
+            let content = fs::read_to_string(&state_path).context("Failed to read state file")?;
+            serde_yaml::from_str::<State>(&content)
+                .with_context(|| format!("{} is corrupt and will be reset", state_path.display()))?;
+        }
+
+        Ok(dir)
+    }
+
+    /// Directory for data files the CLI writes on its own (state, history)
+    pub fn data_dir() -> Result<PathBuf> {
+        let data = dirs::data_dir()
+            .context("Failed to get data directory")?;
+        Ok(data.join("jenkins-cli"))
+    }
+
+    fn state_path() -> Result<PathBuf> {
+        Ok(Self::data_dir()?.join("state.yml"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_host_state_is_failing() {
+        let mut state = State::default();
+        assert!(!state.hosts.contains_key("prod"));
+
+        state.record_auth_failure("prod", 100, "Authentication failed");
+        assert!(state.hosts["prod"].is_failing());
+        assert_eq!(state.hosts["prod"].failure_reason, Some("Authentication failed".to_string()));
+
+        state.record_auth_success("prod", 200);
+        assert!(!state.hosts["prod"].is_failing());
+        assert_eq!(state.hosts["prod"].failure_reason, None);
+
+        state.record_auth_failure("prod", 300, "Authentication failed");
+        assert!(state.hosts["prod"].is_failing());
+        assert_eq!(state.hosts["prod"].last_success, Some(200));
+    }
+
+    #[test]
+    fn test_record_token_uuid() {
+        let mut state = State::default();
+        assert_eq!(state.record_token_uuid("prod", "first"), None);
+        assert_eq!(state.record_token_uuid("prod", "second"), Some("first".to_string()));
+        assert_eq!(state.hosts["prod"].token_uuid.as_deref(), Some("second"));
+    }
+
+    #[test]
+    fn test_last_build_yaml() {
+        let yaml = r#"
+last_build:
+  jenkins: prod
+  job: folder/job/api
+  queue_url: https://jenkins.example.com/queue/item/42/
+  triggered_at: 1700000000000
+"#;
+        let state: State = serde_yaml::from_str(yaml).unwrap();
+        let last = state.last_build.unwrap();
+        assert_eq!(last.jenkins, "prod");
+        assert_eq!(last.job, "folder/job/api");
+        assert_eq!(last.number, None);
+        assert_eq!(last.queue_url.as_deref(), Some("https://jenkins.example.com/queue/item/42/"));
+    }
+
+    #[test]
+    fn test_record_recent_job() {
+        let mut state = State::default();
+        for (i, job) in ["api", "web", "api"].iter().enumerate() {
+            state.record_recent_job("prod", job, i as i64);
+        }
+        let jobs: Vec<(&str, i64)> = state.recent_jobs["prod"].iter().map(|r| (r.job.as_str(), r.used_at)).collect();
+        assert_eq!(jobs, vec![("api", 2), ("web", 1)]);
+
+        for i in 0..30 {
+            state.record_recent_job("dev", &format!("job-{}", i), i);
+        }
+        assert_eq!(state.recent_jobs["dev"].len(), RECENT_JOBS_LIMIT);
+        assert_eq!(state.recent_jobs["dev"][0].job, "job-29");
+        assert_eq!(state.recent_jobs["prod"].len(), 2);
+    }
+
+    #[test]
+    fn test_state_yaml_roundtrip() {
+        let mut state = State::default();
+        state.record_auth_success("prod", 1_700_000_000_000);
+
+        let yaml = serde_yaml::to_string(&state).unwrap();
+        assert!(yaml.contains("last_success: 1700000000000"));
+        assert!(!yaml.contains("last_failure"));
+
+        let parsed: State = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(parsed.hosts["prod"].last_success, Some(1_700_000_000_000));
+    }
+}
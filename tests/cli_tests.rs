@@ -33,6 +33,8 @@ fn test_help_command() {
     assert!(stdout.contains("status"));
     assert!(stdout.contains("logs"));
     assert!(stdout.contains("open"));
+    assert!(stdout.contains("--no-pager"));
+    assert!(stdout.contains("--no-cache"));
 }
 
 #[test]
@@ -47,6 +49,15 @@ fn test_config_help() {
     assert!(stdout.contains("remove"));
 }
 
+#[test]
+fn test_config_add_help() {
+    let output = run_command(&["config", "add", "--help"], None);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("--generate-token"));
+}
+
 #[test]
 fn test_config_list_empty() {
     let temp_dir = TempDir::new().unwrap();
@@ -78,6 +89,7 @@ fn test_status_help() {
     let stdout = String::from_utf8_lossy(&output.stdout);
     assert!(stdout.contains("Check the status of a Jenkins job or build"));
     assert!(stdout.contains("--build"));
+    assert!(stdout.contains("--axis"));
 }
 
 #[test]
@@ -88,6 +100,27 @@ fn test_logs_help() {
     let stdout = String::from_utf8_lossy(&output.stdout);
     assert!(stdout.contains("View console logs for a build"));
     assert!(stdout.contains("--build"));
+    assert!(stdout.contains("--axis"));
+    assert!(stdout.contains("--fold"));
+    assert!(stdout.contains("--expand"));
+}
+
+#[test]
+fn test_logs_fold_conflicts_with_follow() {
+    let output = run_command(&["logs", "some-job", "--fold", "--follow"], None);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("cannot be used with"));
+}
+
+#[test]
+fn test_env_help() {
+    let output = run_command(&["env", "--help"], None);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Show the environment variables injected into a build"));
+    assert!(stdout.contains("--build"));
 }
 
 #[test]
@@ -98,6 +131,8 @@ fn test_open_help() {
     let stdout = String::from_utf8_lossy(&output.stdout);
     assert!(stdout.contains("Open a Jenkins job or build in the browser"));
     assert!(stdout.contains("--build"));
+    assert!(stdout.contains("--print"));
+    assert!(stdout.contains("--copy"));
 }
 
 #[test]
@@ -168,6 +203,9 @@ fn test_build_help() {
 
     let stdout = String::from_utf8_lossy(&output.stdout);
     assert!(stdout.contains("Trigger a build for a Jenkins job"));
+    assert!(stdout.contains("--queue-timeout"));
+    assert!(stdout.contains("--events"));
+    assert!(stdout.contains("--notify"));
 }
 
 #[test]
@@ -238,3 +276,962 @@ jenkins:
     assert!(stdout.contains("https://jenkins-dev.example.com"));
     assert!(stdout.contains("https://jenkins-staging.example.com"));
 }
+
+#[test]
+fn test_alias_add_help() {
+    let output = run_command(&["alias", "add", "--help"], None);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("--param"));
+}
+
+#[test]
+fn test_alias_list_with_parameters() {
+    let temp_dir = TempDir::new().unwrap();
+    let home_dir = temp_dir.path().to_str().unwrap();
+
+    let config_dir = temp_dir.path().join(".config").join("jenkins-cli");
+    fs::create_dir_all(&config_dir).unwrap();
+
+    let config_content = r#"
+jenkins:
+  prod:
+    host: https://jenkins-prod.example.com
+    user: testuser
+    token: testtoken
+job_aliases:
+  deploy-staging:
+    job_name: deploy
+    parameters:
+      ENVIRONMENT: staging
+"#;
+    fs::write(config_dir.join("config.yml"), config_content).unwrap();
+
+    let output = run_command(&["alias", "list"], Some(home_dir));
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("deploy-staging"));
+    assert!(stdout.contains("ENVIRONMENT=staging"));
+}
+
+#[test]
+fn test_alias_rename() {
+    let temp_dir = TempDir::new().unwrap();
+    let home_dir = temp_dir.path().to_str().unwrap();
+
+    let config_dir = temp_dir.path().join(".config").join("jenkins-cli");
+    fs::create_dir_all(&config_dir).unwrap();
+
+    let config_content = r#"
+jenkins:
+  dev:
+    host: https://jenkins-dev.example.com
+    user: devuser
+    token: devtoken
+job_aliases:
+  old-name:
+    job_name: team/job/api
+    jenkins: dev
+"#;
+    fs::write(config_dir.join("config.yml"), config_content).unwrap();
+
+    let output = run_command(&["alias", "rename", "old-name", "new-name"], Some(home_dir));
+    assert!(output.status.success());
+
+    let saved = fs::read_to_string(config_dir.join("config.yml")).unwrap();
+    assert!(saved.contains("new-name:"));
+    assert!(!saved.contains("old-name:"));
+    assert!(saved.contains("job_name: team/job/api"));
+    assert!(saved.contains("jenkins: dev"));
+}
+
+#[test]
+fn test_alias_rename_nonexistent() {
+    let temp_dir = TempDir::new().unwrap();
+    let output = run_command(
+        &["alias", "rename", "missing", "other"],
+        Some(temp_dir.path().to_str().unwrap())
+    );
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Job alias 'missing' not found"));
+}
+
+#[test]
+fn test_build_follow_downstream_requires_follow() {
+    let output = run_command(&["build", "api", "--follow-downstream"], None);
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--follow"));
+}
+
+#[test]
+fn test_hook_install_and_uninstall() {
+    let home = TempDir::new().unwrap();
+    let repo = TempDir::new().unwrap();
+    let run = |args: &[&str]| {
+        Command::new(get_binary_path())
+            .args(args)
+            .current_dir(repo.path())
+            .env("HOME", home.path())
+            .output()
+            .expect("Failed to execute command")
+    };
+    let hook = repo.path().join(".git/hooks/pre-push");
+
+    let output = run(&["hook", "install", "--job", "api"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Not inside a git repository"));
+
+    Command::new("git").args(["init", "--quiet"]).current_dir(repo.path()).status().unwrap();
+    let output = run(&["hook", "install", "--job", "team/api", "-p", "BRANCH={branch}"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let script = fs::read_to_string(&hook).unwrap();
+    assert!(script.contains("JENKINS_PARAM_BRANCH=\"${branch}\""));
+    assert!(script.contains("build 'team/api' --non-interactive"));
+    // Builds wait for the push to finish unless --when pre-push says otherwise
+    assert!(script.contains("while kill -0"));
+
+    assert!(run(&["hook", "uninstall"]).status.success());
+    assert!(!hook.exists());
+
+    // Hooks written by someone else are left alone
+    fs::write(&hook, "#!/bin/sh\nexit 0\n").unwrap();
+    assert!(!run(&["hook", "install", "--job", "api"]).status.success());
+    assert!(!run(&["hook", "uninstall"]).status.success());
+    assert!(hook.exists());
+}
+
+#[test]
+fn test_autobuild_rejects_unknown_trigger() {
+    let output = run_command(&["autobuild", "--job", "api", "--on", "commit"], None);
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("invalid value 'commit'"));
+}
+
+#[test]
+fn test_autobuild_rejects_zero_interval_and_debounce() {
+    for flag in ["--interval", "--debounce"] {
+        let output = run_command(&["autobuild", "--job", "api", flag, "0"], None);
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("invalid value '0'"));
+    }
+}
+
+#[test]
+fn test_jobs_rejects_unknown_filter() {
+    let output = run_command(&["jobs", "--filter", "green"], None);
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("invalid value 'green'"));
+    assert!(stderr.contains("failing"));
+}
+
+#[test]
+fn test_failing_takes_a_folder_or_a_view() {
+    let output = run_command(&["failing", "team", "--view", "Nightly"], None);
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("cannot be used with"));
+}
+
+#[test]
+fn test_health_help() {
+    let output = run_command(&["health", "--help"], None);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("List jobs sorted by health score"));
+}
+
+#[test]
+fn test_coverage_help() {
+    let output = run_command(&["coverage", "--help"], None);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Show code coverage for a build"));
+    assert!(stdout.contains("--build"));
+}
+
+#[test]
+fn test_warnings_help() {
+    let output = run_command(&["warnings", "--help"], None);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Summarize static analysis (warnings-ng) results for a build"));
+    assert!(stdout.contains("--fail-on-new"));
+}
+
+#[test]
+fn test_tests_help() {
+    let output = run_command(&["tests", "--help"], None);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Show test results for a build"));
+    assert!(stdout.contains("--failed"));
+    assert!(stdout.contains("--junit-out"));
+}
+
+#[test]
+fn test_stats_help() {
+    let output = run_command(&["stats", "--help"], None);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Show build duration trends and success rate for a job"));
+    assert!(stdout.contains("--last"));
+}
+
+#[test]
+fn test_export_build_help() {
+    let output = run_command(&["export-build", "--help"], None);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("as one JSON document"));
+    assert!(stdout.contains("--build"));
+    assert!(stdout.contains("--output"));
+}
+
+#[test]
+fn test_timeline_help() {
+    let output = run_command(&["timeline", "--help"], None);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("on a timeline"));
+    assert!(stdout.contains("--build"));
+}
+
+#[test]
+fn test_load_help() {
+    let output = run_command(&["load", "--help"], None);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("queue length and executor usage"));
+}
+
+#[test]
+fn test_capacity_rejects_unknown_output() {
+    let output = run_command(&["capacity", "--output", "yaml"], None);
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("invalid value 'yaml'"));
+}
+
+#[test]
+fn test_monitor_help() {
+    let output = run_command(&["monitor", "--help"], None);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Watch the status of several jobs live"));
+    assert!(stdout.contains("--view"));
+    assert!(stdout.contains("--interval"));
+    assert!(stdout.contains("--daemon"));
+}
+
+#[test]
+fn test_monitor_digest_outputs_require_daemon() {
+    let output = run_command(&["monitor", "api", "--webhook", "https://hooks.example.com/x"], None);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--daemon"));
+
+    let output = run_command(&["monitor", "api", "--interval", "soon"], None);
+    assert!(!output.status.success());
+
+    let output = run_command(&["monitor", "api", "--interval", "0"], None);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("must be at least 1s"));
+}
+
+#[test]
+fn test_dash_rejects_zero_interval() {
+    let output = run_command(&["dash", "--watch", "--interval", "0"], None);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("invalid value '0'"));
+}
+
+#[test]
+fn test_monitor_without_jobs() {
+    let temp_dir = TempDir::new().unwrap();
+    let output = run_command(&["monitor"], Some(temp_dir.path().to_str().unwrap()));
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Specify at least one job"));
+}
+
+#[test]
+fn test_directory_profile_selects_unknown_host() {
+    let temp_dir = TempDir::new().unwrap();
+    let home_dir = temp_dir.path().to_str().unwrap();
+
+    let config_dir = temp_dir.path().join(".config").join("jenkins-cli");
+    fs::create_dir_all(&config_dir).unwrap();
+
+    let project_dir = temp_dir.path().join("project");
+    fs::create_dir_all(&project_dir).unwrap();
+
+    // The profile points at a host that doesn't exist, so the lookup is observable
+    let config_content = format!(r#"
+jenkins:
+  prod:
+    host: https://jenkins-prod.example.com
+    user: testuser
+    token: testtoken
+  dev:
+    host: https://jenkins-dev.example.com
+    user: devuser
+    token: devtoken
+profiles:
+  - path: {}
+    jenkins: missing
+    job: api
+"#, project_dir.display());
+    fs::write(config_dir.join("config.yml"), config_content).unwrap();
+
+    let output = Command::new(get_binary_path())
+        .args(["status"])
+        .env("HOME", home_dir)
+        .current_dir(&project_dir)
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Jenkins 'missing' not found"));
+}
+
+#[test]
+fn test_config_flag_uses_alternate_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_file = temp_dir.path().join("team.yml");
+
+    let config_content = r#"
+jenkins:
+  team:
+    host: https://jenkins-team.example.com
+    user: teamuser
+    token: teamtoken
+"#;
+    fs::write(&config_file, config_content).unwrap();
+
+    // HOME points at an empty directory, so only the alternate file has hosts
+    let output = run_command(
+        &["--config", config_file.to_str().unwrap(), "config", "list"],
+        Some(temp_dir.path().to_str().unwrap())
+    );
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("team"));
+    assert!(stdout.contains("https://jenkins-team.example.com"));
+}
+
+#[test]
+fn test_config_env_var_uses_alternate_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_file = temp_dir.path().join("env.yml");
+
+    let config_content = r#"
+jenkins:
+  from-env:
+    host: https://jenkins-env.example.com
+    user: envuser
+    token: envtoken
+"#;
+    fs::write(&config_file, config_content).unwrap();
+
+    let output = Command::new(get_binary_path())
+        .args(["config", "list"])
+        .env("HOME", temp_dir.path())
+        .env("JENKINS_CLI_CONFIG", &config_file)
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("from-env"));
+}
+
+#[test]
+fn test_config_validate_reports_unknown_host() {
+    let temp_dir = TempDir::new().unwrap();
+    let home_dir = temp_dir.path().to_str().unwrap();
+
+    let config_dir = temp_dir.path().join(".config").join("jenkins-cli");
+    fs::create_dir_all(&config_dir).unwrap();
+
+    let config_content = r#"jenkins:
+  prod:
+    host: https://jenkins-prod.example.com
+    user: testuser
+    token: testtoken
+job_aliases:
+  deploy:
+    job_name: deploy
+    jenkins: staging
+"#;
+    fs::write(config_dir.join("config.yml"), config_content).unwrap();
+
+    let output = run_command(&["config", "validate"], Some(home_dir));
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("line 9: Job alias 'deploy' references unknown Jenkins host 'staging'"));
+    assert!(stderr.contains("Config file has 1 error(s)"));
+}
+
+#[test]
+fn test_config_validate_valid() {
+    let temp_dir = TempDir::new().unwrap();
+    let home_dir = temp_dir.path().to_str().unwrap();
+
+    let config_dir = temp_dir.path().join(".config").join("jenkins-cli");
+    fs::create_dir_all(&config_dir).unwrap();
+
+    let config_content = r#"jenkins:
+  prod:
+    host: https://jenkins-prod.example.com
+    user: testuser
+    token: testtoken
+"#;
+    fs::write(config_dir.join("config.yml"), config_content).unwrap();
+
+    let output = run_command(&["config", "validate"], Some(home_dir));
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("is valid"));
+}
+
+#[test]
+fn test_config_verify_does_not_record_network_errors_as_auth_failures() {
+    let temp_dir = TempDir::new().unwrap();
+    let home_dir = temp_dir.path().to_str().unwrap();
+
+    let config_dir = temp_dir.path().join(".config").join("jenkins-cli");
+    fs::create_dir_all(&config_dir).unwrap();
+
+    // Nothing listens on port 9, so the connection is refused before any credentials are checked
+    let config_content = r#"
+jenkins:
+  local:
+    host: http://127.0.0.1:9
+    user: testuser
+    token: testtoken
+"#;
+    fs::write(config_dir.join("config.yml"), config_content).unwrap();
+
+    let output = run_command(&["config", "verify", "local"], Some(home_dir));
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("1 of 1 host(s) failed verification"));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("credentials were not checked"));
+    assert!(!stdout.contains("may have expired or been revoked"));
+
+    // An unreachable server says nothing about the token, so later commands don't blame it
+    let output = run_command(&["status", "some-job"], Some(home_dir));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("Credentials for 'local' failed their last check"));
+}
+
+#[test]
+fn test_shell_help() {
+    let output = run_command(&["shell", "--help"], None);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("interactive shell"));
+}
+
+#[test]
+fn test_last_without_recorded_build() {
+    let temp_dir = TempDir::new().unwrap();
+    let home_dir = temp_dir.path().to_str().unwrap();
+
+    let output = run_command(&["last"], Some(home_dir));
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("No build has been triggered from this CLI yet"));
+}
+
+#[test]
+fn test_last_help() {
+    let output = run_command(&["last", "--help"], None);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("status"));
+    assert!(stdout.contains("logs"));
+    assert!(stdout.contains("open"));
+}
+
+#[test]
+fn test_mine_help() {
+    let output = run_command(&["mine", "--help"], None);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("started by the configured user"));
+    assert!(stdout.contains("--last"));
+}
+
+#[test]
+fn test_api_help() {
+    let output = run_command(&["api", "--help"], None);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("raw authenticated request"));
+    assert!(stdout.contains("--data"));
+}
+
+#[test]
+fn test_non_interactive_host_selection_fails() {
+    let temp_dir = TempDir::new().unwrap();
+    let home_dir = temp_dir.path().to_str().unwrap();
+
+    let config_dir = temp_dir.path().join(".config").join("jenkins-cli");
+    fs::create_dir_all(&config_dir).unwrap();
+
+    let config_content = r#"
+jenkins:
+  one:
+    host: http://127.0.0.1:9
+    user: testuser
+    token: testtoken
+  two:
+    host: http://127.0.0.1:9
+    user: testuser
+    token: testtoken
+"#;
+    fs::write(config_dir.join("config.yml"), config_content).unwrap();
+
+    let output = run_command(&["--non-interactive", "status", "some-job"], Some(home_dir));
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Cannot prompt for a Jenkins host in non-interactive mode"));
+
+    let output = Command::new(get_binary_path())
+        .args(["alias", "add"])
+        .env("HOME", home_dir)
+        .env("JENKINS_CLI_NONINTERACTIVE", "1")
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Cannot prompt for an alias name in non-interactive mode"));
+}
+
+#[test]
+fn test_piped_output_does_not_prompt() {
+    let temp_dir = TempDir::new().unwrap();
+    let home_dir = temp_dir.path().to_str().unwrap();
+
+    let config_dir = temp_dir.path().join(".config").join("jenkins-cli");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(config_dir.join("config.yml"), "jenkins: {}\n").unwrap();
+
+    // Test output is captured and stdin is closed, so neither is ever a terminal here
+    let output = run_command(&["alias", "add"], Some(home_dir));
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Cannot prompt for an alias name without a terminal to answer on"));
+    assert!(!stderr.contains("\u{1b}["), "piped output should not contain color codes");
+}
+
+#[test]
+fn test_build_events_requires_follow() {
+    let output = run_command(&["build", "some-job", "--events"], None);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--follow"));
+}
+
+#[test]
+fn test_build_notify_requires_follow() {
+    let output = run_command(&["build", "some-job", "--notify", "https://hooks.example.com/T0"], None);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--follow"));
+}
+
+#[test]
+fn test_log_file_records_requests_and_errors() {
+    let temp_dir = TempDir::new().unwrap();
+    let home_dir = temp_dir.path().to_str().unwrap();
+
+    let config_dir = temp_dir.path().join(".config").join("jenkins-cli");
+    fs::create_dir_all(&config_dir).unwrap();
+
+    // Nothing listens on port 9, so the request fails quickly
+    let config_content = r#"
+jenkins:
+  local:
+    host: http://127.0.0.1:9
+    user: testuser
+    token: testtoken
+"#;
+    fs::write(config_dir.join("config.yml"), config_content).unwrap();
+
+    let log_path = temp_dir.path().join("logs").join("jenkins.log");
+    let output = run_command(
+        &["--log-file", log_path.to_str().unwrap(), "status", "some-job"],
+        Some(home_dir),
+    );
+    assert!(!output.status.success());
+
+    let log = fs::read_to_string(&log_path).unwrap();
+    assert!(log.contains("DEBUG jenkins: starting"));
+    assert!(log.contains("request failed method=GET url=http://127.0.0.1:9/job/some-job/api/json"));
+    assert!(log.contains("ERROR jenkins: command failed"));
+}
+
+#[test]
+fn test_running_help() {
+    let output = run_command(&["running", "--help"], None);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("currently running on the instance"));
+}
+
+#[test]
+fn test_artifacts_download_help() {
+    let output = run_command(&["artifacts", "download", "--help"], None);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("verifying their size and recorded checksum"));
+    assert!(stdout.contains("--output"));
+    assert!(stdout.contains("--pattern"));
+    assert!(stdout.contains("--parallel"));
+    assert!(stdout.contains("--resume"));
+}
+
+#[test]
+fn test_artifacts_download_rejects_invalid_pattern() {
+    let output = run_command(&["artifacts", "download", "some-job", "--pattern", "dist/["], None);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Invalid pattern 'dist/['"));
+}
+
+#[test]
+fn test_job_diff_needs_second_job_or_host() {
+    let output = run_command(&["job", "diff", "deploy"], None);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--other-jenkins"));
+}
+
+#[test]
+fn test_backup_help() {
+    let output = run_command(&["backup", "--help"], None);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("keeping the folder hierarchy"));
+    assert!(stdout.contains("--folder"));
+    assert!(stdout.contains("--dest"));
+}
+
+#[test]
+fn test_restore_requires_configs() {
+    let temp_dir = TempDir::new().unwrap();
+    let backup_dir = temp_dir.path().join("backup");
+    fs::create_dir_all(&backup_dir).unwrap();
+
+    let output = run_command(&["restore", backup_dir.to_str().unwrap()], Some(temp_dir.path().to_str().unwrap()));
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("No config.xml files found"));
+}
+
+#[test]
+fn test_casc_export_help() {
+    let output = run_command(&["casc", "export", "--help"], None);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("JCasC YAML"));
+    assert!(stdout.contains("--output"));
+    assert!(stdout.contains("jenkins.yaml"));
+}
+
+#[test]
+fn test_seed_help() {
+    let output = run_command(&["seed", "--help"], None);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Job DSL seed job"));
+    assert!(stdout.contains("--queue-timeout"));
+}
+
+#[test]
+fn test_script_requires_readable_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let missing = temp_dir.path().join("missing.groovy");
+
+    let output = run_command(
+        &["script", missing.to_str().unwrap(), "--node", "agent-1"],
+        Some(temp_dir.path().to_str().unwrap()),
+    );
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Failed to read"));
+}
+
+#[test]
+fn test_node_create_help() {
+    let output = run_command(&["node", "create", "--help"], None);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("inbound (JNLP) agent"));
+    assert!(stdout.contains("--executors"));
+    assert!(stdout.contains("--label"));
+    assert!(stdout.contains("/home/jenkins/agent"));
+}
+
+#[test]
+fn test_node_list_rejects_bad_threshold() {
+    let output = run_command(&["node", "list", "--min-disk", "lots"], None);
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("invalid size 'lots'"));
+}
+
+#[test]
+fn test_node_help_lists_actions() {
+    let output = run_command(&["node", "--help"], None);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("create"));
+    assert!(stdout.contains("list"));
+    assert!(stdout.contains("logs"));
+    assert!(stdout.contains("launch and connection log"));
+    assert!(stdout.contains("delete"));
+}
+
+#[test]
+fn test_plugins_help() {
+    let output = run_command(&["plugins", "--help"], None);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("outdated"));
+    assert!(stdout.contains("security advisories"));
+    assert!(stdout.contains("tree"));
+    assert!(stdout.contains("dependency tree"));
+}
+
+#[test]
+fn test_doctor_reports_missing_config() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let output = run_command(&["doctor"], Some(temp_dir.path().to_str().unwrap()));
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Config file"));
+    assert!(stderr.contains("not found"));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("jenkins config add"));
+}
+
+#[test]
+fn test_ping_help() {
+    let output = run_command(&["ping", "--help"], None);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("quieting down"));
+    assert!(stdout.contains("--count"));
+}
+
+#[test]
+fn test_completion_helper_lists_hosts_and_aliases() {
+    let temp_dir = TempDir::new().unwrap();
+    let home_dir = temp_dir.path().to_str().unwrap();
+    let config_dir = temp_dir.path().join(".config").join("jenkins-cli");
+    fs::create_dir_all(&config_dir).unwrap();
+
+    let config_content = r#"
+jenkins:
+  prod:
+    host: https://jenkins-prod.example.com
+    user: admin
+    token: token
+  dev:
+    host: https://jenkins-dev.example.com
+    user: admin
+    token: token
+job_aliases:
+  deploy:
+    job_name: deploy-production
+"#;
+    fs::write(config_dir.join("config.yml"), config_content).unwrap();
+
+    let output = run_command(&["__complete", "hosts"], Some(home_dir));
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "dev\nprod\n");
+
+    let output = run_command(&["__complete", "aliases"], Some(home_dir));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "deploy\n");
+
+    // The helper is for the scripts only
+    let output = run_command(&["--help"], Some(home_dir));
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("__complete"));
+
+    let output = run_command(&["completion", "bash"], Some(home_dir));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("jenkins __complete hosts"));
+}
+
+#[test]
+fn test_recent_lists_jobs_without_prompting() {
+    let temp_dir = TempDir::new().unwrap();
+    let home_dir = temp_dir.path().to_str().unwrap();
+    let config_dir = temp_dir.path().join(".config").join("jenkins-cli");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.yml"),
+        "jenkins:\n  prod:\n    host: https://jenkins-prod.example.com\n    user: admin\n    token: token\n",
+    )
+    .unwrap();
+
+    let output = run_command(&["recent", "prod"], Some(home_dir));
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("No jobs used on 'prod' yet"));
+}
+
+#[test]
+fn test_build_on_several_hosts_requires_job() {
+    let output = run_command(&["build", "--jenkins", "prod,dr"], None);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Pass the job to build on several hosts"));
+
+    let output = run_command(&["build", "deploy", "--all-hosts", "--follow"], None);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cannot be used with"));
+
+    let output = run_command(&["build", "deploy", "--all-hosts", "--events"], None);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cannot be used with"));
+
+    let output = run_command(&["build", "deploy", "--jenkins", "prod,dr", "--follow", "--notify", "https://hooks.example.com"], None);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("works with a single host only"));
+}
+
+#[test]
+fn test_history_starts_empty() {
+    let temp_dir = TempDir::new().unwrap();
+    let home_dir = temp_dir.path().to_str().unwrap();
+
+    let output = run_command(&["history"], Some(home_dir));
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("No recorded actions"));
+}
+
+#[test]
+fn test_host_tags_are_listed_and_selectable() {
+    let temp_dir = TempDir::new().unwrap();
+    let home_dir = temp_dir.path().to_str().unwrap();
+    let config_dir = temp_dir.path().join(".config").join("jenkins-cli");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.yml"),
+        "jenkins:\n  prod:\n    host: https://jenkins-prod.example.com\n    user: admin\n    token: token\n    tags:\n      env: prod\n      team: payments\n",
+    )
+    .unwrap();
+
+    let output = run_command(&["config", "list"], Some(home_dir));
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Tags: env=prod, team=payments"));
+
+    let output = run_command(&["ping", "tag:staging"], Some(home_dir));
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No Jenkins host is tagged 'staging'"));
+}
+
+#[test]
+fn test_rotate_token_requires_known_host() {
+    let temp_dir = TempDir::new().unwrap();
+    let home_dir = temp_dir.path().to_str().unwrap();
+    let config_dir = temp_dir.path().join(".config").join("jenkins-cli");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.yml"),
+        "jenkins:\n  prod:\n    host: https://jenkins-prod.example.com\n    user: admin\n    token: token\n",
+    )
+    .unwrap();
+
+    let output = run_command(&["config", "rotate-token", "staging"], Some(home_dir));
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Jenkins 'staging' not found"));
+}
+
+#[test]
+fn test_exit_codes_by_failure_class() {
+    let temp_dir = TempDir::new().unwrap();
+    let home_dir = temp_dir.path().to_str().unwrap();
+
+    // No config yet
+    let output = run_command(&["build", "test-job", "--non-interactive"], Some(home_dir));
+    assert_eq!(output.status.code(), Some(3));
+
+    // Nothing listens on port 9 locally, so the request can't connect
+    let config_dir = temp_dir.path().join(".config").join("jenkins-cli");
+    fs::create_dir_all(&config_dir).unwrap();
+    let config_content = r#"
+jenkins:
+  local:
+    host: http://127.0.0.1:9
+    user: testuser
+    token: testtoken
+"#;
+    fs::write(config_dir.join("config.yml"), config_content).unwrap();
+    let output = run_command(&["status", "test-job"], Some(home_dir));
+    assert_eq!(output.status.code(), Some(5));
+
+    let output = run_command(&["config", "verify", "missing"], Some(home_dir));
+    assert_eq!(output.status.code(), Some(3));
+
+    let output = run_command(&["--help"], None);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Exit codes:"));
+}
+
+#[test]
+fn test_replay_requires_readable_jenkinsfile() {
+    let temp_dir = TempDir::new().unwrap();
+    let home_dir = temp_dir.path().to_str().unwrap();
+
+    let missing = temp_dir.path().join("Jenkinsfile");
+    let output = run_command(
+        &["replay", "some-job", "--jenkinsfile", missing.to_str().unwrap()],
+        Some(home_dir),
+    );
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Failed to read"));
+
+    let output = run_command(&["replay", "--help"], None);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("--jenkinsfile"));
+}
+
+#[test]
+fn test_restart_from_stage_help() {
+    let output = run_command(&["restart-from-stage", "--help"], None);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("--stage"));
+    assert!(stdout.contains("--build"));
+}